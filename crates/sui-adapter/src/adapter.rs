@@ -13,18 +13,29 @@ use leb128;
 use move_binary_format::{
     access::ModuleAccess,
     binary_views::BinaryIndexedView,
-    file_format::{AbilitySet, CompiledModule, LocalIndex, SignatureToken, StructHandleIndex},
+    file_format::{
+        AbilitySet, Bytecode, CompiledModule, LocalIndex, SignatureToken, StructHandleIndex,
+    },
+    normalized,
 };
 use move_core_types::{
     account_address::AccountAddress,
-    identifier::Identifier,
+    effects::Op,
+    identifier::{IdentStr, Identifier},
     language_storage::{ModuleId, StructTag, TypeTag},
     resolver::{ModuleResolver, ResourceResolver},
+    vm_status::StatusCode,
 };
 pub use move_vm_runtime::move_vm::MoveVM;
-use move_vm_runtime::{native_functions::NativeFunctionTable, session::SerializedReturnValues};
+use move_vm_runtime::{
+    native_extensions::NativeContextExtensions, native_functions::NativeFunctionTable,
+    session::SerializedReturnValues,
+};
 
-use sui_framework::EventType;
+use sui_framework::{
+    natives::dynamic_field::{ChildObjectEffect, ChildObjectStore},
+    EventType,
+};
 use sui_types::{
     base_types::*,
     coin::Coin,
@@ -33,10 +44,11 @@ use sui_types::{
     event::{Event, TransferType},
     gas::SuiGasStatus,
     id::UID,
-    messages::{CallArg, EntryArgumentErrorKind, InputObjectKind, ObjectArg},
-    object::{self, Data, MoveObject, Object, Owner, ID_END_INDEX},
-    storage::{DeleteKind, ObjectChange, ParentSync, Storage},
-    SUI_SYSTEM_STATE_OBJECT_ID,
+    messages::{CallArg, EntryArgumentErrorKind, InputObjectKind, ObjectArg, PackageUpgradeError},
+    move_package::{MovePackage, UpgradeCap, UpgradeInfo, UpgradePolicy, PACKAGE_MODULE_NAME},
+    object::{self, Data, MoveObject, Object, Owner, ID_END_INDEX, OBJECT_START_VERSION},
+    storage::{ChildObjectResolver, DeleteKind, ObjectChange, ParentSync, Storage},
+    SUI_CLOCK_OBJECT_ID, SUI_FRAMEWORK_ADDRESS, SUI_SYSTEM_STATE_OBJECT_ID,
 };
 use sui_verifier::{
     entry_points_verifier::{is_tx_context, RESOLVED_STD_OPTION, RESOLVED_SUI_ID},
@@ -62,7 +74,11 @@ pub fn new_move_vm(natives: NativeFunctionTable) -> Result<MoveVM, SuiError> {
 #[allow(clippy::too_many_arguments)]
 pub fn execute<
     E: Debug,
-    S: ResourceResolver<Error = E> + ModuleResolver<Error = E> + Storage + ParentSync,
+    S: ResourceResolver<Error = E>
+        + ModuleResolver<Error = E>
+        + Storage
+        + ParentSync
+        + ChildObjectResolver,
 >(
     vm: &MoveVM,
     state_view: &mut S,
@@ -134,7 +150,11 @@ pub fn execute<
 #[allow(clippy::too_many_arguments)]
 fn execute_internal<
     E: Debug,
-    S: ResourceResolver<Error = E> + ModuleResolver<Error = E> + Storage + ParentSync,
+    S: ResourceResolver<Error = E>
+        + ModuleResolver<Error = E>
+        + Storage
+        + ParentSync
+        + ChildObjectResolver,
 >(
     vm: &MoveVM,
     state_view: &mut S,
@@ -161,15 +181,17 @@ fn execute_internal<
         })
         .collect();
 
-    let mut session = vm.new_session(state_view);
+    // Child objects, e.g. dynamic fields, are loaded from `state_view` on demand during execution.
+    let mut extensions = NativeContextExtensions::default();
+    extensions.add(ChildObjectStore::new(Box::new(&*state_view)));
+    let mut session = vm.new_session_with_extensions(&*state_view, extensions);
+    // The VM enters functions in the gas profile when they are called from Move, so the entry
+    // function is entered here. Functions are left on return, or by unwinding if it aborts.
+    if let Some(profile) = gas_status.get_move_gas_status().profile_mut() {
+        profile.enter(format!("{}::{}", module_id.short_str_lossless(), function));
+    }
     // script visibility checked manually for entry points
-    let (
-        SerializedReturnValues {
-            mut mutable_reference_outputs,
-            return_values,
-        },
-        (change_set, events),
-    ) = session
+    let result = session
         .execute_function_bypass_visibility(
             module_id,
             function,
@@ -177,7 +199,32 @@ fn execute_internal<
             args,
             gas_status.get_move_gas_status(),
         )
-        .and_then(|ret| Ok((ret, session.finish()?)))?;
+        .and_then(|ret| Ok((ret, session.finish_with_extensions()?)));
+    if let Some(profile) = gas_status.get_move_gas_status().profile_mut() {
+        profile.unwind();
+    }
+    let (
+        SerializedReturnValues {
+            mut mutable_reference_outputs,
+            return_values,
+        },
+        (change_set, events, native_extensions),
+    ) = result?;
+    // Consume the extensions here, as the child object store borrows `state_view`.
+    let (loaded_child_objects, child_object_effects) = {
+        let mut native_extensions = native_extensions;
+        native_extensions
+            .remove::<ChildObjectStore>()
+            .into_effects()
+            .map_err(|e| e.finish(move_binary_format::errors::Location::Undefined))?
+    };
+    // Child objects read from storage are charged like the input objects of the transaction.
+    let loaded_size = loaded_child_objects
+        .values()
+        .map(Object::object_size_for_gas_metering)
+        .sum();
+    state_view.save_loaded_child_objects(loaded_child_objects);
+    gas_status.charge_storage_read(loaded_size)?;
 
     // Sui Move programs should never touch global state, so ChangeSet should be empty
     debug_assert!(change_set.accounts().is_empty());
@@ -233,6 +280,7 @@ fn execute_internal<
         module_id,
         by_value_object_map,
         mutable_refs,
+        child_object_effects,
         events,
         ctx,
         object_owner_map,
@@ -244,7 +292,11 @@ fn execute_internal<
 
 pub fn publish<
     E: Debug,
-    S: ResourceResolver<Error = E> + ModuleResolver<Error = E> + Storage + ParentSync,
+    S: ResourceResolver<Error = E>
+        + ModuleResolver<Error = E>
+        + Storage
+        + ParentSync
+        + ChildObjectResolver,
 >(
     state_view: &mut S,
     natives: NativeFunctionTable,
@@ -266,22 +318,329 @@ pub fn publish<
     }
 
     let package_id = generate_package_id(&mut modules, ctx)?;
-    let vm = verify_and_link(state_view, &modules, package_id, natives, gas_status)?;
+    let linkage_table = dependency_linkage_table(state_view, &modules, package_id);
+    // Link against, and run initializers with, the dependency versions the package links against.
+    state_view.set_linkage(linkage(&linkage_table));
+    let result =
+        verify_and_link(state_view, &modules, package_id, natives, gas_status).and_then(|vm| {
+            state_view.log_event(Event::Publish {
+                sender: ctx.sender(),
+                package_id,
+            });
+            store_package_and_init_modules(state_view, &vm, modules, linkage_table, ctx, gas_status)
+        });
+    state_view.set_linkage(BTreeMap::new());
+    result?;
+    issue_upgrade_cap(state_view, package_id, ctx);
+    Ok(())
+}
+
+/// Publish `module_bytes` as a new version of the package `package_id`, as authorized by the
+/// `UpgradeCap` `upgrade_cap_id`. The new version is a new package object, whose modules keep the
+/// address of the original version of the package, so that the types it defines are unchanged.
+/// The modules are checked against the previous version according to the policy of the
+/// `UpgradeCap`, which is updated to refer to the new version.
+#[allow(clippy::too_many_arguments)]
+pub fn upgrade<E: Debug, S: ResourceResolver<Error = E> + ModuleResolver<Error = E> + Storage>(
+    state_view: &mut S,
+    natives: NativeFunctionTable,
+    module_bytes: Vec<Vec<u8>>,
+    package_id: ObjectID,
+    upgrade_cap_id: ObjectID,
+    dependencies: Vec<ObjectID>,
+    ctx: &mut TxContext,
+    gas_status: &mut SuiGasStatus,
+) -> Result<(), ExecutionError> {
+    gas_status.charge_publish_package(module_bytes.iter().map(|v| v.len()).sum())?;
+    let mut modules = module_bytes
+        .iter()
+        .map(|b| {
+            CompiledModule::deserialize(b)
+                .map_err(|e| e.finish(move_binary_format::errors::Location::Undefined))
+        })
+        .collect::<move_binary_format::errors::VMResult<Vec<CompiledModule>>>()?;
+
+    if modules.is_empty() {
+        return Err(ExecutionErrorKind::PublishErrorEmptyPackage.into());
+    }
+
+    let mut cap_object = state_view
+        .read_object(&upgrade_cap_id)
+        .expect("We previously checked all input objects exist")
+        .clone();
+    // The input checks let immutable objects through, but an immutable UpgradeCap must not be
+    // usable by anyone.
+    if cap_object.owner != Owner::AddressOwner(ctx.sender()) {
+        return Err(PackageUpgradeError::UpgradeCapNotOwnedBySender {
+            object_id: upgrade_cap_id,
+        }
+        .into());
+    }
+    let mut cap = match cap_object.data.try_as_move() {
+        Some(move_obj) if move_obj.type_ == UpgradeCap::type_() => {
+            bcs::from_bytes::<UpgradeCap>(move_obj.contents()).map_err(|e| {
+                ExecutionError::new_with_source(ExecutionErrorKind::InvariantViolation, e)
+            })?
+        }
+        _ => {
+            return Err(PackageUpgradeError::InvalidUpgradeCap {
+                object_id: upgrade_cap_id,
+            }
+            .into())
+        }
+    };
+    if cap.package.bytes != package_id {
+        return Err(PackageUpgradeError::PackageIdDoesNotMatch {
+            package_id,
+            cap_package_id: cap.package.bytes,
+        }
+        .into());
+    }
+    let policy = UpgradePolicy::try_from(cap.policy)
+        .map_err(|policy| PackageUpgradeError::UnknownUpgradePolicy { policy })?;
+    let previous = match state_view.read_object(&package_id).map(|o| &o.data) {
+        Some(Data::Package(package)) => package.clone(),
+        _ => {
+            return Err(PackageUpgradeError::NotAPackage {
+                object_id: package_id,
+            }
+            .into())
+        }
+    };
+
+    let original_id = previous.original_id();
+    substitute_package_id(&mut modules, original_id)?;
+    check_upgrade_compatibility(&previous, &modules, policy)?;
+
+    // The new version links against the dependency versions the previous version links against,
+    // unless they are upgraded by `dependencies` or one of the dependencies of the new version.
+    let mut linkage_table = previous.linkage_table().clone();
+    for (dependency_original_id, info) in
+        dependency_linkage_table(state_view, &modules, original_id)
+    {
+        merge_upgrade_info(&mut linkage_table, dependency_original_id, info);
+    }
+    for dependency_id in dependencies {
+        let dependency = match state_view.read_object(&dependency_id).map(|o| &o.data) {
+            Some(Data::Package(package)) => package,
+            _ => {
+                return Err(PackageUpgradeError::NotAPackage {
+                    object_id: dependency_id,
+                }
+                .into())
+            }
+        };
+        let info = UpgradeInfo {
+            upgraded_id: dependency.id(),
+            upgraded_version: dependency.version(),
+        };
+        merge_upgrade_info(&mut linkage_table, dependency.original_id(), info);
+        for (dependency_original_id, info) in dependency.linkage_table() {
+            merge_upgrade_info(&mut linkage_table, *dependency_original_id, *info);
+        }
+    }
+    linkage_table.remove(&original_id);
+
+    // The previous version is linked in place of the original one, so that the Move VM checks
+    // the new modules against it.
+    let mut verification_linkage = linkage(&linkage_table);
+    verification_linkage.insert(original_id, package_id);
+    state_view.set_linkage(verification_linkage);
+    let result = verify_and_link(state_view, &modules, original_id, natives, gas_status);
+    state_view.set_linkage(BTreeMap::new());
+    result?;
+
+    let new_package_id = ctx.fresh_id();
+    let package = MovePackage::new_upgraded(new_package_id, &previous, modules, linkage_table);
+    cap.package.bytes = new_package_id;
+    cap.version = package.version();
+    cap_object
+        .data
+        .try_as_move_mut()
+        .expect("We previously checked that the UpgradeCap is a Move object")
+        .update_contents_and_increment_version(cap.to_bcs_bytes());
     state_view.log_event(Event::Publish {
         sender: ctx.sender(),
-        package_id,
+        package_id: new_package_id,
     });
-    store_package_and_init_modules(state_view, &vm, modules, ctx, gas_status)
+    state_view.set_create_object_ids(BTreeSet::from([new_package_id]));
+    state_view.apply_object_changes(BTreeMap::from([
+        (
+            new_package_id,
+            ObjectChange::Write(Object::new_package_from_data(package, ctx.digest())),
+        ),
+        (upgrade_cap_id, ObjectChange::Write(cap_object)),
+    ]));
+    Ok(())
+}
+
+/// Transfer a new `UpgradeCap` for the package `package_id` to the sender.
+fn issue_upgrade_cap<S: Storage>(state_view: &mut S, package_id: ObjectID, ctx: &mut TxContext) {
+    let cap = UpgradeCap::new(ctx.fresh_id(), package_id);
+    let cap_id = *cap.id.object_id();
+    let owner = Owner::AddressOwner(ctx.sender());
+    let cap_object = Object::new_move(
+        MoveObject::new_upgrade_cap(OBJECT_START_VERSION, cap.to_bcs_bytes()),
+        owner,
+        ctx.digest(),
+    );
+    state_view.log_event(Event::new_object(
+        &SUI_FRAMEWORK_ADDRESS,
+        PACKAGE_MODULE_NAME,
+        ctx.sender(),
+        owner,
+        cap_id,
+    ));
+    // Module initializers may have created objects as well.
+    state_view.set_create_object_ids(ctx.recreate_all_ids());
+    state_view.apply_object_changes(BTreeMap::from([(cap_id, ObjectChange::Write(cap_object))]));
+}
+
+/// Returns the linkage table of a package with `modules`, which links against the dependency
+/// versions its direct dependencies link against. When two dependencies link against different
+/// versions of the same package, the most recent one is used.
+fn dependency_linkage_table<S: Storage>(
+    state_view: &S,
+    modules: &[CompiledModule],
+    package_id: ObjectID,
+) -> BTreeMap<ObjectID, UpgradeInfo> {
+    let dependencies: BTreeSet<_> = modules
+        .iter()
+        .flat_map(|module| module.immediate_dependencies())
+        .map(|module_id| ObjectID::from(*module_id.address()))
+        .filter(|id| id != &package_id)
+        .collect();
+    let mut linkage_table = BTreeMap::new();
+    for id in dependencies {
+        if let Some(Data::Package(package)) = state_view.read_object(&id).map(|o| &o.data) {
+            for (original_id, info) in package.linkage_table() {
+                merge_upgrade_info(&mut linkage_table, *original_id, *info);
+            }
+        }
+    }
+    linkage_table
+}
+
+fn merge_upgrade_info(
+    linkage_table: &mut BTreeMap<ObjectID, UpgradeInfo>,
+    original_id: ObjectID,
+    info: UpgradeInfo,
+) {
+    let entry = linkage_table.entry(original_id).or_insert(info);
+    if entry.upgraded_version < info.upgraded_version {
+        *entry = info;
+    }
+}
+
+fn linkage(linkage_table: &BTreeMap<ObjectID, UpgradeInfo>) -> BTreeMap<ObjectID, ObjectID> {
+    linkage_table
+        .iter()
+        .map(|(original_id, info)| (*original_id, info.upgraded_id))
+        .collect()
+}
+
+/// Check that `modules` are allowed by `policy` as a new version of `previous`. Modules cannot be
+/// removed by an upgrade.
+fn check_upgrade_compatibility(
+    previous: &MovePackage,
+    modules: &[CompiledModule],
+    policy: UpgradePolicy,
+) -> Result<(), ExecutionError> {
+    let new_modules: BTreeMap<_, _> = modules
+        .iter()
+        .map(|module| (module.self_id().name().to_string(), module))
+        .collect();
+    if policy == UpgradePolicy::DepOnly
+        && new_modules.len() != previous.serialized_module_map().len()
+    {
+        return Err(ExecutionError::new_with_source(
+            PackageUpgradeError::IncompatibleUpgrade.into(),
+            "Modules cannot be added by a dependency only upgrade",
+        ));
+    }
+    for (name, bytes) in previous.serialized_module_map() {
+        let new_module = match new_modules.get(name) {
+            Some(module) => *module,
+            None => {
+                return Err(ExecutionError::new_with_source(
+                    PackageUpgradeError::IncompatibleUpgrade.into(),
+                    format!("Module {name} cannot be removed by an upgrade"),
+                ))
+            }
+        };
+        let old_module = CompiledModule::deserialize(bytes)
+            .expect("Unwrap safe because Sui serializes/verifies modules before publishing them");
+        let allowed = match policy {
+            // The Move VM checks that the new modules are compatible with the previous version
+            // when linking them in its place, in `verify_and_link`.
+            UpgradePolicy::Compatible => true,
+            UpgradePolicy::Additive => is_additive(&old_module, new_module),
+            UpgradePolicy::DepOnly => {
+                let mut new_bytes = Vec::new();
+                new_module.serialize(&mut new_bytes).unwrap();
+                &new_bytes == bytes
+            }
+        };
+        if !allowed {
+            return Err(ExecutionError::new_with_source(
+                PackageUpgradeError::IncompatibleUpgrade.into(),
+                format!("Changes to module {name} are not allowed by the {policy:?} policy"),
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// In addition to being compatible, which the Move VM checks in `verify_and_link`, all functions
+/// must keep their signatures and code. Code is compared instruction by instruction, so a function
+/// whose instructions refer to different indices of the module's tables after recompilation is
+/// considered changed.
+fn is_additive(old: &CompiledModule, new: &CompiledModule) -> bool {
+    let old_normalized = normalized::Module::new(old);
+    let new_normalized = normalized::Module::new(new);
+    if old_normalized.friends != new_normalized.friends
+        || old_normalized
+            .exposed_functions
+            .iter()
+            .any(|(name, old_function)| {
+                new_normalized.exposed_functions.get(name) != Some(old_function)
+            })
+    {
+        return false;
+    }
+    let new_code = function_code(new);
+    function_code(old)
+        .into_iter()
+        .all(|(name, old_code)| new_code.get(&name) == Some(&old_code))
+}
+
+fn function_code(module: &CompiledModule) -> BTreeMap<&IdentStr, Option<&Vec<Bytecode>>> {
+    module
+        .function_defs
+        .iter()
+        .map(|fdef| {
+            let fhandle = module.function_handle_at(fdef.function);
+            (
+                module.identifier_at(fhandle.name),
+                fdef.code.as_ref().map(|code| &code.code),
+            )
+        })
+        .collect()
 }
 
 /// Store package in state_view and call module initializers
 pub fn store_package_and_init_modules<
     E: Debug,
-    S: ResourceResolver<Error = E> + ModuleResolver<Error = E> + Storage + ParentSync,
+    S: ResourceResolver<Error = E>
+        + ModuleResolver<Error = E>
+        + Storage
+        + ParentSync
+        + ChildObjectResolver,
 >(
     state_view: &mut S,
     vm: &MoveVM,
     modules: Vec<CompiledModule>,
+    linkage_table: BTreeMap<ObjectID, UpgradeInfo>,
     ctx: &mut TxContext,
     gas_status: &mut SuiGasStatus,
 ) -> Result<(), ExecutionError> {
@@ -304,7 +663,10 @@ pub fn store_package_and_init_modules<
 
     // wrap the modules in an object, write it to the store
     // The call to unwrap() will go away once we remove address owner from Immutable objects.
-    let package_object = Object::new_package(modules, ctx.digest());
+    let package_object = Object::new_package_from_data(
+        MovePackage::from_iter(modules).with_linkage_table(linkage_table),
+        ctx.digest(),
+    );
     let id = package_object.id();
     state_view.set_create_object_ids(BTreeSet::from([id]));
     let changes = BTreeMap::from([(id, ObjectChange::Write(package_object))]);
@@ -316,7 +678,11 @@ pub fn store_package_and_init_modules<
 /// Modules in module_ids_to_init must have the init method defined
 fn init_modules<
     E: Debug,
-    S: ResourceResolver<Error = E> + ModuleResolver<Error = E> + Storage + ParentSync,
+    S: ResourceResolver<Error = E>
+        + ModuleResolver<Error = E>
+        + Storage
+        + ParentSync
+        + ChildObjectResolver,
 >(
     state_view: &mut S,
     vm: &MoveVM,
@@ -385,13 +751,23 @@ pub fn verify_and_link<
             bytes
         })
         .collect();
-    session.publish_module_bundle(
-        new_module_bytes,
-        AccountAddress::from(package_id),
-        // TODO: publish_module_bundle() currently doesn't charge gas.
-        // Do we want to charge there?
-        gas_status.get_move_gas_status(),
-    )?;
+    session
+        .publish_module_bundle(
+            new_module_bytes,
+            AccountAddress::from(package_id),
+            // TODO: publish_module_bundle() currently doesn't charge gas.
+            // Do we want to charge there?
+            gas_status.get_move_gas_status(),
+        )
+        .map_err(|e| {
+            // When upgrading, the previous version of the modules is linked at `package_id`, and
+            // the Move VM checks that the new modules are compatible with it.
+            if e.major_status() == StatusCode::BACKWARD_INCOMPATIBLE_MODULE_UPDATE {
+                ExecutionError::new_with_source(PackageUpgradeError::IncompatibleUpgrade.into(), e)
+            } else {
+                e.into()
+            }
+        })?;
 
     // run the Sui verifier
     for module in modules.iter() {
@@ -411,8 +787,18 @@ pub fn generate_package_id(
     modules: &mut [CompiledModule],
     ctx: &mut TxContext,
 ) -> Result<ObjectID, ExecutionError> {
-    let mut sub_map = BTreeMap::new();
     let package_id = ctx.fresh_id();
+    substitute_package_id(modules, package_id)?;
+    Ok(package_id)
+}
+
+/// Mutate each module's self ID to `package_id`, which must be zero before, and update its
+/// module handle tables to reflect the new ID's of its dependencies.
+fn substitute_package_id(
+    modules: &mut [CompiledModule],
+    package_id: ObjectID,
+) -> Result<(), ExecutionError> {
+    let mut sub_map = BTreeMap::new();
     for module in modules.iter() {
         let old_module_id = module.self_id();
         let old_address = *old_module_id.address();
@@ -442,7 +828,7 @@ pub fn generate_package_id(
         // rewrite module handles to reflect freshly generated ID's
         rewriter.sub_module_ids(module);
     }
-    Ok(package_id)
+    Ok(())
 }
 
 type MoveEvent = (Vec<u8>, u64, TypeTag, AbilitySet, Vec<u8>);
@@ -460,6 +846,7 @@ fn process_successful_execution<
     module_id: &ModuleId,
     mut by_value_objects: BTreeMap<ObjectID, (object::Owner, SequenceNumber, Option<u32>)>,
     mutable_refs: Vec<(ObjectID, Vec<u8>)>,
+    child_object_effects: BTreeMap<ObjectID, ChildObjectEffect>,
     events: Vec<MoveEvent>,
     ctx: &TxContext,
     mut object_owner_map: BTreeMap<SuiAddress, SuiAddress>,
@@ -488,6 +875,77 @@ fn process_successful_execution<
     let mut child_count_deltas: BTreeMap<ObjectID, i64> = BTreeMap::new();
     let mut newly_generated_deleted = BTreeSet::new();
     let mut newly_generated_unused = newly_generated_ids.clone();
+    // Dynamic fields are not counted as children of their parent, so that objects with dynamic
+    // fields can still be wrapped or deleted.
+    for (
+        id,
+        ChildObjectEffect {
+            owner,
+            type_,
+            effect,
+        },
+    ) in child_object_effects
+    {
+        let old_object = state_view.read_object(&id).map(|object| {
+            let child_count = object.data.try_as_move().and_then(MoveObject::child_count);
+            (object.owner, object.version(), child_count)
+        });
+        match effect {
+            // The child was removed from its parent. From here on it is handled like an object
+            // passed by value, which the events below delete, transfer or wrap.
+            Op::Delete => {
+                if let Some((old_owner, version, child_count)) = old_object {
+                    if let Owner::ObjectOwner(parent) = old_owner {
+                        // Compensates the decrement applied when the object is consumed.
+                        let delta = child_count_deltas.entry(parent.into()).or_insert(0);
+                        *delta += 1
+                    }
+                    by_value_objects.insert(id, (old_owner, version, child_count));
+                }
+            }
+            Op::New(contents) | Op::Modify(contents) => {
+                let new_owner = Owner::ObjectOwner(owner.into());
+                let (version, child_count) = match old_object {
+                    Some((_, version, child_count)) => (version, child_count),
+                    None => {
+                        // A new dynamic field. Its ID may have been used by a dynamic field
+                        // deleted earlier, in which case its version must keep increasing.
+                        let version = match state_view.get_latest_parent_entry_ref(id) {
+                            Ok(entry) => entry
+                                .map(|(_, version, _)| version)
+                                .unwrap_or_else(SequenceNumber::new),
+                            Err(_) => {
+                                return Err(ExecutionError::new_with_source(
+                                    ExecutionErrorKind::InvariantViolation,
+                                    format!("Unable to retrieve the last known version of {id}"),
+                                ));
+                            }
+                        };
+                        newly_generated_ids.insert(id);
+                        state_view.log_event(Event::new_object(
+                            module_id.address(),
+                            module_id.name(),
+                            ctx.sender(),
+                            new_owner,
+                            id,
+                        ));
+                        (version, None)
+                    }
+                };
+                // safe because only `dynamic_field::Field`, which does not have `store`, is
+                // stored as a child object.
+                let mut move_obj = unsafe {
+                    MoveObject::new_from_execution(type_, false, version, child_count, contents)
+                };
+                move_obj.increment_version();
+                changes.insert(
+                    id,
+                    ObjectChange::Write(Object::new_move(move_obj, new_owner, tx_digest)),
+                );
+            }
+        }
+    }
+
     // process events to identify transfers, freezes
     for e in events {
         let (recipient, event_type, type_, abilities, event_bytes) = e;
@@ -749,7 +1207,9 @@ fn handle_transfer<
         .expect("object contents should start with an id");
     newly_generated_unused.remove(&id);
     let old_object = by_value_objects.remove(&id);
-    let mut is_unwrapped = !(newly_generated_ids.contains(&id) || id == SUI_SYSTEM_STATE_OBJECT_ID);
+    let mut is_unwrapped = !(newly_generated_ids.contains(&id)
+        || id == SUI_SYSTEM_STATE_OBJECT_ID
+        || id == SUI_CLOCK_OBJECT_ID);
     let (version, child_count) = match old_object {
         Some((_, version, child_count)) => (version, child_count),
         // When an object was wrapped at version `v`, we added an record into `parent_sync`
@@ -1223,6 +1683,19 @@ fn inner_param_type<'a>(
                     error,
                 ));
             }
+            if object_id == SUI_CLOCK_OBJECT_ID {
+                let error = format!(
+                    "Argument {} is the Clock, which can only be passed by immutable reference",
+                    idx
+                );
+                return Err(ExecutionError::new_with_source(
+                    ExecutionErrorKind::entry_argument_error(
+                        idx,
+                        EntryArgumentErrorKind::InvalidObjectByMuteRef,
+                    ),
+                    error,
+                ));
+            }
             mutable_ref_objects.insert(idx as LocalIndex, object_id);
             Ok(&**inner_t)
         }
@@ -7,7 +7,9 @@ use sui_types::{
     base_types::{ObjectID, ObjectRef, SequenceNumber},
     error::{SuiError, SuiResult},
     object::Object,
-    storage::{BackingPackageStore, DeleteKind, ParentSync},
+    storage::{
+        check_child_object_owner, BackingPackageStore, ChildObjectResolver, DeleteKind, ParentSync,
+    },
 };
 
 // TODO: We should use AuthorityTemporaryStore instead.
@@ -24,6 +26,16 @@ impl BackingPackageStore for InMemoryStorage {
     }
 }
 
+impl ChildObjectResolver for InMemoryStorage {
+    fn read_child_object(&self, parent: &ObjectID, child: &ObjectID) -> SuiResult<Option<Object>> {
+        self.persistent
+            .get(child)
+            .cloned()
+            .map(|child| check_child_object_owner(parent, child))
+            .transpose()
+    }
+}
+
 impl ParentSync for InMemoryStorage {
     fn get_latest_parent_entry_ref(&self, object_id: ObjectID) -> SuiResult<Option<ObjectRef>> {
         debug_assert!(!self.persistent.contains_key(&object_id));
@@ -12,11 +12,16 @@ use sui_types::error::{ExecutionError, SuiError, SuiResult};
 use sui_types::fp_bail;
 use sui_types::messages::{ExecutionStatus, InputObjects, TransactionEffects};
 use sui_types::object::{Data, Object};
-use sui_types::storage::{BackingPackageStore, DeleteKind, ObjectChange, ParentSync, Storage};
+use sui_types::storage::{
+    check_child_object_owner, BackingPackageStore, ChildObjectResolver, DeleteKind, ObjectChange,
+    ParentSync, Storage,
+};
 use sui_types::{
+    clock::Clock,
     event::Event,
     gas::{GasCostSummary, SuiGasStatus},
     object::Owner,
+    SUI_CLOCK_OBJECT_ID,
 };
 
 pub struct InnerTemporaryStore {
@@ -24,6 +29,8 @@ pub struct InnerTemporaryStore {
     pub mutable_inputs: Vec<ObjectRef>,
     pub written: BTreeMap<ObjectID, (ObjectRef, Object)>,
     pub deleted: BTreeMap<ObjectID, (SequenceNumber, DeleteKind)>,
+    /// Child objects read from storage during execution, as they were before the transaction.
+    pub loaded_child_objects: BTreeMap<ObjectID, Object>,
 }
 
 pub struct TemporaryStore<S> {
@@ -47,6 +54,10 @@ pub struct TemporaryStore<S> {
     // New object IDs created during the transaction, needed for
     // telling apart unwrapped objects.
     created_object_ids: BTreeSet<ObjectID>,
+    /// Child objects read from the store during execution, e.g. dynamic fields.
+    loaded_child_objects: BTreeMap<ObjectID, Object>,
+    /// Upgraded versions of packages that modules are loaded from, keyed by original ID.
+    linkage: BTreeMap<ObjectID, ObjectID>,
 }
 
 impl<S> TemporaryStore<S> {
@@ -64,6 +75,8 @@ impl<S> TemporaryStore<S> {
             deleted: BTreeMap::new(),
             events: Vec::new(),
             created_object_ids: BTreeSet::new(),
+            loaded_child_objects: BTreeMap::new(),
+            linkage: BTreeMap::new(),
         }
     }
 
@@ -76,6 +89,10 @@ impl<S> TemporaryStore<S> {
         &self.deleted
     }
 
+    pub fn loaded_child_objects(&self) -> &BTreeMap<ObjectID, Object> {
+        &self.loaded_child_objects
+    }
+
     /// Break up the structure and return its internal stores (objects, active_inputs, written, deleted)
     pub fn into_inner(self) -> (InnerTemporaryStore, Vec<Event>) {
         #[cfg(debug_assertions)]
@@ -98,6 +115,7 @@ impl<S> TemporaryStore<S> {
                 mutable_inputs: self.mutable_input_refs,
                 written,
                 deleted,
+                loaded_child_objects: self.loaded_child_objects,
             },
             self.events,
         )
@@ -147,15 +165,18 @@ impl<S> TemporaryStore<S> {
         objects_to_update.push(gas_object.clone());
 
         for (object_id, object) in &mut self._written {
-            let (old_object_size, storage_rebate) =
-                if let Some(old_object) = self.input_objects.get(object_id) {
-                    (
-                        old_object.object_size_for_gas_metering(),
-                        old_object.storage_rebate,
-                    )
-                } else {
-                    (0, 0)
-                };
+            let (old_object_size, storage_rebate) = if let Some(old_object) = self
+                .input_objects
+                .get(object_id)
+                .or_else(|| self.loaded_child_objects.get(object_id))
+            {
+                (
+                    old_object.object_size_for_gas_metering(),
+                    old_object.storage_rebate,
+                )
+            } else {
+                (0, 0)
+            };
             let new_storage_rebate = gas_status.charge_storage_mutation(
                 old_object_size,
                 object.object_size_for_gas_metering(),
@@ -174,7 +195,12 @@ impl<S> TemporaryStore<S> {
             // Otherwise if an object is in `self.deleted` but not in `self.objects`, it means this
             // object was unwrapped and then deleted. The rebate would have been provided already when
             // mutating the object that wrapped this object.
-            if let Some(old_object) = self.input_objects.get(object_id) {
+            // Child objects loaded during execution are treated like inputs.
+            if let Some(old_object) = self
+                .input_objects
+                .get(object_id)
+                .or_else(|| self.loaded_child_objects.get(object_id))
+            {
                 gas_status.charge_storage_mutation(
                     old_object.object_size_for_gas_metering(),
                     0,
@@ -220,7 +246,7 @@ impl<S> TemporaryStore<S> {
         for (id, object_ref_and_owner) in written {
             match (
                 self.created_object_ids.contains(&id),
-                self.input_objects.contains_key(&id),
+                self.input_objects.contains_key(&id) || self.loaded_child_objects.contains_key(&id),
             ) {
                 (true, _) => created.push(object_ref_and_owner),
                 (false, true) => mutated.push(object_ref_and_owner),
@@ -245,6 +271,10 @@ impl<S> TemporaryStore<S> {
                 }
             }
         }
+        let clock_timestamp_ms = self
+            .input_objects
+            .get(&SUI_CLOCK_OBJECT_ID)
+            .map(|clock| Clock::from_object(clock).timestamp_ms);
         let (inner, events) = self.into_inner();
 
         let effects = TransactionEffects {
@@ -260,6 +290,7 @@ impl<S> TemporaryStore<S> {
             gas_object: updated_gas_object_info,
             events,
             dependencies: transaction_dependencies,
+            clock_timestamp_ms,
         };
         (inner, effects)
     }
@@ -359,12 +390,16 @@ impl<S> Storage for TemporaryStore<S> {
         self.deleted.clear();
         self.events.clear();
         self.created_object_ids.clear();
+        self.loaded_child_objects.clear();
     }
 
     fn read_object(&self, id: &ObjectID) -> Option<&Object> {
         // there should be no read after delete
         debug_assert!(self.deleted.get(id) == None);
-        self._written.get(id).or_else(|| self.input_objects.get(id))
+        self._written
+            .get(id)
+            .or_else(|| self.input_objects.get(id))
+            .or_else(|| self.loaded_child_objects.get(id))
     }
 
     fn set_create_object_ids(&mut self, ids: BTreeSet<ObjectID>) {
@@ -382,12 +417,24 @@ impl<S> Storage for TemporaryStore<S> {
             }
         }
     }
+
+    fn save_loaded_child_objects(&mut self, loaded_child_objects: BTreeMap<ObjectID, Object>) {
+        for (id, object) in loaded_child_objects {
+            // Keep the version read by the first Move call that loaded this child.
+            self.loaded_child_objects.entry(id).or_insert(object);
+        }
+    }
+
+    fn set_linkage(&mut self, linkage: BTreeMap<ObjectID, ObjectID>) {
+        self.linkage = linkage;
+    }
 }
 
 impl<S: BackingPackageStore> ModuleResolver for TemporaryStore<S> {
     type Error = SuiError;
     fn get_module(&self, module_id: &ModuleId) -> Result<Option<Vec<u8>>, Self::Error> {
-        let package_id = &ObjectID::from(*module_id.address());
+        let original_id = ObjectID::from(*module_id.address());
+        let package_id = self.linkage.get(&original_id).unwrap_or(&original_id);
         let package_obj;
         let package = match self.read_object(package_id) {
             Some(object) => object,
@@ -448,6 +495,19 @@ impl<S> ResourceResolver for TemporaryStore<S> {
     }
 }
 
+impl<S: ChildObjectResolver> ChildObjectResolver for TemporaryStore<S> {
+    fn read_child_object(&self, parent: &ObjectID, child: &ObjectID) -> SuiResult<Option<Object>> {
+        if self.deleted.contains_key(child) {
+            return Ok(None);
+        }
+        // The child may have been written by an earlier Move call of this transaction.
+        match self.read_object(child) {
+            Some(object) => check_child_object_owner(parent, object.clone()).map(Some),
+            None => self.store.read_child_object(parent, child),
+        }
+    }
+}
+
 impl<S: ParentSync> ParentSync for TemporaryStore<S> {
     fn get_latest_parent_entry_ref(&self, object_id: ObjectID) -> SuiResult<Option<ObjectRef>> {
         self.store.get_latest_parent_entry_ref(object_id)
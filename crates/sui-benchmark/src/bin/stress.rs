@@ -220,7 +220,7 @@ async fn main() -> Result<()> {
         let cloned_gas = primary_gas;
         let auth_clients = GatewayState::make_authority_clients(
             &gateway_config,
-            NetworkAuthorityClientMetrics::new_for_tests(),
+            Arc::new(NetworkAuthorityClientMetrics::new_for_tests()),
         );
         // spawn a thread to spin up sui nodes on the multi-threaded server runtime
         let _ = std::thread::spawn(move || {
@@ -279,7 +279,7 @@ async fn main() -> Result<()> {
         let committee = GatewayState::make_committee(&config)?;
         let authority_clients = GatewayState::make_authority_clients(
             &config,
-            NetworkAuthorityClientMetrics::new_for_tests(),
+            Arc::new(NetworkAuthorityClientMetrics::new_for_tests()),
         );
         let registry = prometheus::Registry::new();
         let epoch_store = Arc::new(EpochStore::new_for_testing(&committee));
@@ -347,7 +347,7 @@ async fn main() -> Result<()> {
             let committee = GatewayState::make_committee(&gateway_config).unwrap();
             let authority_clients = GatewayState::make_authority_clients(
                 &gateway_config,
-                NetworkAuthorityClientMetrics::new_for_tests(),
+                Arc::new(NetworkAuthorityClientMetrics::new_for_tests()),
             );
             let registry: Registry = metrics::start_prometheus_server(
                 format!("{}:{}", opts.client_metric_host, opts.client_metric_port)
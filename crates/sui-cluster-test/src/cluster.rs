@@ -4,10 +4,12 @@ use super::config::{ClusterTestOpt, Env};
 use async_trait::async_trait;
 use clap::*;
 use std::net::SocketAddr;
+use std::num::NonZeroUsize;
 use sui::client_commands::WalletContext;
 use sui::config::SuiClientConfig;
 use sui_config::genesis_config::GenesisConfig;
 use sui_config::Config;
+use sui_config::ConsensusProtocol;
 use sui_config::SUI_KEYSTORE_FILENAME;
 use sui_sdk::crypto::KeystoreType;
 use sui_sdk::ClientType;
@@ -17,7 +19,7 @@ use sui_types::base_types::SuiAddress;
 use sui_types::crypto::KeypairTraits;
 use sui_types::crypto::SuiKeyPair;
 use sui_types::crypto::{get_key_pair, AccountKeyPair};
-use test_utils::network::{start_rpc_test_network_with_fullnode, TestNetwork};
+use test_utils::network::{start_rpc_test_network_with_swarm_builder, TestNetwork};
 use tracing::info;
 
 const DEVNET_FAUCET_ADDR: &str = "https://faucet.devnet.sui.io:443";
@@ -151,8 +153,14 @@ impl LocalNewCluster {
 #[async_trait]
 impl Cluster for LocalNewCluster {
     async fn start(options: &ClusterTestOpt) -> Result<Self, anyhow::Error> {
+        let (committee_size, consensus_protocol) = if options.single_node {
+            (1, ConsensusProtocol::SingleNode)
+        } else {
+            (4, ConsensusProtocol::Narwhal)
+        };
+
         // Let the faucet account hold 1000 gas objects on genesis
-        let genesis_config = GenesisConfig::custom_genesis(4, 1, 1000);
+        let genesis_config = GenesisConfig::custom_genesis(committee_size, 1, 1000);
 
         let gateway_port = options.gateway_address.as_ref().map(|addr| {
             addr.parse::<SocketAddr>()
@@ -172,9 +180,14 @@ impl Cluster for LocalNewCluster {
                 .port()
         });
 
-        let mut test_network = start_rpc_test_network_with_fullnode(
+        let builder = Swarm::builder()
+            .committee_size(NonZeroUsize::new(committee_size).unwrap())
+            .with_consensus_protocol(consensus_protocol)
+            .with_fullnode_count(1);
+
+        let mut test_network = start_rpc_test_network_with_swarm_builder(
+            builder,
             Some(genesis_config),
-            1,
             gateway_port,
             fullnode_port,
             websocket_port,
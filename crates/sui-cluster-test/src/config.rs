@@ -24,6 +24,10 @@ pub struct ClusterTestOpt {
     pub fullnode_address: Option<String>,
     #[clap(long)]
     pub websocket_address: Option<String>,
+    /// Run a single validator that sequences transactions in-process instead of through Narwhal.
+    /// Only applies to `Env::NewLocal`.
+    #[clap(long)]
+    pub single_node: bool,
 }
 
 impl ClusterTestOpt {
@@ -34,6 +38,7 @@ impl ClusterTestOpt {
             faucet_address: None,
             fullnode_address: None,
             websocket_address: None,
+            single_node: false,
         }
     }
 }
@@ -103,6 +103,7 @@ impl TestCaseImpl for FullNodeExecuteTransactionTest {
         if let SuiExecuteTransactionResponse::EffectsCert {
             certificate,
             effects,
+            ..
         } = response
         {
             assert_eq!(txn_digest, certificate.transaction_digest);
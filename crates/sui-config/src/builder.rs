@@ -4,8 +4,8 @@
 use crate::{
     genesis,
     genesis_config::{GenesisConfig, ValidatorGenesisInfo},
-    utils, ConsensusConfig, NetworkConfig, NodeConfig, ValidatorInfo, AUTHORITIES_DB_NAME,
-    CONSENSUS_DB_NAME,
+    utils, ConsensusConfig, ConsensusProtocol, NetworkConfig, NodeConfig, ValidatorInfo,
+    AUTHORITIES_DB_NAME, CONSENSUS_DB_NAME,
 };
 use rand::rngs::OsRng;
 use std::{
@@ -39,6 +39,7 @@ pub struct ConfigBuilder<R = OsRng> {
     initial_accounts_config: Option<GenesisConfig>,
     with_swarm: bool,
     validator_ip_sel: ValidatorIpSelection,
+    consensus_protocol: ConsensusProtocol,
 }
 
 impl ConfigBuilder {
@@ -57,6 +58,7 @@ impl ConfigBuilder {
             } else {
                 ValidatorIpSelection::Localhost
             },
+            consensus_protocol: ConsensusProtocol::Narwhal,
         }
     }
 }
@@ -92,6 +94,11 @@ impl<R> ConfigBuilder<R> {
         self
     }
 
+    pub fn consensus_protocol(mut self, consensus_protocol: ConsensusProtocol) -> Self {
+        self.consensus_protocol = consensus_protocol;
+        self
+    }
+
     pub fn rng<N: ::rand::RngCore + ::rand::CryptoRng>(self, rng: N) -> ConfigBuilder<N> {
         ConfigBuilder {
             rng: Some(rng),
@@ -101,6 +108,7 @@ impl<R> ConfigBuilder<R> {
             initial_accounts_config: self.initial_accounts_config,
             with_swarm: self.with_swarm,
             validator_ip_sel: self.validator_ip_sel,
+            consensus_protocol: self.consensus_protocol,
         }
     }
 }
@@ -262,6 +270,8 @@ impl<R: ::rand::RngCore + ::rand::CryptoRng> ConfigBuilder<R> {
                     consensus_address,
                     consensus_db_path,
                     delay_step: Some(15_000),
+                    max_pending_transactions_per_shared_object: None,
+                    protocol: self.consensus_protocol,
                     narwhal_config: Default::default(),
                 };
 
@@ -274,6 +284,8 @@ impl<R: ::rand::RngCore + ::rand::CryptoRng> ConfigBuilder<R> {
                     network_address,
                     metrics_address: utils::available_local_socket_address(),
                     admin_interface_port: utils::get_available_port(),
+                    admin_interface_address: None,
+                    admin_interface_token: None,
                     json_rpc_address: utils::available_local_socket_address(),
                     websocket_address: None,
                     consensus_config: Some(consensus_config),
@@ -281,6 +293,9 @@ impl<R: ::rand::RngCore + ::rand::CryptoRng> ConfigBuilder<R> {
                     enable_gossip: true,
                     enable_checkpoint: true,
                     enable_reconfig: false,
+                    enable_checkpoint_sync: false,
+                    state_sync_peers: vec![],
+                    checkpoint_archive_path: None,
                     genesis: crate::node::Genesis::new(genesis.clone()),
                     grpc_load_shed: initial_accounts_config.grpc_load_shed,
                     grpc_concurrency_limit: initial_accounts_config.grpc_concurrency_limit,
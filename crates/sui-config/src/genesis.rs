@@ -509,6 +509,7 @@ fn process_package(
         &mut temporary_store,
         &vm,
         modules,
+        BTreeMap::new(),
         ctx,
         &mut gas_status,
     )?;
@@ -18,7 +18,7 @@ pub mod node;
 mod swarm;
 pub mod utils;
 
-pub use node::{ConsensusConfig, NodeConfig, ValidatorInfo};
+pub use node::{ConsensusConfig, ConsensusProtocol, NodeConfig, ValidatorInfo};
 pub use swarm::NetworkConfig;
 
 const SUI_DIR: &str = ".sui";
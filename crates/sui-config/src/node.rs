@@ -9,7 +9,7 @@ use narwhal_config::Parameters as ConsensusParameters;
 use serde::{Deserialize, Serialize};
 use serde_with::serde_as;
 use std::collections::BTreeMap;
-use std::net::SocketAddr;
+use std::net::{IpAddr, SocketAddr};
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use sui_types::base_types::SuiAddress;
@@ -54,6 +54,14 @@ pub struct NodeConfig {
     pub metrics_address: SocketAddr,
     #[serde(default = "default_admin_interface_port")]
     pub admin_interface_port: u16,
+    /// The address the admin interface listens on, which must be a loopback address. Defaults to
+    /// `127.0.0.1`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub admin_interface_address: Option<IpAddr>,
+    /// If set, requests to the admin interface must carry this token as a bearer token in
+    /// their `Authorization` header.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub admin_interface_token: Option<String>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
     pub consensus_config: Option<ConsensusConfig>,
@@ -70,6 +78,23 @@ pub struct NodeConfig {
     #[serde(default)]
     pub enable_reconfig: bool,
 
+    /// When set, a full node syncs only by downloading and executing certified checkpoints,
+    /// instead of following the batch streams of validators.
+    #[serde(default)]
+    pub enable_checkpoint_sync: bool,
+
+    /// Other full nodes to sync checkpoints, certificates and effects from, before falling back
+    /// to validators. Everything they serve is verified, so they do not need to be trusted.
+    #[serde(default)]
+    pub state_sync_peers: Vec<Multiaddr>,
+
+    /// If set, every certified checkpoint is archived, with its contents, certificates and
+    /// effects, as compressed files below this directory. A full node with checkpoint sync
+    /// enabled also restores the checkpoints it has not synced yet from this directory on
+    /// startup.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub checkpoint_archive_path: Option<PathBuf>,
+
     #[serde(default)]
     pub grpc_load_shed: Option<bool>,
 
@@ -165,10 +190,35 @@ pub struct ConsensusConfig {
     pub consensus_address: Multiaddr,
     pub consensus_db_path: PathBuf,
     pub delay_step: Option<u64>,
+    /// The maximum number of certificates touching the same shared object that may be
+    /// sequenced at the same time. No limit if not set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_pending_transactions_per_shared_object: Option<usize>,
+    /// The consensus protocol sequencing the transactions of this validator.
+    #[serde(default, skip_serializing_if = "ConsensusProtocol::is_narwhal")]
+    pub protocol: ConsensusProtocol,
 
     pub narwhal_config: ConsensusParameters,
 }
 
+/// The consensus protocols a validator can sequence its transactions with.
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum ConsensusProtocol {
+    /// Run Narwhal primaries and workers along the validator.
+    #[default]
+    Narwhal,
+    /// Sequence transactions in-process, in the order they are submitted. Only suitable for a
+    /// committee made of a single validator, e.g. for local development networks and tests.
+    SingleNode,
+}
+
+impl ConsensusProtocol {
+    pub fn is_narwhal(&self) -> bool {
+        matches!(self, ConsensusProtocol::Narwhal)
+    }
+}
+
 impl ConsensusConfig {
     pub fn address(&self) -> &Multiaddr {
         &self.consensus_address
@@ -101,6 +101,8 @@ impl NetworkConfig {
             network_address: utils::new_network_address(),
             metrics_address: utils::available_local_socket_address(),
             admin_interface_port: utils::get_available_port(),
+            admin_interface_address: None,
+            admin_interface_token: None,
             json_rpc_address: utils::available_local_socket_address(),
             websocket_address: if enable_websocket {
                 Some(utils::available_local_socket_address())
@@ -112,6 +114,9 @@ impl NetworkConfig {
             enable_gossip: true,
             enable_checkpoint: true,
             enable_reconfig: false,
+            enable_checkpoint_sync: false,
+            state_sync_peers: vec![],
+            checkpoint_archive_path: None,
             genesis: validator_config.genesis.clone(),
             grpc_load_shed: None,
             grpc_concurrency_limit: None,
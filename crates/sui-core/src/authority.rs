@@ -20,7 +20,10 @@ use chrono::prelude::*;
 use fastcrypto::ed25519::Ed25519KeyPair as ConsensusKeyPair;
 use fastcrypto::traits::KeyPair;
 use move_bytecode_utils::module_cache::SyncModuleCache;
-use move_core_types::{language_storage::ModuleId, resolver::ModuleResolver};
+use move_core_types::{
+    language_storage::{ModuleId, StructTag},
+    resolver::ModuleResolver,
+};
 use move_vm_runtime::{move_vm::MoveVM, native_functions::NativeFunctionTable};
 use narwhal_config::{
     Committee as ConsensusCommittee, WorkerCache as ConsensusWorkerCache,
@@ -36,12 +39,13 @@ use prometheus::{
 use std::ops::Deref;
 use std::path::PathBuf;
 use std::{
-    collections::{HashMap, VecDeque},
+    collections::{BTreeMap, HashMap, VecDeque},
     pin::Pin,
     sync::{
         atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering},
         Arc,
     },
+    time::Duration,
 };
 use sui_adapter::adapter;
 use sui_adapter::temporary_store::InnerTemporaryStore;
@@ -57,12 +61,15 @@ use sui_types::{
     batch::{TxSequenceNumber, UpdateItem},
     committee::Committee,
     crypto::AuthoritySignature,
+    display::DisplayVersionUpdatedEvent,
     error::{SuiError, SuiResult},
+    event::Event,
     fp_ensure,
+    gas::GasProfile,
     messages::*,
     object::{Object, ObjectFormatOptions, ObjectRead},
     storage::{BackingPackageStore, DeleteKind},
-    MOVE_STDLIB_ADDRESS, SUI_FRAMEWORK_ADDRESS, SUI_SYSTEM_STATE_OBJECT_ID,
+    MOVE_STDLIB_ADDRESS, SUI_CLOCK_OBJECT_ID, SUI_FRAMEWORK_ADDRESS, SUI_SYSTEM_STATE_OBJECT_ID,
 };
 use tap::TapFallible;
 use thiserror::Error;
@@ -94,7 +101,7 @@ pub use sui_adapter::temporary_store::TemporaryStore;
 pub mod authority_store_tables;
 
 mod authority_store;
-use crate::epoch::epoch_store::EpochStore;
+use crate::epoch::epoch_store::{EpochEconomics, EpochStore};
 pub use authority_store::{
     AuthorityStore, GatewayStore, ResolverWrapper, SuiDataStore, UpdateType,
 };
@@ -110,6 +117,9 @@ pub mod authority_notifier;
 
 pub const MAX_ITEMS_LIMIT: u64 = 1_000;
 const BROADCAST_CAPACITY: usize = 10_000;
+const EPOCH_CHANGE_BROADCAST_CAPACITY: usize = 16;
+/// The minimum time between two submissions of the time of this authority to consensus.
+const CLOCK_TIMESTAMP_INTERVAL_MS: u64 = 100;
 
 pub(crate) const MAX_TX_RECOVERY_RETRY: u32 = 3;
 type CertTxGuard<'a> = DBTxGuard<'a, CertifiedTransaction>;
@@ -398,8 +408,14 @@ pub struct AuthorityState {
     // Cache the latest checkpoint number to avoid expensive locking to access checkpoint store
     latest_checkpoint_num: AtomicU64,
 
+    /// The latest time of this authority submitted to consensus, to advance the Clock.
+    last_clock_timestamp_ms: AtomicU64,
+
     /// A channel to tell consensus to reconfigure.
     tx_reconfigure_consensus: Sender<ReconfigConsensusMessage>,
+
+    /// Notifies the number of every new epoch, once this authority finished moving to it.
+    epoch_change_channel: tokio::sync::broadcast::Sender<EpochId>,
 }
 
 /// The authority state encapsulates all state, drives execution, and ensures safety.
@@ -418,6 +434,11 @@ impl AuthorityState {
         self.batch_channels.subscribe()
     }
 
+    /// Get a broadcast receiver for the epochs this authority moves to
+    pub fn subscribe_epoch_change(&self) -> tokio::sync::broadcast::Receiver<EpochId> {
+        self.epoch_change_channel.subscribe()
+    }
+
     pub fn epoch(&self) -> EpochId {
         self.committee.load().epoch
     }
@@ -757,7 +778,7 @@ impl AuthorityState {
         transaction_digest: TransactionDigest,
     ) -> SuiResult<(InnerTemporaryStore, SignedTransactionEffects)> {
         let start_ts = Instant::now();
-        let (gas_status, input_objects) =
+        let (mut gas_status, mut input_objects) =
             transaction_input_checker::check_transaction_input(&self.database, certificate).await?;
 
         // At this point we need to check if any shared objects need locks,
@@ -771,6 +792,18 @@ impl AuthorityState {
             // TODO: Add some assert here to make sure consensus is indeed off with is_system_tx.
             self.check_shared_locks(&transaction_digest, &shared_object_refs)
                 .await?;
+
+            // The Clock observed by the transaction is assigned along with the shared locks.
+            if shared_object_refs
+                .iter()
+                .any(|(object_id, _, _)| *object_id == SUI_CLOCK_OBJECT_ID)
+            {
+                let timestamp_ms = self
+                    .database
+                    .get_assigned_clock_timestamp(&transaction_digest)?
+                    .ok_or(SuiError::SharedObjectLockNotSetObject)?;
+                input_objects.set_clock_timestamp(timestamp_ms);
+            }
         }
 
         debug!(
@@ -790,7 +823,7 @@ impl AuthorityState {
                 transaction_dependencies,
                 &self.move_vm,
                 &self._native_functions,
-                gas_status,
+                &mut gas_status,
                 self.epoch(),
             );
 
@@ -802,6 +835,41 @@ impl AuthorityState {
         Ok((inner_temp_store, signed_effects))
     }
 
+    /// Execute `transaction` against the current state of this authority without committing it or
+    /// locking its inputs, returning the effects it would have. When `profile_gas` is set, also
+    /// returns where the gas of the transaction goes. The signature of the transaction is not
+    /// checked, and shared objects are read at their latest version.
+    pub async fn dry_exec_transaction(
+        &self,
+        transaction: &Transaction,
+        profile_gas: bool,
+    ) -> SuiResult<(TransactionEffects, Option<GasProfile>)> {
+        let transaction_digest = *transaction.digest();
+        let (mut gas_status, input_objects) =
+            transaction_input_checker::check_transaction_input(&self.database, transaction).await?;
+        if profile_gas {
+            gas_status.enable_profiling();
+        }
+
+        let shared_object_refs = input_objects.filter_shared_objects();
+        let transaction_dependencies = input_objects.transaction_dependencies();
+        let temporary_store =
+            TemporaryStore::new(self.database.clone(), input_objects, transaction_digest);
+        let (_inner_temp_store, effects, _execution_error) =
+            execution_engine::execute_transaction_to_effects(
+                shared_object_refs,
+                temporary_store,
+                transaction.signed_data.data.clone(),
+                transaction_digest,
+                transaction_dependencies,
+                &self.move_vm,
+                &self._native_functions,
+                &mut gas_status,
+                self.epoch(),
+            );
+        Ok((effects, gas_status.take_gas_profile()))
+    }
+
     pub async fn check_tx_already_executed(
         &self,
         digest: &TransactionDigest,
@@ -836,6 +904,20 @@ impl AuthorityState {
                 .move_calls()
                 .iter()
                 .map(|mc| (mc.package.0, mc.module.clone(), mc.function.clone())),
+            effects
+                .effects
+                .events
+                .iter()
+                .filter_map(|event| match event {
+                    Event::MoveEvent {
+                        type_, contents, ..
+                    } => {
+                        let displayed = DisplayVersionUpdatedEvent::displayed_type(type_)?;
+                        let update: DisplayVersionUpdatedEvent = bcs::from_bytes(contents).ok()?;
+                        Some((displayed.clone(), update))
+                    }
+                    _ => None,
+                }),
             seq,
             digest,
             timestamp_ms,
@@ -931,11 +1013,73 @@ impl AuthorityState {
         Ok(())
     }
 
+    /// Wait until this authority has executed the transaction `digest`, or `timeout` elapses.
+    /// Returns whether the effects were committed locally in time.
+    pub async fn wait_for_local_execution(
+        &self,
+        digest: &TransactionDigest,
+        timeout: Duration,
+    ) -> SuiResult<bool> {
+        // Subscribe before checking the store, so that an execution in between is not missed.
+        let mut subscriber = self.subscribe_batch();
+        if self.database.effects_exists(digest)? {
+            return Ok(true);
+        }
+
+        let wait = async {
+            loop {
+                match subscriber.recv().await {
+                    Ok(UpdateItem::Transaction((_, digests))) if digests.transaction == *digest => {
+                        return Ok(true);
+                    }
+                    Ok(_) => (),
+                    // Some updates were skipped, the transaction may be among them.
+                    Err(RecvError::Lagged(_)) => {
+                        if self.database.effects_exists(digest)? {
+                            return Ok(true);
+                        }
+                    }
+                    Err(RecvError::Closed) => {
+                        return Err(SuiError::GenericAuthorityError {
+                            error: "Batch channel closed while waiting for execution".into(),
+                        });
+                    }
+                }
+            }
+        };
+
+        match tokio::time::timeout(timeout, wait).await {
+            Ok(result) => result,
+            Err(_) => Ok(false),
+        }
+    }
+
     pub fn unixtime_now_ms() -> u64 {
         let ts_ms = Utc::now().timestamp_millis();
         u64::try_from(ts_ms).expect("Travelling in time machine")
     }
 
+    /// Sign the current time of this authority, to be submitted to consensus to advance the Clock.
+    /// Returns None if it was already done less than `CLOCK_TIMESTAMP_INTERVAL_MS` ago.
+    pub fn clock_timestamp_to_submit(&self) -> Option<SignedClockTimestamp> {
+        let now_ms = Self::unixtime_now_ms();
+        let last_ms = self.last_clock_timestamp_ms.load(Ordering::Relaxed);
+        if now_ms < last_ms + CLOCK_TIMESTAMP_INTERVAL_MS
+            || self
+                .last_clock_timestamp_ms
+                .compare_exchange(last_ms, now_ms, Ordering::Relaxed, Ordering::Relaxed)
+                .is_err()
+        {
+            return None;
+        }
+        Some(SignedClockTimestamp::new(
+            self.epoch(),
+            now_ms,
+            self.name,
+            &*self.secret,
+        ))
+    }
+
     pub async fn handle_transaction_info_request(
         &self,
         request: TransactionInfoRequest,
@@ -1166,6 +1310,8 @@ impl AuthorityState {
         tx_reconfigure_consensus: Sender<ReconfigConsensusMessage>,
     ) -> Self {
         let (tx, _rx) = tokio::sync::broadcast::channel(BROADCAST_CAPACITY);
+        let (epoch_change_channel, _rx) =
+            tokio::sync::broadcast::channel(EPOCH_CHANGE_BROADCAST_CAPACITY);
         let native_functions =
             sui_framework::natives::all_natives(MOVE_STDLIB_ADDRESS, SUI_FRAMEWORK_ADDRESS);
         let move_vm = Arc::new(
@@ -1215,7 +1361,9 @@ impl AuthorityState {
             consensus_guardrail: AtomicUsize::new(0),
             metrics: Arc::new(AuthorityMetrics::new(prometheus_registry)),
             latest_checkpoint_num: AtomicU64::new(0),
+            last_clock_timestamp_ms: AtomicU64::new(0),
             tx_reconfigure_consensus,
+            epoch_change_channel,
         };
 
         // Process tx recovery log first, so that the batch and checkpoint recovery (below)
@@ -1346,10 +1494,12 @@ impl AuthorityState {
                     // either.
                     error!(
                         ?digest,
-                        "Abandoning in-progress TX after {} retries.", MAX_TX_RECOVERY_RETRY
+                        "Quarantining in-progress TX after {} retries.", MAX_TX_RECOVERY_RETRY
                     );
-                    // prevent the tx from going back into the recovery list again.
-                    tx_guard.release();
+                    // Move the tx aside so that it does not go back into the recovery list. A
+                    // resubmitted certificate releases it for a bounded number of further
+                    // attempts, and it can be inspected or dropped with `sui-tool wal`.
+                    tx_guard.quarantine();
                     continue;
                 }
 
@@ -1396,11 +1546,45 @@ impl AuthorityState {
         self.epoch_store
             .epochs
             .insert(&cur_epoch, &AuthenticatedEpoch::Signed(signed_epoch))?;
-        // TODO: Do we want to make it possible to subscribe to committee changes?
         self.committee.swap(Arc::new(new_committee));
         Ok(())
     }
 
+    /// Move a full node to the committee of a certified epoch after its current one. Validators
+    /// sign the new epoch instead, see `sign_new_epoch_and_update_committee`.
+    pub(crate) fn update_committee(&self, new_committee: Committee) -> SuiResult {
+        fp_ensure!(
+            self.epoch() < new_committee.epoch,
+            SuiError::from("Cannot move to an older epoch")
+        );
+        fp_ensure!(
+            matches!(
+                self.epoch_store
+                    .get_authenticated_epoch(&new_committee.epoch)?,
+                Some(AuthenticatedEpoch::Certified(_))
+            ),
+            SuiError::from("The new epoch is not certified")
+        );
+        self.committee.swap(Arc::new(new_committee));
+        Ok(())
+    }
+
+    /// Record the economics of the epoch that just ended, if known, and notify subscribers that
+    /// this authority moved to the next epoch.
+    pub(crate) fn record_epoch_change(
+        &self,
+        finished_epoch: EpochId,
+        economics: Option<EpochEconomics>,
+    ) -> SuiResult {
+        if let Some(economics) = economics {
+            self.epoch_store
+                .record_epoch_economics(finished_epoch, economics)?;
+        }
+        // Sending only fails when nobody is subscribed.
+        let _ = self.epoch_change_channel.send(finished_epoch + 1);
+        Ok(())
+    }
+
     pub(crate) fn promote_signed_epoch_to_cert(&self, cert: CertifiedEpoch) -> SuiResult {
         Ok(self.epoch_store.epochs.insert(
             &cert.epoch_info.epoch(),
@@ -1587,6 +1771,15 @@ impl AuthorityState {
         Ok(self.get_indexes()?.get_timestamp_ms(digest)?)
     }
 
+    /// Returns the fields to display objects of type `type_` with, as set by the publisher of the
+    /// type through `sui::display`.
+    pub async fn get_display_fields(
+        &self,
+        type_: &StructTag,
+    ) -> Result<Option<BTreeMap<String, String>>, anyhow::Error> {
+        Ok(self.get_indexes()?.get_display(type_)?)
+    }
+
     pub async fn get_transactions_by_input_object(
         &self,
         object: ObjectID,
@@ -1989,6 +2182,34 @@ impl ExecutionState for AuthorityState {
                 // TODO [2533]: edit once integrating Narwhal reconfiguration
                 Ok(Vec::default())
             }
+            ConsensusTransactionKind::ClockTimestamp(timestamp) => {
+                let committee = self.committee.load();
+                timestamp
+                    .verify(&committee)
+                    .map_err(NarwhalHandlerError::SkipNarwhalTransaction)?;
+
+                // NOTE: Recording a timestamp is idempotent, so we don't need to persist the
+                // consensus index either.
+                let consensus_timestamp = self
+                    .database
+                    .record_clock_timestamp(
+                        *timestamp.authority(),
+                        timestamp.timestamp_ms(),
+                        &committee,
+                    )
+                    .map_err(NarwhalHandlerError::NodeError)?;
+
+                debug!(
+                    ?consensus_index,
+                    ?tracking_id,
+                    ?consensus_timestamp,
+                    "handle_consensus_transaction ClockTimestamp. Authority: {}",
+                    timestamp.authority(),
+                );
+
+                // TODO [2533]: edit once integrating Narwhal reconfiguration
+                Ok(Vec::default())
+            }
         }
     }
 
@@ -13,12 +13,16 @@ use std::{fmt::Debug, path::PathBuf};
 use sui_storage::{
     mutex_table::{LockGuard, MutexTable},
     write_ahead_log::{DBWriteAheadLog, WriteAheadLog},
-    LockService,
+    LockService, LockServiceStats,
 };
 use sui_types::batch::{SignedBatch, TxSequenceNumber};
+use sui_types::clock::Clock;
 use sui_types::crypto::{AuthoritySignInfo, EmptySignInfo};
 use sui_types::object::{Owner, OBJECT_START_VERSION};
-use sui_types::{base_types::SequenceNumber, storage::ParentSync};
+use sui_types::{
+    base_types::SequenceNumber,
+    storage::{check_child_object_owner, ChildObjectResolver, ParentSync},
+};
 use tokio::sync::Notify;
 use tokio_retry::strategy::{jitter, ExponentialBackoff};
 use tracing::{debug, error, info, trace};
@@ -39,6 +43,9 @@ const SHARD_SIZE: usize = 128;
 // TODO: Make a single table (e.g., called `variables`) storing all our lonely variables in one place.
 const LAST_CONSENSUS_INDEX_ADDR: u64 = 0;
 
+/// The key where the latest consensus timestamp is stored in the database.
+const CONSENSUS_TIMESTAMP_ADDR: u64 = 0;
+
 /// ALL_OBJ_VER determines whether we want to store all past
 /// versions of every object in the store. Authority doesn't store
 /// them, but other entities such as replicas will.
@@ -56,6 +63,9 @@ pub struct SuiDataStore<S> {
     /// Internal vector of locks to manage concurrent writes to the database
     mutex_table: MutexTable<ObjectDigest>,
 
+    /// Serializes writes of the Clock, which is stored without changing its version.
+    clock_lock: parking_lot::Mutex<()>,
+
     // The next sequence number.
     next_pending_seq: AtomicU64,
     // A notifier for new pending certificates
@@ -93,6 +103,7 @@ impl<S: Eq + Debug + Serialize + for<'de> Deserialize<'de>> SuiDataStore<S> {
             wal,
             lock_service,
             mutex_table: MutexTable::new(NUM_SHARDS, SHARD_SIZE),
+            clock_lock: parking_lot::Mutex::new(()),
             next_pending_seq,
             pending_notifier: Arc::new(Notify::new()),
             tables,
@@ -235,6 +246,11 @@ impl<S: Eq + Debug + Serialize + for<'de> Deserialize<'de>> SuiDataStore<S> {
         Ok(self.tables.pending_execution.iter().collect())
     }
 
+    /// Current queue lengths of the LockService backing this store.
+    pub fn lock_service_stats(&self) -> LockServiceStats {
+        self.lock_service.stats()
+    }
+
     /// Remove entries from pending certificates
     pub fn remove_pending_certificates(&self, seqs: Vec<InternalSequenceNumber>) -> SuiResult<()> {
         let batch = self.tables.pending_execution.batch();
@@ -445,6 +461,17 @@ impl<S: Eq + Debug + Serialize + for<'de> Deserialize<'de>> SuiDataStore<S> {
             }))
     }
 
+    /// Read the timestamp of the Clock assigned to a transaction reading it.
+    pub fn get_assigned_clock_timestamp(
+        &self,
+        transaction_digest: &TransactionDigest,
+    ) -> SuiResult<Option<u64>> {
+        Ok(self
+            .tables
+            .assigned_clock_timestamps
+            .get(transaction_digest)?)
+    }
+
     /// Read a lock for a specific (transaction, shared object) pair.
     pub fn get_assigned_object_versions<'a>(
         &self,
@@ -762,14 +789,23 @@ impl<S: Eq + Debug + Serialize + for<'de> Deserialize<'de>> SuiDataStore<S> {
             mutable_inputs: active_inputs,
             written,
             deleted,
+            loaded_child_objects,
         } = inner_temporary_store;
         trace!(written =? written.values().map(|((obj_id, ver, _), _)| (obj_id, ver)).collect::<Vec<_>>(),
                "batch_update_objects: temp store written");
 
+        // Child objects loaded during execution are owned by their parent, and their locks are
+        // replaced like those of the owned inputs if they were written or deleted.
         let owned_inputs: Vec<_> = active_inputs
             .iter()
             .filter(|(id, _, _)| objects.get(id).unwrap().is_owned_or_quasi_shared())
             .cloned()
+            .chain(
+                loaded_child_objects
+                    .iter()
+                    .filter(|(id, _)| written.contains_key(id) || deleted.contains_key(id))
+                    .map(|(_, object)| object.compute_object_reference()),
+            )
             .collect();
 
         // Make an iterator over all objects that are either deleted or have changed owner,
@@ -782,17 +818,20 @@ impl<S: Eq + Debug + Serialize + for<'de> Deserialize<'de>> SuiDataStore<S> {
             // We need to call get() on objects because some object that were just deleted may not
             // be in the objects list. This can happen if these deleted objects were wrapped in the past,
             // and hence will not show up in the input objects.
-            .filter_map(|(id, _)| objects.get(id).and_then(Object::get_owner_and_id))
-            .chain(
-                written
-                    .iter()
-                    .filter_map(|(id, (_, new_object))| match objects.get(id) {
-                        Some(old_object) if old_object.owner != new_object.owner => {
-                            old_object.get_owner_and_id()
-                        }
-                        _ => None,
-                    }),
-            );
+            .filter_map(|(id, _)| {
+                objects
+                    .get(id)
+                    .or_else(|| loaded_child_objects.get(id))
+                    .and_then(Object::get_owner_and_id)
+            })
+            .chain(written.iter().filter_map(|(id, (_, new_object))| {
+                match objects.get(id).or_else(|| loaded_child_objects.get(id)) {
+                    Some(old_object) if old_object.owner != new_object.owner => {
+                        old_object.get_owner_and_id()
+                    }
+                    _ => None,
+                }
+            }));
 
         // Delete the old owner index entries
         write_batch = write_batch.delete_batch(&self.tables.owner_index, old_object_owners)?;
@@ -857,6 +896,11 @@ impl<S: Eq + Debug + Serialize + for<'de> Deserialize<'de>> SuiDataStore<S> {
         write_batch.write()?;
         trace!("Finished writing batch");
 
+        // The Clock observed by the transaction, with the consensus timestamp it was sequenced at.
+        if let Some(clock) = objects.get(&SUI_CLOCK_OBJECT_ID) {
+            self.update_clock(clock)?;
+        }
+
         // Need to have a critical section for now because we need to prevent execution of older
         // certs which may overwrite newer objects with older ones.  This can be removed once we have
         // an object storage supporting multiple object versions at once, then there is idempotency and
@@ -916,6 +960,33 @@ impl<S: Eq + Debug + Serialize + for<'de> Deserialize<'de>> SuiDataStore<S> {
         Ok(assigned_seq)
     }
 
+    /// Store `clock` unless the stored Clock has a later timestamp. Transactions only read the
+    /// Clock, so its version does not change, but the stored Clock follows the latest timestamp
+    /// observed by a transaction so that reading it outside of transactions is up to date.
+    fn update_clock(&self, clock: &Object) -> SuiResult {
+        let _guard = self.clock_lock.lock();
+        let timestamp_ms = Clock::from_object(clock).timestamp_ms;
+        if let Some(stored) = self.get_object(&SUI_CLOCK_OBJECT_ID)? {
+            if Clock::from_object(&stored).timestamp_ms >= timestamp_ms {
+                return Ok(());
+            }
+        }
+        let clock_ref = clock.compute_object_reference();
+        let mut write_batch = self.tables.objects.batch();
+        write_batch = write_batch.insert_batch(
+            &self.tables.objects,
+            std::iter::once((ObjectKey::from(&clock_ref), clock)),
+        )?;
+        if let Some(owner_id) = clock.get_owner_and_id() {
+            write_batch = write_batch.insert_batch(
+                &self.tables.owner_index,
+                std::iter::once((owner_id, ObjectInfo::new(&clock_ref, clock))),
+            )?;
+        }
+        write_batch.write()?;
+        Ok(())
+    }
+
     /// This function is called at the end of epoch for each transaction that's
     /// executed locally on the validator but didn't make to the last checkpoint.
     /// The effects of the execution is reverted here.
@@ -1043,6 +1114,10 @@ impl<S: Eq + Debug + Serialize + for<'de> Deserialize<'de>> SuiDataStore<S> {
             write_batch.delete_batch(&self.tables.assigned_object_versions, sequenced_to_delete)?;
         write_batch =
             write_batch.delete_batch(&self.tables.next_object_versions, schedule_to_delete)?;
+        write_batch = write_batch.delete_batch(
+            &self.tables.assigned_clock_timestamps,
+            iter::once(*transaction_digest),
+        )?;
         write_batch.write()?;
         Ok(())
     }
@@ -1068,6 +1143,12 @@ impl<S: Eq + Debug + Serialize + for<'de> Deserialize<'de>> SuiDataStore<S> {
 
         let mut write_batch = self.tables.assigned_object_versions.batch();
         write_batch = write_batch.insert_batch(&self.tables.assigned_object_versions, sequenced)?;
+        write_batch = write_batch.insert_batch(
+            &self.tables.assigned_clock_timestamps,
+            effects
+                .clock_timestamp_ms
+                .map(|timestamp_ms| (digest, timestamp_ms)),
+        )?;
         write_batch.write()?;
 
         Ok(())
@@ -1106,7 +1187,12 @@ impl<S: Eq + Debug + Serialize + for<'de> Deserialize<'de>> SuiDataStore<S> {
                 // sequence number (`OBJECT_START_VERSION`). Otherwise use the `scheduled` map to
                 // to assign the next sequence number.
                 let version = v.unwrap_or_else(|| OBJECT_START_VERSION);
-                let next_version = version.increment();
+                // The Clock is read-only, so transactions reading it do not depend on each other.
+                let next_version = if *id == SUI_CLOCK_OBJECT_ID {
+                    version
+                } else {
+                    version.increment()
+                };
 
                 let sequenced = ((transaction_digest, *id), version);
                 let scheduled = (id, next_version);
@@ -1115,8 +1201,19 @@ impl<S: Eq + Debug + Serialize + for<'de> Deserialize<'de>> SuiDataStore<S> {
             })
             .unzip();
 
+        // Transactions reading the Clock observe the consensus timestamp at which they are
+        // sequenced.
+        let clock_timestamp_to_write = if certificate
+            .shared_input_objects()
+            .any(|id| *id == SUI_CLOCK_OBJECT_ID)
+        {
+            Some((transaction_digest, self.consensus_timestamp()?))
+        } else {
+            None
+        };
+
         trace!(tx_digest = ?transaction_digest,
-               ?sequenced_to_write, ?schedule_to_write,
+               ?sequenced_to_write, ?schedule_to_write, ?clock_timestamp_to_write,
                "locking shared objects");
 
         // Make an iterator to update the last consensus index.
@@ -1159,6 +1256,10 @@ impl<S: Eq + Debug + Serialize + for<'de> Deserialize<'de>> SuiDataStore<S> {
         if !self.effects_exists(&transaction_digest)? {
             write_batch = write_batch
                 .insert_batch(&self.tables.assigned_object_versions, sequenced_to_write)?;
+            write_batch = write_batch.insert_batch(
+                &self.tables.assigned_clock_timestamps,
+                clock_timestamp_to_write,
+            )?;
         }
 
         write_batch =
@@ -1299,6 +1400,77 @@ impl<S: Eq + Debug + Serialize + for<'de> Deserialize<'de>> SuiDataStore<S> {
             .map_err(SuiError::from)
     }
 
+    /// Return the latest consensus timestamp, or 0 if no authority reported its time yet.
+    pub fn consensus_timestamp(&self) -> SuiResult<u64> {
+        self.tables
+            .consensus_timestamp
+            .get(&CONSENSUS_TIMESTAMP_ADDR)
+            .map(|x| x.unwrap_or_default())
+            .map_err(SuiError::from)
+    }
+
+    /// Record the time of `authority` sequenced by consensus, and advance the consensus timestamp.
+    /// The consensus timestamp is the highest time reached by authorities with at least a validity
+    /// threshold of stake, so that it is never ahead of the clocks of all honest authorities.
+    /// It never decreases. This function must only be called from the consensus task (i.e. from
+    /// handle_consensus_transaction), and returns the updated consensus timestamp.
+    pub fn record_clock_timestamp(
+        &self,
+        authority: AuthorityName,
+        timestamp_ms: u64,
+        committee: &Committee,
+    ) -> SuiResult<u64> {
+        let previous_timestamp = self.consensus_timestamp()?;
+        let authority_timestamp = self
+            .tables
+            .authority_clock_timestamps
+            .get(&authority)?
+            .unwrap_or_default();
+        if timestamp_ms <= authority_timestamp {
+            return Ok(previous_timestamp);
+        }
+
+        let names: Vec<_> = committee.names().copied().collect();
+        let stored_timestamps = self.tables.authority_clock_timestamps.multi_get(&names)?;
+        let mut timestamps: Vec<_> = names
+            .iter()
+            .zip(stored_timestamps)
+            .map(|(name, t)| {
+                let t = if *name == authority {
+                    timestamp_ms
+                } else {
+                    t.unwrap_or_default()
+                };
+                (t, committee.weight(name))
+            })
+            .collect();
+        // Sorting by ascending time, the time reached by a quorum has at least a validity
+        // threshold of stake at or above it.
+        timestamps.sort();
+        let mut stake = 0;
+        let timestamp = timestamps
+            .into_iter()
+            .find(|(_, weight)| {
+                stake += weight;
+                stake >= committee.quorum_threshold()
+            })
+            .map(|(t, _)| t)
+            .unwrap_or_default();
+        let timestamp = timestamp.max(previous_timestamp);
+
+        let mut write_batch = self.tables.authority_clock_timestamps.batch();
+        write_batch = write_batch.insert_batch(
+            &self.tables.authority_clock_timestamps,
+            iter::once((authority, timestamp_ms)),
+        )?;
+        write_batch = write_batch.insert_batch(
+            &self.tables.consensus_timestamp,
+            iter::once((CONSENSUS_TIMESTAMP_ADDR, timestamp)),
+        )?;
+        write_batch.write()?;
+        Ok(timestamp)
+    }
+
     pub fn get_transaction(
         &self,
         transaction_digest: &TransactionDigest,
@@ -1375,6 +1547,16 @@ impl<S: Eq + Debug + Serialize + for<'de> Deserialize<'de>> BackingPackageStore
     }
 }
 
+impl<S: Eq + Debug + Serialize + for<'de> Deserialize<'de>> ChildObjectResolver
+    for SuiDataStore<S>
+{
+    fn read_child_object(&self, parent: &ObjectID, child: &ObjectID) -> SuiResult<Option<Object>> {
+        self.get_object(child)?
+            .map(|child| check_child_object_owner(parent, child))
+            .transpose()
+    }
+}
+
 impl<S: Eq + Debug + Serialize + for<'de> Deserialize<'de>> ParentSync for SuiDataStore<S> {
     fn get_latest_parent_entry_ref(&self, object_id: ObjectID) -> SuiResult<Option<ObjectRef>> {
         Ok(self
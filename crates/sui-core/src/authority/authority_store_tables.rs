@@ -87,6 +87,15 @@ pub struct AuthorityStoreTables<S> {
     /// epoch change.
     pub(crate) consensus_message_processed: DBMap<TransactionDigest, bool>,
 
+    /// The timestamp of the Clock observed by each transaction reading it. Like the locks of its
+    /// shared objects, it is assigned when the transaction is sequenced by consensus, or copied
+    /// from its effects.
+    pub(crate) assigned_clock_timestamps: DBMap<TransactionDigest, u64>,
+
+    /// The latest timestamp sequenced by consensus for each authority. The consensus timestamp is
+    /// derived from these.
+    pub(crate) authority_clock_timestamps: DBMap<AuthorityName, u64>,
+
     // Tables used for authority batch structure
     /// A sequence on all executed certificates and effects.
     pub executed_sequence: DBMap<TxSequenceNumber, ExecutionDigests>,
@@ -99,6 +108,11 @@ pub struct AuthorityStoreTables<S> {
     /// by a single process acting as consensus (light) client. It is used to ensure the authority processes
     /// every message output by consensus (and in the right order).
     pub(crate) last_consensus_index: DBMap<u64, ExecutionIndices>,
+
+    /// The following table is used to store a single value (the corresponding key is a constant). The value
+    /// is the latest consensus timestamp in milliseconds, which is assigned to the Clock for transactions
+    /// reading it. It is written by the same process as `last_consensus_index`, and it never decreases.
+    pub(crate) consensus_timestamp: DBMap<u64, u64>,
 }
 
 // These functions are used to initialize the DB tables
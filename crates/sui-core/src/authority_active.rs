@@ -37,7 +37,7 @@ use sui_types::{
     error::{SuiError, SuiResult},
 };
 use tokio::{
-    sync::{oneshot, Mutex, MutexGuard},
+    sync::{oneshot, watch, Mutex, MutexGuard, Notify},
     task::JoinHandle,
     time::timeout,
 };
@@ -47,7 +47,8 @@ use crate::{
     authority::AuthorityState,
     authority_aggregator::AuthorityAggregator,
     authority_client::AuthorityAPI,
-    node_sync::{node_sync_process, NodeSyncHandle, NodeSyncState},
+    node_sync::{checkpoint_sync_process, node_sync_process, NodeSyncHandle, NodeSyncState},
+    state_sync::StateSyncPeer,
 };
 use futures::pin_mut;
 use once_cell::sync::OnceCell;
@@ -61,6 +62,7 @@ use gossip::{gossip_process, GossipMetrics};
 pub mod checkpoint_driver;
 use crate::authority_active::checkpoint_driver::CheckpointMetrics;
 use crate::authority_client::NetworkAuthorityClientMetrics;
+use crate::epoch::epoch_store::EpochEconomics;
 use crate::epoch::reconfiguration::Reconfigurable;
 use checkpoint_driver::{checkpoint_process, get_latest_checkpoint_from_all, sync_to_checkpoint};
 
@@ -73,6 +75,8 @@ const MAX_RETRIES_RECORDED: u32 = 10;
 const DELAY_FOR_1_RETRY_MS: u64 = 2_000;
 const EXPONENTIAL_DELAY_BASIS: u64 = 2;
 pub const MAX_RETRY_DELAY_MS: u64 = 30_000;
+/// How often a full node asks the committee whether a new epoch was certified.
+const EPOCH_SYNC_INTERVAL: Duration = Duration::from_secs(10);
 
 #[derive(Debug)]
 pub struct AuthorityHealth {
@@ -113,7 +117,20 @@ impl AuthorityHealth {
     }
 }
 
-struct NodeSyncProcessHandle(tokio::task::JoinHandle<()>, oneshot::Sender<()>);
+/// Selects how a full node learns about new transactions to sync.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NodeSyncMode {
+    /// Follow the batch streams of validators and wait for a quorum of effects votes.
+    Follow,
+    /// Download certified checkpoints and execute their contents.
+    Checkpoint,
+}
+
+struct NodeSyncProcessHandle(
+    tokio::task::JoinHandle<()>,
+    oneshot::Sender<()>,
+    NodeSyncMode,
+);
 
 pub struct ActiveAuthority<A> {
     // The local authority state
@@ -128,6 +145,16 @@ pub struct ActiveAuthority<A> {
     // cancel sender which can be used to terminate that task gracefully.
     node_sync_process: Arc<Mutex<Option<NodeSyncProcessHandle>>>,
 
+    // The mode of the node sync process while it is paused, so that it can be resumed.
+    paused_node_sync_mode: Arc<Mutex<Option<NodeSyncMode>>>,
+
+    // Set to true to make the gossip process drop its peers until it is set back to false.
+    gossip_paused: Arc<watch::Sender<bool>>,
+
+    // Wakes up the checkpoint process to make a new proposal without waiting for the pause
+    // between checkpoints to elapse.
+    checkpoint_trigger: Arc<Notify>,
+
     // The network interfaces to other authorities
     pub net: ArcSwap<AuthorityAggregator<A>>,
     // Network health
@@ -139,6 +166,9 @@ pub struct ActiveAuthority<A> {
     // This is only meaningful if A is of type NetworkAuthorityClient,
     // and stored here for reconfiguration purposes.
     pub network_metrics: Arc<NetworkAuthorityClientMetrics>,
+
+    // Full node peers that checkpoint sync tries before falling back to validators.
+    state_sync_peers: Vec<StateSyncPeer>,
 }
 
 impl<A> ActiveAuthority<A> {
@@ -171,12 +201,23 @@ impl<A> ActiveAuthority<A> {
             node_sync_state,
             node_sync_handle: OnceCell::new(),
             node_sync_process: Default::default(),
+            paused_node_sync_mode: Default::default(),
+            gossip_paused: Arc::new(watch::channel(false).0),
+            checkpoint_trigger: Arc::new(Notify::new()),
             net: ArcSwap::from(net),
             gossip_metrics,
             network_metrics,
+            state_sync_peers: Vec::new(),
         })
     }
 
+    /// Sync checkpoints and transactions from these full nodes when possible, instead of
+    /// pulling everything from validators.
+    pub fn with_state_sync_peers(mut self, peers: Vec<StateSyncPeer>) -> Self {
+        self.state_sync_peers = peers;
+        self
+    }
+
     fn net(&self) -> Arc<AuthorityAggregator<A>> {
         self.net.load().clone()
     }
@@ -268,10 +309,14 @@ impl<A> Clone for ActiveAuthority<A> {
             node_sync_state: self.node_sync_state.clone(),
             node_sync_handle: self.node_sync_handle.clone(),
             node_sync_process: self.node_sync_process.clone(),
+            paused_node_sync_mode: self.paused_node_sync_mode.clone(),
+            gossip_paused: self.gossip_paused.clone(),
+            checkpoint_trigger: self.checkpoint_trigger.clone(),
             net: ArcSwap::from(self.net.load().clone()),
             health: self.health.clone(),
             gossip_metrics: self.gossip_metrics.clone(),
             network_metrics: self.network_metrics.clone(),
+            state_sync_peers: self.state_sync_peers.clone(),
         }
     }
 }
@@ -340,44 +385,101 @@ where
     /// Restart the node sync process only if one currently exists.
     pub async fn respawn_node_sync_process(&self) {
         let lock_guard = self.node_sync_process.lock().await;
-        if lock_guard.is_some() {
-            self.respawn_node_sync_process_impl(lock_guard).await
+        if let Some(NodeSyncProcessHandle(_, _, mode)) = &*lock_guard {
+            let mode = *mode;
+            self.respawn_node_sync_process_impl(lock_guard, mode).await
         } else {
             debug!("no active node sync process - not respawning");
         }
     }
 
+    /// Stop the node sync process, remembering its mode so that it can be resumed later.
+    /// Returns false if there is no node sync process to pause.
+    pub async fn pause_node_sync_process(&self) -> bool {
+        let mut lock_guard = self.node_sync_process.lock().await;
+        match Self::cancel_node_sync_process_impl(&mut lock_guard).await {
+            Some(mode) => {
+                info!(?mode, "node sync process paused");
+                *self.paused_node_sync_mode.lock().await = Some(mode);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Restart a node sync process that was stopped by `pause_node_sync_process`.
+    /// Returns false if the node sync process is not paused.
+    pub async fn resume_node_sync_process(&self) -> bool {
+        let lock_guard = self.node_sync_process.lock().await;
+        let mode = self.paused_node_sync_mode.lock().await.take();
+        match mode {
+            Some(mode) => {
+                self.respawn_node_sync_process_impl(lock_guard, mode).await;
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub async fn is_node_sync_paused(&self) -> bool {
+        self.paused_node_sync_mode.lock().await.is_some()
+    }
+
+    /// Make the gossip process disconnect from its peers, until `resume_gossip` is called.
+    pub fn pause_gossip(&self) {
+        info!("gossip paused");
+        self.gossip_paused.send_replace(true);
+    }
+
+    pub fn resume_gossip(&self) {
+        info!("gossip resumed");
+        self.gossip_paused.send_replace(false);
+    }
+
+    pub fn is_gossip_paused(&self) -> bool {
+        *self.gossip_paused.borrow()
+    }
+
+    pub(crate) fn subscribe_gossip_paused(&self) -> watch::Receiver<bool> {
+        self.gossip_paused.subscribe()
+    }
+
+    /// Ask the checkpoint process to make a new proposal now, rather than after the pause
+    /// between checkpoints. Has no effect if the checkpoint process is not running.
+    pub fn trigger_checkpoint_proposal(&self) {
+        info!("checkpoint proposal triggered");
+        self.checkpoint_trigger.notify_one();
+    }
+
+    /// Wait until a checkpoint proposal is triggered, returning at once if one was triggered
+    /// since the last wait.
+    pub async fn wait_for_checkpoint_trigger(&self) {
+        self.checkpoint_trigger.notified().await
+    }
+
     /// Start the node sync process.
     pub async fn spawn_node_sync_process(&self) {
         let lock_guard = self.node_sync_process.lock().await;
-        self.respawn_node_sync_process_impl(lock_guard).await
+        self.respawn_node_sync_process_impl(lock_guard, NodeSyncMode::Follow)
+            .await
+    }
+
+    /// Start the node sync process in checkpoint mode, i.e. sync only by downloading and
+    /// executing certified checkpoints.
+    pub async fn spawn_checkpoint_sync_process(&self) {
+        let lock_guard = self.node_sync_process.lock().await;
+        self.respawn_node_sync_process_impl(lock_guard, NodeSyncMode::Checkpoint)
+            .await
     }
 
     async fn respawn_node_sync_process_impl(
         &self,
         mut lock_guard: MutexGuard<'_, Option<NodeSyncProcessHandle>>,
+        mode: NodeSyncMode,
     ) {
-        info!(epoch = ?self.state.committee.load().epoch, "respawn_node_sync_process");
-
-        if let Some(NodeSyncProcessHandle(join_handle, cancel_sender)) = lock_guard.take() {
-            info!("sending cancel request to node sync task");
-            let _ = cancel_sender
-                .send(())
-                .tap_err(|_| warn!("failed to request cancellation of node sync task"));
-
-            pin_mut!(join_handle);
-
-            // try to join the task, then kill it if it doesn't cancel on its own.
-            info!("waiting node sync task to exit");
-            if timeout(Duration::from_secs(1), &mut join_handle)
-                .await
-                .is_err()
-            {
-                error!("node sync task did not terminate on its own. aborting.");
-                join_handle.abort();
-                let _ = join_handle.await;
-            }
-        }
+        info!(epoch = ?self.state.committee.load().epoch, ?mode, "respawn_node_sync_process");
+
+        Self::cancel_node_sync_process_impl(&mut lock_guard).await;
 
         let (cancel_sender, cancel_receiver) = oneshot::channel();
         let aggregator = self.net();
@@ -385,15 +487,51 @@ where
         let node_sync_handle = self.node_sync_handle();
         let node_sync_state = self.node_sync_state.clone();
 
-        info!("spawning node sync task");
-        let join_handle = tokio::task::spawn(node_sync_process(
-            node_sync_handle,
-            node_sync_state,
-            aggregator,
-            cancel_receiver,
-        ));
+        info!(?mode, "spawning node sync task");
+        let join_handle = match mode {
+            NodeSyncMode::Follow => tokio::task::spawn(node_sync_process(
+                node_sync_handle,
+                node_sync_state,
+                aggregator,
+                cancel_receiver,
+            )),
+            NodeSyncMode::Checkpoint => tokio::task::spawn(checkpoint_sync_process(
+                node_sync_handle,
+                node_sync_state,
+                aggregator,
+                self.state_sync_peers.clone(),
+                cancel_receiver,
+            )),
+        };
 
-        *lock_guard = Some(NodeSyncProcessHandle(join_handle, cancel_sender));
+        *lock_guard = Some(NodeSyncProcessHandle(join_handle, cancel_sender, mode));
+    }
+
+    /// Stop the current node sync process, if any, and return its mode.
+    async fn cancel_node_sync_process_impl(
+        lock_guard: &mut MutexGuard<'_, Option<NodeSyncProcessHandle>>,
+    ) -> Option<NodeSyncMode> {
+        let NodeSyncProcessHandle(join_handle, cancel_sender, mode) = lock_guard.take()?;
+
+        info!("sending cancel request to node sync task");
+        let _ = cancel_sender
+            .send(())
+            .tap_err(|_| warn!("failed to request cancellation of node sync task"));
+
+        pin_mut!(join_handle);
+
+        // try to join the task, then kill it if it doesn't cancel on its own.
+        info!("waiting node sync task to exit");
+        if timeout(Duration::from_secs(1), &mut join_handle)
+            .await
+            .is_err()
+        {
+            error!("node sync task did not terminate on its own. aborting.");
+            join_handle.abort();
+            let _ = join_handle.await;
+        }
+
+        Some(mode)
     }
 
     /// Spawn pending certificate execution process
@@ -432,4 +570,54 @@ where
             checkpoint_process(&self, &checkpoint_process_control, metrics, enable_reconfig).await;
         })
     }
+
+    /// Spawn the process following epoch changes on a full node, see `sync_epochs`.
+    pub async fn spawn_epoch_sync_process(self: Arc<Self>) -> JoinHandle<()> {
+        tokio::task::spawn(async move {
+            loop {
+                if let Err(err) = self.sync_epochs().await {
+                    error!("Failed to sync epochs: {:?}", err);
+                }
+                tokio::time::sleep(EPOCH_SYNC_INTERVAL).await;
+            }
+        })
+    }
+
+    /// Move a full node to the latest epoch certified by the committee. The epoch certificates are
+    /// stored, the committee of the node and of its network interfaces is updated, and epoch change
+    /// subscribers are notified of every new epoch. Returns true if the epoch changed.
+    pub async fn sync_epochs(&self) -> SuiResult<bool> {
+        let finished_epoch = self.state.epoch();
+        let new_net = match self
+            .net()
+            .reconfigure_to_latest_epoch(self.network_metrics.clone())
+            .await?
+        {
+            Some(new_net) => new_net,
+            None => return Ok(false),
+        };
+        let new_epoch = new_net.committee.epoch;
+
+        // The economics of the finished epoch are only known if this node has not executed past
+        // its end yet, since they are read from the Sui system state.
+        let system_state = self.state.get_sui_system_state_object().await?;
+        let economics = (system_state.epoch == finished_epoch).then(|| EpochEconomics {
+            reference_gas_price: system_state.reference_gas_price,
+            storage_fund: system_state.storage_fund.value(),
+            rewards_distributed: None,
+        });
+
+        self.state.update_committee(new_net.committee.clone())?;
+        self.net.store(Arc::new(new_net));
+        self.state.record_epoch_change(finished_epoch, economics)?;
+        // Epochs skipped over since the last sync are reported without economics.
+        for epoch in finished_epoch + 1..new_epoch {
+            self.state.record_epoch_change(epoch, None)?;
+        }
+        info!(epoch = new_epoch, "Full node moved to a new epoch");
+
+        // Restart the node sync process so it talks to the new committee.
+        self.respawn_node_sync_process().await;
+        Ok(true)
+    }
 }
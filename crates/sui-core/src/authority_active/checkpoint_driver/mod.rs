@@ -164,7 +164,7 @@ pub async fn checkpoint_process<A>(
     }
     info!("Start active checkpoint process.");
 
-    tokio::time::sleep(timing.long_pause_between_checkpoints).await;
+    pause_between_checkpoints(active_authority, timing).await;
 
     let mut last_cert_time = Instant::now();
 
@@ -208,7 +208,7 @@ pub async fn checkpoint_process<A>(
                                 }
                             }
                         }
-                        tokio::time::sleep(timing.long_pause_between_checkpoints).await;
+                        pause_between_checkpoints(active_authority, timing).await;
                     }
                 }
             }
@@ -269,6 +269,22 @@ pub async fn checkpoint_process<A>(
     }
 }
 
+/// Wait for the pause between checkpoints to elapse, or for a checkpoint proposal to be
+/// triggered through the active authority, whichever comes first.
+async fn pause_between_checkpoints<A>(
+    active_authority: &ActiveAuthority<A>,
+    timing: &CheckpointProcessControl,
+) where
+    A: AuthorityAPI + Send + Sync + 'static + Clone,
+{
+    tokio::select! {
+        _ = tokio::time::sleep(timing.long_pause_between_checkpoints) => (),
+        _ = active_authority.wait_for_checkpoint_trigger() => {
+            info!("Checkpoint proposal triggered, skipping the pause between checkpoints");
+        }
+    }
+}
+
 pub async fn checkpoint_process_step<A>(
     active_authority: &ActiveAuthority<A>,
     timing: &CheckpointProcessControl,
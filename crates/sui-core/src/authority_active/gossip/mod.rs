@@ -55,6 +55,9 @@ pub struct GossipMetrics {
     pub total_attempts_cert_downloads: IntCounter,
     pub total_successful_attempts_cert_downloads: IntCounter,
     pub follower_stream_duration: Histogram,
+    pub checkpoint_sync_sequence_number: IntGauge,
+    pub checkpoint_sync_peer_checkpoints: IntCounter,
+    pub checkpoint_sync_peer_transactions: IntCounter,
 }
 
 impl GossipMetrics {
@@ -108,6 +111,24 @@ impl GossipMetrics {
                 registry,
             )
                 .unwrap(),
+            checkpoint_sync_sequence_number: register_int_gauge_with_registry!(
+                "node_sync_checkpoint_sequence_number",
+                "Latest checkpoint whose transactions have all been executed by checkpoint sync",
+                registry,
+            )
+            .unwrap(),
+            checkpoint_sync_peer_checkpoints: register_int_counter_with_registry!(
+                "node_sync_peer_checkpoints",
+                "Total number of checkpoints checkpoint sync downloaded from full node peers",
+                registry,
+            )
+            .unwrap(),
+            checkpoint_sync_peer_transactions: register_int_counter_with_registry!(
+                "node_sync_peer_transactions",
+                "Total number of certs/effects checkpoint sync downloaded from full node peers",
+                registry,
+            )
+            .unwrap(),
         }
     }
 
@@ -157,7 +178,21 @@ async fn follower_process<A, Handler: DigestHandler<A> + Clone>(
         .gossip_metrics
         .concurrent_followed_validators;
     let metrics_reconnect_interval_ms = &active_authority.gossip_metrics.reconnect_interval_ms;
+    let mut gossip_paused = active_authority.subscribe_gossip_paused();
     loop {
+        if *gossip_paused.borrow() {
+            // Drop all running gossip tasks, which disconnects from their peers, and wait to
+            // be resumed.
+            gossip_tasks.clear();
+            peer_names.clear();
+            metrics_concurrent_followed_validators.set(0);
+            while *gossip_paused.borrow() {
+                if gossip_paused.changed().await.is_err() {
+                    return;
+                }
+            }
+        }
+
         if active_authority.state.committee.load().epoch != committee.epoch {
             // If epoch has changed, we need to make a new copy of the active authority,
             // and update all local variables.
@@ -226,7 +261,10 @@ async fn follower_process<A, Handler: DigestHandler<A> + Clone>(
         }
 
         metrics_concurrent_followed_validators.set(gossip_tasks.len() as i64);
-        wait_for_one_gossip_task_to_finish(&local_active, &mut peer_names, &mut gossip_tasks).await;
+        tokio::select! {
+            _ = wait_for_one_gossip_task_to_finish(&local_active, &mut peer_names, &mut gossip_tasks) => (),
+            _ = gossip_paused.changed() => (),
+        }
     }
 }
 
@@ -2,13 +2,15 @@
 // Copyright (c) 2022, Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::authority_client::AuthorityAPI;
+use crate::authority_client::{AuthorityAPI, NetworkAuthorityClientMetrics};
+use crate::epoch::reconfiguration::{recreate_clients, Reconfigurable};
 use crate::safe_client::{SafeClient, SafeClientMetrics};
 use async_trait::async_trait;
 
+use fastcrypto::traits::ToFromBytes;
 use futures::{future, future::BoxFuture, stream::FuturesUnordered, StreamExt};
 use move_core_types::value::MoveStructLayout;
-use sui_types::crypto::AuthoritySignature;
+use sui_types::crypto::{AuthorityPublicKeyBytes, AuthoritySignature};
 use sui_types::object::{Object, ObjectFormatOptions, ObjectRead};
 use sui_types::{
     base_types::*,
@@ -25,13 +27,15 @@ use tracing::{debug, error, info, instrument, trace, Instrument};
 use prometheus::{
     register_histogram_with_registry, register_int_counter_with_registry, Histogram, IntCounter,
 };
-use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
 use std::string::ToString;
 use std::sync::Arc;
 use std::time::Duration;
-use sui_types::committee::StakeUnit;
+use sui_types::committee::{EpochId, StakeUnit};
+use sui_types::sui_system_state::SuiSystemState;
+use sui_types::SUI_SYSTEM_STATE_OBJECT_ID;
 use tokio::sync::mpsc::Receiver;
-use tokio::time::{sleep, timeout};
+use tokio::time::{sleep, timeout, Instant};
 
 use crate::epoch::epoch_store::EpochStore;
 use sui_types::messages_checkpoint::CheckpointSequenceNumber;
@@ -39,6 +43,8 @@ use tap::TapFallible;
 
 const OBJECT_DOWNLOAD_CHANNEL_BOUND: usize = 1024;
 pub const DEFAULT_RETRIES: usize = 4;
+// The hedge delay is this multiple of the latency of the slowest authority contacted first.
+const HEDGE_DELAY_LATENCY_MULTIPLE: u32 = 2;
 
 #[cfg(test)]
 #[path = "unit_tests/authority_aggregator_tests.rs"]
@@ -67,6 +73,12 @@ pub struct TimeoutConfig {
     // it is set to a value greater than serial_authority_request_timeout then it becomes
     // completely serial.
     pub serial_authority_request_interval: Duration,
+
+    // Bounds of the delay after which quorum_map_then_reduce_with_timeout contacts the
+    // authorities beyond the fastest ones carrying a quorum of stake. Within these bounds the
+    // delay adapts to the observed latency of the authorities contacted first.
+    pub min_hedge_delay: Duration,
+    pub max_hedge_delay: Duration,
 }
 
 impl Default for TimeoutConfig {
@@ -77,6 +89,8 @@ impl Default for TimeoutConfig {
             post_quorum_timeout: Duration::from_secs(30),
             serial_authority_request_timeout: Duration::from_secs(5),
             serial_authority_request_interval: Duration::from_millis(1000),
+            min_hedge_delay: Duration::from_millis(50),
+            max_hedge_delay: Duration::from_millis(500),
         }
     }
 }
@@ -89,6 +103,7 @@ pub struct AuthAggMetrics {
     pub num_good_stake: Histogram,
     pub num_bad_stake: Histogram,
     pub total_quorum_once_timeout: IntCounter,
+    pub total_hedged_requests: IntCounter,
 }
 
 // Override default Prom buckets for positive numbers in 0-50k range
@@ -134,6 +149,12 @@ impl AuthAggMetrics {
                 registry,
             )
             .unwrap(),
+            total_hedged_requests: register_int_counter_with_registry!(
+                "total_hedged_requests",
+                "Total number of requests sent to slower authorities after the hedge delay",
+                registry,
+            )
+            .unwrap(),
         }
     }
 
@@ -154,6 +175,8 @@ pub struct AuthorityAggregator<A> {
     pub timeouts: TimeoutConfig,
     // Store here for clone during re-config
     pub safe_client_metrics: SafeClientMetrics,
+    // Store here for clone during re-config
+    pub epoch_store: Arc<EpochStore>,
 }
 
 impl<A> AuthorityAggregator<A> {
@@ -201,6 +224,7 @@ impl<A> AuthorityAggregator<A> {
             metrics,
             timeouts,
             safe_client_metrics,
+            epoch_store,
         }
     }
 
@@ -221,6 +245,33 @@ impl<A> AuthorityAggregator<A> {
         }
         clients
     }
+
+    /// The authorities ordered by their routing score, fastest first. Preferred authorities
+    /// come before all others, and authorities with equal scores keep their stake-weighted
+    /// shuffle order.
+    fn authorities_by_latency(
+        &self,
+        preferences: Option<&BTreeSet<AuthorityName>>,
+        restrict_to: Option<&BTreeSet<AuthorityName>>,
+    ) -> Vec<AuthorityName> {
+        let mut authorities = self.committee.shuffle_by_stake(preferences, restrict_to);
+        authorities.sort_by_cached_key(|name| {
+            let preferred = preferences.map_or(false, |p| p.contains(name));
+            (!preferred, self.authority_clients[name].routing_score())
+        });
+        authorities
+    }
+
+    /// How long to wait for `authorities` before also contacting the others.
+    fn hedge_delay<'b>(&self, authorities: impl Iterator<Item = &'b AuthorityName>) -> Duration {
+        let slowest = authorities
+            .filter_map(|name| self.authority_clients[name].latency_estimate())
+            .max()
+            .unwrap_or_default();
+        (slowest * HEDGE_DELAY_LATENCY_MULTIPLE)
+            .max(self.timeouts.min_hedge_delay)
+            .min(self.timeouts.max_hedge_delay)
+    }
 }
 
 pub enum ReduceOutput<S> {
@@ -557,6 +608,9 @@ where
     /// MReduce function to overall recover from it. This is necessary to ensure byzantine authorities cannot
     /// interrupt the logic of this function.
     ///
+    /// FMap is first applied to the fastest authorities that carry a quorum of stake, and to the
+    /// others only once one of those fails or the hedge delay elapses (see `TimeoutConfig`).
+    ///
     /// FReduce returns a result to a ReduceOutput. If the result is Err the function
     /// shortcuts and the Err is returned. An Ok ReduceOutput result can be used to shortcut and return
     /// the resulting state (ReduceOutput::End), continue the folding as new states arrive (ReduceOutput::Continue),
@@ -614,31 +668,89 @@ where
             Result<V, SuiError>,
         ) -> AsyncResult<'a, ReduceOutput<S>, SuiError>,
     {
-        let authorities_shuffled = self.committee.shuffle_by_stake(authority_prefences, None);
+        let mut pending: VecDeque<_> = self
+            .authorities_by_latency(authority_prefences, None)
+            .into();
 
-        // First, execute in parallel for each authority FMap.
-        let mut responses: futures::stream::FuturesUnordered<_> = authorities_shuffled
+        // Start with the fastest authorities that together carry a quorum of stake. The others
+        // are contacted when one of these fails, or all at once when the hedge delay elapses.
+        let threshold = self.committee.quorum_threshold();
+        let mut stake = 0;
+        let initial_count = pending
             .iter()
-            .map(|name| {
-                let client = &self.authority_clients[name];
-                let execute = map_each_authority.clone();
-                async move {
-                    (
-                        *name,
-                        execute(*name, client)
-                            .instrument(tracing::trace_span!("quorum_map_auth", authority =? name))
-                            .await,
-                    )
-                }
+            .position(|name| {
+                stake += self.committee.weight(name);
+                stake >= threshold
             })
-            .collect();
+            .map_or(pending.len(), |index| index + 1);
+        let hedge_delay = self.hedge_delay(pending.iter().take(initial_count));
+
+        let start_request = |name: AuthorityName| {
+            let client = &self.authority_clients[&name];
+            let execute = map_each_authority.clone();
+            async move {
+                (
+                    name,
+                    execute(name, client)
+                        .instrument(tracing::trace_span!("quorum_map_auth", authority =? name))
+                        .await,
+                )
+            }
+        };
+
+        let mut responses = FuturesUnordered::new();
+        let mut started = HashMap::new();
+        for name in pending.drain(..initial_count) {
+            started.insert(name, Instant::now());
+            responses.push(start_request(name));
+        }
+        let hedge = sleep(hedge_delay);
+        tokio::pin!(hedge);
+
+        enum Event<R> {
+            Response(R),
+            Hedge,
+        }
 
         let mut current_timeout = initial_timeout;
         let mut accumulated_state = initial_state;
         // Then, as results become available fold them into the state using FReduce.
-        while let Ok(Some((authority_name, result))) =
-            timeout(current_timeout, responses.next()).await
-        {
+        loop {
+            let event = tokio::select! {
+                response = timeout(current_timeout, responses.next()) => Event::Response(response),
+                _ = &mut hedge, if !pending.is_empty() => Event::Hedge,
+            };
+            let (authority_name, result) = match event {
+                Event::Response(Ok(Some(response))) => response,
+                // All started requests completed, so the remaining authorities are needed.
+                Event::Hedge | Event::Response(Ok(None)) if !pending.is_empty() => {
+                    debug!(
+                        ?hedge_delay,
+                        count = pending.len(),
+                        "hedging to remaining authorities"
+                    );
+                    self.metrics
+                        .total_hedged_requests
+                        .inc_by(pending.len() as u64);
+                    for name in pending.drain(..) {
+                        started.insert(name, Instant::now());
+                        responses.push(start_request(name));
+                    }
+                    continue;
+                }
+                // Timed out, or all authorities responded.
+                _ => break,
+            };
+            started.remove(&authority_name);
+
+            // Replace a failed authority right away rather than waiting for the hedge delay.
+            if result.is_err() {
+                if let Some(name) = pending.pop_front() {
+                    started.insert(name, Instant::now());
+                    responses.push(start_request(name));
+                }
+            }
+
             let authority_weight = self.committee.weight(&authority_name);
             accumulated_state =
                 match reduce_result(accumulated_state, authority_name, authority_weight, result)
@@ -652,7 +764,11 @@ where
                         state
                     }
                     ReduceOutput::End(state) => {
-                        // The reducer tells us that we have the result needed. Just return it.
+                        // The reducer tells us that we have the result needed. Stop contacting
+                        // the remaining authorities, and just return it.
+                        for (name, start) in started {
+                            self.authority_clients[&name].report_abandoned(start.elapsed());
+                        }
                         return Ok(state);
                     }
                 }
@@ -682,7 +798,7 @@ where
         let start = tokio::time::Instant::now();
         let mut delay = Duration::from_secs(1);
         loop {
-            let authorities_shuffled = self.committee.shuffle_by_stake(preferences, restrict_to);
+            let authorities_shuffled = self.authorities_by_latency(preferences, restrict_to);
             let mut authorities_shuffled = authorities_shuffled.iter();
 
             type RequestResult<S> = Result<Result<S, SuiError>, tokio::time::error::Elapsed>;
@@ -747,6 +863,8 @@ where
                             // timeout
                             Err(_) => {
                                 debug!(?name, "authority request timed out");
+                                self.authority_clients[&name]
+                                    .report_timeout(timeout_each_authority);
                                 authority_errors.insert(name, SuiError::TimeoutError);
                             }
                             // request completed
@@ -1657,6 +1775,173 @@ where
         Ok(ObjectRead::NotExists(object_id))
     }
 
+    /// Ask every authority which transaction, if any, holds the lock on each of the given owned
+    /// object versions. Returns, for each object, the stake of the authorities that locked it on
+    /// each transaction. Authorities that fail to answer, or whose latest version of the object is
+    /// not the requested one, are not counted.
+    pub async fn get_object_locks(
+        &self,
+        object_refs: &[ObjectRef],
+    ) -> BTreeMap<ObjectRef, BTreeMap<TransactionDigest, StakeUnit>> {
+        let timeout = self.timeouts.authority_request_timeout;
+        let requests = self.authority_clients.iter().flat_map(|(name, client)| {
+            object_refs.iter().map(move |object_ref| async move {
+                let request = ObjectInfoRequest::latest_object_info_request(object_ref.0, None);
+                let response =
+                    tokio::time::timeout(timeout, client.handle_object_info_request(request)).await;
+                (*name, *object_ref, response)
+            })
+        });
+
+        let mut locks: BTreeMap<ObjectRef, BTreeMap<TransactionDigest, StakeUnit>> =
+            BTreeMap::new();
+        for (name, object_ref, response) in future::join_all(requests).await {
+            let lock = match response {
+                Ok(Ok(ObjectInfoResponse {
+                    object_and_lock:
+                        Some(ObjectResponse {
+                            object,
+                            lock: Some(lock),
+                            ..
+                        }),
+                    ..
+                })) if object.compute_object_reference() == object_ref => lock,
+                _ => continue,
+            };
+            *locks
+                .entry(object_ref)
+                .or_default()
+                .entry(*lock.digest())
+                .or_default() += self.committee.weight(&name);
+        }
+        locks
+    }
+
+    /// Ask the authorities for the certificate of the given epoch, which is signed by the committee
+    /// of the previous epoch. Returns `None` if no authority has it.
+    async fn get_certified_epoch(&self, epoch_id: EpochId) -> Option<CertifiedEpoch> {
+        for name in self.authorities_by_latency(None, None) {
+            let client = match self.authority_clients.get(&name) {
+                Some(client) => client,
+                None => continue,
+            };
+            let request = EpochRequest {
+                epoch_id: Some(epoch_id),
+            };
+            match timeout(
+                self.timeouts.authority_request_timeout,
+                client.handle_epoch(request),
+            )
+            .await
+            {
+                Ok(Ok(EpochResponse {
+                    epoch_info: Some(AuthenticatedEpoch::Certified(cert)),
+                })) => return Some(cert),
+                Ok(Ok(_)) => (),
+                Ok(Err(err)) => debug!(?name, ?epoch_id, "Failed to get epoch: {:?}", err),
+                Err(_) => debug!(?name, ?epoch_id, "Timeout getting epoch"),
+            }
+        }
+        None
+    }
+
+    /// Follow the epoch changes certified since the epoch of our committee, one epoch at a time.
+    /// Returns an aggregator talking to the committee of the latest certified epoch, or `None` if
+    /// the committee did not change. The certified epochs are added to the epoch store, and the
+    /// network addresses of new authorities are read from the Sui system state.
+    pub async fn reconfigure_to_latest_epoch(
+        &self,
+        network_metrics: Arc<NetworkAuthorityClientMetrics>,
+    ) -> SuiResult<Option<Self>>
+    where
+        A: Reconfigurable,
+    {
+        let mut latest: Option<Self> = None;
+        loop {
+            let net = latest.as_ref().unwrap_or(self);
+            let next_epoch = net.committee.epoch + 1;
+            // The certificate is verified against the committee of the previous epoch.
+            let cert = match net.get_certified_epoch(next_epoch).await {
+                Some(cert) => cert,
+                None => break,
+            };
+            let committee = cert.epoch_info.committee().clone();
+            self.epoch_store
+                .epochs
+                .insert(&next_epoch, &AuthenticatedEpoch::Certified(cert))?;
+
+            let mut validators = BTreeMap::new();
+            if !committee
+                .names()
+                .all(|name| net.authority_clients.contains_key(name))
+            {
+                // The new authorities are either validators of the next epoch, or already active
+                // if the system state moved to the new epoch.
+                let system_state = net.get_sui_system_state().await?;
+                for validator in system_state
+                    .validators
+                    .active_validators
+                    .into_iter()
+                    .map(|validator| validator.metadata)
+                    .chain(system_state.validators.next_epoch_validators)
+                {
+                    if let Ok(name) = AuthorityPublicKeyBytes::from_bytes(&validator.pubkey_bytes) {
+                        if committee.authority_exists(&name) {
+                            validators.entry(name).or_insert(validator);
+                        }
+                    }
+                }
+            }
+            let mut clients = recreate_clients(
+                validators.into_values(),
+                &net.authority_clients,
+                network_metrics.clone(),
+            );
+            // Authorities of the previous committee keep their client.
+            for name in committee.names() {
+                if !clients.contains_key(name) {
+                    if let Some(client) = net.authority_clients.get(name) {
+                        clients.insert(*name, client.authority_client().clone());
+                    }
+                }
+            }
+            info!(
+                epoch = next_epoch,
+                "Moving the authority aggregator to a new committee"
+            );
+            latest = Some(Self::new_with_timeouts(
+                committee,
+                self.epoch_store.clone(),
+                clients,
+                self.metrics.clone(),
+                self.safe_client_metrics.clone(),
+                self.timeouts.clone(),
+            ));
+        }
+        Ok(latest)
+    }
+
+    async fn get_sui_system_state(&self) -> SuiResult<SuiSystemState> {
+        match self
+            .get_object_info_execute(SUI_SYSTEM_STATE_OBJECT_ID)
+            .await?
+        {
+            ObjectRead::Exists(_, object, _) => {
+                let move_object = object.data.try_as_move().ok_or_else(|| {
+                    SuiError::from("The Sui system state object is not a Move object")
+                })?;
+                bcs::from_bytes(move_object.contents()).map_err(|err| {
+                    SuiError::ObjectSerializationError {
+                        error: err.to_string(),
+                    }
+                })
+            }
+            _ => Err(SuiError::ObjectNotFound {
+                object_id: SUI_SYSTEM_STATE_OBJECT_ID,
+            }),
+        }
+    }
+
     /// Given a list of object refs, download the objects.
     pub fn fetch_objects_from_authorities(
         &self,
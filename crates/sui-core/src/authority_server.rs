@@ -5,9 +5,10 @@
 use crate::{
     authority::{AuthorityState, ReconfigConsensusMessage},
     consensus_adapter::{
-        CheckpointConsensusAdapter, CheckpointSender, ConsensusAdapter, ConsensusAdapterMetrics,
-        ConsensusListener, ConsensusListenerMessage,
+        narwhal_consensus_client, CheckpointConsensusAdapter, CheckpointSender, ConsensusAdapter,
+        ConsensusAdapterMetrics, ConsensusListener, ConsensusListenerMessage, SubmitToConsensus,
     },
+    consensus_sequencer::SingleNodeSequencer,
     metrics::start_timer,
 };
 use anyhow::anyhow;
@@ -18,13 +19,13 @@ use futures::{stream::BoxStream, TryStreamExt};
 use multiaddr::Multiaddr;
 use prometheus::{register_histogram_with_registry, Histogram, Registry};
 use std::{io, sync::Arc, time::Duration};
-use sui_config::NodeConfig;
+use sui_config::{ConsensusProtocol, NodeConfig};
 use sui_network::{
     api::{Validator, ValidatorServer},
     tonic,
 };
 
-use sui_types::{error::*, messages::*};
+use sui_types::{error::*, messages::*, SUI_CLOCK_OBJECT_ID};
 use tokio::{
     sync::mpsc::{channel, Receiver, Sender},
     task::JoinHandle,
@@ -34,7 +35,7 @@ use tokio::{
 use sui_types::messages_checkpoint::CheckpointRequest;
 use sui_types::messages_checkpoint::CheckpointResponse;
 
-use tracing::{info, Instrument};
+use tracing::{info, warn, Instrument};
 
 #[cfg(test)]
 #[path = "unit_tests/server_tests.rs"]
@@ -90,10 +91,11 @@ impl AuthorityServer {
     ) -> Self {
         let metrics = ConsensusAdapterMetrics::new_test();
         let consensus_adapter = ConsensusAdapter::new(
-            consensus_address,
+            narwhal_consensus_client(&consensus_address),
             state.clone_committee(),
             tx_consensus_listener,
             /* max_delay */ Duration::from_millis(20_000),
+            /* max_pending_transactions_per_shared_object */ None,
             metrics,
         );
 
@@ -255,30 +257,48 @@ impl ValidatorService {
         let consensus_config = config
             .consensus_config()
             .ok_or_else(|| anyhow!("Validator is missing consensus config"))?;
-        let consensus_keypair = config.protocol_key_pair().copy();
-        let consensus_worker_keypair = config.worker_key_pair().copy();
-        let consensus_committee = config.genesis()?.narwhal_committee().load();
-        let consensus_worker_cache = config.genesis()?.narwhal_worker_cache();
-        let consensus_storage_base_path = consensus_config.db_path().to_path_buf();
-        let consensus_execution_state = state.clone();
-        let consensus_parameters = consensus_config.narwhal_config().to_owned();
-
-        let registry = prometheus_registry.clone();
-        tokio::spawn(async move {
-            narwhal_node::restarter::NodeRestarter::watch(
-                consensus_keypair,
-                vec![(0, consensus_worker_keypair)],
-                &consensus_committee,
-                consensus_worker_cache,
-                consensus_storage_base_path,
-                consensus_execution_state,
-                consensus_parameters,
-                rx_reconfigure_consensus,
-                /* tx_output */ tx_consensus_to_sui,
-                &registry,
-            )
-            .await
-        });
+        let consensus_client: Arc<dyn SubmitToConsensus> = match consensus_config.protocol {
+            ConsensusProtocol::Narwhal => {
+                let consensus_keypair = config.protocol_key_pair().copy();
+                let consensus_worker_keypair = config.worker_key_pair().copy();
+                let consensus_committee = config.genesis()?.narwhal_committee().load();
+                let consensus_worker_cache = config.genesis()?.narwhal_worker_cache();
+                let consensus_storage_base_path = consensus_config.db_path().to_path_buf();
+                let consensus_execution_state = state.clone();
+                let consensus_parameters = consensus_config.narwhal_config().to_owned();
+
+                let registry = prometheus_registry.clone();
+                tokio::spawn(async move {
+                    narwhal_node::restarter::NodeRestarter::watch(
+                        consensus_keypair,
+                        vec![(0, consensus_worker_keypair)],
+                        &consensus_committee,
+                        consensus_worker_cache,
+                        consensus_storage_base_path,
+                        consensus_execution_state,
+                        consensus_parameters,
+                        rx_reconfigure_consensus,
+                        /* tx_output */ tx_consensus_to_sui,
+                        &registry,
+                    )
+                    .await
+                });
+                narwhal_consensus_client(consensus_config.address())
+            }
+            ConsensusProtocol::SingleNode => {
+                let committee_size = state.committee.load().voting_rights.len();
+                if committee_size != 1 {
+                    return Err(anyhow!(
+                        "Single node consensus requires a committee of one validator, found {committee_size}"
+                    ));
+                }
+                // There is no consensus node to reconfigure.
+                drop(rx_reconfigure_consensus);
+                let (sequencer, _handle) =
+                    SingleNodeSequencer::spawn(state.clone(), tx_consensus_to_sui);
+                Arc::new(sequencer)
+            }
+        };
 
         // Spawn a consensus listener. It listen for consensus outputs and notifies the
         // authority server when a sequenced transaction is ready for execution.
@@ -293,10 +313,11 @@ impl ValidatorService {
         let delay_step = consensus_config.delay_step.unwrap_or(15_000);
         // The consensus adapter allows the authority to send user certificates through consensus.
         let consensus_adapter = ConsensusAdapter::new(
-            consensus_config.address().to_owned(),
+            consensus_client.clone(),
             state.clone_committee(),
             tx_sui_to_consensus.clone(),
             Duration::from_millis(delay_step),
+            consensus_config.max_pending_transactions_per_shared_object,
             ca_metrics.clone(),
         );
 
@@ -309,7 +330,7 @@ impl ValidatorService {
                 .set_consensus(Box::new(consensus_sender))?;
 
             let handle = CheckpointConsensusAdapter::new(
-                /* consensus_address */ consensus_config.address().to_owned(),
+                consensus_client,
                 /* tx_consensus_listener */ tx_sui_to_consensus,
                 rx_checkpoint_consensus_adapter,
                 /* checkpoint_locals */ checkpoint_store,
@@ -425,10 +446,34 @@ impl ValidatorService {
         {
             let start_ts = Instant::now();
             let _metrics_guard = start_timer!(metrics.consensus_latency.clone(), &start_ts);
+
+            // Report the time of this authority ahead of certificates reading the Clock, so that
+            // the consensus timestamp they observe keeps up with the clocks of the authorities.
+            if certificate
+                .shared_input_objects()
+                .any(|object_id| *object_id == SUI_CLOCK_OBJECT_ID)
+            {
+                if let Some(timestamp) = state.clock_timestamp_to_submit() {
+                    // The certificate can be sequenced without it, the Clock then lags behind.
+                    if let Err(e) = consensus_adapter.submit_clock_timestamp(timestamp).await {
+                        warn!(
+                            ?tx_digest,
+                            "Failed to submit clock timestamp to consensus: {e}"
+                        );
+                    }
+                }
+            }
+
             consensus_adapter
                 .submit(&state.name, &certificate)
                 .await
-                .map_err(|e| tonic::Status::internal(e.to_string()))?;
+                .map_err(|e| match e {
+                    // Let clients know they can retry later.
+                    SuiError::SharedObjectCongested { .. } => {
+                        tonic::Status::resource_exhausted(e.to_string())
+                    }
+                    _ => tonic::Status::internal(e.to_string()),
+                })?;
         }
 
         // 4) Execute the certificate.
@@ -0,0 +1,484 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+/*
+    Certified checkpoints can be archived, together with their contents, certificates and
+    effects, to an object store so that history survives pruning and can seed new nodes.
+
+    The archive uses flat, '/' separated keys so that it maps directly onto S3-style storage:
+
+    - `blobs/<xx>/<digest>` holds a zstd compressed, BCS encoded `ArchivedCheckpoint`. The
+      digest is the hex encoded SHA3-256 of the uncompressed bytes, and `<xx>` are its first
+      two characters, to spread blobs over prefixes.
+    - `checkpoints/<sequence number>` holds the digest of the blob for that checkpoint. The
+      sequence number is zero padded so that listing the prefix returns checkpoints in order.
+
+    Blobs are written before the index entry that points at them, so an interrupted write
+    never leaves an index entry pointing at a missing blob. Everything read back from an
+    archive is checked against its digest, and can be verified against the committee of the
+    epoch that certified the checkpoint.
+
+    A node restores from an archive by seeding its NodeSyncStore with the archived checkpoints,
+    certificates and effects. Checkpoint sync then executes them locally, exactly as if they
+    had been downloaded from the network.
+*/
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use sha3::{Digest, Sha3_256};
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+use sui_storage::node_sync_store::NodeSyncStore;
+use sui_types::{
+    committee::{Committee, EpochId},
+    error::{SuiError, SuiResult},
+    messages::{CertifiedTransaction, SignedTransactionEffects},
+    messages_checkpoint::{
+        AuthenticatedCheckpoint, CertifiedCheckpointSummary, CheckpointContents,
+        CheckpointSequenceNumber,
+    },
+};
+use tracing::{debug, error, info};
+
+use super::CheckpointStore;
+use crate::authority::AuthorityState;
+use crate::epoch::epoch_store::EpochStore;
+
+const BLOBS_PREFIX: &str = "blobs";
+const CHECKPOINTS_PREFIX: &str = "checkpoints";
+const COMPRESSION_LEVEL: i32 = 3;
+
+// How often the archive process looks for newly certified checkpoints.
+const ARCHIVE_POLL_INTERVAL: Duration = Duration::from_secs(10);
+
+/// A minimal object store interface, modelled on S3: opaque values under '/' separated keys.
+#[async_trait]
+pub trait ArchiveStore: Send + Sync + 'static {
+    async fn put(&self, key: &str, value: Vec<u8>) -> SuiResult;
+
+    async fn get(&self, key: &str) -> SuiResult<Option<Vec<u8>>>;
+
+    async fn exists(&self, key: &str) -> SuiResult<bool>;
+
+    /// Returns all keys starting with `prefix`, in lexicographic order.
+    async fn list(&self, prefix: &str) -> SuiResult<Vec<String>>;
+}
+
+/// An ArchiveStore that keeps every key as a file below a root directory.
+pub struct LocalFsArchiveStore {
+    root: PathBuf,
+}
+
+impl LocalFsArchiveStore {
+    pub fn new(root: PathBuf) -> Self {
+        Self { root }
+    }
+
+    fn path(&self, key: &str) -> PathBuf {
+        self.root.join(key)
+    }
+}
+
+fn archive_error(error: impl ToString) -> SuiError {
+    SuiError::CheckpointArchiveError {
+        error: error.to_string(),
+    }
+}
+
+#[async_trait]
+impl ArchiveStore for LocalFsArchiveStore {
+    async fn put(&self, key: &str, value: Vec<u8>) -> SuiResult {
+        let path = self.path(key);
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent)
+                .await
+                .map_err(archive_error)?;
+        }
+        // Write to a temporary file first so that readers never see a partial value.
+        let tmp_path = path.with_extension("tmp");
+        tokio::fs::write(&tmp_path, value)
+            .await
+            .map_err(archive_error)?;
+        tokio::fs::rename(&tmp_path, &path)
+            .await
+            .map_err(archive_error)
+    }
+
+    async fn get(&self, key: &str) -> SuiResult<Option<Vec<u8>>> {
+        match tokio::fs::read(self.path(key)).await {
+            Ok(value) => Ok(Some(value)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(archive_error(e)),
+        }
+    }
+
+    async fn exists(&self, key: &str) -> SuiResult<bool> {
+        Ok(tokio::fs::metadata(self.path(key)).await.is_ok())
+    }
+
+    async fn list(&self, prefix: &str) -> SuiResult<Vec<String>> {
+        // Keys are only ever listed by directory prefix in this module.
+        let dir = self.path(prefix);
+        let mut entries = match tokio::fs::read_dir(&dir).await {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(vec![]),
+            Err(e) => return Err(archive_error(e)),
+        };
+
+        let mut keys = vec![];
+        while let Some(entry) = entries.next_entry().await.map_err(archive_error)? {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            if !name.ends_with(".tmp") {
+                keys.push(format!("{}/{}", prefix.trim_end_matches('/'), name));
+            }
+        }
+        keys.sort();
+        Ok(keys)
+    }
+}
+
+/// A certified checkpoint together with everything needed to replay it.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ArchivedCheckpoint {
+    pub checkpoint: CertifiedCheckpointSummary,
+    pub contents: CheckpointContents,
+    /// Certificates and effects of the checkpointed transactions, in the order of `contents`.
+    pub transactions: Vec<(CertifiedTransaction, SignedTransactionEffects)>,
+}
+
+impl ArchivedCheckpoint {
+    pub fn sequence_number(&self) -> CheckpointSequenceNumber {
+        self.checkpoint.summary.sequence_number
+    }
+
+    /// Check the checkpoint certificate against `committee`, and that the archived certificates
+    /// and effects are exactly the ones listed in the checkpoint contents.
+    pub fn verify(&self, committee: &Committee) -> SuiResult {
+        self.checkpoint.verify(committee, Some(&self.contents))?;
+
+        ensure_archived(
+            self.transactions.len() == self.contents.size(),
+            "number of archived transactions does not match the checkpoint contents",
+        )?;
+        for (digests, (cert, effects)) in self.contents.iter().zip(&self.transactions) {
+            ensure_archived(
+                *cert.digest() == digests.transaction
+                    && *effects.digest() == digests.effects
+                    && effects.effects.transaction_digest == digests.transaction,
+                "archived transaction does not match the checkpoint contents",
+            )?;
+            cert.verify(committee)?;
+        }
+        Ok(())
+    }
+}
+
+fn ensure_archived(condition: bool, error: &str) -> SuiResult {
+    if condition {
+        Ok(())
+    } else {
+        Err(archive_error(error))
+    }
+}
+
+/// The committee of `epoch`, if this node knows it yet.
+fn epoch_committee(epoch_store: &EpochStore, epoch: EpochId) -> SuiResult<Option<Committee>> {
+    Ok(epoch_store
+        .get_authenticated_epoch(&epoch)?
+        .map(|epoch| epoch.into_epoch_info().into_committee()))
+}
+
+fn checkpoint_key(seq: CheckpointSequenceNumber) -> String {
+    format!("{}/{:020}", CHECKPOINTS_PREFIX, seq)
+}
+
+fn blob_key(digest: &str) -> String {
+    format!("{}/{}/{}", BLOBS_PREFIX, &digest[..2], digest)
+}
+
+/// Writes certified checkpoints to an archive.
+pub struct CheckpointArchiveWriter<S> {
+    store: Arc<S>,
+}
+
+impl<S: ArchiveStore> CheckpointArchiveWriter<S> {
+    pub fn new(store: Arc<S>) -> Self {
+        Self { store }
+    }
+
+    /// Archive a checkpoint, returning the digest of the blob it was stored in.
+    /// Archiving the same checkpoint twice is a no-op.
+    pub async fn write(&self, checkpoint: &ArchivedCheckpoint) -> SuiResult<String> {
+        let bytes = bcs::to_bytes(checkpoint).map_err(archive_error)?;
+        let digest = hex::encode(Sha3_256::digest(&bytes));
+
+        let key = blob_key(&digest);
+        if !self.store.exists(&key).await? {
+            let compressed =
+                zstd::encode_all(bytes.as_slice(), COMPRESSION_LEVEL).map_err(archive_error)?;
+            self.store.put(&key, compressed).await?;
+        }
+        self.store
+            .put(
+                &checkpoint_key(checkpoint.sequence_number()),
+                digest.clone().into_bytes(),
+            )
+            .await?;
+
+        debug!(seq = ?checkpoint.sequence_number(), ?digest, "archived checkpoint");
+        Ok(digest)
+    }
+}
+
+/// Reads certified checkpoints back from an archive.
+pub struct CheckpointArchiveReader<S> {
+    store: Arc<S>,
+}
+
+impl<S: ArchiveStore> CheckpointArchiveReader<S> {
+    pub fn new(store: Arc<S>) -> Self {
+        Self { store }
+    }
+
+    /// The sequence numbers of all archived checkpoints, in order.
+    pub async fn list_checkpoints(&self) -> SuiResult<Vec<CheckpointSequenceNumber>> {
+        self.store
+            .list(CHECKPOINTS_PREFIX)
+            .await?
+            .iter()
+            .map(|key| {
+                key.rsplit('/')
+                    .next()
+                    .and_then(|seq| seq.parse().ok())
+                    .ok_or_else(|| archive_error(format!("unexpected archive key {}", key)))
+            })
+            .collect()
+    }
+
+    pub async fn latest_checkpoint(&self) -> SuiResult<Option<CheckpointSequenceNumber>> {
+        Ok(self.list_checkpoints().await?.last().copied())
+    }
+
+    /// Read an archived checkpoint and check it against the digest it is stored under.
+    /// Callers that do not trust the archive should also call `ArchivedCheckpoint::verify`.
+    pub async fn read(
+        &self,
+        seq: CheckpointSequenceNumber,
+    ) -> SuiResult<Option<ArchivedCheckpoint>> {
+        let digest = match self.store.get(&checkpoint_key(seq)).await? {
+            Some(digest) => String::from_utf8(digest).map_err(archive_error)?,
+            None => return Ok(None),
+        };
+        ensure_archived(
+            digest.len() == 64,
+            "archived checkpoint does not point at a valid blob digest",
+        )?;
+
+        let compressed = self.store.get(&blob_key(&digest)).await?.ok_or_else(|| {
+            archive_error(format!("missing blob {} for checkpoint {}", digest, seq))
+        })?;
+        let bytes = zstd::decode_all(compressed.as_slice()).map_err(archive_error)?;
+
+        ensure_archived(
+            hex::encode(Sha3_256::digest(&bytes)) == digest,
+            "archived blob does not match its digest",
+        )?;
+        let checkpoint: ArchivedCheckpoint = bcs::from_bytes(&bytes).map_err(archive_error)?;
+        ensure_archived(
+            checkpoint.sequence_number() == seq,
+            "archived blob is for a different checkpoint",
+        )?;
+
+        Ok(Some(checkpoint))
+    }
+
+    /// Read and verify a contiguous range of checkpoints, each against the committee of its
+    /// epoch, checking that each one extends the previous one.
+    pub async fn read_verified_range(
+        &self,
+        start: CheckpointSequenceNumber,
+        end: CheckpointSequenceNumber,
+        epoch_store: &EpochStore,
+    ) -> SuiResult<Vec<ArchivedCheckpoint>> {
+        let mut checkpoints: Vec<ArchivedCheckpoint> = Vec::new();
+        for seq in start..end {
+            let checkpoint = self
+                .read(seq)
+                .await?
+                .ok_or_else(|| archive_error(format!("checkpoint {} is not archived", seq)))?;
+            let epoch = checkpoint.checkpoint.summary.epoch;
+            let committee = epoch_committee(epoch_store, epoch)?.ok_or_else(|| {
+                archive_error(format!(
+                    "no committee for epoch {} of checkpoint {}",
+                    epoch, seq
+                ))
+            })?;
+            checkpoint.verify(&committee)?;
+            if let Some(previous) = checkpoints.last() {
+                ensure_archived(
+                    checkpoint.checkpoint.summary.previous_digest
+                        == Some(previous.checkpoint.summary.digest()),
+                    "archived checkpoints do not form a chain",
+                )?;
+            }
+            checkpoints.push(checkpoint);
+        }
+        Ok(checkpoints)
+    }
+
+    /// Seed `node_sync_store` with the archived checkpoints that follow the last synced one,
+    /// together with their certificates and effects, so that checkpoint sync executes them
+    /// without downloading them. Every checkpoint is verified against the committee of its
+    /// epoch, and restoring stops at the first checkpoint of an epoch this node does not know
+    /// yet. Returns the number of checkpoints restored.
+    pub async fn restore_checkpoints(
+        &self,
+        node_sync_store: &NodeSyncStore,
+        epoch_store: &EpochStore,
+    ) -> SuiResult<u64> {
+        let latest_synced = node_sync_store.latest_synced_checkpoint()?;
+        let mut seq = latest_synced.map(|(seq, _)| seq + 1).unwrap_or(0);
+        let mut previous_digest = latest_synced.map(|(_, digest)| digest);
+        let mut restored = 0;
+
+        loop {
+            if let Some((checkpoint, _)) = node_sync_store.get_pending_checkpoint(seq)? {
+                // Restored before, or downloaded and not executed yet.
+                previous_digest = Some(checkpoint.summary.digest());
+                seq += 1;
+                continue;
+            }
+
+            let archived = match self.read(seq).await? {
+                Some(archived) => archived,
+                None => break,
+            };
+            let epoch = archived.checkpoint.summary.epoch;
+            let committee = match epoch_committee(epoch_store, epoch)? {
+                Some(committee) => committee,
+                None => {
+                    info!(
+                        ?seq,
+                        ?epoch,
+                        "not restoring checkpoints of an unknown epoch"
+                    );
+                    break;
+                }
+            };
+            archived.verify(&committee)?;
+            ensure_archived(
+                previous_digest.is_none()
+                    || archived.checkpoint.summary.previous_digest == previous_digest,
+                "archived checkpoint does not extend the last synced checkpoint",
+            )?;
+
+            for (cert, effects) in &archived.transactions {
+                node_sync_store.store_cert(cert)?;
+                node_sync_store.store_effects(cert.digest(), effects)?;
+            }
+            node_sync_store.store_pending_checkpoint(&archived.checkpoint, &archived.contents)?;
+
+            previous_digest = Some(archived.checkpoint.summary.digest());
+            seq += 1;
+            restored += 1;
+        }
+
+        if restored > 0 {
+            info!(?restored, next = ?seq, "restored checkpoints from archive");
+        }
+        Ok(restored)
+    }
+}
+
+/// Archive every certified checkpoint of `state`, in order. The archive itself records
+/// progress, so a restarted process continues after the last archived checkpoint. The archive
+/// is only listed once, at start, after which the process tracks the next checkpoint itself.
+pub async fn checkpoint_archive_process<S: ArchiveStore>(
+    state: Arc<AuthorityState>,
+    store: Arc<S>,
+) {
+    let checkpoints = match state.checkpoints() {
+        Some(checkpoints) => checkpoints,
+        None => {
+            info!("No checkpoint store, not archiving checkpoints");
+            return;
+        }
+    };
+
+    let reader = CheckpointArchiveReader::new(store.clone());
+    let writer = CheckpointArchiveWriter::new(store);
+    let mut next: Option<CheckpointSequenceNumber> = None;
+
+    loop {
+        if next.is_none() {
+            match reader.latest_checkpoint().await {
+                Ok(latest) => next = Some(latest.map(|seq| seq + 1).unwrap_or(0)),
+                Err(err) => error!("Failed to read the latest archived checkpoint: {}", err),
+            }
+        }
+
+        if let Some(seq) = next.as_mut() {
+            match archive_new_checkpoints(&state, &checkpoints, &writer, seq).await {
+                Ok(0) => (),
+                Ok(archived) => debug!(?archived, "archived new checkpoints"),
+                Err(err) => error!("Failed to archive checkpoints: {}", err),
+            }
+        }
+
+        tokio::time::sleep(ARCHIVE_POLL_INTERVAL).await;
+    }
+}
+
+/// Archive the certified checkpoints from `next` on, advancing `next` past every checkpoint
+/// that was archived, and return how many were.
+async fn archive_new_checkpoints<S: ArchiveStore>(
+    state: &AuthorityState,
+    checkpoints: &parking_lot::Mutex<CheckpointStore>,
+    writer: &CheckpointArchiveWriter<S>,
+    next: &mut CheckpointSequenceNumber,
+) -> SuiResult<u64> {
+    let mut archived = 0;
+
+    loop {
+        let seq = *next;
+        let (checkpoint, contents) = {
+            let checkpoints = checkpoints.lock();
+            let checkpoint = match checkpoints.tables.checkpoints.get(&seq)? {
+                Some(AuthenticatedCheckpoint::Certified(checkpoint)) => checkpoint,
+                // Not certified yet, or not known yet.
+                _ => return Ok(archived),
+            };
+            let contents = match checkpoints.tables.checkpoint_contents.get(&seq)? {
+                Some(contents) => contents,
+                None => return Ok(archived),
+            };
+            (checkpoint, contents)
+        };
+
+        let mut transactions = Vec::with_capacity(contents.size());
+        for digests in contents.iter() {
+            let info = state
+                .db()
+                .get_signed_transaction_info(&digests.transaction)?;
+            match (info.certified_transaction, info.signed_effects) {
+                (Some(cert), Some(effects)) => transactions.push((cert, effects)),
+                _ => {
+                    return Err(SuiError::TransactionNotFound {
+                        digest: digests.transaction,
+                    })
+                }
+            }
+        }
+
+        writer
+            .write(&ArchivedCheckpoint {
+                checkpoint,
+                contents,
+                transactions,
+            })
+            .await?;
+        archived += 1;
+        *next += 1;
+    }
+}
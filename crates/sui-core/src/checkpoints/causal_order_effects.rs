@@ -276,6 +276,7 @@ mod tests {
                 Owner::Immutable,
             ),
             events: vec![],
+            clock_timestamp_ms: None,
         }
     }
 
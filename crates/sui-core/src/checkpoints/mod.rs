@@ -1,6 +1,7 @@
 // Copyright (c) 2022, Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+pub mod archive;
 pub mod causal_order_effects;
 pub mod reconstruction;
 
@@ -172,6 +173,27 @@ impl CheckpointStore {
         Ok(self.tables.checkpoints.get(&seq)?)
     }
 
+    /// Return the certified checkpoint including the given transaction, with its contents. This
+    /// is `None` until the checkpoint is certified.
+    pub fn get_checkpoint_of_transaction(
+        &self,
+        digests: &ExecutionDigests,
+    ) -> Result<Option<(CertifiedCheckpointSummary, CheckpointContents)>, SuiError> {
+        let seq = match self.tables.transactions_to_checkpoint.get(digests)? {
+            Some(seq) => seq,
+            None => return Ok(None),
+        };
+        match (
+            self.get_checkpoint(seq)?,
+            self.tables.checkpoint_contents.get(&seq)?,
+        ) {
+            (Some(AuthenticatedCheckpoint::Certified(checkpoint)), Some(contents)) => {
+                Ok(Some((checkpoint, contents)))
+            }
+            _ => Ok(None),
+        }
+    }
+
     fn get_prev_checkpoint_digest(
         &mut self,
         checkpoint_sequence: CheckpointSequenceNumber,
@@ -1950,3 +1950,101 @@ async fn test_no_more_fragments() {
         .attempt_to_construct_checkpoint(&setup.committee)
         .is_ok());
 }
+
+#[tokio::test]
+async fn archive_and_restore_checkpoints() {
+    use super::archive::{
+        ArchiveStore, ArchivedCheckpoint, CheckpointArchiveReader, CheckpointArchiveWriter,
+        LocalFsArchiveStore,
+    };
+    use sui_storage::node_sync_store::NodeSyncStore;
+
+    let (committee, _keys, mut stores) = random_ckpoint_store();
+    let (_, mut cps1) = stores.pop().unwrap();
+    let (_, mut cps2) = stores.pop().unwrap();
+    let (_, mut cps3) = stores.pop().unwrap();
+    let epoch = committee.epoch;
+
+    // Certify an empty checkpoint 0.
+    let empty: Vec<ExecutionDigests> = vec![];
+    let mut signed_checkpoint: Vec<SignedCheckpointSummary> = Vec::new();
+    for x in [&mut cps1, &mut cps2, &mut cps3] {
+        x.sign_new_checkpoint(epoch, 0, empty.iter(), TestCausalOrderPendCertNoop)
+            .unwrap();
+        match x.handle_authenticated_checkpoint(&Some(0), true).unwrap() {
+            CheckpointResponse::AuthenticatedCheckpoint {
+                checkpoint: Some(AuthenticatedCheckpoint::Signed(signed)),
+                ..
+            } => signed_checkpoint.push(signed),
+            _ => unreachable!(),
+        };
+    }
+    let checkpoint = CertifiedCheckpointSummary::aggregate(signed_checkpoint, &committee).unwrap();
+    let archived = ArchivedCheckpoint {
+        checkpoint,
+        contents: CheckpointContents::new_with_causally_ordered_transactions(empty.into_iter()),
+        transactions: vec![],
+    };
+
+    let archive_dir = tempfile::tempdir().unwrap();
+    let archive_store = Arc::new(LocalFsArchiveStore::new(archive_dir.path().to_path_buf()));
+    let writer = CheckpointArchiveWriter::new(archive_store.clone());
+    let reader = CheckpointArchiveReader::new(archive_store.clone());
+
+    assert_eq!(reader.latest_checkpoint().await.unwrap(), None);
+    let digest = writer.write(&archived).await.unwrap();
+    // Writing the same checkpoint again stores it under the same digest.
+    assert_eq!(writer.write(&archived).await.unwrap(), digest);
+    assert_eq!(reader.list_checkpoints().await.unwrap(), vec![0]);
+
+    let read = reader.read(0).await.unwrap().unwrap();
+    read.verify(&committee).unwrap();
+    assert_eq!(
+        read.checkpoint.summary.digest(),
+        archived.checkpoint.summary.digest()
+    );
+    assert!(reader.read(1).await.unwrap().is_none());
+
+    // A fresh node seeds checkpoint sync from the archive, verifying the checkpoints against
+    // the committee of their epoch.
+    let node_sync_dir = tempfile::tempdir().unwrap();
+    let node_sync_store =
+        NodeSyncStore::open_tables_read_write(node_sync_dir.path().join("sync_store"), None, None);
+    let epoch_store = EpochStore::new_for_testing(&committee);
+    assert_eq!(
+        reader
+            .restore_checkpoints(&node_sync_store, &epoch_store)
+            .await
+            .unwrap(),
+        1
+    );
+    let (pending, _) = node_sync_store.get_pending_checkpoint(0).unwrap().unwrap();
+    assert_eq!(
+        pending.summary.digest(),
+        archived.checkpoint.summary.digest()
+    );
+    // Checkpoints that are already pending are not restored again.
+    assert_eq!(
+        reader
+            .restore_checkpoints(&node_sync_store, &epoch_store)
+            .await
+            .unwrap(),
+        0
+    );
+    assert_eq!(
+        reader
+            .read_verified_range(0, 1, &epoch_store)
+            .await
+            .unwrap()
+            .len(),
+        1
+    );
+
+    // Corrupted blobs are detected.
+    let blob_key = format!("blobs/{}/{}", &digest[..2], digest);
+    let mut blob = archive_store.get(&blob_key).await.unwrap().unwrap();
+    let last = blob.len() - 1;
+    blob[last] ^= 0xff;
+    archive_store.put(&blob_key, blob).await.unwrap();
+    assert!(reader.read(0).await.is_err());
+}
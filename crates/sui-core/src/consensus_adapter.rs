@@ -3,6 +3,7 @@
 
 use crate::checkpoints::CheckpointStore;
 use crate::checkpoints::ConsensusSender;
+use async_trait::async_trait;
 use bytes::Bytes;
 use futures::stream::FuturesUnordered;
 use futures::StreamExt;
@@ -11,10 +12,14 @@ use narwhal_executor::SubscriberResult;
 use narwhal_types::TransactionProto;
 use narwhal_types::TransactionsClient;
 use parking_lot::Mutex;
+use prometheus::register_int_counter_vec_with_registry;
 use prometheus::register_int_counter_with_registry;
+use prometheus::register_int_gauge_vec_with_registry;
 use prometheus::register_int_gauge_with_registry;
 use prometheus::IntCounter;
+use prometheus::IntCounterVec;
 use prometheus::IntGauge;
+use prometheus::IntGaugeVec;
 use prometheus::Registry;
 use std::collections::VecDeque;
 use std::sync::atomic::AtomicU64;
@@ -29,13 +34,14 @@ use sui_types::messages_checkpoint::CheckpointSequenceNumber;
 use sui_types::{
     committee::Committee,
     error::{SuiError, SuiResult},
-    messages::ConsensusTransaction,
+    messages::{ConsensusTransaction, SignedClockTimestamp},
+    SUI_CLOCK_OBJECT_ID,
 };
 
 use tap::prelude::*;
 use tokio::time::Instant;
 
-use sui_types::base_types::AuthorityName;
+use sui_types::base_types::{AuthorityName, ObjectID};
 use sui_types::messages::CertifiedTransaction;
 use tokio::{
     sync::{
@@ -53,13 +59,13 @@ use tracing::log::error;
 pub mod consensus_tests;
 
 /// A serialized consensus transaction.
-type SerializedConsensusTransaction = Vec<u8>;
+pub type SerializedConsensusTransaction = Vec<u8>;
 
 /// The digest of a consensus transactions.
 type ConsensusTransactionDigest = u64;
 
 /// Transaction info response serialized by Sui.
-type SerializedTransactionInfoResponse = Vec<u8>;
+pub type SerializedTransactionInfoResponse = Vec<u8>;
 
 /// Channel to notify the caller when the Sui certificate has been sequenced.
 type TxSequencedNotifier = oneshot::Sender<SuiResult<SerializedTransactionInfoResponse>>;
@@ -72,6 +78,11 @@ pub struct ConsensusAdapterMetrics {
     pub sequencing_certificate_timeouts: IntCounter,
     pub sequencing_certificate_control_delay: IntGauge,
 
+    // Shared object congestion metrics
+    pub sequencing_in_flight_shared_object: IntGaugeVec,
+    pub sequencing_shared_object_success: IntCounterVec,
+    pub sequencing_certificate_rejected_congestion: IntCounter,
+
     // Certificate sequencing metrics
     pub sequencing_fragment_attempt: IntCounter,
     pub sequencing_fragment_success: IntCounter,
@@ -113,6 +124,26 @@ impl ConsensusAdapterMetrics {
                 registry,
             )
             .unwrap(),
+            sequencing_in_flight_shared_object: register_int_gauge_vec_with_registry!(
+                "sequencing_in_flight_shared_object",
+                "The number of certificates touching a shared object that are being sequenced.",
+                &["object_id"],
+                registry,
+            )
+            .unwrap(),
+            sequencing_shared_object_success: register_int_counter_vec_with_registry!(
+                "sequencing_shared_object_success",
+                "Counts the number of successfully sequenced certificates touching a shared object.",
+                &["object_id"],
+                registry,
+            )
+            .unwrap(),
+            sequencing_certificate_rejected_congestion: register_int_counter_with_registry!(
+                "sequencing_certificate_rejected_congestion",
+                "Counts the number of certificates rejected because a shared object they touch has too many certificates being sequenced.",
+                registry,
+            )
+            .unwrap(),
             sequencing_fragment_attempt: register_int_counter_with_registry!(
                 "sequencing_fragment_attempt",
                 "Counts the number of sequenced fragments submitted.",
@@ -194,15 +225,46 @@ impl ConsensusWaiter {
 
 /// The message returned by the consensus to notify that a Sui certificate has been sequenced
 /// and all its shared objects are locked.
-type ConsensusOutput = (
+pub type ConsensusOutput = (
     /* result */ SubscriberResult<SerializedTransactionInfoResponse>,
     /* transaction */ SerializedConsensusTransaction,
 );
 
+/// The boundary between Sui and the consensus protocol sequencing its transactions. Sequenced
+/// transactions are handed back to the authority state, and their outcome is sent to the
+/// `ConsensusListener`.
+#[async_trait]
+pub trait SubmitToConsensus: Send + Sync + 'static {
+    /// Submit a serialized consensus transaction to be sequenced. A successful submission does
+    /// not guarantee that the transaction will be sequenced.
+    async fn submit_to_consensus(&self, transaction: SerializedConsensusTransaction) -> SuiResult;
+}
+
+#[async_trait]
+impl SubmitToConsensus for TransactionsClient<sui_network::tonic::transport::Channel> {
+    async fn submit_to_consensus(&self, transaction: SerializedConsensusTransaction) -> SuiResult {
+        self.clone()
+            .submit_transaction(TransactionProto {
+                transaction: Bytes::from(transaction),
+            })
+            .await
+            .map_err(|e| SuiError::ConsensusConnectionBroken(format!("{:?}", e)))
+            .map(|_| ())
+    }
+}
+
+/// Make a client submitting transactions to the Narwhal worker listening on `consensus_address`.
+pub fn narwhal_consensus_client(consensus_address: &Multiaddr) -> Arc<dyn SubmitToConsensus> {
+    Arc::new(TransactionsClient::new(
+        mysten_network::client::connect_lazy(consensus_address)
+            .expect("Failed to connect to consensus"),
+    ))
+}
+
 /// Submit Sui certificates to the consensus.
 pub struct ConsensusAdapter {
-    /// The network client connecting to the consensus node of this authority.
-    consensus_client: TransactionsClient<sui_network::tonic::transport::Channel>,
+    /// The client submitting transactions to the consensus protocol of this authority.
+    consensus_client: Arc<dyn SubmitToConsensus>,
     /// The Sui committee information.
     committee: Committee,
     /// A channel to notify the consensus listener to take action for a transactions.
@@ -216,29 +278,77 @@ pub struct ConsensusAdapter {
     /// same transaction.
     delay_ms: AtomicU64,
 
+    /// The maximum number of certificates touching the same shared object that may be
+    /// sequenced at the same time. Further submissions touching that object are rejected
+    /// until some of them are sequenced or time out. No limit if None.
+    max_pending_transactions_per_shared_object: Option<usize>,
+    /// The number of certificates being sequenced for each shared object.
+    pending_shared_objects: Arc<Mutex<HashMap<ObjectID, usize>>>,
+
     /// A structure to register metrics
     opt_metrics: OptArcConsensusAdapterMetrics,
 }
 
+/// Tracks the shared objects of a certificate being sequenced, and releases them when dropped.
+struct PendingSharedObjectsGuard {
+    objects: Vec<ObjectID>,
+    pending_shared_objects: Arc<Mutex<HashMap<ObjectID, usize>>>,
+    opt_metrics: OptArcConsensusAdapterMetrics,
+}
+
+impl Drop for PendingSharedObjectsGuard {
+    fn drop(&mut self) {
+        let mut pending_shared_objects = self.pending_shared_objects.lock();
+        for object_id in &self.objects {
+            let label = object_id.to_string();
+            let remaining = match pending_shared_objects.get_mut(object_id) {
+                Some(count) if *count > 1 => {
+                    *count -= 1;
+                    *count
+                }
+                _ => {
+                    pending_shared_objects.remove(object_id);
+                    0
+                }
+            };
+            if let Some(metrics) = &self.opt_metrics {
+                if remaining == 0 {
+                    // Do not keep a time series around for every shared object ever sequenced.
+                    let _ = metrics
+                        .sequencing_in_flight_shared_object
+                        .remove_label_values(&[&label]);
+                    let _ = metrics
+                        .sequencing_shared_object_success
+                        .remove_label_values(&[&label]);
+                } else {
+                    metrics
+                        .sequencing_in_flight_shared_object
+                        .with_label_values(&[&label])
+                        .set(remaining as i64);
+                }
+            }
+        }
+    }
+}
+
 impl ConsensusAdapter {
     /// Make a new Consensus adapter instance.
     pub fn new(
-        consensus_address: Multiaddr,
+        consensus_client: Arc<dyn SubmitToConsensus>,
         committee: Committee,
         tx_consensus_listener: Sender<ConsensusListenerMessage>,
         delay_step: Duration,
+        max_pending_transactions_per_shared_object: Option<usize>,
         opt_metrics: OptArcConsensusAdapterMetrics,
     ) -> Self {
-        let consensus_client = TransactionsClient::new(
-            mysten_network::client::connect_lazy(&consensus_address)
-                .expect("Failed to connect to consensus"),
-        );
         Self {
             consensus_client,
             committee,
             tx_consensus_listener,
             delay_step,
             delay_ms: AtomicU64::new(delay_step.as_millis() as u64),
+            max_pending_transactions_per_shared_object,
+            pending_shared_objects: Arc::new(Mutex::new(HashMap::new())),
             opt_metrics,
         }
     }
@@ -249,6 +359,62 @@ impl ConsensusAdapter {
         true
     }
 
+    /// Register the shared objects of the certificate as being sequenced, unless one of them
+    /// already has the maximum number of certificates being sequenced.
+    /// The Clock is read-only, so certificates reading it do not contend and are not counted.
+    fn admit(&self, certificate: &CertifiedTransaction) -> SuiResult<PendingSharedObjectsGuard> {
+        let objects: Vec<ObjectID> = certificate
+            .shared_input_objects()
+            .filter(|object_id| **object_id != SUI_CLOCK_OBJECT_ID)
+            .copied()
+            .collect();
+        let mut pending_shared_objects = self.pending_shared_objects.lock();
+        if let Some(max_pending) = self.max_pending_transactions_per_shared_object {
+            if let Some(object_id) = objects.iter().find(|object_id| {
+                pending_shared_objects.get(object_id).copied().unwrap_or(0) >= max_pending
+            }) {
+                if let Some(metrics) = &self.opt_metrics {
+                    metrics.sequencing_certificate_rejected_congestion.inc();
+                }
+                return Err(SuiError::SharedObjectCongested {
+                    object_id: *object_id,
+                    max_pending_transactions: max_pending,
+                });
+            }
+        }
+        for object_id in &objects {
+            let count = pending_shared_objects.entry(*object_id).or_insert(0);
+            *count += 1;
+            if let Some(metrics) = &self.opt_metrics {
+                metrics
+                    .sequencing_in_flight_shared_object
+                    .with_label_values(&[&object_id.to_string()])
+                    .set(*count as i64);
+            }
+        }
+        Ok(PendingSharedObjectsGuard {
+            objects,
+            pending_shared_objects: self.pending_shared_objects.clone(),
+            opt_metrics: self.opt_metrics.clone(),
+        })
+    }
+
+    /// Submit the time of this authority to consensus, to advance the timestamp of the Clock.
+    /// Nothing waits for it to be sequenced: if it is dropped, a later one replaces it.
+    pub async fn submit_clock_timestamp(&self, timestamp: SignedClockTimestamp) -> SuiResult {
+        let transaction = ConsensusTransaction::new_clock_timestamp_message(timestamp);
+        let tracking_id = transaction.get_tracking_id();
+        debug!(?tracking_id, "Clock timestamp consensus message created");
+        let serialized = bincode::serialize(&transaction)
+            .expect("Serializing consensus transaction cannot fail");
+        self.consensus_client
+            .submit_to_consensus(serialized)
+            .await
+            .tap_err(|r| {
+                error!("Submit clock timestamp failed with: {:?}", r);
+            })
+    }
+
     /// Submit a transaction to consensus, wait for its processing, and notify the caller.
     // Use .inspect when its stable.
     #[allow(clippy::option_map_unit_fn)]
@@ -260,6 +426,10 @@ impl ConsensusAdapter {
         // Check the Sui certificate (submitted by the user).
         certificate.verify(&self.committee)?;
 
+        // Reject the certificate early if one of its shared objects is congested, so that a
+        // single hot object cannot use up the whole capacity of consensus and of the listener.
+        let pending_guard = self.admit(certificate)?;
+
         // Serialize the certificate in a way that is understandable to consensus (i.e., using
         // bincode) and it certificate to consensus.
         let transaction =
@@ -273,7 +443,6 @@ impl ConsensusAdapter {
         );
         let serialized = bincode::serialize(&transaction)
             .expect("Serializing consensus transaction cannot fail");
-        let bytes = Bytes::from(serialized.clone());
 
         // Notify the consensus listener that we are expecting to process this certificate.
         let (waiter, signals) = ConsensusWaiter::new();
@@ -289,10 +458,8 @@ impl ConsensusAdapter {
         let should_submit = Self::should_submit(certificate);
         if should_submit {
             self.consensus_client
-                .clone()
-                .submit_transaction(TransactionProto { transaction: bytes })
+                .submit_to_consensus(serialized)
                 .await
-                .map_err(|e| SuiError::ConsensusConnectionBroken(format!("{:?}", e)))
                 .tap_err(|r| {
                     error!("Submit transaction failed with: {:?}", r);
                 })?;
@@ -315,6 +482,12 @@ impl ConsensusAdapter {
                 // Increment the attempted certificate sequencing success
                 self.opt_metrics.as_ref().map(|metrics| {
                     metrics.sequencing_certificate_success.inc();
+                    for object_id in &pending_guard.objects {
+                        metrics
+                            .sequencing_shared_object_success
+                            .with_label_values(&[&object_id.to_string()])
+                            .inc();
+                    }
                 });
 
                 Ok(())
@@ -507,8 +680,8 @@ impl ConsensusSender for CheckpointSender {
 
 /// Reliably submit checkpoints fragments to consensus.
 pub struct CheckpointConsensusAdapter {
-    /// The network client connecting to the consensus node of this authority.
-    consensus_client: TransactionsClient<sui_network::tonic::transport::Channel>,
+    /// The client submitting transactions to the consensus protocol of this authority.
+    consensus_client: Arc<dyn SubmitToConsensus>,
     /// Channel to request to be notified when a given consensus transaction is sequenced.
     tx_consensus_listener: Sender<ConsensusListenerMessage>,
     /// Receive new checkpoint fragments to sequence.
@@ -529,7 +702,7 @@ pub struct CheckpointConsensusAdapter {
 impl CheckpointConsensusAdapter {
     /// Create a new `CheckpointConsensusAdapter`.
     pub fn new(
-        consensus_address: Multiaddr,
+        consensus_client: Arc<dyn SubmitToConsensus>,
         tx_consensus_listener: Sender<ConsensusListenerMessage>,
         rx_checkpoint_consensus_adapter: Receiver<CheckpointFragment>,
         checkpoint_db: Arc<Mutex<CheckpointStore>>,
@@ -537,12 +710,6 @@ impl CheckpointConsensusAdapter {
         max_pending_transactions: usize,
         opt_metrics: OptArcConsensusAdapterMetrics,
     ) -> Self {
-        // Create a new network client.
-        let connection = mysten_network::client::connect_lazy(&consensus_address)
-            .expect("Failed to connect to consensus");
-        let consensus_client = TransactionsClient::new(connection);
-
-        // Create the new instance.
         Self {
             consensus_client,
             tx_consensus_listener,
@@ -564,20 +731,12 @@ impl CheckpointConsensusAdapter {
     // Use .inspect when its stable.
     #[allow(clippy::option_map_unit_fn)]
     async fn submit(&self, serialized: SerializedConsensusTransaction) -> SuiResult {
-        let transaction = Bytes::from(serialized);
-        let proto_transaction = TransactionProto { transaction };
-
         // Increment the attempted fragment sequencing failure
         self.opt_metrics.as_ref().map(|metrics| {
             metrics.sequencing_fragment_attempt.inc();
         });
 
-        self.consensus_client
-            .clone()
-            .submit_transaction(proto_transaction)
-            .await
-            .map_err(|e| SuiError::ConsensusConnectionBroken(format!("{:?}", e)))
-            .map(|_| ())
+        self.consensus_client.submit_to_consensus(serialized).await
     }
 
     /// Wait for a transaction to be sequenced by consensus (or to timeout).
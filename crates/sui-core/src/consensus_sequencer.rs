@@ -0,0 +1,114 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::authority::AuthorityState;
+use crate::consensus_adapter::{
+    ConsensusOutput, SerializedConsensusTransaction, SubmitToConsensus,
+};
+use async_trait::async_trait;
+use narwhal_executor::{ExecutionIndices, ExecutionState, ExecutionStateError, SubscriberError};
+use std::sync::Arc;
+use sui_types::error::{SuiError, SuiResult};
+use tokio::{
+    sync::mpsc::{channel, Receiver, Sender},
+    task::JoinHandle,
+};
+use tracing::{debug, error, warn};
+
+#[cfg(test)]
+#[path = "unit_tests/consensus_sequencer_tests.rs"]
+mod consensus_sequencer_tests;
+
+/// The maximum number of submitted transactions waiting to be sequenced.
+const SEQUENCER_CHANNEL_SIZE: usize = 1_000;
+
+/// An in-process consensus protocol for a committee made of a single validator. Transactions
+/// are sequenced in the order they are submitted and handed to the authority state right away,
+/// without running Narwhal primaries and workers.
+pub struct SingleNodeSequencer {
+    tx_sequencer: Sender<SerializedConsensusTransaction>,
+}
+
+impl SingleNodeSequencer {
+    /// Spawn a sequencer for `state` in a dedicated tokio task. The outcome of every sequenced
+    /// transaction is sent to `tx_output`, which is meant to be read by the `ConsensusListener`.
+    pub fn spawn(
+        state: Arc<AuthorityState>,
+        tx_output: Sender<ConsensusOutput>,
+    ) -> (Self, JoinHandle<()>) {
+        let (tx_sequencer, rx_sequencer) = channel(SEQUENCER_CHANNEL_SIZE);
+        let handle = tokio::spawn(Self::run(state, rx_sequencer, tx_output));
+        (Self { tx_sequencer }, handle)
+    }
+
+    /// Main loop sequencing the submitted transactions one at a time.
+    async fn run(
+        state: Arc<AuthorityState>,
+        mut rx_sequencer: Receiver<SerializedConsensusTransaction>,
+        tx_output: Sender<ConsensusOutput>,
+    ) {
+        // Resume after the last transaction sequenced before a restart.
+        let mut index = match state.load_execution_indices().await {
+            Ok(index) => index,
+            Err(e) => {
+                error!("Failed to load the last consensus index: {e}");
+                return;
+            }
+        };
+
+        while let Some(serialized) = rx_sequencer.recv().await {
+            let transaction = match AuthorityState::deserialize(&serialized) {
+                Ok(transaction) => transaction,
+                Err(e) => {
+                    warn!("Dropping consensus transaction that cannot be deserialized: {e}");
+                    continue;
+                }
+            };
+
+            // Every transaction is sequenced as a certificate of its own.
+            index = ExecutionIndices {
+                next_certificate_index: index.next_certificate_index + 1,
+                next_batch_index: 0,
+                next_transaction_index: 0,
+            };
+            debug!(?index, "Sequencing consensus transaction");
+
+            let outcome = state
+                .handle_consensus_transaction(
+                    &narwhal_consensus::ConsensusOutput {
+                        certificate: narwhal_types::Certificate::default(),
+                        consensus_index: index.next_certificate_index,
+                    },
+                    index.clone(),
+                    transaction,
+                )
+                .await;
+            let stop = matches!(&outcome, Err(e) if e.node_error());
+            let result = outcome.map_err(|e| SubscriberError::ClientExecutionError(e.to_string()));
+            if let Err(e) = &result {
+                warn!("Failed to process sequenced transaction: {e}");
+            }
+            if tx_output.send((result, serialized)).await.is_err() {
+                debug!("Consensus listener dropped, stopping the sequencer");
+                return;
+            }
+            // Like Narwhal, do not move past a transaction the authority failed to process.
+            if stop {
+                error!(
+                    "Authority failed to process a sequenced transaction, stopping the sequencer"
+                );
+                return;
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl SubmitToConsensus for SingleNodeSequencer {
+    async fn submit_to_consensus(&self, transaction: SerializedConsensusTransaction) -> SuiResult {
+        self.tx_sequencer
+            .send(transaction)
+            .await
+            .map_err(|e| SuiError::ConsensusConnectionBroken(e.to_string()))
+    }
+}
@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use rocksdb::Options;
+use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 use sui_storage::default_db_options;
 use sui_types::base_types::ObjectID;
@@ -20,6 +21,21 @@ pub struct EpochStore {
     /// or is certified (signed by a quorum).
     #[default_options_override_fn = "epochs_table_default_config"]
     pub(crate) epochs: DBMap<EpochId, AuthenticatedEpoch>,
+
+    /// Map from each finished epoch ID to the economic figures of the epoch, recorded by this node
+    /// when it moved to the next epoch.
+    #[default_options_override_fn = "epochs_table_default_config"]
+    epoch_economics: DBMap<EpochId, EpochEconomics>,
+}
+
+/// Economic figures of an epoch, as read from the Sui system state when the epoch ends.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct EpochEconomics {
+    pub reference_gas_price: u64,
+    /// The storage fund during the epoch, before the storage charges of the epoch are added.
+    pub storage_fund: u64,
+    /// The computation charges distributed as rewards at the end of the epoch, if known.
+    pub rewards_distributed: Option<u64>,
 }
 
 // These functions are used to initialize the DB tables
@@ -70,6 +86,20 @@ impl EpochStore {
             .1
     }
 
+    pub fn record_epoch_economics(
+        &self,
+        epoch_id: EpochId,
+        economics: EpochEconomics,
+    ) -> SuiResult {
+        Ok(self.epoch_economics.insert(&epoch_id, &economics)?)
+    }
+
+    /// Economic figures of a finished epoch. This is `None` for the current epoch, and for epochs
+    /// that ended before this node joined the network.
+    pub fn get_epoch_economics(&self, epoch_id: &EpochId) -> SuiResult<Option<EpochEconomics>> {
+        Ok(self.epoch_economics.get(epoch_id)?)
+    }
+
     fn database_is_empty(&self) -> bool {
         self.epochs.iter().next().is_none()
     }
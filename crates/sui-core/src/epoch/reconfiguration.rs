@@ -4,6 +4,8 @@
 use crate::authority_active::ActiveAuthority;
 use crate::authority_aggregator::{AuthorityAggregator, ReduceOutput};
 use crate::authority_client::{AuthorityAPI, NetworkAuthorityClientMetrics};
+use crate::epoch::epoch_store::EpochEconomics;
+use crate::safe_client::SafeClient;
 use async_trait::async_trait;
 use fastcrypto::traits::ToFromBytes;
 use multiaddr::Multiaddr;
@@ -19,7 +21,7 @@ use sui_types::messages::{
     AuthenticatedEpoch, CertifiedEpoch, EpochInfoDigest, EpochRequest, EpochResponse,
     SignedTransaction,
 };
-use sui_types::sui_system_state::SuiSystemState;
+use sui_types::sui_system_state::{SuiSystemState, ValidatorMetadata};
 use tracing::{debug, error, info, warn};
 use typed_store::Map;
 
@@ -88,6 +90,8 @@ where
         let next_checkpoint = checkpoints.lock().next_checkpoint();
 
         let sui_system_state = self.state.get_sui_system_state_object().await?;
+        let reference_gas_price = sui_system_state.reference_gas_price;
+        let storage_fund = sui_system_state.storage_fund.value();
         let next_epoch = epoch + 1;
         let next_epoch_validators = &sui_system_state.validators.next_epoch_validators;
         let votes = next_epoch_validators
@@ -200,6 +204,16 @@ where
         self.state.unhalt_validator();
         info!(?epoch, "Validator unhalted.");
 
+        self.state.record_epoch_change(
+            epoch,
+            Some(EpochEconomics {
+                reference_gas_price,
+                storage_fund,
+                // Unknown until the computation charges of the epoch are filled in above.
+                rewards_distributed: None,
+            }),
+        )?;
+
         // Restart the node sync process so it gets the new epoch info.
         self.respawn_node_sync_process().await;
 
@@ -216,64 +230,11 @@ where
         &self,
         sui_system_state: SuiSystemState,
     ) -> SuiResult<BTreeMap<AuthorityName, A>> {
-        let mut new_clients = BTreeMap::new();
-        let next_epoch_validators = sui_system_state.validators.next_epoch_validators;
-
-        let mut net_config = mysten_network::config::Config::new();
-        net_config.connect_timeout = Some(Duration::from_secs(5));
-        net_config.request_timeout = Some(Duration::from_secs(5));
-        net_config.http2_keepalive_interval = Some(Duration::from_secs(5));
-
-        let cur_clients = self.net.load().authority_clients.clone();
-
-        for validator in next_epoch_validators {
-            let public_key_bytes = match AuthorityPublicKeyBytes::from_bytes(
-                &validator.pubkey_bytes,
-            ) {
-                Err(err) => {
-                    error!("Error parsing validator public key. Skip this validator in the committee: {:?}", err);
-                    continue;
-                }
-                Ok(result) => result,
-            };
-            // TODO: We only recreate network connection if this is a new validator.
-            // This is because creating a new network connection on the same address doesn't
-            // work. We may want to look into this and see why it doesn't work.
-            if let Some(existing_client) = cur_clients.get(&public_key_bytes) {
-                // TODO: Since we rely purely on the public key to decide whether to recreate
-                // the network, it means that validators won't be able to modify their network
-                // information without also using a new public key.
-                new_clients.insert(public_key_bytes, existing_client.authority_client().clone());
-                debug!(
-                    "Adding unchanged client to the new network: {}",
-                    public_key_bytes
-                );
-                continue;
-            }
-
-            let address = match Multiaddr::try_from(validator.net_address) {
-                Err(err) => {
-                    error!("Error parsing validator network address. Skip this validator in the committee: {:?}", err);
-                    continue;
-                }
-                Ok(result) => result,
-            };
-
-            let channel = match net_config.connect_lazy(&address) {
-                Err(err) => {
-                    error!("Error connecting to client {} with address {:?}. Skip this validator in the committee: {:?}", public_key_bytes, address, err);
-                    continue;
-                }
-                Ok(result) => result,
-            };
-            let client: A = A::recreate(channel, self.network_metrics.clone());
-            debug!(
-                "New network client created for {} at {:?}",
-                public_key_bytes, address
-            );
-            new_clients.insert(public_key_bytes, client);
-        }
-        Ok(new_clients)
+        Ok(recreate_clients(
+            sui_system_state.validators.next_epoch_validators,
+            &self.net.load().authority_clients,
+            self.network_metrics.clone(),
+        ))
     }
 
     async fn wait_for_epoch_cert(
@@ -413,3 +374,68 @@ where
         Ok(())
     }
 }
+
+/// Creates the clients of the given validators. Validators that already have a client in
+/// `cur_clients` keep it, a new network connection is made to the others.
+pub fn recreate_clients<A>(
+    validators: impl IntoIterator<Item = ValidatorMetadata>,
+    cur_clients: &BTreeMap<AuthorityName, SafeClient<A>>,
+    network_metrics: Arc<NetworkAuthorityClientMetrics>,
+) -> BTreeMap<AuthorityName, A>
+where
+    A: Clone + Reconfigurable,
+{
+    let mut new_clients = BTreeMap::new();
+
+    let mut net_config = mysten_network::config::Config::new();
+    net_config.connect_timeout = Some(Duration::from_secs(5));
+    net_config.request_timeout = Some(Duration::from_secs(5));
+    net_config.http2_keepalive_interval = Some(Duration::from_secs(5));
+
+    for validator in validators {
+        let public_key_bytes = match AuthorityPublicKeyBytes::from_bytes(&validator.pubkey_bytes) {
+            Err(err) => {
+                error!("Error parsing validator public key. Skip this validator in the committee: {:?}", err);
+                continue;
+            }
+            Ok(result) => result,
+        };
+        // TODO: We only recreate network connection if this is a new validator.
+        // This is because creating a new network connection on the same address doesn't
+        // work. We may want to look into this and see why it doesn't work.
+        if let Some(existing_client) = cur_clients.get(&public_key_bytes) {
+            // TODO: Since we rely purely on the public key to decide whether to recreate
+            // the network, it means that validators won't be able to modify their network
+            // information without also using a new public key.
+            new_clients.insert(public_key_bytes, existing_client.authority_client().clone());
+            debug!(
+                "Adding unchanged client to the new network: {}",
+                public_key_bytes
+            );
+            continue;
+        }
+
+        let address = match Multiaddr::try_from(validator.net_address) {
+            Err(err) => {
+                error!("Error parsing validator network address. Skip this validator in the committee: {:?}", err);
+                continue;
+            }
+            Ok(result) => result,
+        };
+
+        let channel = match net_config.connect_lazy(&address) {
+            Err(err) => {
+                error!("Error connecting to client {} with address {:?}. Skip this validator in the committee: {:?}", public_key_bytes, address, err);
+                continue;
+            }
+            Ok(result) => result,
+        };
+        let client: A = A::recreate(channel, network_metrics.clone());
+        debug!(
+            "New network client created for {} at {:?}",
+            public_key_bytes, address
+        );
+        new_clients.insert(public_key_bytes, client);
+    }
+    new_clients
+}
@@ -26,6 +26,7 @@ use crate::{
     authority::TemporaryStore,
     authority_active::ActiveAuthority,
     authority_aggregator::authority_aggregator_tests::init_local_authorities,
+    authority_client::NetworkAuthorityClientMetrics,
     checkpoints::{CheckpointLocals, CHECKPOINT_COUNT_PER_EPOCH},
     execution_engine,
 };
@@ -152,7 +153,7 @@ async fn test_start_epoch_change() {
         BTreeSet::new(),
         &state.move_vm,
         &state._native_functions,
-        SuiGasStatus::new_with_budget(1000, 1.into(), 1.into()),
+        &mut SuiGasStatus::new_with_budget(1000, 1.into(), 1.into()),
         state.epoch(),
     );
     let signed_effects = effects.to_sign_effects(0, &state.name, &*state.secret);
@@ -177,6 +178,10 @@ async fn test_finish_epoch_change() {
                 .unwrap()
         })
         .collect();
+    let epoch_changes: Vec<_> = states
+        .iter()
+        .map(|state| state.subscribe_epoch_change())
+        .collect();
 
     let results: Vec<_> = states
         .iter()
@@ -242,5 +247,42 @@ async fn test_finish_epoch_change() {
         assert!(response.signed_effects.is_some());
         assert!(response.certified_transaction.is_some());
         assert!(response.signed_effects.is_some());
+        // The economics of the finished epoch are recorded.
+        let economics = active
+            .state
+            .epoch_store()
+            .get_epoch_economics(&0)
+            .unwrap()
+            .unwrap();
+        assert_eq!(economics.rewards_distributed, None);
+        assert!(active
+            .state
+            .epoch_store()
+            .get_epoch_economics(&1)
+            .unwrap()
+            .is_none());
+    }
+
+    // Subscribers are notified of the new epoch.
+    for mut epoch_change in epoch_changes {
+        assert_eq!(epoch_change.recv().await.unwrap(), 1);
     }
+
+    // An authority aggregator still talking to the genesis committee follows the epoch change.
+    let new_net = net
+        .reconfigure_to_latest_epoch(Arc::new(NetworkAuthorityClientMetrics::new_for_tests()))
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(new_net.committee.epoch, 1);
+    assert_eq!(new_net.authority_clients.len(), 4);
+    assert!(matches!(
+        net.epoch_store.get_authenticated_epoch(&1).unwrap(),
+        Some(AuthenticatedEpoch::Certified(..))
+    ));
+    assert!(new_net
+        .reconfigure_to_latest_epoch(Arc::new(NetworkAuthorityClientMetrics::new_for_tests()))
+        .await
+        .unwrap()
+        .is_none());
 }
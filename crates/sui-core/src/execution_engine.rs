@@ -3,12 +3,15 @@
 
 use move_core_types::ident_str;
 use move_core_types::identifier::Identifier;
-use std::{collections::BTreeSet, sync::Arc};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    sync::Arc,
+};
 use sui_adapter::temporary_store::InnerTemporaryStore;
-use sui_types::storage::ParentSync;
+use sui_types::storage::{ChildObjectResolver, ParentSync};
 
 use crate::authority::TemporaryStore;
-use move_core_types::language_storage::ModuleId;
+use move_core_types::language_storage::{ModuleId, TypeTag};
 use move_vm_runtime::{move_vm::MoveVM, native_functions::NativeFunctionTable};
 use sui_adapter::adapter;
 use sui_types::coin::Coin;
@@ -23,10 +26,10 @@ use sui_types::{
     event::{Event, TransferType},
     gas::{self, SuiGasStatus},
     messages::{
-        CallArg, ChangeEpoch, ExecutionStatus, MoveCall, MoveModulePublish, SingleTransactionKind,
-        TransactionData, TransactionEffects, TransferObject, TransferSui,
+        CallArg, ChangeEpoch, ExecutionStatus, MoveCall, MoveModulePublish, MovePackageUpgrade,
+        SingleTransactionKind, TransactionData, TransactionEffects, TransferObject, TransferSui,
     },
-    object::Object,
+    object::{Data, Object},
     storage::{BackingPackageStore, Storage},
     sui_system_state::{ADVANCE_EPOCH_FUNCTION_NAME, SUI_SYSTEM_MODULE_NAME},
     SUI_FRAMEWORK_ADDRESS, SUI_SYSTEM_STATE_OBJECT_ID,
@@ -34,7 +37,7 @@ use sui_types::{
 use tracing::{debug, instrument, trace};
 
 #[instrument(name = "tx_execute_to_effects", level = "debug", skip_all)]
-pub fn execute_transaction_to_effects<S: BackingPackageStore + ParentSync>(
+pub fn execute_transaction_to_effects<S: BackingPackageStore + ParentSync + ChildObjectResolver>(
     shared_object_refs: Vec<ObjectRef>,
     mut temporary_store: TemporaryStore<S>,
     transaction_data: TransactionData,
@@ -42,7 +45,7 @@ pub fn execute_transaction_to_effects<S: BackingPackageStore + ParentSync>(
     mut transaction_dependencies: BTreeSet<TransactionDigest>,
     move_vm: &Arc<MoveVM>,
     native_functions: &NativeFunctionTable,
-    gas_status: SuiGasStatus,
+    gas_status: &mut SuiGasStatus,
     epoch: EpochId,
 ) -> (
     InnerTemporaryStore,
@@ -77,6 +80,15 @@ pub fn execute_transaction_to_effects<S: BackingPackageStore + ParentSync>(
         status
     );
 
+    // Child objects loaded during execution are read like inputs, so the transactions that last
+    // wrote them are dependencies as well.
+    transaction_dependencies.extend(
+        temporary_store
+            .loaded_child_objects()
+            .values()
+            .map(|object| object.previous_transaction),
+    );
+
     // Remove from dependencies the generic hash
     transaction_dependencies.remove(&TransactionDigest::genesis());
 
@@ -107,18 +119,18 @@ fn charge_gas_for_object_read<S>(
 }
 
 #[instrument(name = "tx_execute", level = "debug", skip_all)]
-fn execute_transaction<S: BackingPackageStore + ParentSync>(
+fn execute_transaction<S: BackingPackageStore + ParentSync + ChildObjectResolver>(
     temporary_store: &mut TemporaryStore<S>,
     transaction_data: TransactionData,
     gas_object_id: ObjectID,
     tx_ctx: &mut TxContext,
     move_vm: &Arc<MoveVM>,
     native_functions: &NativeFunctionTable,
-    mut gas_status: SuiGasStatus,
+    gas_status: &mut SuiGasStatus,
 ) -> (GasCostSummary, Result<(), ExecutionError>) {
     // We must charge object read gas inside here during transaction execution, because if this fails
     // we must still ensure an effect is committed and all objects versions incremented.
-    let mut result = charge_gas_for_object_read(temporary_store, &mut gas_status);
+    let mut result = charge_gas_for_object_read(temporary_store, gas_status);
     if result.is_ok() {
         // TODO: Since we require all mutable objects to not show up more than
         // once across single tx, we should be able to run them in parallel.
@@ -150,25 +162,39 @@ fn execute_transaction<S: BackingPackageStore + ParentSync>(
                     function,
                     type_arguments,
                     arguments,
-                }) => {
-                    let module_id = ModuleId::new(package.0.into(), module);
-                    adapter::execute(
-                        move_vm,
-                        temporary_store,
-                        module_id,
-                        &function,
-                        type_arguments,
-                        arguments,
-                        &mut gas_status,
-                        tx_ctx,
-                    )
-                }
+                }) => execute_move_call(
+                    temporary_store,
+                    move_vm,
+                    native_functions,
+                    package.0,
+                    module,
+                    &function,
+                    type_arguments,
+                    arguments,
+                    gas_status,
+                    tx_ctx,
+                ),
                 SingleTransactionKind::Publish(MoveModulePublish { modules }) => adapter::publish(
                     temporary_store,
                     native_functions.clone(),
                     modules,
                     tx_ctx,
-                    &mut gas_status,
+                    gas_status,
+                ),
+                SingleTransactionKind::Upgrade(MovePackageUpgrade {
+                    package,
+                    upgrade_cap,
+                    modules,
+                    dependencies,
+                }) => adapter::upgrade(
+                    temporary_store,
+                    native_functions.clone(),
+                    modules,
+                    package,
+                    upgrade_cap.0,
+                    dependencies,
+                    tx_ctx,
+                    gas_status,
                 ),
                 SingleTransactionKind::ChangeEpoch(ChangeEpoch {
                     epoch,
@@ -190,7 +216,7 @@ fn execute_transaction<S: BackingPackageStore + ParentSync>(
                             CallArg::Pure(bcs::to_bytes(&storage_charge).unwrap()),
                             CallArg::Pure(bcs::to_bytes(&computation_charge).unwrap()),
                         ],
-                        &mut gas_status,
+                        gas_status,
                         tx_ctx,
                     )
                 }
@@ -219,7 +245,7 @@ fn execute_transaction<S: BackingPackageStore + ParentSync>(
             .clone();
         trace!(?gas_object_id, "Obtained gas object");
         if let Err(err) =
-            temporary_store.charge_gas_for_storage_changes(&mut gas_status, &mut gas_object)
+            temporary_store.charge_gas_for_storage_changes(gas_status, &mut gas_object)
         {
             // If `result` is already `Err`, we basically have two errors at the same time.
             // Users should be generally more interested in the actual execution error, so we
@@ -253,6 +279,57 @@ fn execute_transaction<S: BackingPackageStore + ParentSync>(
     (cost_summary, result)
 }
 
+/// Call `function` in `module` of the package `package_id`. Modules of an upgraded package keep
+/// the address of its original version, and are loaded from the package versions it links
+/// against. As the Move VM caches modules by address, a package that links against versions
+/// other than the original ones is executed by a dedicated VM.
+#[allow(clippy::too_many_arguments)]
+fn execute_move_call<S: BackingPackageStore + ParentSync + ChildObjectResolver>(
+    temporary_store: &mut TemporaryStore<S>,
+    move_vm: &Arc<MoveVM>,
+    native_functions: &NativeFunctionTable,
+    package_id: ObjectID,
+    module: Identifier,
+    function: &Identifier,
+    type_arguments: Vec<TypeTag>,
+    arguments: Vec<CallArg>,
+    gas_status: &mut SuiGasStatus,
+    tx_ctx: &mut TxContext,
+) -> Result<(), ExecutionError> {
+    let (original_id, linkage) = match temporary_store.read_object(&package_id).map(|o| &o.data) {
+        Some(Data::Package(package)) => (package.original_id(), package.linkage()),
+        _ => (package_id, BTreeMap::new()),
+    };
+    let module_id = ModuleId::new(original_id.into(), module);
+    if linkage.is_empty() {
+        return adapter::execute(
+            move_vm,
+            temporary_store,
+            module_id,
+            function,
+            type_arguments,
+            arguments,
+            gas_status,
+            tx_ctx,
+        );
+    }
+    let vm = adapter::new_move_vm(native_functions.clone())
+        .expect("VM creation only fails if natives are invalid, and we created the natives");
+    temporary_store.set_linkage(linkage);
+    let result = adapter::execute(
+        &vm,
+        temporary_store,
+        module_id,
+        function,
+        type_arguments,
+        arguments,
+        gas_status,
+        tx_ctx,
+    );
+    temporary_store.set_linkage(BTreeMap::new());
+    result
+}
+
 fn transfer_object<S>(
     temporary_store: &mut TemporaryStore<S>,
     mut object: Object,
@@ -21,7 +21,7 @@ use prometheus::{
     Registry,
 };
 use sui_types::SUI_SYSTEM_STATE_OBJECT_ID;
-use tracing::{debug, error, trace, Instrument};
+use tracing::{debug, error, info, trace, Instrument};
 
 use sui_adapter::adapter::resolve_and_type_check;
 use sui_config::gateway::GatewayConfig;
@@ -58,6 +58,8 @@ use sui_json_rpc_types::{
 use sui_types::error::SuiError::ConflictingTransaction;
 
 use crate::epoch::epoch_store::EpochStore;
+use crate::epoch::reconfiguration::Reconfigurable;
+use arc_swap::ArcSwap;
 use tap::TapFallible;
 
 #[cfg(test)]
@@ -169,7 +171,10 @@ impl GatewayMetrics {
 }
 
 pub struct GatewayState<A> {
-    authorities: AuthorityAggregator<A>,
+    /// Swapped for an aggregator talking to the new committee when the epoch changes.
+    authorities: ArcSwap<AuthorityAggregator<A>>,
+    /// Metrics of the network clients created for the authorities joining the committee.
+    network_metrics: Arc<NetworkAuthorityClientMetrics>,
     store: Arc<GatewayStore>,
     /// Every transaction committed in authorities (and hence also committed in the Gateway)
     /// will have a unique sequence number. This number is specific to this gateway,
@@ -187,6 +192,7 @@ impl<A> GatewayState<A> {
         base_path: &Path,
         committee: Committee,
         authority_clients: BTreeMap<AuthorityName, A>,
+        network_metrics: Arc<NetworkAuthorityClientMetrics>,
         prometheus_registry: &Registry,
     ) -> SuiResult<Self> {
         let gateway_metrics = GatewayMetrics::new(prometheus_registry);
@@ -203,6 +209,7 @@ impl<A> GatewayState<A> {
                 auth_agg_metrics,
                 safe_client_metrics,
             ),
+            network_metrics,
             gateway_metrics,
         )
     }
@@ -210,12 +217,14 @@ impl<A> GatewayState<A> {
     pub fn new_with_authorities(
         gateway_store: Arc<GatewayStore>,
         authorities: AuthorityAggregator<A>,
+        network_metrics: Arc<NetworkAuthorityClientMetrics>,
         metrics: GatewayMetrics,
     ) -> SuiResult<Self> {
         let next_tx_seq_number = AtomicU64::new(gateway_store.next_sequence_number()?);
         Ok(Self {
             store: gateway_store.clone(),
-            authorities,
+            authorities: ArcSwap::from_pointee(authorities),
+            network_metrics,
             next_tx_seq_number,
             metrics,
             module_cache: SyncModuleCache::new(ResolverWrapper(gateway_store)),
@@ -234,8 +243,8 @@ impl<A> GatewayState<A> {
     }
 
     #[cfg(test)]
-    pub fn get_authorities(&self) -> &AuthorityAggregator<A> {
-        &self.authorities
+    pub fn get_authorities(&self) -> Arc<AuthorityAggregator<A>> {
+        self.authorities.load_full()
     }
 
     #[cfg(test)]
@@ -252,13 +261,15 @@ impl GatewayState<NetworkAuthorityClient> {
         let committee = Self::make_committee(config)?;
         let default_registry = Registry::new();
         let prometheus_registry = prometheus_registry.unwrap_or(&default_registry);
-        let network_metrics = NetworkAuthorityClientMetrics::new(prometheus_registry);
-        let authority_clients = Self::make_authority_clients(config, network_metrics);
+        // Shared with the clients of the authorities joining the committee at epoch changes.
+        let network_metrics = Arc::new(NetworkAuthorityClientMetrics::new(prometheus_registry));
+        let authority_clients = Self::make_authority_clients(config, network_metrics.clone());
 
         Ok(Arc::new(GatewayState::new(
             &config.db_folder_path,
             committee,
             authority_clients,
+            network_metrics,
             prometheus_registry,
         )?))
     }
@@ -272,18 +283,17 @@ impl GatewayState<NetworkAuthorityClient> {
 
     pub fn make_authority_clients(
         config: &GatewayConfig,
-        network_metrics: NetworkAuthorityClientMetrics,
+        network_metrics: Arc<NetworkAuthorityClientMetrics>,
     ) -> BTreeMap<AuthorityName, NetworkAuthorityClient> {
         let mut authority_clients = BTreeMap::new();
         let mut network_config = mysten_network::config::Config::new();
         network_config.connect_timeout = Some(config.send_timeout);
         network_config.request_timeout = Some(config.recv_timeout);
-        let net_metrics = Arc::new(network_metrics);
         for authority in &config.validator_set {
             let channel = network_config
                 .connect_lazy(authority.network_address())
                 .unwrap();
-            let client = NetworkAuthorityClient::new(channel, net_metrics.clone());
+            let client = NetworkAuthorityClient::new(channel, network_metrics.clone());
             authority_clients.insert(authority.protocol_key(), client);
         }
         authority_clients
@@ -447,8 +457,28 @@ pub trait GatewayAPI {
 
 impl<A> GatewayState<A>
 where
-    A: AuthorityAPI + Send + Sync + 'static + Clone,
+    A: AuthorityAPI + Send + Sync + 'static + Clone + Reconfigurable,
 {
+    /// Move to the committee of the latest epoch certified by the authorities, if the epoch
+    /// changed. Returns true if the committee changed.
+    pub async fn refresh_committee(&self) -> SuiResult<bool> {
+        let authorities = self.authorities.load_full();
+        match authorities
+            .reconfigure_to_latest_epoch(self.network_metrics.clone())
+            .await?
+        {
+            Some(authorities) => {
+                info!(
+                    epoch = authorities.committee.epoch,
+                    "Gateway moved to a new committee"
+                );
+                self.authorities.store(Arc::new(authorities));
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
     pub async fn get_framework_object_ref(&self) -> Result<ObjectRef, anyhow::Error> {
         Ok(self
             .get_object_ref(&ObjectID::from(SUI_FRAMEWORK_ADDRESS))
@@ -635,6 +665,7 @@ where
         );
         let exec_result = self
             .authorities
+            .load_full()
             .execute_transaction(&transaction)
             .instrument(span)
             .await;
@@ -783,7 +814,11 @@ where
     }
 
     async fn download_object_from_authorities(&self, object_id: ObjectID) -> SuiResult<ObjectRead> {
-        let result = self.authorities.get_object_info_execute(object_id).await?;
+        let result = self
+            .authorities
+            .load_full()
+            .get_object_info_execute(object_id)
+            .await?;
         if let ObjectRead::Exists(obj_ref, object, _) = &result {
             let local_object = self.store.get_object(&object_id)?;
             let should_update = match local_object {
@@ -823,6 +858,7 @@ where
     ) -> Result<HashMap<ObjectRef, Object>, SuiError> {
         let mut receiver = self
             .authorities
+            .load_full()
             .fetch_objects_from_authorities(object_refs.clone());
 
         let mut objects = HashMap::new();
@@ -1259,7 +1295,7 @@ where
 #[async_trait]
 impl<A> GatewayAPI for GatewayState<A>
 where
-    A: AuthorityAPI + Send + Sync + Clone + 'static,
+    A: AuthorityAPI + Send + Sync + Clone + 'static + Reconfigurable,
 {
     async fn execute_transaction(
         &self,
@@ -1303,6 +1339,11 @@ where
                     remaining_retries -= 1;
                     self.metrics.total_tx_retries.inc();
 
+                    // The transaction may have failed because the epoch changed.
+                    if let Err(err) = self.refresh_committee().await {
+                        debug!(?tx_digest, "Failed to refresh the committee: {:?}", err);
+                    }
+
                     debug!(
                         remaining_retries,
                         ?tx_digest,
@@ -1426,6 +1467,7 @@ where
 
         let (active_object_certs, _deleted_refs_certs) = self
             .authorities
+            .load_full()
             .sync_all_owned_objects(account_addr, Duration::from_secs(60))
             .await?;
 
@@ -20,7 +20,7 @@ use sui_types::{
     },
     messages::{
         CallArg, EntryArgumentErrorKind, ExecutionFailureStatus, ExecutionStatus, ObjectArg,
-        ObjectInfoRequestKind, SingleTransactionKind, TransactionKind,
+        ObjectInfoRequestKind, PackageUpgradeError, SingleTransactionKind, TransactionKind,
     },
     object::{Data, Owner},
     storage::DeleteKind,
@@ -77,6 +77,7 @@ fn get_registry() -> Result<Registry> {
     tracer.trace_type::<ExecutionFailureStatus>(&samples)?;
     tracer.trace_type::<AbortLocation>(&samples)?;
     tracer.trace_type::<EntryArgumentErrorKind>(&samples)?;
+    tracer.trace_type::<PackageUpgradeError>(&samples)?;
     tracer.trace_type::<CallArg>(&samples)?;
     tracer.trace_type::<ObjectArg>(&samples)?;
     tracer.trace_type::<Data>(&samples)?;
@@ -9,12 +9,14 @@ pub mod authority_client;
 pub mod authority_server;
 pub mod checkpoints;
 pub mod consensus_adapter;
+pub mod consensus_sequencer;
 pub mod epoch;
 pub mod event_handler;
 pub mod execution_engine;
 pub mod gateway_state;
 pub mod metrics;
 pub mod safe_client;
+pub mod state_sync;
 pub mod streamer;
 pub mod transaction_input_checker;
 pub mod transaction_streamer;
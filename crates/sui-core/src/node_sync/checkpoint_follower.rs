@@ -0,0 +1,321 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    authority_active::checkpoint_driver::get_latest_checkpoint_from_all,
+    authority_aggregator::AuthorityAggregator, authority_client::AuthorityAPI,
+    state_sync::StateSyncPeer,
+};
+use std::collections::BTreeSet;
+use std::sync::Arc;
+use sui_types::{
+    base_types::AuthorityName,
+    committee::Committee,
+    error::{SuiError, SuiResult},
+    messages_checkpoint::{
+        CertifiedCheckpointSummary, CheckpointContents, CheckpointSequenceNumber,
+    },
+};
+
+use futures::StreamExt;
+use tokio::sync::oneshot;
+use tokio::time::{sleep, Duration};
+use tracing::{debug, error, info, warn};
+
+use super::{NodeSyncHandle, NodeSyncState};
+
+// How long to wait between polls of the network for a newer checkpoint.
+const CHECKPOINT_POLL_INTERVAL: Duration = Duration::from_secs(10);
+// How long to wait for a quorum of validators to report their latest checkpoint.
+const LATEST_CHECKPOINT_TIMEOUT: Duration = Duration::from_secs(60);
+// How long to wait for further responses once a quorum has reported its latest checkpoint.
+const LATEST_CHECKPOINT_EXTRA_TIME: Duration = Duration::from_millis(200);
+// How long to keep asking peers for a single checkpoint before giving up on this round.
+const CHECKPOINT_DOWNLOAD_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Sync the node by following certified checkpoints instead of validator batch streams.
+///
+/// Each checkpoint is downloaded together with its contents from a full node peer or any
+/// validator, verified against the committee, and then all of its transactions are executed
+/// locally in causal order. Certificates and effects are fetched from full node peers first, and
+/// only the ones no peer could serve are downloaded from validators. Progress is persisted in the
+/// NodeSyncStore after every checkpoint, so that a restarted node resumes at the first checkpoint
+/// it has not fully executed.
+pub async fn checkpoint_sync_process<A>(
+    node_sync_handle: NodeSyncHandle,
+    node_sync_state: Arc<NodeSyncState<A>>,
+    aggregator: Arc<AuthorityAggregator<A>>,
+    state_sync_peers: Vec<StateSyncPeer>,
+    mut cancel_receiver: oneshot::Receiver<()>,
+) where
+    A: AuthorityAPI + Send + Sync + 'static + Clone,
+{
+    debug!("starting checkpoint sync process");
+
+    loop {
+        tokio::select! {
+            biased;
+
+            _ = &mut cancel_receiver => {
+                info!("checkpoint_sync_process cancelled");
+                return;
+            }
+
+            result = sync_to_latest_checkpoint(
+                &node_sync_handle,
+                &node_sync_state,
+                &aggregator,
+                &state_sync_peers,
+            ) => {
+                match result {
+                    Ok(Some(seq)) => debug!(?seq, "synced to latest checkpoint"),
+                    Ok(None) => debug!("no certified checkpoints found"),
+                    Err(err) => warn!("checkpoint sync failed: {}", err),
+                }
+            }
+        }
+
+        tokio::select! {
+            biased;
+
+            _ = &mut cancel_receiver => {
+                info!("checkpoint_sync_process cancelled");
+                return;
+            }
+
+            _ = sleep(CHECKPOINT_POLL_INTERVAL) => (),
+        }
+    }
+}
+
+/// Sync every checkpoint between the last synced checkpoint and the latest checkpoint that is
+/// known to the network. Returns the sequence number of the last synced checkpoint, if any.
+async fn sync_to_latest_checkpoint<A>(
+    node_sync_handle: &NodeSyncHandle,
+    node_sync_state: &NodeSyncState<A>,
+    aggregator: &Arc<AuthorityAggregator<A>>,
+    peers: &[StateSyncPeer],
+) -> SuiResult<Option<CheckpointSequenceNumber>>
+where
+    A: AuthorityAPI + Send + Sync + 'static + Clone,
+{
+    let store = &node_sync_state.node_sync_store;
+    let latest_synced = store.latest_synced_checkpoint()?;
+
+    let latest_known = match get_latest_checkpoint_from_all(
+        aggregator.clone(),
+        LATEST_CHECKPOINT_EXTRA_TIME,
+        LATEST_CHECKPOINT_TIMEOUT,
+    )
+    .await?
+    {
+        Some(checkpoint) => checkpoint.summary.sequence_number,
+        None => return Ok(latest_synced.map(|(seq, _)| seq)),
+    };
+
+    let next_seq = latest_synced.map(|(seq, _)| seq + 1).unwrap_or(0);
+    if next_seq > latest_known {
+        return Ok(latest_synced.map(|(seq, _)| seq));
+    }
+
+    info!(?next_seq, ?latest_known, "syncing checkpoints");
+    for seq in next_seq..=latest_known {
+        sync_one_checkpoint(node_sync_handle, node_sync_state, aggregator, peers, seq).await?;
+        node_sync_state
+            .metrics
+            .checkpoint_sync_sequence_number
+            .set(seq as i64);
+    }
+
+    Ok(Some(latest_known))
+}
+
+async fn sync_one_checkpoint<A>(
+    node_sync_handle: &NodeSyncHandle,
+    node_sync_state: &NodeSyncState<A>,
+    aggregator: &Arc<AuthorityAggregator<A>>,
+    peers: &[StateSyncPeer],
+    seq: CheckpointSequenceNumber,
+) -> SuiResult
+where
+    A: AuthorityAPI + Send + Sync + 'static + Clone,
+{
+    let store = &node_sync_state.node_sync_store;
+
+    let (checkpoint, contents) = match store.get_pending_checkpoint(seq)? {
+        Some(pending) => {
+            debug!(?seq, "resuming partially synced checkpoint");
+            pending
+        }
+        None => {
+            let (checkpoint, contents) =
+                download_checkpoint(node_sync_state, aggregator, peers, seq).await?;
+            verify_checkpoint_chain(node_sync_state, &checkpoint)?;
+            store.store_pending_checkpoint(&checkpoint, &contents)?;
+            (checkpoint, contents)
+        }
+    };
+
+    prefetch_from_peers(node_sync_state, &aggregator.committee, peers, &contents).await?;
+
+    debug!(?seq, num_txes = ?contents.size(), "executing checkpoint transactions");
+    let errors = node_sync_handle
+        .sync_checkpoint_cert_transactions(&contents)
+        .await?
+        .zip(futures::stream::iter(contents.iter()))
+        .filter_map(|(r, digests)| async move {
+            r.map_err(|e| {
+                info!(?digests, "failed to execute digest from checkpoint: {}", e);
+                e
+            })
+            .err()
+        })
+        .collect::<Vec<SuiError>>()
+        .await;
+
+    if !errors.is_empty() {
+        let error = format!("Failed to sync transactions in checkpoint {}", seq);
+        error!(?seq, ?errors, "{}", error);
+        return Err(SuiError::CheckpointingError { error });
+    }
+
+    // Make the checkpoint available to readers of the local checkpoint store. This may already
+    // have happened if we crashed after this write but before marking the checkpoint as synced.
+    if let Some(checkpoints) = &node_sync_state.state.checkpoints {
+        let mut checkpoints = checkpoints.lock();
+        if checkpoints.get_checkpoint(seq)?.is_none() {
+            checkpoints.process_synced_checkpoint_certificate(
+                &checkpoint,
+                &contents,
+                &aggregator.committee,
+            )?;
+        }
+    }
+
+    store.mark_checkpoint_synced(&checkpoint)?;
+    info!(?seq, "checkpoint synced");
+    Ok(())
+}
+
+/// Download a certified checkpoint and its contents from a full node peer, or from any
+/// validator if no peer has it, and verify both against the committee.
+async fn download_checkpoint<A>(
+    node_sync_state: &NodeSyncState<A>,
+    aggregator: &Arc<AuthorityAggregator<A>>,
+    peers: &[StateSyncPeer],
+    seq: CheckpointSequenceNumber,
+) -> SuiResult<(CertifiedCheckpointSummary, CheckpointContents)>
+where
+    A: AuthorityAPI + Send + Sync + 'static + Clone,
+{
+    for peer in peers {
+        match peer.get_checkpoint(seq, &aggregator.committee).await {
+            Ok(checkpoint) => {
+                node_sync_state
+                    .metrics
+                    .checkpoint_sync_peer_checkpoints
+                    .inc();
+                return Ok(checkpoint);
+            }
+            Err(err) => {
+                debug!(?seq, peer = %peer.address(), "checkpoint not available from peer: {}", err)
+            }
+        }
+    }
+
+    let authorities: BTreeSet<AuthorityName> = aggregator.committee.names().cloned().collect();
+    let (checkpoint, contents) = aggregator
+        .get_certified_checkpoint(seq, true, &authorities, Some(CHECKPOINT_DOWNLOAD_TIMEOUT))
+        .await?;
+
+    let contents = contents.ok_or_else(|| SuiError::CheckpointingError {
+        error: format!("Checkpoint {} was returned without its contents", seq),
+    })?;
+
+    checkpoint.verify(&aggregator.committee, Some(&contents))?;
+    Ok((checkpoint, contents))
+}
+
+/// Fetch the certificates and effects of a verified checkpoint from full node peers into the
+/// NodeSyncStore, where the node sync process picks them up instead of asking validators.
+/// Transactions that no peer can serve are left for the validators.
+async fn prefetch_from_peers<A>(
+    node_sync_state: &NodeSyncState<A>,
+    committee: &Committee,
+    peers: &[StateSyncPeer],
+    contents: &CheckpointContents,
+) -> SuiResult {
+    if peers.is_empty() {
+        return Ok(());
+    }
+
+    let store = &node_sync_state.node_sync_store;
+    for digests in contents.iter() {
+        if node_sync_state
+            .state
+            .database
+            .effects_exists(&digests.transaction)?
+        {
+            continue;
+        }
+        if let (Some(_), Some(_)) = store.get_cert_and_effects(&digests.transaction)? {
+            continue;
+        }
+
+        for peer in peers {
+            match peer.get_transaction(digests, committee).await {
+                Ok((cert, effects)) => {
+                    store.store_cert(&cert)?;
+                    store.store_effects(&digests.transaction, &effects)?;
+                    node_sync_state
+                        .metrics
+                        .checkpoint_sync_peer_transactions
+                        .inc();
+                    break;
+                }
+                Err(err) => debug!(
+                    ?digests,
+                    peer = %peer.address(),
+                    "transaction not available from peer: {}",
+                    err
+                ),
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Make sure a downloaded checkpoint extends the last checkpoint we synced.
+fn verify_checkpoint_chain<A>(
+    node_sync_state: &NodeSyncState<A>,
+    checkpoint: &CertifiedCheckpointSummary,
+) -> SuiResult {
+    let seq = checkpoint.summary.sequence_number;
+    let previous = node_sync_state.node_sync_store.latest_synced_checkpoint()?;
+
+    match previous {
+        Some((prev_seq, prev_digest)) if prev_seq + 1 == seq => {
+            if checkpoint.summary.previous_digest != Some(prev_digest) {
+                return Err(SuiError::CheckpointingError {
+                    error: format!(
+                        "Checkpoint {} does not extend the previously synced checkpoint",
+                        seq
+                    ),
+                });
+            }
+        }
+        None if seq == 0 => (),
+        _ => {
+            return Err(SuiError::CheckpointingError {
+                error: format!(
+                    "Checkpoint {} is not the next checkpoint to sync (last synced: {:?})",
+                    seq,
+                    previous.map(|(prev_seq, _)| prev_seq)
+                ),
+            });
+        }
+    }
+
+    Ok(())
+}
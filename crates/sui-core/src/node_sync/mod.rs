@@ -6,3 +6,6 @@ pub use node_state::*;
 
 mod node_follower;
 pub use node_follower::*;
+
+mod checkpoint_follower;
+pub use checkpoint_follower::*;
@@ -190,7 +190,7 @@ impl DownloadRequest {
 /// TXes locally.
 pub struct NodeSyncState<A> {
     committee: Arc<Committee>,
-    state: Arc<AuthorityState>,
+    pub(super) state: Arc<AuthorityState>,
     pub(super) node_sync_store: Arc<NodeSyncStore>,
     aggregator: Arc<AuthorityAggregator<A>>,
 
@@ -208,7 +208,7 @@ pub struct NodeSyncState<A> {
     receiver: Arc<tokio::sync::Mutex<mpsc::Receiver<DigestsMessage>>>,
 
     // Gossip Metrics
-    metrics: GossipMetrics,
+    pub(super) metrics: GossipMetrics,
 }
 
 impl<A> NodeSyncState<A> {
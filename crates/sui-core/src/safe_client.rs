@@ -6,12 +6,15 @@ use crate::authority_client::{AuthorityAPI, BatchInfoResponseItemStream};
 use crate::epoch::epoch_store::EpochStore;
 use crate::metrics::start_timer;
 use futures::StreamExt;
+use parking_lot::Mutex;
 use prometheus::core::{GenericCounter, GenericGauge};
 use prometheus::{
-    register_histogram_vec_with_registry, register_int_counter_vec_with_registry,
-    register_int_gauge_vec_with_registry, Histogram, HistogramVec, IntCounterVec, IntGaugeVec,
+    register_gauge_vec_with_registry, register_histogram_vec_with_registry,
+    register_int_counter_vec_with_registry, register_int_gauge_vec_with_registry, Gauge, GaugeVec,
+    Histogram, HistogramVec, IntCounterVec, IntGaugeVec,
 };
 use std::sync::Arc;
+use std::time::Duration;
 use sui_types::batch::{AuthorityBatch, SignedBatch, TxSequenceNumber, UpdateItem};
 use sui_types::crypto::AuthorityPublicKeyBytes;
 use sui_types::messages_checkpoint::{
@@ -35,6 +38,8 @@ pub struct SafeClientMetrics {
     pub(crate) follower_streaming_from_seq_number_by_address: IntGaugeVec,
     pub(crate) follower_streaming_reconnect_times_by_address: IntCounterVec,
     latency: HistogramVec,
+    latency_estimate: GaugeVec,
+    error_rate: GaugeVec,
 }
 
 const LATENCY_SEC_BUCKETS: &[f64] = &[0.001, 0.01, 0.1, 1., 2., 3., 5., 10., 20., 30., 60., 180.];
@@ -78,6 +83,20 @@ impl SafeClientMetrics {
                 registry,
             )
             .unwrap(),
+            latency_estimate: register_gauge_vec_with_registry!(
+                "safe_client_latency_estimate",
+                "Moving average of the latency of each validator in seconds, used to route requests",
+                &["address"],
+                registry,
+            )
+            .unwrap(),
+            error_rate: register_gauge_vec_with_registry!(
+                "safe_client_error_rate",
+                "Moving average of the rate of failed or timed out requests to each validator",
+                &["address"],
+                registry,
+            )
+            .unwrap(),
         }
     }
 
@@ -87,6 +106,38 @@ impl SafeClientMetrics {
     }
 }
 
+// Weight of a new sample in the moving averages of latency and error rate.
+const MOVING_AVERAGE_WEIGHT: f64 = 0.2;
+// Latency added to the routing score of an authority that fails every request.
+const ERROR_RATE_PENALTY: Duration = Duration::from_secs(1);
+
+/// Latency and error rate observed for an authority, used to route requests to the fastest
+/// authorities first. Errors caused by the request itself, such as an invalid transaction,
+/// do not count against the authority.
+#[derive(Debug, Default)]
+struct AuthorityStats {
+    latency: Option<Duration>,
+    error_rate: f64,
+}
+
+impl AuthorityStats {
+    fn record_latency(&mut self, latency: Duration) {
+        self.latency = Some(match self.latency {
+            Some(average) => {
+                average.mul_f64(1.0 - MOVING_AVERAGE_WEIGHT)
+                    + latency.mul_f64(MOVING_AVERAGE_WEIGHT)
+            }
+            None => latency,
+        });
+    }
+
+    fn record_outcome(&mut self, is_error: bool) {
+        let sample = if is_error { 1.0 } else { 0.0 };
+        self.error_rate =
+            self.error_rate * (1.0 - MOVING_AVERAGE_WEIGHT) + sample * MOVING_AVERAGE_WEIGHT;
+    }
+}
+
 /// See `SafeClientMetrics::new` for description of each metrics.
 /// The metrics are per validator client.
 #[derive(Clone)]
@@ -94,6 +145,8 @@ pub struct SafeClient<C> {
     authority_client: C,
     epoch_store: Arc<EpochStore>,
     address: AuthorityPublicKeyBytes,
+    // Shared by all clones of the client.
+    stats: Arc<Mutex<AuthorityStats>>,
 
     metrics_total_requests_handle_transaction_and_effects_info_request:
         GenericCounter<prometheus::core::AtomicU64>,
@@ -115,6 +168,8 @@ pub struct SafeClient<C> {
     metrics_handle_certificate_latency: Histogram,
     metrics_handle_obj_info_latency: Histogram,
     metrics_handle_tx_info_latency: Histogram,
+    metrics_latency_estimate: Gauge,
+    metrics_error_rate: Gauge,
 }
 
 impl<C> SafeClient<C> {
@@ -174,11 +229,18 @@ impl<C> SafeClient<C> {
         let metrics_handle_tx_info_latency = safe_client_metrics
             .latency
             .with_label_values(&[&validator_address, "handle_transaction_info_request"]);
+        let metrics_latency_estimate = safe_client_metrics
+            .latency_estimate
+            .with_label_values(&[&validator_address]);
+        let metrics_error_rate = safe_client_metrics
+            .error_rate
+            .with_label_values(&[&validator_address]);
 
         Self {
             authority_client,
             epoch_store,
             address,
+            stats: Arc::new(Mutex::new(AuthorityStats::default())),
 
             metrics_total_requests_handle_transaction_and_effects_info_request,
             metrics_total_ok_responses_handle_transaction_and_effects_info_request,
@@ -194,6 +256,8 @@ impl<C> SafeClient<C> {
             metrics_handle_certificate_latency,
             metrics_handle_obj_info_latency,
             metrics_handle_tx_info_latency,
+            metrics_latency_estimate,
+            metrics_error_rate,
         }
     }
 
@@ -448,6 +512,64 @@ impl<C> SafeClient<C> {
     /// TODO: Get rid of this. https://github.com/MystenLabs/sui/issues/3740
     pub fn report_client_error(&self, error: &SuiError) {
         info!(?error, authority =? self.address, "Client error");
+        self.update_stats(|stats| stats.record_outcome(true));
+    }
+
+    /// Moving average of the latency of this authority, if any request completed yet.
+    pub fn latency_estimate(&self) -> Option<Duration> {
+        self.stats.lock().latency
+    }
+
+    /// Moving average of the fraction of requests to this authority that failed or timed out.
+    pub fn error_rate(&self) -> f64 {
+        self.stats.lock().error_rate
+    }
+
+    /// The expected latency of this authority, penalized by its error rate. Authorities without
+    /// any latency sample yet score zero, so that they are tried early.
+    pub fn routing_score(&self) -> Duration {
+        let stats = self.stats.lock();
+        stats.latency.unwrap_or_default() + ERROR_RATE_PENALTY.mul_f64(stats.error_rate)
+    }
+
+    /// Report that a request did not complete within `timeout`.
+    pub fn report_timeout(&self, timeout: Duration) {
+        self.update_stats(|stats| {
+            stats.record_latency(timeout);
+            stats.record_outcome(true);
+        });
+    }
+
+    /// Report a request that was abandoned after `elapsed`, because enough other authorities
+    /// responded. The latency estimate is only raised, as the request may have been about to
+    /// complete.
+    pub fn report_abandoned(&self, elapsed: Duration) {
+        self.update_stats(|stats| {
+            if stats.latency.map_or(true, |latency| latency < elapsed) {
+                stats.record_latency(elapsed);
+            }
+        });
+    }
+
+    // Transport errors are the fault of the authority, while other errors are answers to an
+    // invalid request and still tell us how fast the authority is.
+    fn record_response<T>(&self, start_ts: Instant, response: &SuiResult<T>) {
+        self.update_stats(|stats| match response {
+            Err(SuiError::RpcError(..)) | Err(SuiError::TimeoutError) => stats.record_outcome(true),
+            _ => {
+                stats.record_latency(start_ts.elapsed());
+                stats.record_outcome(false);
+            }
+        });
+    }
+
+    fn update_stats(&self, update: impl FnOnce(&mut AuthorityStats)) {
+        let mut stats = self.stats.lock();
+        update(&mut stats);
+        if let Some(latency) = stats.latency {
+            self.metrics_latency_estimate.set(latency.as_secs_f64());
+        }
+        self.metrics_error_rate.set(stats.error_rate);
     }
 }
 
@@ -464,10 +586,9 @@ where
         let start_ts = Instant::now();
         let _metrics_guard =
             start_timer!(self.metrics_handle_transaction_latency.clone(), &start_ts);
-        let transaction_info = self
-            .authority_client
-            .handle_transaction(transaction)
-            .await?;
+        let response = self.authority_client.handle_transaction(transaction).await;
+        self.record_response(start_ts, &response);
+        let transaction_info = response?;
         if let Err(err) = self.check_transaction_response(&digest, None, &transaction_info) {
             self.report_client_error(&err);
             return Err(err);
@@ -500,10 +621,9 @@ where
         let start_ts = Instant::now();
         let _metrics_guard =
             start_timer!(self.metrics_handle_certificate_latency.clone(), &start_ts);
-        let transaction_info = self
-            .authority_client
-            .handle_certificate(certificate)
-            .await?;
+        let response = self.authority_client.handle_certificate(certificate).await;
+        self.record_response(start_ts, &response);
+        let transaction_info = response?;
 
         if let Err(err) = self.verify_certificate_response(&digest, &transaction_info) {
             self.report_client_error(&err);
@@ -533,7 +653,9 @@ where
         let response = self
             .authority_client
             .handle_object_info_request(request.clone())
-            .await?;
+            .await;
+        self.record_response(start_ts, &response);
+        let response = response?;
         if let Err(err) = self.check_object_response(&request, &response) {
             self.report_client_error(&err);
             return Err(err);
@@ -555,10 +677,12 @@ where
         let start_ts = Instant::now();
         let _metrics_guard = start_timer!(self.metrics_handle_tx_info_latency.clone(), &start_ts);
 
-        let transaction_info = self
+        let response = self
             .authority_client
             .handle_transaction_info_request(request)
-            .await?;
+            .await;
+        self.record_response(start_ts, &response);
+        let transaction_info = response?;
 
         if let Err(err) = self.check_transaction_response(&digest, None, &transaction_info) {
             self.report_client_error(&err);
@@ -576,10 +700,13 @@ where
     ) -> Result<TransactionInfoResponse, SuiError> {
         self.metrics_total_requests_handle_transaction_and_effects_info_request
             .inc();
-        let transaction_info = self
+        let start_ts = Instant::now();
+        let response = self
             .authority_client
             .handle_transaction_info_request(digests.transaction.into())
-            .await?;
+            .await;
+        self.record_response(start_ts, &response);
+        let transaction_info = response?;
 
         if let Err(err) = self.check_transaction_response(
             &digests.transaction,
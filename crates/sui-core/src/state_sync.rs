@@ -0,0 +1,261 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+/*
+    Full nodes serve the state they have synced (checkpoints, certificates, effects and
+    objects) to other full nodes, so that new full nodes do not have to pull everything
+    from validators.
+
+    Peers are not trusted. Every response is verified before use:
+    - checkpoints must carry a valid certificate from the committee, and their contents must
+      match the digest in the certified summary;
+    - certificates must be signed by a quorum of the committee, and effects must match the
+      effects digest listed in a certified checkpoint;
+    - objects must match an object reference taken from verified effects.
+*/
+
+use crate::authority::AuthorityState;
+use anyhow::anyhow;
+use async_trait::async_trait;
+use multiaddr::Multiaddr;
+use std::sync::Arc;
+use sui_network::{
+    api::{StateSync, StateSyncClient},
+    tonic,
+};
+use sui_types::{
+    base_types::{ExecutionDigests, ObjectRef},
+    committee::Committee,
+    error::{SuiError, SuiResult},
+    messages::{
+        CertifiedTransaction, SignedTransactionEffects, StateSyncObjectRequest,
+        StateSyncObjectResponse, StateSyncTransactionRequest, StateSyncTransactionResponse,
+    },
+    messages_checkpoint::{
+        AuthenticatedCheckpoint, CertifiedCheckpointSummary, CheckpointContents, CheckpointRequest,
+        CheckpointRequestType, CheckpointResponse, CheckpointSequenceNumber,
+    },
+    object::Object,
+};
+
+/// Serves the locally synced state of a full node to other full nodes.
+pub struct StateSyncService {
+    state: Arc<AuthorityState>,
+}
+
+impl StateSyncService {
+    pub fn new(state: Arc<AuthorityState>) -> Self {
+        Self { state }
+    }
+}
+
+#[async_trait]
+impl StateSync for StateSyncService {
+    async fn checkpoint(
+        &self,
+        request: tonic::Request<CheckpointRequest>,
+    ) -> Result<tonic::Response<CheckpointResponse>, tonic::Status> {
+        let request = request.into_inner();
+
+        // Full nodes do not take part in checkpoint creation, so only certified checkpoints
+        // are served.
+        if !matches!(
+            request.request_type,
+            CheckpointRequestType::AuthenticatedCheckpoint(_)
+        ) {
+            return Err(tonic::Status::invalid_argument(
+                "only authenticated checkpoints are served by state sync",
+            ));
+        }
+
+        let response = self
+            .state
+            .handle_checkpoint_request(&request)
+            .map_err(|e| tonic::Status::internal(e.to_string()))?;
+
+        Ok(tonic::Response::new(response))
+    }
+
+    async fn transaction(
+        &self,
+        request: tonic::Request<StateSyncTransactionRequest>,
+    ) -> Result<tonic::Response<StateSyncTransactionResponse>, tonic::Status> {
+        let request = request.into_inner();
+
+        let info = self
+            .state
+            .database
+            .get_signed_transaction_info(&request.digests.transaction)
+            .map_err(|e| tonic::Status::internal(e.to_string()))?;
+
+        Ok(tonic::Response::new(StateSyncTransactionResponse {
+            certified_transaction: info.certified_transaction,
+            signed_effects: info.signed_effects,
+        }))
+    }
+
+    async fn object(
+        &self,
+        request: tonic::Request<StateSyncObjectRequest>,
+    ) -> Result<tonic::Response<StateSyncObjectResponse>, tonic::Status> {
+        let (object_id, version, _) = request.into_inner().object_ref;
+
+        let object = self
+            .state
+            .database
+            .get_object_by_key(&object_id, version)
+            .map_err(|e| tonic::Status::internal(e.to_string()))?;
+
+        Ok(tonic::Response::new(StateSyncObjectResponse { object }))
+    }
+}
+
+/// A client for the state sync service of a single (untrusted) full node peer.
+/// All data returned by this client has been verified.
+#[derive(Clone)]
+pub struct StateSyncPeer {
+    address: Multiaddr,
+    client: StateSyncClient<tonic::transport::Channel>,
+}
+
+impl StateSyncPeer {
+    pub fn connect_lazy(address: &Multiaddr) -> anyhow::Result<Self> {
+        let channel = mysten_network::client::connect_lazy(address)
+            .map_err(|err| anyhow!(err.to_string()))?;
+        Ok(Self {
+            address: address.clone(),
+            client: StateSyncClient::new(channel),
+        })
+    }
+
+    pub fn address(&self) -> &Multiaddr {
+        &self.address
+    }
+
+    fn error(&self, error: impl Into<String>) -> SuiError {
+        SuiError::StateSyncPeerError {
+            peer: self.address.to_string(),
+            error: error.into(),
+        }
+    }
+
+    /// Fetch a certified checkpoint and its contents, verified against `committee`.
+    pub async fn get_checkpoint(
+        &self,
+        sequence_number: CheckpointSequenceNumber,
+        committee: &Committee,
+    ) -> SuiResult<(CertifiedCheckpointSummary, CheckpointContents)> {
+        let response = self
+            .client
+            .clone()
+            .checkpoint(CheckpointRequest::authenticated(
+                Some(sequence_number),
+                true,
+            ))
+            .await
+            .map(tonic::Response::into_inner)?;
+
+        match response {
+            CheckpointResponse::AuthenticatedCheckpoint {
+                checkpoint: Some(AuthenticatedCheckpoint::Certified(checkpoint)),
+                contents: Some(contents),
+            } if checkpoint.summary.sequence_number == sequence_number => {
+                checkpoint
+                    .verify(committee, Some(&contents))
+                    .map_err(|e| self.error(e.to_string()))?;
+                Ok((checkpoint, contents))
+            }
+            _ => Err(self.error(format!(
+                "no certified checkpoint {} with contents",
+                sequence_number
+            ))),
+        }
+    }
+
+    /// Fetch the certificate and effects for `digests`, which must come from the contents of a
+    /// verified checkpoint.
+    pub async fn get_transaction(
+        &self,
+        digests: &ExecutionDigests,
+        committee: &Committee,
+    ) -> SuiResult<(CertifiedTransaction, SignedTransactionEffects)> {
+        let response = self
+            .client
+            .clone()
+            .transaction(StateSyncTransactionRequest { digests: *digests })
+            .await
+            .map(tonic::Response::into_inner)?;
+
+        let (cert, effects) = match response {
+            StateSyncTransactionResponse {
+                certified_transaction: Some(cert),
+                signed_effects: Some(effects),
+            } => (cert, effects),
+            _ => {
+                return Err(self.error(format!(
+                    "no certificate and effects for {:?}",
+                    digests.transaction
+                )))
+            }
+        };
+
+        if *cert.digest() != digests.transaction {
+            return Err(self.error(format!(
+                "certificate digest {:?} does not match requested {:?}",
+                cert.digest(),
+                digests.transaction
+            )));
+        }
+        cert.verify(committee)
+            .map_err(|e| self.error(e.to_string()))?;
+
+        if *effects.digest() != digests.effects
+            || effects.effects.transaction_digest != digests.transaction
+        {
+            return Err(self.error(format!(
+                "effects for {:?} do not match the checkpointed effects digest",
+                digests.transaction
+            )));
+        }
+
+        Ok((cert, effects))
+    }
+
+    /// Fetch the objects written by `effects`, which must come from `get_transaction`, so that
+    /// each object is checked against the reference listed for it in checkpointed effects.
+    pub async fn get_written_objects(
+        &self,
+        effects: &SignedTransactionEffects,
+    ) -> SuiResult<Vec<Object>> {
+        let mut objects = Vec::new();
+        for (object_ref, _) in effects.effects.all_mutated() {
+            objects.push(self.get_object(object_ref).await?);
+        }
+        Ok(objects)
+    }
+
+    /// Fetch the object at `object_ref`, which must come from verified effects.
+    pub async fn get_object(&self, object_ref: &ObjectRef) -> SuiResult<Object> {
+        let response = self
+            .client
+            .clone()
+            .object(StateSyncObjectRequest {
+                object_ref: *object_ref,
+            })
+            .await
+            .map(tonic::Response::into_inner)?;
+
+        let object = response
+            .object
+            .ok_or_else(|| self.error(format!("object {:?} not found", object_ref)))?;
+
+        if object.compute_object_reference() != *object_ref {
+            return Err(self.error(format!(
+                "object does not match requested reference {:?}",
+                object_ref
+            )));
+        }
+
+        Ok(object)
+    }
+}
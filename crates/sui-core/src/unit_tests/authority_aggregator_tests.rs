@@ -108,6 +108,8 @@ pub async fn init_local_authorities_with_genesis(
         post_quorum_timeout: Duration::from_secs(5),
         serial_authority_request_timeout: Duration::from_secs(1),
         serial_authority_request_interval: Duration::from_secs(1),
+        min_hedge_delay: Duration::from_millis(50),
+        max_hedge_delay: Duration::from_millis(500),
     };
     let epoch_store = Arc::new(EpochStore::new_for_testing(&committee));
     (
@@ -530,6 +532,82 @@ async fn test_map_reducer() {
     assert!(!res.as_ref().unwrap().contains(&bad_auth));
 }
 
+#[tokio::test]
+async fn test_map_reducer_hedging() {
+    let (authorities, _, _) = init_local_authorities(4, vec![]).await;
+    let mut names = authorities.committee.shuffle_by_stake(None, None);
+    let slow_auth = names.pop().unwrap();
+    for name in &names {
+        authorities.authority_clients[name].report_abandoned(Duration::from_millis(1));
+    }
+    authorities.authority_clients[&slow_auth].report_timeout(Duration::from_secs(10));
+    assert_eq!(
+        authorities.authorities_by_latency(None, None).last(),
+        Some(&slow_auth)
+    );
+
+    let contacted = Arc::new(Mutex::new(HashSet::new()));
+    fn reduce_until_quorum<'a>(
+        mut accumulated_state: usize,
+        _authority_name: AuthorityName,
+        _authority_weight: StakeUnit,
+        _result: Result<(), SuiError>,
+    ) -> AsyncResult<'a, ReduceOutput<usize>, SuiError> {
+        Box::pin(async move {
+            accumulated_state += 1;
+            if accumulated_state == 3 {
+                Ok(ReduceOutput::End(accumulated_state))
+            } else {
+                Ok(ReduceOutput::Continue(accumulated_state))
+            }
+        })
+    }
+
+    // Test: the slow authority is not contacted when the fast ones carry a quorum.
+    let res = authorities
+        .quorum_map_then_reduce_with_timeout(
+            0usize,
+            |name, _client| {
+                let contacted = contacted.clone();
+                Box::pin(async move {
+                    contacted.lock().unwrap().insert(name);
+                    Ok(())
+                })
+            },
+            reduce_until_quorum,
+            Duration::from_secs(60),
+        )
+        .await;
+    assert_eq!(Ok(3), res);
+    assert!(!contacted.lock().unwrap().contains(&slow_auth));
+
+    // Test: when a fast authority hangs, the slow one is contacted after the hedge delay.
+    let hanging_auth = names[0];
+    contacted.lock().unwrap().clear();
+    let start = Instant::now();
+    let res = authorities
+        .quorum_map_then_reduce_with_timeout(
+            0usize,
+            |name, _client| {
+                let contacted = contacted.clone();
+                Box::pin(async move {
+                    contacted.lock().unwrap().insert(name);
+                    if name == hanging_auth {
+                        tokio::time::sleep(Duration::from_secs(10 * 60)).await;
+                    }
+                    Ok(())
+                })
+            },
+            reduce_until_quorum,
+            Duration::from_secs(60),
+        )
+        .await;
+    assert_eq!(Ok(3), res);
+    assert!(contacted.lock().unwrap().contains(&slow_auth));
+    assert!(start.elapsed() < authorities.timeouts.max_hedge_delay * 2);
+    assert_eq!(authorities.metrics.total_hedged_requests.get(), 1);
+}
+
 #[tokio::test]
 async fn test_get_all_owned_objects() {
     let (addr1, key1): (_, AccountKeyPair) = get_key_pair();
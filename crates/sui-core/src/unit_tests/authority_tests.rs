@@ -2078,6 +2078,28 @@ async fn send_consensus(authority: &AuthorityState, cert: &CertifiedTransaction)
         .unwrap();
 }
 
+#[cfg(test)]
+pub async fn send_consensus_clock_timestamp(authority: &AuthorityState, timestamp_ms: u64) {
+    let timestamp = SignedClockTimestamp::new(
+        authority.epoch(),
+        timestamp_ms,
+        authority.name,
+        &*authority.secret,
+    );
+    authority
+        .handle_consensus_transaction(
+            // TODO [2533]: use this once integrating Narwhal reconfiguration
+            &narwhal_consensus::ConsensusOutput {
+                certificate: narwhal_types::Certificate::default(),
+                consensus_index: narwhal_types::SequenceNumber::default(),
+            },
+            /* last_consensus_index */ ExecutionIndices::default(),
+            ConsensusTransaction::new_clock_timestamp_message(timestamp),
+        )
+        .await
+        .unwrap();
+}
+
 pub async fn call_move(
     authority: &AuthorityState,
     gas_object_id: &ObjectID,
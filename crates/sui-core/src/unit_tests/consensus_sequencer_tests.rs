@@ -0,0 +1,55 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+use super::*;
+use crate::authority::authority_tests::init_state_with_objects;
+use crate::consensus_adapter::consensus_tests::{
+    test_certificates, test_gas_objects, test_shared_object,
+};
+use sui_types::messages::ConsensusTransaction;
+
+#[tokio::test]
+async fn sequence_shared_object_certificates() {
+    // Initialize an authority with gas objects and a shared object; then make test
+    // certificates all touching the shared object.
+    let mut objects = test_gas_objects();
+    objects.push(test_shared_object());
+    let state = Arc::new(init_state_with_objects(objects).await);
+    let certificates = test_certificates(&state).await;
+
+    let (tx_output, mut rx_output) = channel(10);
+    let (sequencer, _handle) = SingleNodeSequencer::spawn(state.clone(), tx_output);
+
+    for certificate in certificates {
+        assert!(!state
+            .transaction_shared_locks_exist(&certificate)
+            .await
+            .unwrap());
+
+        let message =
+            ConsensusTransaction::new_certificate_message(&state.name, certificate.clone());
+        let serialized = bincode::serialize(&message).unwrap();
+        sequencer
+            .submit_to_consensus(serialized.clone())
+            .await
+            .unwrap();
+
+        // The certificate is sequenced right away, and its shared objects are locked.
+        let (result, sequenced) = rx_output.recv().await.unwrap();
+        assert!(result.is_ok());
+        assert_eq!(sequenced, serialized);
+        assert!(state
+            .transaction_shared_locks_exist(&certificate)
+            .await
+            .unwrap());
+    }
+
+    // Sequencing resumes after the last sequenced transaction.
+    assert_eq!(
+        state
+            .load_execution_indices()
+            .await
+            .unwrap()
+            .next_certificate_index,
+        test_gas_objects().len() as u64
+    );
+}
@@ -156,14 +156,16 @@ async fn submit_transaction_to_consensus() {
     let committee = state.clone_committee();
     let name = state.name;
     let state_guard = Arc::new(state);
-    let metrics = ConsensusAdapterMetrics::new_test();
+    let registry = Registry::new();
+    let metrics = ConsensusAdapterMetrics::new(&registry);
 
     // Make a new consensus submitter instance.
     let submitter = ConsensusAdapter::new(
-        consensus_address.clone(),
+        narwhal_consensus_client(&consensus_address),
         committee,
         tx_consensus_listener,
         /* max_delay */ Duration::from_millis(1_000),
+        /* max_pending_transactions_per_shared_object */ None,
         metrics,
     );
 
@@ -214,6 +216,13 @@ async fn submit_transaction_to_consensus() {
         }
     }
 
+    // The time series of the shared object are removed once it has no certificate being
+    // sequenced.
+    assert!(!registry.gather().iter().any(|family| {
+        family.get_name() == "sequencing_shared_object_success"
+            || family.get_name() == "sequencing_in_flight_shared_object"
+    }));
+
     // Ensure the consensus node got the transaction.
     let bytes = handle.recv().await.unwrap().transaction;
     let message: ConsensusTransaction = bincode::deserialize(&bytes).unwrap();
@@ -225,6 +234,70 @@ async fn submit_transaction_to_consensus() {
     }
 }
 
+#[tokio::test]
+async fn reject_transactions_on_congested_shared_object() {
+    let port = sui_config::utils::get_available_port();
+    let consensus_address: Multiaddr = format!("/dns/localhost/tcp/{port}/http").parse().unwrap();
+    let (tx_consensus_listener, _rx_consensus_listener) = channel(100);
+
+    // Initialize an authority with gas objects and a shared object; then make test
+    // certificates all touching the shared object.
+    let mut objects = test_gas_objects();
+    objects.push(test_shared_object());
+    let state = init_state_with_objects(objects).await;
+    let mut certificates = test_certificates(&state).await;
+    let first = certificates.pop().unwrap();
+    let second = certificates.pop().unwrap();
+
+    // Make a consensus submitter letting a single certificate per shared object be sequenced.
+    let submitter = Arc::new(ConsensusAdapter::new(
+        narwhal_consensus_client(&consensus_address),
+        state.clone_committee(),
+        tx_consensus_listener,
+        /* max_delay */ Duration::from_millis(1_000),
+        /* max_pending_transactions_per_shared_object */ Some(1),
+        ConsensusAdapterMetrics::new_test(),
+    ));
+    let name = state.name;
+    let mut handle = ConsensusMockServer::spawn(consensus_address);
+
+    // Nobody notifies the submitter that the first certificate is sequenced, so it remains
+    // pending until it times out.
+    let first_submission = {
+        let submitter = submitter.clone();
+        tokio::spawn(async move {
+            loop {
+                match submitter.submit(&name, &first).await {
+                    Err(SuiError::ConsensusConnectionBroken(..)) => (),
+                    result => break result,
+                }
+            }
+        })
+    };
+    handle.recv().await.unwrap();
+
+    // The second certificate touches the same shared object, and is rejected.
+    assert!(matches!(
+        submitter.submit(&name, &second).await,
+        Err(SuiError::SharedObjectCongested {
+            max_pending_transactions: 1,
+            ..
+        })
+    ));
+
+    // Once the first certificate timed out, the shared object accepts certificates again.
+    assert!(matches!(
+        first_submission.await.unwrap(),
+        Err(SuiError::FailedToHearBackFromConsensus(..))
+    ));
+    let second_submission = tokio::spawn(async move { submitter.submit(&name, &second).await });
+    handle.recv().await.unwrap();
+    assert!(matches!(
+        second_submission.await.unwrap(),
+        Err(SuiError::FailedToHearBackFromConsensus(..))
+    ));
+}
+
 pub struct ConsensusMockServer {
     sender: Sender<TransactionProto>,
 }
@@ -0,0 +1,14 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+module clock_reader::clock_reader {
+    use sui::clock::{Self, Clock};
+
+    const EUnexpectedTimestamp: u64 = 0;
+
+    public entry fun check_timestamp(clock: &Clock, timestamp_ms: u64) {
+        assert!(clock::timestamp_ms(clock) == timestamp_ms, EUnexpectedTimestamp);
+    }
+
+    public entry fun take_mut(_clock: &mut Clock) {}
+}
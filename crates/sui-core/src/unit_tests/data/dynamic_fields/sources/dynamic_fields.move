@@ -0,0 +1,42 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+module dynamic_fields::dynamic_fields {
+    use sui::dynamic_field;
+    use sui::object::{Self, UID};
+    use sui::transfer;
+    use sui::tx_context::{Self, TxContext};
+
+    struct Counters has key {
+        id: UID,
+    }
+
+    public entry fun create(ctx: &mut TxContext) {
+        transfer::transfer(
+            Counters { id: object::new(ctx) },
+            tx_context::sender(ctx),
+        );
+    }
+
+    public entry fun add(counters: &mut Counters, name: u64, value: u64) {
+        dynamic_field::add(&mut counters.id, name, value);
+    }
+
+    public entry fun increment(counters: &mut Counters, name: u64) {
+        let value = dynamic_field::borrow_mut<u64, u64>(&mut counters.id, name);
+        *value = *value + 1;
+    }
+
+    public entry fun remove(counters: &mut Counters, name: u64) {
+        dynamic_field::remove<u64, u64>(&mut counters.id, name);
+    }
+
+    public entry fun assert_value(counters: &Counters, name: u64, value: u64) {
+        assert!(*dynamic_field::borrow<u64, u64>(&counters.id, name) == value, 0);
+    }
+
+    public entry fun delete(counters: Counters) {
+        let Counters { id } = counters;
+        object::delete(id);
+    }
+}
@@ -0,0 +1,30 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+module upgrade::counter {
+    use sui::object::{Self, UID};
+    use sui::transfer;
+    use sui::tx_context::{Self, TxContext};
+
+    // The layout of `Counter` cannot change.
+    struct Counter has key {
+        id: UID,
+        value: u64,
+        step: u64,
+    }
+
+    public entry fun create(ctx: &mut TxContext) {
+        transfer::transfer(
+            Counter { id: object::new(ctx), value: 0, step: 1 },
+            tx_context::sender(ctx),
+        );
+    }
+
+    public entry fun increment(counter: &mut Counter) {
+        counter.value = counter.value + counter.step;
+    }
+
+    public entry fun assert_value(counter: &Counter, value: u64) {
+        assert!(counter.value == value, 0);
+    }
+}
@@ -0,0 +1,34 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+module upgrade::counter {
+    use sui::object::{Self, UID};
+    use sui::transfer;
+    use sui::tx_context::{Self, TxContext};
+
+    struct Counter has key {
+        id: UID,
+        value: u64,
+    }
+
+    public entry fun create(ctx: &mut TxContext) {
+        transfer::transfer(
+            Counter { id: object::new(ctx), value: 0 },
+            tx_context::sender(ctx),
+        );
+    }
+
+    // Changed in this version.
+    public entry fun increment(counter: &mut Counter) {
+        counter.value = counter.value + 10;
+    }
+
+    public entry fun assert_value(counter: &Counter, value: u64) {
+        assert!(counter.value == value, 0);
+    }
+
+    // Added in this version.
+    public entry fun reset(counter: &mut Counter) {
+        counter.value = 0;
+    }
+}
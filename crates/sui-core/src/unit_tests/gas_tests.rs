@@ -265,8 +265,20 @@ async fn test_publish_gas() -> anyhow::Result<()> {
     let gas_cost = effects.gas_used;
     assert!(gas_cost.storage_cost > 0);
 
-    let ((package_id, _, _), _) = effects.created[0];
+    // Publishing creates the package and its UpgradeCap.
+    assert_eq!(effects.created.len(), 2);
+    let ((package_id, _, _), _) = *effects
+        .created
+        .iter()
+        .find(|(_, owner)| owner == &Owner::Immutable)
+        .unwrap();
+    let ((upgrade_cap_id, _, _), _) = *effects
+        .created
+        .iter()
+        .find(|(_, owner)| owner == &Owner::AddressOwner(sender))
+        .unwrap();
     let package = authority_state.get_object(&package_id).await?.unwrap();
+    let upgrade_cap = authority_state.get_object(&upgrade_cap_id).await?.unwrap();
     let gas_object = authority_state.get_object(&gas_object_id).await?.unwrap();
     let expected_gas_balance = GAS_VALUE_FOR_TESTING - gas_cost.gas_used();
     assert_eq!(
@@ -303,6 +315,7 @@ async fn test_publish_gas() -> anyhow::Result<()> {
     gas_status.charge_storage_read(gas_object.object_size_for_gas_metering())?;
     gas_status.charge_publish_package(publish_bytes.iter().map(|v| v.len()).sum())?;
     gas_status.charge_storage_mutation(0, package.object_size_for_gas_metering(), 0.into())?;
+    gas_status.charge_storage_mutation(0, upgrade_cap.object_size_for_gas_metering(), 0.into())?;
     // Remember the gas used so far. We will use this to create another failure case latter.
     let gas_used_after_package_creation = gas_status.summary(true).gas_used();
     gas_status.charge_storage_mutation(
@@ -497,6 +510,94 @@ async fn test_move_call_gas() -> SuiResult {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_dry_exec_transaction_gas_profile() -> SuiResult {
+    let (sender, sender_key): (_, AccountKeyPair) = get_key_pair();
+    let gas_object_id = ObjectID::random();
+    let (authority_state, package_object_ref) =
+        init_state_with_ids_and_object_basics(vec![(sender, gas_object_id)]).await;
+    let gas_object = authority_state.get_object(&gas_object_id).await?.unwrap();
+
+    let data = TransactionData::new_move_call(
+        sender,
+        package_object_ref,
+        ident_str!("object_basics").to_owned(),
+        ident_str!("create").to_owned(),
+        Vec::new(),
+        gas_object.compute_object_reference(),
+        vec![
+            CallArg::Pure(16u64.to_le_bytes().to_vec()),
+            CallArg::Pure(bcs::to_bytes(&AccountAddress::from(sender)).unwrap()),
+        ],
+        GAS_VALUE_FOR_TESTING,
+    );
+    let signature = Signature::new(&data, &sender_key);
+    let transaction = Transaction::new(data, signature);
+
+    let (effects, profile) = authority_state
+        .dry_exec_transaction(&transaction, true)
+        .await?;
+    assert!(effects.status.is_ok());
+    assert_eq!(effects.created.len(), 1);
+    let profile = profile.unwrap();
+    assert!(profile.total() > 0);
+    let function = format!(
+        "{}::object_basics::create",
+        package_object_ref.0.to_hex_literal()
+    );
+    assert!(profile.function_costs()[&function] > 0);
+    assert!(profile.instruction_costs()["[storage_read]"] > 0);
+    for line in profile.to_folded_stacks().lines() {
+        let (stack, gas) = line.rsplit_once(' ').unwrap();
+        assert!(stack.starts_with(&function) || stack.starts_with('['));
+        assert!(gas.parse::<u64>().unwrap() > 0);
+    }
+
+    // Nothing was committed, so the transaction can still be executed.
+    let (_, profile) = authority_state
+        .dry_exec_transaction(&transaction, false)
+        .await?;
+    assert!(profile.is_none());
+    assert_eq!(
+        authority_state
+            .get_object(&gas_object_id)
+            .await?
+            .unwrap()
+            .version(),
+        gas_object.version()
+    );
+    let response = send_and_confirm_transaction(&authority_state, transaction).await?;
+    let committed = response.signed_effects.unwrap().effects;
+    assert_eq!(committed.gas_used, effects.gas_used);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_gas_profile_out_of_gas() -> SuiResult {
+    let mut gas_status = SuiGasStatus::new_with_budget(1_000, 1.into(), 1.into());
+    gas_status.enable_profiling();
+    gas_status.charge_storage_read(1)?;
+    let per_byte = gas_status.gas_profile().unwrap().total();
+    assert!(per_byte > 0);
+
+    // Only the gas left in the budget is recorded when a charge runs out of gas.
+    let size = 1_000_000;
+    assert!(gas_status.charge_storage_read(size).is_err());
+    assert_eq!(
+        u64::from(gas_status.get_move_gas_status().remaining_gas()),
+        0
+    );
+    let profile = gas_status.gas_profile().unwrap();
+    let recorded = profile.instruction_costs()["[storage_read]"];
+    assert_eq!(recorded, profile.total());
+    assert!(recorded < per_byte * (size as u64 + 1));
+
+    // Nothing is recorded for charges once the budget is spent.
+    assert!(gas_status.charge_storage_read(1).is_err());
+    assert_eq!(gas_status.gas_profile().unwrap().total(), recorded);
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_storage_gas_unit_price() -> SuiResult {
     let mut gas_status1 = SuiGasStatus::new_with_budget(*MAX_GAS_BUDGET, 1.into(), 1.into());
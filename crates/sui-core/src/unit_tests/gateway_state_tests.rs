@@ -37,6 +37,7 @@ async fn create_gateway_state_with_object_basics_ref(
     let gateway = GatewayState::new_with_authorities(
         gateway_store,
         authorities,
+        Arc::new(NetworkAuthorityClientMetrics::new_for_tests()),
         GatewayMetrics::new_for_tests(),
     )
     .unwrap();
@@ -636,7 +637,8 @@ async fn test_multiple_gateways() {
     // gateway2 shares the same set of authorities as gateway1.
     let gateway2 = GatewayState::new_with_authorities(
         Arc::new(GatewayStore::open(&path, None)),
-        gateway1.authorities.clone(),
+        gateway1.get_authorities().as_ref().clone(),
+        Arc::new(NetworkAuthorityClientMetrics::new_for_tests()),
         GatewayMetrics::new_for_tests(),
     )
     .unwrap();
@@ -5,16 +5,22 @@
 use super::*;
 use crate::authority::authority_tests::{
     call_move, call_move_with_shared, init_state_with_ids, send_and_confirm_transaction,
-    TestCallArg,
+    send_consensus_clock_timestamp, TestCallArg,
 };
 
 use move_core_types::language_storage::TypeTag;
 use move_package::BuildConfig;
 use sui_types::{
+    clock::Clock,
     crypto::{get_key_pair, AccountKeyPair, Signature},
     event::{Event, EventType, TransferType},
-    messages::ExecutionStatus,
-    object::OBJECT_START_VERSION,
+    messages::{
+        EntryArgumentError, EntryArgumentErrorKind, ExecutionFailureStatus, ExecutionStatus,
+        PackageUpgradeError,
+    },
+    move_package::UpgradeCap,
+    object::{MoveObject, OBJECT_START_VERSION},
+    SUI_CLOCK_OBJECT_ID,
 };
 
 use std::path::PathBuf;
@@ -447,6 +453,496 @@ async fn test_object_owning_another_object() {
     assert_eq!(effects.deleted.len(), 2);
 }
 
+#[tokio::test]
+async fn test_dynamic_fields() {
+    let (sender, sender_key): (_, AccountKeyPair) = get_key_pair();
+    let gas = ObjectID::random();
+    let authority = init_state_with_ids(vec![(sender, gas)]).await;
+
+    let package =
+        build_and_publish_test_package(&authority, &sender, &sender_key, &gas, "dynamic_fields")
+            .await;
+
+    // Create an object to hold the dynamic fields.
+    let effects = call_move(
+        &authority,
+        &gas,
+        &sender,
+        &sender_key,
+        &package,
+        "dynamic_fields",
+        "create",
+        vec![],
+        vec![],
+    )
+    .await
+    .unwrap();
+    assert!(effects.status.is_ok());
+    let parent = effects.created[0].0;
+
+    // Adding a dynamic field creates a child object, without changing the parent's child count.
+    let effects = call_move(
+        &authority,
+        &gas,
+        &sender,
+        &sender_key,
+        &package,
+        "dynamic_fields",
+        "add",
+        vec![],
+        vec![
+            TestCallArg::Object(parent.0),
+            TestCallArg::U64(0),
+            TestCallArg::U64(10),
+        ],
+    )
+    .await
+    .unwrap();
+    assert!(effects.status.is_ok());
+    assert_eq!(effects.created.len(), 1);
+    let (field, field_owner) = effects.created[0];
+    assert_eq!(field_owner, Owner::ObjectOwner(parent.0.into()));
+    let add_digest = effects.transaction_digest;
+    let parent_object = authority.get_object(&parent.0).await.unwrap().unwrap();
+    assert_eq!(
+        parent_object.data.try_as_move().unwrap().child_count(),
+        None
+    );
+
+    // The field is loaded on demand, only the parent is an input.
+    let effects = call_move(
+        &authority,
+        &gas,
+        &sender,
+        &sender_key,
+        &package,
+        "dynamic_fields",
+        "increment",
+        vec![],
+        vec![TestCallArg::Object(parent.0), TestCallArg::U64(0)],
+    )
+    .await
+    .unwrap();
+    assert!(effects.status.is_ok());
+    assert!(effects.created.is_empty());
+    let (mutated_field, _) = effects
+        .mutated
+        .iter()
+        .find(|((id, _, _), _)| id == &field.0)
+        .unwrap();
+    assert_eq!(mutated_field.1, field.1.increment());
+    // The transaction that wrote the field is a dependency of the one that loaded it.
+    assert!(effects.dependencies.contains(&add_digest));
+    // The lock of the previous version of the field is replaced by a lock of the new version.
+    assert!(matches!(
+        authority.db().get_transaction_envelope(&field).await,
+        Err(SuiError::TransactionLockDoesNotExist)
+    ));
+    assert!(authority
+        .db()
+        .get_transaction_envelope(mutated_field)
+        .await
+        .is_ok());
+
+    let effects = call_move(
+        &authority,
+        &gas,
+        &sender,
+        &sender_key,
+        &package,
+        "dynamic_fields",
+        "assert_value",
+        vec![],
+        vec![
+            TestCallArg::Object(parent.0),
+            TestCallArg::U64(0),
+            TestCallArg::U64(11),
+        ],
+    )
+    .await
+    .unwrap();
+    assert!(effects.status.is_ok());
+
+    // Removing the field deletes the child object.
+    let effects = call_move(
+        &authority,
+        &gas,
+        &sender,
+        &sender_key,
+        &package,
+        "dynamic_fields",
+        "remove",
+        vec![],
+        vec![TestCallArg::Object(parent.0), TestCallArg::U64(0)],
+    )
+    .await
+    .unwrap();
+    assert!(effects.status.is_ok());
+    assert!(effects.deleted.iter().any(|(id, _, _)| id == &field.0));
+    assert!(authority.get_object(&field.0).await.unwrap().is_none());
+    // The deleted field is no longer indexed as owned by its parent.
+    assert!(authority
+        .get_owner_objects(Owner::ObjectOwner(parent.0.into()))
+        .unwrap()
+        .iter()
+        .all(|info| info.object_id != field.0));
+
+    let effects = call_move(
+        &authority,
+        &gas,
+        &sender,
+        &sender_key,
+        &package,
+        "dynamic_fields",
+        "assert_value",
+        vec![],
+        vec![
+            TestCallArg::Object(parent.0),
+            TestCallArg::U64(0),
+            TestCallArg::U64(11),
+        ],
+    )
+    .await
+    .unwrap();
+    assert!(matches!(effects.status, ExecutionStatus::Failure { .. }));
+
+    // An object with dynamic fields can be deleted.
+    let effects = call_move(
+        &authority,
+        &gas,
+        &sender,
+        &sender_key,
+        &package,
+        "dynamic_fields",
+        "add",
+        vec![],
+        vec![
+            TestCallArg::Object(parent.0),
+            TestCallArg::U64(1),
+            TestCallArg::U64(1),
+        ],
+    )
+    .await
+    .unwrap();
+    assert!(effects.status.is_ok());
+    let effects = call_move(
+        &authority,
+        &gas,
+        &sender,
+        &sender_key,
+        &package,
+        "dynamic_fields",
+        "delete",
+        vec![],
+        vec![TestCallArg::Object(parent.0)],
+    )
+    .await
+    .unwrap();
+    assert!(effects.status.is_ok());
+    assert!(effects.deleted.iter().any(|(id, _, _)| id == &parent.0));
+}
+
+#[tokio::test]
+async fn test_package_upgrade() {
+    let (sender, sender_key): (_, AccountKeyPair) = get_key_pair();
+    let gas = ObjectID::random();
+    let authority = init_state_with_ids(vec![(sender, gas)]).await;
+
+    let effects = build_and_try_publish_test_package(
+        &authority,
+        &sender,
+        &sender_key,
+        &gas,
+        "upgrade_v1",
+        MAX_GAS,
+    )
+    .await
+    .signed_effects
+    .unwrap()
+    .effects;
+    assert!(effects.status.is_ok());
+    let (package_v1, _) = *effects
+        .created
+        .iter()
+        .find(|(_, owner)| owner == &Owner::Immutable)
+        .unwrap();
+    let ((upgrade_cap, _, _), cap_owner) = *effects
+        .created
+        .iter()
+        .find(|(_, owner)| owner != &Owner::Immutable)
+        .unwrap();
+    assert_eq!(cap_owner, Owner::AddressOwner(sender));
+
+    let effects = call_move(
+        &authority,
+        &gas,
+        &sender,
+        &sender_key,
+        &package_v1,
+        "counter",
+        "create",
+        vec![],
+        vec![],
+    )
+    .await
+    .unwrap();
+    assert!(effects.status.is_ok());
+    let ((counter, _, _), _) = effects.created[0];
+
+    // The new version is a new package, and the UpgradeCap now refers to it.
+    let effects = upgrade_test_package(
+        &authority,
+        &sender,
+        &sender_key,
+        &gas,
+        &package_v1.0,
+        &upgrade_cap,
+        "upgrade_v2",
+    )
+    .await;
+    assert!(effects.status.is_ok(), "{:?}", effects.status);
+    assert_eq!(effects.created.len(), 1);
+    let (package_v2, package_owner) = effects.created[0];
+    assert_eq!(package_owner, Owner::Immutable);
+    assert!(effects
+        .mutated
+        .iter()
+        .any(|((id, _, _), _)| id == &upgrade_cap));
+    let cap = authority.get_object(&upgrade_cap).await.unwrap().unwrap();
+    let cap: UpgradeCap = bcs::from_bytes(cap.data.try_as_move().unwrap().contents()).unwrap();
+    assert_eq!(cap.package.bytes, package_v2.0);
+    assert_eq!(cap.version, 2);
+
+    // Objects created by the previous version can be used with the new one, and vice versa.
+    for (package, function) in [
+        (package_v2, "increment"),
+        (package_v1, "increment"),
+        (package_v2, "reset"),
+        (package_v1, "increment"),
+        (package_v2, "increment"),
+    ] {
+        let effects = call_move(
+            &authority,
+            &gas,
+            &sender,
+            &sender_key,
+            &package,
+            "counter",
+            function,
+            vec![],
+            vec![TestCallArg::Object(counter)],
+        )
+        .await
+        .unwrap();
+        assert!(effects.status.is_ok(), "{:?}", effects.status);
+    }
+    let effects = call_move(
+        &authority,
+        &gas,
+        &sender,
+        &sender_key,
+        &package_v2,
+        "counter",
+        "assert_value",
+        vec![],
+        vec![TestCallArg::Object(counter), TestCallArg::U64(11)],
+    )
+    .await
+    .unwrap();
+    assert!(effects.status.is_ok(), "{:?}", effects.status);
+
+    // Struct layouts cannot change.
+    let effects = upgrade_test_package(
+        &authority,
+        &sender,
+        &sender_key,
+        &gas,
+        &package_v2.0,
+        &upgrade_cap,
+        "upgrade_incompatible",
+    )
+    .await;
+    assert_eq!(
+        effects.status.unwrap_err(),
+        PackageUpgradeError::IncompatibleUpgrade.into()
+    );
+
+    // Only the latest version can be upgraded.
+    let effects = upgrade_test_package(
+        &authority,
+        &sender,
+        &sender_key,
+        &gas,
+        &package_v1.0,
+        &upgrade_cap,
+        "upgrade_v2",
+    )
+    .await;
+    assert_eq!(
+        effects.status.unwrap_err(),
+        PackageUpgradeError::PackageIdDoesNotMatch {
+            package_id: package_v1.0,
+            cap_package_id: package_v2.0,
+        }
+        .into()
+    );
+
+    // The UpgradeCap must be owned by the sender, an immutable one cannot be used.
+    let immutable_cap = Object::new_move(
+        MoveObject::new_upgrade_cap(
+            OBJECT_START_VERSION,
+            UpgradeCap::new(ObjectID::random(), package_v2.0).to_bcs_bytes(),
+        ),
+        Owner::Immutable,
+        TransactionDigest::genesis(),
+    );
+    let immutable_cap_id = immutable_cap.id();
+    authority.insert_genesis_object(immutable_cap).await;
+    let effects = upgrade_test_package(
+        &authority,
+        &sender,
+        &sender_key,
+        &gas,
+        &package_v2.0,
+        &immutable_cap_id,
+        "upgrade_v2",
+    )
+    .await;
+    assert_eq!(
+        effects.status.unwrap_err(),
+        PackageUpgradeError::UpgradeCapNotOwnedBySender {
+            object_id: immutable_cap_id,
+        }
+        .into()
+    );
+
+    // Once restricted to additive upgrades, existing functions cannot change.
+    let effects = call_move(
+        &authority,
+        &gas,
+        &sender,
+        &sender_key,
+        &authority.get_framework_object_ref().await.unwrap(),
+        "package",
+        "only_additive_upgrades",
+        vec![],
+        vec![TestCallArg::Object(upgrade_cap)],
+    )
+    .await
+    .unwrap();
+    assert!(effects.status.is_ok(), "{:?}", effects.status);
+    let effects = upgrade_test_package(
+        &authority,
+        &sender,
+        &sender_key,
+        &gas,
+        &package_v2.0,
+        &upgrade_cap,
+        "upgrade_v1",
+    )
+    .await;
+    assert_eq!(
+        effects.status.unwrap_err(),
+        PackageUpgradeError::IncompatibleUpgrade.into()
+    );
+    let effects = upgrade_test_package(
+        &authority,
+        &sender,
+        &sender_key,
+        &gas,
+        &package_v2.0,
+        &upgrade_cap,
+        "upgrade_v2",
+    )
+    .await;
+    assert!(effects.status.is_ok(), "{:?}", effects.status);
+}
+
+#[tokio::test]
+async fn test_clock() {
+    let (sender, sender_key): (_, AccountKeyPair) = get_key_pair();
+    let gas = ObjectID::random();
+    let authority = init_state_with_ids(vec![(sender, gas)]).await;
+
+    let package =
+        build_and_publish_test_package(&authority, &sender, &sender_key, &gas, "clock").await;
+
+    let check_timestamp = |timestamp_ms: u64| {
+        call_move_with_shared(
+            &authority,
+            &gas,
+            &sender,
+            &sender_key,
+            &package,
+            "clock_reader",
+            "check_timestamp",
+            vec![],
+            vec![
+                TestCallArg::Object(SUI_CLOCK_OBJECT_ID),
+                TestCallArg::U64(timestamp_ms),
+            ],
+            true,
+        )
+    };
+
+    // The Clock starts at the genesis timestamp.
+    let effects = check_timestamp(0).await.unwrap();
+    assert!(effects.status.is_ok(), "{:?}", effects.status);
+    assert_eq!(effects.clock_timestamp_ms, Some(0));
+
+    // Transactions observe the time sequenced by consensus, without mutating the Clock.
+    send_consensus_clock_timestamp(&authority, 1000).await;
+    let effects = check_timestamp(1000).await.unwrap();
+    assert!(effects.status.is_ok(), "{:?}", effects.status);
+    assert_eq!(effects.clock_timestamp_ms, Some(1000));
+    assert!(effects
+        .mutated
+        .iter()
+        .all(|((id, _, _), _)| *id != SUI_CLOCK_OBJECT_ID));
+    let clock = authority
+        .get_object(&SUI_CLOCK_OBJECT_ID)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(clock.version(), OBJECT_START_VERSION);
+    // The stored Clock follows the time observed by transactions.
+    assert_eq!(Clock::from_object(&clock).timestamp_ms, 1000);
+
+    // The consensus timestamp never goes back.
+    send_consensus_clock_timestamp(&authority, 500).await;
+    let effects = check_timestamp(1000).await.unwrap();
+    assert!(effects.status.is_ok(), "{:?}", effects.status);
+    let clock = authority
+        .get_object(&SUI_CLOCK_OBJECT_ID)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(Clock::from_object(&clock).timestamp_ms, 1000);
+
+    // The Clock cannot be passed by mutable reference.
+    let effects = call_move_with_shared(
+        &authority,
+        &gas,
+        &sender,
+        &sender_key,
+        &package,
+        "clock_reader",
+        "take_mut",
+        vec![],
+        vec![TestCallArg::Object(SUI_CLOCK_OBJECT_ID)],
+        true,
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        effects.status.unwrap_err(),
+        ExecutionFailureStatus::EntryArgumentError(EntryArgumentError {
+            argument_idx: 0,
+            kind: EntryArgumentErrorKind::InvalidObjectByMuteRef,
+        })
+    );
+}
+
 #[tokio::test]
 async fn test_entry_point_vector_empty() {
     let (sender, sender_key): (_, AccountKeyPair) = get_key_pair();
@@ -921,20 +1417,7 @@ pub async fn build_and_try_publish_test_package(
     test_dir: &str,
     gas_budget: u64,
 ) -> TransactionInfoResponse {
-    let build_config = BuildConfig::default();
-    let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
-    path.push("src/unit_tests/data/");
-    path.push(test_dir);
-    let modules = sui_framework::build_move_package(&path, build_config).unwrap();
-
-    let all_module_bytes = modules
-        .iter()
-        .map(|m| {
-            let mut module_bytes = Vec::new();
-            m.serialize(&mut module_bytes).unwrap();
-            module_bytes
-        })
-        .collect();
+    let all_module_bytes = build_test_modules(test_dir);
 
     let gas_object = authority.get_object(gas_object_id).await.unwrap();
     let gas_object_ref = gas_object.unwrap().compute_object_reference();
@@ -971,7 +1454,71 @@ async fn build_and_publish_test_package(
         "{:?}",
         effects.status
     );
-    effects.created[0].0
+    // The UpgradeCap for the package is created too.
+    effects
+        .created
+        .iter()
+        .find(|(_, owner)| owner == &Owner::Immutable)
+        .unwrap()
+        .0
+}
+
+fn build_test_modules(test_dir: &str) -> Vec<Vec<u8>> {
+    let build_config = BuildConfig::default();
+    let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    path.push("src/unit_tests/data/");
+    path.push(test_dir);
+    let modules = sui_framework::build_move_package(&path, build_config).unwrap();
+
+    modules
+        .iter()
+        .map(|m| {
+            let mut module_bytes = Vec::new();
+            m.serialize(&mut module_bytes).unwrap();
+            module_bytes
+        })
+        .collect()
+}
+
+async fn upgrade_test_package(
+    authority: &AuthorityState,
+    sender: &SuiAddress,
+    sender_key: &AccountKeyPair,
+    gas_object_id: &ObjectID,
+    package: &ObjectID,
+    upgrade_cap: &ObjectID,
+    test_dir: &str,
+) -> TransactionEffects {
+    let gas_object_ref = authority
+        .get_object(gas_object_id)
+        .await
+        .unwrap()
+        .unwrap()
+        .compute_object_reference();
+    let upgrade_cap_ref = authority
+        .get_object(upgrade_cap)
+        .await
+        .unwrap()
+        .unwrap()
+        .compute_object_reference();
+
+    let data = TransactionData::new_upgrade(
+        *sender,
+        gas_object_ref,
+        *package,
+        upgrade_cap_ref,
+        build_test_modules(test_dir),
+        vec![],
+        MAX_GAS,
+    );
+    let signature = Signature::new(&data, sender_key);
+    let transaction = Transaction::new(data, signature);
+    send_and_confirm_transaction(authority, transaction)
+        .await
+        .unwrap()
+        .signed_effects
+        .unwrap()
+        .effects
 }
 
 async fn check_latest_object_ref(authority: &AuthorityState, object_ref: &ObjectRef) {
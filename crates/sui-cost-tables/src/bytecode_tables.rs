@@ -14,6 +14,7 @@ use move_vm_types::gas::{GasMeter, SimpleInstruction};
 use move_vm_types::views::{TypeView, ValueView};
 use once_cell::sync::Lazy;
 
+use crate::gas_profiler::{GasProfile, NATIVE_FRAME};
 use crate::units_types::{CostTable, Gas, GasCost};
 use move_binary_format::{
     file_format::{
@@ -48,6 +49,8 @@ pub struct GasStatus<'a> {
     cost_table: &'a CostTable,
     gas_left: InternalGas,
     charge: bool,
+    /// Where the gas goes, when profiling is enabled.
+    profile: Option<GasProfile>,
 }
 
 impl<'a> GasStatus<'a> {
@@ -60,6 +63,7 @@ impl<'a> GasStatus<'a> {
             gas_left: gas_left.to_unit(),
             cost_table,
             charge: true,
+            profile: None,
         }
     }
 
@@ -72,6 +76,7 @@ impl<'a> GasStatus<'a> {
             gas_left: InternalGas::new(0),
             cost_table: &ZERO_COST_SCHEDULE,
             charge: false,
+            profile: None,
         }
     }
 
@@ -103,13 +108,24 @@ impl<'a> GasStatus<'a> {
         }
     }
 
+    /// Charge a given amount of gas like `deduct_gas`, recording under `label` in the gas profile
+    /// only what was actually deducted.
+    pub fn deduct_gas_recorded(&mut self, label: &str, amount: InternalGas) -> PartialVMResult<()> {
+        let gas_left = u64::from(self.gas_left);
+        let result = self.deduct_gas(amount);
+        if let Some(profile) = &mut self.profile {
+            profile.record(label, gas_left - u64::from(self.gas_left));
+        }
+        result
+    }
+
     fn charge_instr(&mut self, opcode: Opcodes) -> PartialVMResult<()> {
-        self.deduct_gas(
-            self.cost_table
-                .instruction_cost(opcode as u8)
-                .total()
-                .into(),
-        )
+        let amount = self
+            .cost_table
+            .instruction_cost(opcode as u8)
+            .total()
+            .into();
+        self.deduct_instr(opcode, amount)
     }
 
     /// Charge an instruction over data with a given size and fail if not enough gas units are left.
@@ -121,17 +137,58 @@ impl<'a> GasStatus<'a> {
         // Make sure that the size is always non-zero
         let size = std::cmp::max(1.into(), size);
         debug_assert!(size > 0.into());
-        self.deduct_gas(
-            InternalGasPerAbstractMemoryUnit::new(
-                self.cost_table.instruction_cost(opcode as u8).total(),
-            )
-            .mul(size),
+        let amount = InternalGasPerAbstractMemoryUnit::new(
+            self.cost_table.instruction_cost(opcode as u8).total(),
         )
+        .mul(size);
+        self.deduct_instr(opcode, amount)
     }
 
     pub fn set_metering(&mut self, enabled: bool) {
         self.charge = enabled
     }
+
+    /// Start recording where the gas goes in a `GasProfile`.
+    pub fn enable_profiling(&mut self) {
+        self.profile.get_or_insert_with(GasProfile::new);
+    }
+
+    /// The gas recorded so far, if profiling is enabled.
+    pub fn profile(&self) -> Option<&GasProfile> {
+        self.profile.as_ref()
+    }
+
+    /// The gas profile, to enter frames or record charges made outside of the Move VM, if
+    /// profiling is enabled.
+    pub fn profile_mut(&mut self) -> Option<&mut GasProfile> {
+        self.profile.as_mut()
+    }
+
+    /// Stop profiling, returning the gas recorded.
+    pub fn take_profile(&mut self) -> Option<GasProfile> {
+        self.profile.take()
+    }
+
+    /// Charge an instruction like `deduct_gas_recorded`, formatting its label only when profiling.
+    fn deduct_instr(&mut self, opcode: Opcodes, amount: InternalGas) -> PartialVMResult<()> {
+        if self.profile.is_some() {
+            self.deduct_gas_recorded(&format!("{:?}", opcode), amount)
+        } else {
+            self.deduct_gas(amount)
+        }
+    }
+
+    fn enter_function(&mut self, module_id: &ModuleId, func_name: &str) {
+        if let Some(profile) = &mut self.profile {
+            profile.enter(format!("{}::{}", module_id.short_str_lossless(), func_name));
+        }
+    }
+
+    fn exit_function(&mut self) {
+        if let Some(profile) = &mut self.profile {
+            profile.exit();
+        }
+    }
 }
 
 fn get_simple_instruction_opcode(instr: SimpleInstruction) -> Opcodes {
@@ -193,33 +250,47 @@ fn get_simple_instruction_opcode(instr: SimpleInstruction) -> Opcodes {
 impl<'b> GasMeter for GasStatus<'b> {
     /// Charge an instruction and fail if not enough gas units are left.
     fn charge_simple_instr(&mut self, instr: SimpleInstruction) -> PartialVMResult<()> {
-        self.charge_instr(get_simple_instruction_opcode(instr))
+        let opcode = get_simple_instruction_opcode(instr);
+        let result = self.charge_instr(opcode);
+        if opcode == Opcodes::RET {
+            self.exit_function();
+        }
+        result
     }
 
+    /// Charge the cost of the native function called last, which does not return through `Ret`.
     fn charge_native_function(&mut self, amount: InternalGas) -> PartialVMResult<()> {
-        self.deduct_gas(amount)
+        let result = self.deduct_gas_recorded(NATIVE_FRAME, amount);
+        if let Some(profile) = &mut self.profile {
+            profile.exit();
+        }
+        result
     }
 
     fn charge_call(
         &mut self,
-        _module_id: &ModuleId,
-        _func_name: &str,
+        module_id: &ModuleId,
+        func_name: &str,
         args: impl ExactSizeIterator<Item = impl ValueView>,
     ) -> PartialVMResult<()> {
-        self.charge_instr_with_size(Opcodes::CALL, (args.len() as u64 + 1).into())
+        self.charge_instr_with_size(Opcodes::CALL, (args.len() as u64 + 1).into())?;
+        self.enter_function(module_id, func_name);
+        Ok(())
     }
 
     fn charge_call_generic(
         &mut self,
-        _module_id: &ModuleId,
-        _func_name: &str,
+        module_id: &ModuleId,
+        func_name: &str,
         ty_args: impl ExactSizeIterator<Item = impl TypeView>,
         args: impl ExactSizeIterator<Item = impl ValueView>,
     ) -> PartialVMResult<()> {
         self.charge_instr_with_size(
             Opcodes::CALL_GENERIC,
             ((ty_args.len() + args.len() + 1) as u64).into(),
-        )
+        )?;
+        self.enter_function(module_id, func_name);
+        Ok(())
     }
 
     fn charge_ld_const(&mut self, size: NumBytes) -> PartialVMResult<()> {
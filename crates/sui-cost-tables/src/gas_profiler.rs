@@ -0,0 +1,175 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Profiling of where the gas of an execution goes.
+//!
+//! A `GasProfile` attributes every charge to the stack of Move functions executing when it was
+//! made, with the charged instruction as the innermost frame. The stacks are reported in the
+//! folded format read by flamegraph tools, e.g. `inferno-flamegraph` or `flamegraph.pl`, with one
+//! `frame;frame;...;instruction gas` line per stack.
+
+use std::collections::BTreeMap;
+
+/// Separates the frames of a folded stack.
+const FRAME_SEPARATOR: &str = ";";
+
+/// The leaf of the stack for the gas charged by native functions.
+pub const NATIVE_FRAME: &str = "NATIVE";
+
+/// The gas charged during an execution, in internal gas units, by stack of Move functions and by
+/// instruction.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct GasProfile {
+    /// The functions being executed, outermost first.
+    frames: Vec<String>,
+    /// The gas charged by folded stack.
+    stacks: BTreeMap<String, u64>,
+    /// The gas charged by each function's own instructions, excluding its callees.
+    functions: BTreeMap<String, u64>,
+    /// The gas charged by each instruction, across functions.
+    instructions: BTreeMap<String, u64>,
+}
+
+impl GasProfile {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Enter a call to `function`. Charges are attributed to it until the matching `exit`.
+    pub fn enter(&mut self, function: String) {
+        self.frames.push(function);
+    }
+
+    /// Return from the function entered last.
+    pub fn exit(&mut self) {
+        self.frames.pop();
+    }
+
+    /// Leave all the functions entered, e.g. after an execution aborted inside of them.
+    pub fn unwind(&mut self) {
+        self.frames.clear();
+    }
+
+    /// Record that `amount` gas was charged for `instruction` in the current function. Charges
+    /// made outside of Move functions, e.g. for reading objects, are recorded at the root of the
+    /// stack, labelled by `instruction`.
+    pub fn record(&mut self, instruction: &str, amount: u64) {
+        if amount == 0 {
+            return;
+        }
+        let mut stack = self.frames.join(FRAME_SEPARATOR);
+        if !stack.is_empty() {
+            stack.push_str(FRAME_SEPARATOR);
+        }
+        stack.push_str(instruction);
+        add(&mut self.stacks, stack, amount);
+        add(&mut self.instructions, instruction.to_string(), amount);
+        if let Some(function) = self.frames.last() {
+            add(&mut self.functions, function.clone(), amount);
+        }
+    }
+
+    /// Add the gas recorded in `other`, e.g. by another execution, to this profile.
+    pub fn merge(&mut self, other: GasProfile) {
+        for (stack, amount) in other.stacks {
+            add(&mut self.stacks, stack, amount);
+        }
+        for (function, amount) in other.functions {
+            add(&mut self.functions, function, amount);
+        }
+        for (instruction, amount) in other.instructions {
+            add(&mut self.instructions, instruction, amount);
+        }
+    }
+
+    /// The total gas recorded.
+    pub fn total(&self) -> u64 {
+        self.stacks
+            .values()
+            .fold(0, |total, amount| total.saturating_add(*amount))
+    }
+
+    /// The gas charged by each function's own instructions and natives, excluding its callees.
+    pub fn function_costs(&self) -> &BTreeMap<String, u64> {
+        &self.functions
+    }
+
+    /// The gas charged by each instruction, across functions.
+    pub fn instruction_costs(&self) -> &BTreeMap<String, u64> {
+        &self.instructions
+    }
+
+    /// The gas charged by each stack, in the folded format of flamegraph tools.
+    pub fn to_folded_stacks(&self) -> String {
+        self.stacks
+            .iter()
+            .map(|(stack, amount)| format!("{stack} {amount}\n"))
+            .collect()
+    }
+}
+
+fn add(costs: &mut BTreeMap<String, u64>, key: String, amount: u64) {
+    let cost = costs.entry(key).or_insert(0);
+    *cost = cost.saturating_add(amount);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn folded_stacks() {
+        let mut profile = GasProfile::new();
+        profile.record("[storage_read]", 7);
+        profile.enter("0x2::m::f".to_string());
+        profile.record("CALL", 3);
+        profile.enter("0x2::m::g".to_string());
+        profile.record("ADD", 1);
+        profile.record("ADD", 1);
+        profile.record("NOP", 0);
+        profile.exit();
+        profile.enter("0x2::n::native_g".to_string());
+        profile.record(NATIVE_FRAME, 10);
+        profile.exit();
+        profile.record("RET", 2);
+        profile.exit();
+
+        assert_eq!(
+            profile.to_folded_stacks(),
+            "0x2::m::f;0x2::m::g;ADD 2\n\
+             0x2::m::f;0x2::n::native_g;NATIVE 10\n\
+             0x2::m::f;CALL 3\n\
+             0x2::m::f;RET 2\n\
+             [storage_read] 7\n"
+        );
+        assert_eq!(profile.total(), 24);
+        assert_eq!(profile.function_costs()["0x2::m::f"], 5);
+        assert_eq!(profile.function_costs()["0x2::m::g"], 2);
+        assert_eq!(profile.function_costs()["0x2::n::native_g"], 10);
+        assert_eq!(profile.instruction_costs()["ADD"], 2);
+        assert!(!profile.instruction_costs().contains_key("NOP"));
+    }
+
+    #[test]
+    fn merge_profiles() {
+        let mut profile = GasProfile::new();
+        profile.enter("0x2::m::f".to_string());
+        profile.record("ADD", 1);
+
+        let mut other = GasProfile::new();
+        other.enter("0x2::m::f".to_string());
+        other.record("ADD", 2);
+        other.exit();
+        other.enter("0x2::m::g".to_string());
+        other.record("RET", 3);
+
+        profile.merge(other);
+        assert_eq!(
+            profile.to_folded_stacks(),
+            "0x2::m::f;ADD 3\n\
+             0x2::m::g;RET 3\n"
+        );
+        assert_eq!(profile.function_costs()["0x2::m::f"], 3);
+        assert_eq!(profile.instruction_costs()["RET"], 3);
+    }
+}
@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 pub mod bytecode_tables;
+pub mod gas_profiler;
 pub mod natives_tables;
 pub mod non_execution_tables;
 pub mod units_types;
@@ -1,9 +1,11 @@
 // Copyright (c) 2022, Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use move_core_types::gas_algebra::{Byte, InternalGas, InternalGasPerByte, InternalGasUnit};
+
 use crate::{
     non_execution_tables::{MAX_NUM_EVENT_EMIT, MAX_TX_GAS},
-    units_types::GasCost,
+    units_types::{GasCost, LinearEquation},
 };
 
 //
@@ -56,3 +58,176 @@ pub fn _native_cost_schedule() -> Vec<GasCost> {
         .map(|(_, cost)| cost)
         .collect::<Vec<_>>()
 }
+
+//
+// Crypto native costs: hashing and signature verification take time proportional to the size of
+// their input, so they are charged a flat cost plus a cost per byte of input.
+//
+pub const CRYPTO_SHA2_256_COST_FIXED: u64 = 500;
+pub const CRYPTO_SHA2_256_COST_PER_BYTE: u64 = 2;
+
+pub const CRYPTO_SHA3_256_COST_FIXED: u64 = 500;
+pub const CRYPTO_SHA3_256_COST_PER_BYTE: u64 = 2;
+
+pub const CRYPTO_BLAKE2B256_COST_FIXED: u64 = 500;
+pub const CRYPTO_BLAKE2B256_COST_PER_BYTE: u64 = 1;
+
+// Charged per byte of the key and the message
+pub const CRYPTO_HMAC_SHA3_256_COST_FIXED: u64 = 1_000;
+pub const CRYPTO_HMAC_SHA3_256_COST_PER_BYTE: u64 = 2;
+
+// Charged per byte of the message
+pub const CRYPTO_ED25519_VERIFY_COST_FIXED: u64 = 20_000;
+pub const CRYPTO_ED25519_VERIFY_COST_PER_BYTE: u64 = 2;
+
+// Charged per byte of the message
+pub const CRYPTO_SECP256R1_VERIFY_COST_FIXED: u64 = 50_000;
+pub const CRYPTO_SECP256R1_VERIFY_COST_PER_BYTE: u64 = 2;
+
+// Charged per public key, each one is decoded and added to the aggregate key, and per byte of the
+// message, the pairings dominate the fixed cost
+pub const CRYPTO_BLS12381_AGGREGATE_VERIFY_COST_FIXED: u64 = 200_000;
+pub const CRYPTO_BLS12381_AGGREGATE_VERIFY_COST_PER_BYTE: u64 = 20;
+pub const CRYPTO_BLS12381_AGGREGATE_VERIFY_COST_PER_PUBLIC_KEY: u64 = 50_000;
+
+pub const CRYPTO_SHA2_256_COST_EQUATION: LinearEquation<InternalGasUnit, Byte> =
+    native_cost_equation(CRYPTO_SHA2_256_COST_FIXED, CRYPTO_SHA2_256_COST_PER_BYTE);
+
+pub const CRYPTO_SHA3_256_COST_EQUATION: LinearEquation<InternalGasUnit, Byte> =
+    native_cost_equation(CRYPTO_SHA3_256_COST_FIXED, CRYPTO_SHA3_256_COST_PER_BYTE);
+
+pub const CRYPTO_BLAKE2B256_COST_EQUATION: LinearEquation<InternalGasUnit, Byte> =
+    native_cost_equation(
+        CRYPTO_BLAKE2B256_COST_FIXED,
+        CRYPTO_BLAKE2B256_COST_PER_BYTE,
+    );
+
+pub const CRYPTO_HMAC_SHA3_256_COST_EQUATION: LinearEquation<InternalGasUnit, Byte> =
+    native_cost_equation(
+        CRYPTO_HMAC_SHA3_256_COST_FIXED,
+        CRYPTO_HMAC_SHA3_256_COST_PER_BYTE,
+    );
+
+pub const CRYPTO_ED25519_VERIFY_COST_EQUATION: LinearEquation<InternalGasUnit, Byte> =
+    native_cost_equation(
+        CRYPTO_ED25519_VERIFY_COST_FIXED,
+        CRYPTO_ED25519_VERIFY_COST_PER_BYTE,
+    );
+
+pub const CRYPTO_SECP256R1_VERIFY_COST_EQUATION: LinearEquation<InternalGasUnit, Byte> =
+    native_cost_equation(
+        CRYPTO_SECP256R1_VERIFY_COST_FIXED,
+        CRYPTO_SECP256R1_VERIFY_COST_PER_BYTE,
+    );
+
+/// The cost of verifying a BLS12-381 aggregate signature by `num_public_keys` signers of a message
+/// of `msg_len` bytes.
+pub fn bls12381_aggregate_verify_cost(num_public_keys: u64, msg_len: u64) -> InternalGas {
+    InternalGas::new(
+        CRYPTO_BLS12381_AGGREGATE_VERIFY_COST_PER_PUBLIC_KEY
+            .saturating_mul(num_public_keys)
+            .saturating_add(CRYPTO_BLS12381_AGGREGATE_VERIFY_COST_PER_BYTE.saturating_mul(msg_len))
+            .saturating_add(CRYPTO_BLS12381_AGGREGATE_VERIFY_COST_FIXED)
+            .min(MAX_TX_GAS),
+    )
+}
+
+//
+// Groth16 native costs: preparing a verifying key and verifying a proof compute pairings, so
+// they are charged a large flat cost, plus a cost per byte of the key or per public input.
+//
+
+// Charged per byte of the verifying key, preparing it computes a pairing
+pub const CRYPTO_GROTH16_PREPARE_VERIFYING_KEY_COST_FIXED: u64 = 1_000_000;
+pub const CRYPTO_GROTH16_PREPARE_VERIFYING_KEY_COST_PER_BYTE: u64 = 20;
+
+// Groth16 verification computes three pairings, and one scalar multiplication per public input
+pub const CRYPTO_GROTH16_VERIFY_BLS12381_COST_FIXED: u64 = 3_000_000;
+pub const CRYPTO_GROTH16_VERIFY_BLS12381_COST_PER_INPUT: u64 = 100_000;
+pub const CRYPTO_GROTH16_VERIFY_BN254_COST_FIXED: u64 = 2_000_000;
+pub const CRYPTO_GROTH16_VERIFY_BN254_COST_PER_INPUT: u64 = 60_000;
+
+pub const CRYPTO_GROTH16_PREPARE_VERIFYING_KEY_COST_EQUATION: LinearEquation<
+    InternalGasUnit,
+    Byte,
+> = native_cost_equation(
+    CRYPTO_GROTH16_PREPARE_VERIFYING_KEY_COST_FIXED,
+    CRYPTO_GROTH16_PREPARE_VERIFYING_KEY_COST_PER_BYTE,
+);
+
+/// The cost of verifying a Groth16 proof with `num_inputs` public inputs, given the fixed and per
+/// input costs for the curve the proof is over.
+pub fn groth16_verify_cost(fixed: u64, per_input: u64, num_inputs: u64) -> InternalGas {
+    InternalGas::new(
+        per_input
+            .saturating_mul(num_inputs)
+            .saturating_add(fixed)
+            .min(MAX_TX_GAS),
+    )
+}
+
+//
+// Dynamic field native costs: a child object is read from storage the first time it is accessed,
+// so accessing a child is charged a flat cost plus a cost per byte of the object read, if any.
+//
+
+// Charged per byte of the key and of its type, which are hashed
+pub const DYNAMIC_FIELD_HASH_TYPE_AND_KEY_COST_FIXED: u64 = 500;
+pub const DYNAMIC_FIELD_HASH_TYPE_AND_KEY_COST_PER_BYTE: u64 = 2;
+
+pub const DYNAMIC_FIELD_ADD_CHILD_OBJECT_COST_FIXED: u64 = 1_000;
+pub const DYNAMIC_FIELD_BORROW_CHILD_OBJECT_COST_FIXED: u64 = 1_000;
+pub const DYNAMIC_FIELD_REMOVE_CHILD_OBJECT_COST_FIXED: u64 = 1_000;
+pub const DYNAMIC_FIELD_HAS_CHILD_OBJECT_COST_FIXED: u64 = 500;
+// Charged per byte of a child object read from storage, which is deserialized into a Move value
+pub const DYNAMIC_FIELD_CHILD_OBJECT_LOAD_COST_PER_BYTE: u64 = 10;
+
+pub const DYNAMIC_FIELD_HASH_TYPE_AND_KEY_COST_EQUATION: LinearEquation<InternalGasUnit, Byte> =
+    native_cost_equation(
+        DYNAMIC_FIELD_HASH_TYPE_AND_KEY_COST_FIXED,
+        DYNAMIC_FIELD_HASH_TYPE_AND_KEY_COST_PER_BYTE,
+    );
+
+pub const DYNAMIC_FIELD_ADD_CHILD_OBJECT_COST_EQUATION: LinearEquation<InternalGasUnit, Byte> =
+    native_cost_equation(
+        DYNAMIC_FIELD_ADD_CHILD_OBJECT_COST_FIXED,
+        DYNAMIC_FIELD_CHILD_OBJECT_LOAD_COST_PER_BYTE,
+    );
+
+pub const DYNAMIC_FIELD_BORROW_CHILD_OBJECT_COST_EQUATION: LinearEquation<InternalGasUnit, Byte> =
+    native_cost_equation(
+        DYNAMIC_FIELD_BORROW_CHILD_OBJECT_COST_FIXED,
+        DYNAMIC_FIELD_CHILD_OBJECT_LOAD_COST_PER_BYTE,
+    );
+
+pub const DYNAMIC_FIELD_REMOVE_CHILD_OBJECT_COST_EQUATION: LinearEquation<InternalGasUnit, Byte> =
+    native_cost_equation(
+        DYNAMIC_FIELD_REMOVE_CHILD_OBJECT_COST_FIXED,
+        DYNAMIC_FIELD_CHILD_OBJECT_LOAD_COST_PER_BYTE,
+    );
+
+pub const DYNAMIC_FIELD_HAS_CHILD_OBJECT_COST_EQUATION: LinearEquation<InternalGasUnit, Byte> =
+    native_cost_equation(
+        DYNAMIC_FIELD_HAS_CHILD_OBJECT_COST_FIXED,
+        DYNAMIC_FIELD_CHILD_OBJECT_LOAD_COST_PER_BYTE,
+    );
+
+//
+// Type native costs: the name of a type is formatted from the type and returned as a vector, so
+// getting it is charged a flat cost plus a cost per byte of the name.
+//
+
+pub const TYPES_TYPE_NAME_COST_FIXED: u64 = 500;
+pub const TYPES_TYPE_NAME_COST_PER_BYTE: u64 = 10;
+
+pub const TYPES_TYPE_NAME_COST_EQUATION: LinearEquation<InternalGasUnit, Byte> =
+    native_cost_equation(TYPES_TYPE_NAME_COST_FIXED, TYPES_TYPE_NAME_COST_PER_BYTE);
+
+const fn native_cost_equation(fixed: u64, per_byte: u64) -> LinearEquation<InternalGasUnit, Byte> {
+    LinearEquation::new(
+        InternalGasPerByte::new(per_byte),
+        InternalGas::new(fixed),
+        InternalGas::new(fixed),
+        InternalGas::new(MAX_TX_GAS),
+    )
+}
@@ -45,6 +45,7 @@ pub fn estimate_computational_costs_for_transaction(
 
             SingleTransactionKind::TransferObject(_) => unsupported_tx_kind,
             SingleTransactionKind::Publish(_) => unsupported_tx_kind,
+            SingleTransactionKind::Upgrade(_) => unsupported_tx_kind,
             SingleTransactionKind::Call(_) => unsupported_tx_kind,
             SingleTransactionKind::ChangeEpoch(_) => unsupported_tx_kind,
         },
@@ -0,0 +1,59 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+/// Access to the time agreed on by consensus. The singleton `Clock` is a shared object that
+/// entry functions can take by immutable reference. It is read-only: its timestamp is set by
+/// validators when they sequence a transaction reading it, so reading it does not contend with
+/// other transactions.
+module sui::clock {
+    use sui::object::{Self, UID};
+    use sui::transfer;
+    #[test_only]
+    use sui::tx_context::TxContext;
+
+    friend sui::genesis;
+
+    /// Singleton shared object that exposes the time of the consensus commit that sequenced the
+    /// transaction reading it.
+    struct Clock has key {
+        id: UID,
+        /// The consensus commit timestamp, in milliseconds since the Unix epoch. It only ever
+        /// increases.
+        timestamp_ms: u64,
+    }
+
+    /// The consensus commit timestamp in milliseconds.
+    public fun timestamp_ms(clock: &Clock): u64 {
+        clock.timestamp_ms
+    }
+
+    /// Create the singleton Clock object and make it shared.
+    /// This function will be called only once in Genesis.
+    public(friend) fun create() {
+        transfer::share_object(Clock {
+            // Use a hardcoded ID.
+            id: object::clock(),
+            // The timestamp is set by validators when sequencing transactions.
+            timestamp_ms: 0,
+        })
+    }
+
+    #[test_only]
+    public fun create_for_testing(ctx: &mut TxContext): Clock {
+        Clock {
+            id: object::new(ctx),
+            timestamp_ms: 0,
+        }
+    }
+
+    #[test_only]
+    public fun increment_for_testing(clock: &mut Clock, tick: u64) {
+        clock.timestamp_ms = clock.timestamp_ms + tick;
+    }
+
+    #[test_only]
+    public fun destroy_for_testing(clock: Clock) {
+        let Clock { id, timestamp_ms: _ } = clock;
+        object::delete(id);
+    }
+}
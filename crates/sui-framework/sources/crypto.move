@@ -22,6 +22,23 @@ module sui::crypto {
     /// Hash the input bytes using keccak256 and returns 32 bytes.
     public native fun keccak256(data: vector<u8>): vector<u8>;
 
+    /// @param data: arbitrary bytes data to hash
+    /// Hash the input bytes using SHA2-256 and returns 32 bytes.
+    public native fun sha2_256(data: vector<u8>): vector<u8>;
+
+    /// @param data: arbitrary bytes data to hash
+    /// Hash the input bytes using SHA3-256 and returns 32 bytes.
+    public native fun sha3_256(data: vector<u8>): vector<u8>;
+
+    /// @param data: arbitrary bytes data to hash
+    /// Hash the input bytes using BLAKE2b with a 32-byte output and returns 32 bytes.
+    public native fun blake2b256(data: vector<u8>): vector<u8>;
+
+    /// @param key: the secret key, of any length
+    /// @param msg: the message to authenticate
+    /// Compute the HMAC-SHA3-256 of the message under the key and returns 32 bytes.
+    public native fun hmac_sha3_256(key: vector<u8>, msg: vector<u8>): vector<u8>;
+
     /// @param signature: A 48-bytes signature that is a point on the G1 subgroup
     /// @param public_key: A 96-bytes public key that is a point on the G2 subgroup
     /// @param msg: The message that we test the signature against.
@@ -30,6 +47,19 @@ module sui::crypto {
     /// Otherwise, return false.
     public native fun bls12381_verify_g1_sig(signature: vector<u8>, public_key: vector<u8>, msg: vector<u8>): bool; 
 
+    /// @param signature: A 48-bytes aggregate signature that is a point on the G1 subgroup
+    /// @param public_keys: The 96-bytes public keys of the signers, points on the G2 subgroup
+    /// @param msg: The message that all signers signed.
+    ///
+    /// If the signature is a valid BLS12381 aggregate of signatures of the message by all of the
+    /// public keys, return true. Otherwise, including when no public keys are given, return false.
+    ///
+    /// The public keys are not checked for a proof of possession. The caller must only pass keys
+    /// whose proof of possession was verified before, e.g. when they were registered, otherwise a
+    /// signer can choose its key from the others' (a rogue key attack) and forge an aggregate
+    /// signature of all of them on its own.
+    public native fun bls12381_aggregate_verify(signature: vector<u8>, public_keys: vector<vector<u8>>, msg: vector<u8>): bool;
+
     /// @param signature: A 65-bytes signature in form (r, s, v) that is signed using 
     /// Secp256k1. Reference implementation on signature generation using RFC6979: 
     /// https://github.com/MystenLabs/narwhal/blob/5d6f6df8ccee94446ff88786c0dbbc98be7cfc09/crypto/src/secp256k1.rs
@@ -40,6 +70,18 @@ module sui::crypto {
     /// If the signature is valid to the pubkey and hashed message, return true. Else false.
     public native fun secp256k1_verify(signature: vector<u8>, public_key: vector<u8>, hashed_msg: vector<u8>): bool;
 
+    /// @param signature: A 64-bytes signature in form (r, s) that is signed using Secp256r1
+    /// (NIST P-256) over the SHA2-256 hash of the message.
+    /// @param public_key: The 33-bytes compressed or 65-bytes uncompressed public key to verify the
+    /// signature against
+    /// @param msg: The message that the signature is signed against, it is hashed with SHA2-256 as
+    /// part of the verification.
+    ///
+    /// If the signature is valid to the pubkey and message, return true. Else false. Signatures with
+    /// a high s (greater than half the group order) are rejected, so a signature cannot be
+    /// malleated into a second valid one.
+    public native fun secp256r1_verify(signature: vector<u8>, public_key: vector<u8>, msg: vector<u8>): bool;
+
     use sui::elliptic_curve::{Self as ec, RistrettoPoint};
 
     /// Only bit_length = 64, 32, 16, 8 will work.
@@ -60,7 +102,7 @@ module sui::crypto {
     /// 
     /// If the signature is a valid Ed25519 signature of the message and public key, return true.
     /// Otherwise, return false.
-    public(friend) native fun ed25519_verify(signature: vector<u8>, public_key: vector<u8>, msg: vector<u8>): bool;
+    public native fun ed25519_verify(signature: vector<u8>, public_key: vector<u8>, msg: vector<u8>): bool;
 
     /// @param signature: 32-byte signature that is a point on the Ed25519 elliptic curve.
     /// @param public_key: 32-byte signature that is a point on the Ed25519 elliptic curve.
@@ -0,0 +1,119 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+/// Verification of Groth16 zk-SNARK proofs over the BLS12-381 and BN254 curves.
+///
+/// Keys, inputs and proofs are passed in the compressed serialization of the arkworks libraries.
+/// A verifying key is prepared once with `prepare_verifying_key`, and the `PreparedVerifyingKey`
+/// can be stored to verify any number of proofs against it.
+module sui::groth16 {
+
+    /// The verifying key could not be deserialized.
+    const EInvalidVerifyingKey: u64 = 0;
+    /// The curve is not supported.
+    const EInvalidCurve: u64 = 1;
+    /// The public inputs are not a sequence of 32-byte scalars, or not as many as the key expects.
+    const EInvalidPublicInputs: u64 = 2;
+    /// The proof points could not be deserialized.
+    const EInvalidProof: u64 = 3;
+
+    /// The curve a proof is over.
+    struct Curve has store, copy, drop {
+        id: u8,
+    }
+
+    /// A verifying key preprocessed for verification: the pairing of its `alpha` and `beta`
+    /// points is computed once here instead of on each verification.
+    struct PreparedVerifyingKey has store, copy, drop {
+        curve: Curve,
+        vk_gamma_abc_g1_bytes: vector<u8>,
+        alpha_g1_beta_g2_bytes: vector<u8>,
+        gamma_g2_neg_pc_bytes: vector<u8>,
+        delta_g2_neg_pc_bytes: vector<u8>,
+    }
+
+    /// The public inputs of a proof, a concatenation of 32-byte little-endian scalars.
+    struct PublicProofInputs has store, copy, drop {
+        bytes: vector<u8>,
+    }
+
+    /// The serialized `A`, `B` and `C` points of a proof.
+    struct ProofPoints has store, copy, drop {
+        bytes: vector<u8>,
+    }
+
+    /// The BLS12-381 curve.
+    public fun bls12381(): Curve {
+        Curve { id: 0 }
+    }
+
+    /// The BN254 curve.
+    public fun bn254(): Curve {
+        Curve { id: 1 }
+    }
+
+    public fun public_proof_inputs_from_bytes(bytes: vector<u8>): PublicProofInputs {
+        PublicProofInputs { bytes }
+    }
+
+    public fun proof_points_from_bytes(bytes: vector<u8>): ProofPoints {
+        ProofPoints { bytes }
+    }
+
+    /// Prepare the serialized verifying key `verifying_key` of a circuit over `curve` for
+    /// verifying proofs. Aborts if the key is invalid.
+    public fun prepare_verifying_key(curve: &Curve, verifying_key: &vector<u8>): PreparedVerifyingKey {
+        let (
+            vk_gamma_abc_g1_bytes,
+            alpha_g1_beta_g2_bytes,
+            gamma_g2_neg_pc_bytes,
+            delta_g2_neg_pc_bytes,
+        ) = prepare_verifying_key_internal(curve.id, *verifying_key);
+        PreparedVerifyingKey {
+            curve: *curve,
+            vk_gamma_abc_g1_bytes,
+            alpha_g1_beta_g2_bytes,
+            gamma_g2_neg_pc_bytes,
+            delta_g2_neg_pc_bytes,
+        }
+    }
+
+    /// Whether `proof_points` is a valid proof of the statement with public inputs
+    /// `public_proof_inputs`, for the circuit of `prepared_verifying_key`. Aborts if the inputs or
+    /// proof cannot be deserialized, and costs gas in proportion to the number of public inputs.
+    public fun verify_groth16_proof(
+        prepared_verifying_key: &PreparedVerifyingKey,
+        public_proof_inputs: &PublicProofInputs,
+        proof_points: &ProofPoints,
+    ): bool {
+        verify_groth16_proof_internal(
+            prepared_verifying_key.curve.id,
+            prepared_verifying_key.vk_gamma_abc_g1_bytes,
+            prepared_verifying_key.alpha_g1_beta_g2_bytes,
+            prepared_verifying_key.gamma_g2_neg_pc_bytes,
+            prepared_verifying_key.delta_g2_neg_pc_bytes,
+            public_proof_inputs.bytes,
+            proof_points.bytes,
+        )
+    }
+
+    /// The curve `prepared_verifying_key` is over.
+    public fun curve(prepared_verifying_key: &PreparedVerifyingKey): Curve {
+        prepared_verifying_key.curve
+    }
+
+    native fun prepare_verifying_key_internal(
+        curve: u8,
+        verifying_key: vector<u8>,
+    ): (vector<u8>, vector<u8>, vector<u8>, vector<u8>);
+
+    native fun verify_groth16_proof_internal(
+        curve: u8,
+        vk_gamma_abc_g1_bytes: vector<u8>,
+        alpha_g1_beta_g2_bytes: vector<u8>,
+        gamma_g2_neg_pc_bytes: vector<u8>,
+        delta_g2_neg_pc_bytes: vector<u8>,
+        public_proof_inputs: vector<u8>,
+        proof_points: vector<u8>,
+    ): bool;
+}
@@ -0,0 +1,130 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+/// Describes how objects of a type should be displayed, e.g. by wallets and explorers.
+///
+/// A `Display<T>` maps field names such as `name`, `description` or `image_url` to templates.
+/// Each `{path}` in a template is replaced with the value found at `path` in the object being
+/// displayed, so `"{name} #{id}"` renders an object with `name: "Capy"` as `"Capy #0x..."`.
+/// Nested fields are addressed with dots, as in `{metadata.url}`, and `{{`, `}}` escape braces.
+///
+/// Only the publisher of the package defining `T` can create a `Display<T>`. Changes take effect
+/// once they are published with `update_version`, which full nodes pick up to render objects.
+module sui::display {
+    use std::string::{Self, String};
+    use std::vector;
+    use sui::event;
+    use sui::object::{Self, ID, UID};
+    use sui::package::{Self, Publisher};
+    use sui::transfer;
+    use sui::tx_context::{Self, TxContext};
+    use sui::vec_map::{Self, VecMap};
+
+    /// The `Publisher` was not claimed for the package defining `T`.
+    const ENotOwner: u64 = 0;
+
+    /// Different numbers of field names and templates were passed.
+    const EVecLengthMismatch: u64 = 1;
+
+    /// The templates to display objects of type `T` with, keyed by field name.
+    struct Display<phantom T: key> has key, store {
+        id: UID,
+        fields: VecMap<String, String>,
+        /// Incremented by `update_version`, only the latest version is used for display.
+        version: u64,
+    }
+
+    /// Emitted when a `Display<T>` is created, so its creation can be tracked off chain.
+    struct DisplayCreated<phantom T: key> has copy, drop {
+        id: ID,
+    }
+
+    /// Emitted by `update_version` with the fields to display objects of type `T` with from now on.
+    struct VersionUpdated<phantom T: key> has copy, drop {
+        id: ID,
+        version: u64,
+        fields: VecMap<String, String>,
+    }
+
+    /// Create an empty `Display<T>`, authorized by the publisher of `T`.
+    public fun new<T: key>(publisher: &Publisher, ctx: &mut TxContext): Display<T> {
+        assert!(is_authorized<T>(publisher), ENotOwner);
+        let display = Display<T> { id: object::new(ctx), fields: vec_map::empty(), version: 0 };
+        event::emit(DisplayCreated<T> { id: object::uid_to_inner(&display.id) });
+        display
+    }
+
+    /// Create a `Display<T>` with the template `values[i]` for the field `fields[i]`.
+    public fun new_with_fields<T: key>(
+        publisher: &Publisher,
+        fields: vector<vector<u8>>,
+        values: vector<vector<u8>>,
+        ctx: &mut TxContext,
+    ): Display<T> {
+        let display = new<T>(publisher, ctx);
+        add_multiple(&mut display, fields, values);
+        display
+    }
+
+    /// Create an empty `Display<T>` and send it to the transaction sender.
+    public entry fun create_and_keep<T: key>(publisher: &Publisher, ctx: &mut TxContext) {
+        transfer::transfer(new<T>(publisher, ctx), tx_context::sender(ctx))
+    }
+
+    /// Publish the current fields of `display` as its next version.
+    public entry fun update_version<T: key>(display: &mut Display<T>) {
+        display.version = display.version + 1;
+        event::emit(VersionUpdated<T> {
+            id: object::uid_to_inner(&display.id),
+            version: display.version,
+            fields: display.fields,
+        });
+    }
+
+    /// Add the template `value` for the field `name`.
+    public entry fun add<T: key>(display: &mut Display<T>, name: vector<u8>, value: vector<u8>) {
+        vec_map::insert(&mut display.fields, string::utf8(name), string::utf8(value));
+    }
+
+    /// Add the template `values[i]` for the field `fields[i]`.
+    public entry fun add_multiple<T: key>(
+        display: &mut Display<T>,
+        fields: vector<vector<u8>>,
+        values: vector<vector<u8>>,
+    ) {
+        let len = vector::length(&fields);
+        assert!(len == vector::length(&values), EVecLengthMismatch);
+        let i = 0;
+        while (i < len) {
+            add(display, *vector::borrow(&fields, i), *vector::borrow(&values, i));
+            i = i + 1;
+        };
+    }
+
+    /// Replace the template for the field `name` with `value`.
+    public entry fun edit<T: key>(display: &mut Display<T>, name: vector<u8>, value: vector<u8>) {
+        let name = string::utf8(name);
+        let (_, _) = vec_map::remove(&mut display.fields, &name);
+        vec_map::insert(&mut display.fields, name, string::utf8(value));
+    }
+
+    /// Remove the field `name` from `display`.
+    public entry fun remove<T: key>(display: &mut Display<T>, name: vector<u8>) {
+        let (_, _) = vec_map::remove(&mut display.fields, &string::utf8(name));
+    }
+
+    /// Whether `publisher` can create a `Display<T>`.
+    public fun is_authorized<T: key>(publisher: &Publisher): bool {
+        package::from_package<T>(publisher)
+    }
+
+    /// The latest version published with `update_version`.
+    public fun version<T: key>(display: &Display<T>): u64 {
+        display.version
+    }
+
+    /// The templates of `display`, keyed by field name.
+    public fun fields<T: key>(display: &Display<T>): &VecMap<String, String> {
+        &display.fields
+    }
+}
@@ -0,0 +1,143 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+/// In addition to the fields declared in its type definition, a Sui object can have dynamic fields
+/// that can be added after the object has been constructed. Unlike ordinary field names
+/// (which are always statically declared identifiers) a dynamic field name can be any value with
+/// the `copy`, `drop`, and `store` abilities, e.g. an integer, a boolean, or a string.
+/// This gives Sui programmers the flexibility to extend objects on-the-fly, and it also serves as a
+/// building block for core collection types like `Table` and `Bag`.
+/// Dynamic fields are stored as child objects of their parent, and are loaded on demand during
+/// execution: they do not need to be passed as transaction inputs.
+module sui::dynamic_field {
+    use sui::object::{Self, UID};
+
+    /// The object already has a dynamic field with this name
+    const EFieldAlreadyExists: u64 = 0;
+
+    /// Cannot load dynamic field, the object does not have a dynamic field with this name
+    const EFieldDoesNotExist: u64 = 1;
+
+    /// The object has a dynamic field with this name, but its value has a different type
+    const EFieldTypeMismatch: u64 = 2;
+
+    /// Failed to serialize the name of the field
+    const EBCSSerializationFailure: u64 = 3;
+
+    /// Internal object used for storing the field and value
+    struct Field<Name: copy + drop + store, Value: store> has key {
+        /// Determined by the hash of the object ID, the field name value and its type,
+        /// i.e. hash(parent.id || name || Name)
+        id: UID,
+        /// The value for the name of this field
+        name: Name,
+        /// The value bound to this field
+        value: Value,
+    }
+
+    /// Adds a dynamic field to the object `object: &mut UID` at field specified by `name: Name`.
+    /// Aborts with `EFieldAlreadyExists` if the object already has that field with that name.
+    public fun add<Name: copy + drop + store, Value: store>(
+        // we use &mut UID in several spots for access control
+        object: &mut UID,
+        name: Name,
+        value: Value,
+    ) {
+        let object_addr = object::uid_to_address(object);
+        let hash = hash_type_and_key(object_addr, name);
+        assert!(!has_child_object(object_addr, hash), EFieldAlreadyExists);
+        let field = Field {
+            id: object::new_uid_from_hash(hash),
+            name,
+            value,
+        };
+        add_child_object(object_addr, field)
+    }
+
+    /// Immutably borrows the `object`s dynamic field with the name specified by `name: Name`.
+    /// Aborts with `EFieldDoesNotExist` if the object does not have a field with that name.
+    /// Aborts with `EFieldTypeMismatch` if the field exists, but the value does not have the
+    /// specified type.
+    public fun borrow<Name: copy + drop + store, Value: store>(
+        object: &UID,
+        name: Name,
+    ): &Value {
+        let object_addr = object::uid_to_address(object);
+        let hash = hash_type_and_key(object_addr, name);
+        let field = borrow_child_object<Field<Name, Value>>(object, hash);
+        &field.value
+    }
+
+    /// Mutably borrows the `object`s dynamic field with the name specified by `name: Name`.
+    /// Aborts with `EFieldDoesNotExist` if the object does not have a field with that name.
+    /// Aborts with `EFieldTypeMismatch` if the field exists, but the value does not have the
+    /// specified type.
+    public fun borrow_mut<Name: copy + drop + store, Value: store>(
+        object: &mut UID,
+        name: Name,
+    ): &mut Value {
+        let object_addr = object::uid_to_address(object);
+        let hash = hash_type_and_key(object_addr, name);
+        let field = borrow_child_object_mut<Field<Name, Value>>(object, hash);
+        &mut field.value
+    }
+
+    /// Removes the `object`s dynamic field with the name specified by `name: Name` and returns the
+    /// bound value.
+    /// Aborts with `EFieldDoesNotExist` if the object does not have a field with that name.
+    /// Aborts with `EFieldTypeMismatch` if the field exists, but the value does not have the
+    /// specified type.
+    public fun remove<Name: copy + drop + store, Value: store>(
+        object: &mut UID,
+        name: Name,
+    ): Value {
+        let object_addr = object::uid_to_address(object);
+        let hash = hash_type_and_key(object_addr, name);
+        let Field { id, name: _, value } = remove_child_object<Field<Name, Value>>(object_addr, hash);
+        object::delete(id);
+        value
+    }
+
+    /// Returns true if and only if the `object` has a dynamic field with the name specified by
+    /// `name: Name` but without specifying the `Value` type
+    public fun exists_<Name: copy + drop + store>(
+        object: &UID,
+        name: Name,
+    ): bool {
+        let object_addr = object::uid_to_address(object);
+        let hash = hash_type_and_key(object_addr, name);
+        has_child_object(object_addr, hash)
+    }
+
+    /// Returns true if and only if the `object` has a dynamic field with the name specified by
+    /// `name: Name` with an assigned value of type `Value`.
+    public fun exists_with_type<Name: copy + drop + store, Value: store>(
+        object: &UID,
+        name: Name,
+    ): bool {
+        let object_addr = object::uid_to_address(object);
+        let hash = hash_type_and_key(object_addr, name);
+        has_child_object_with_ty<Field<Name, Value>>(object_addr, hash)
+    }
+
+    // === internal functions ===
+
+    native fun hash_type_and_key<K: copy + drop + store>(parent: address, k: K): address;
+
+    native fun add_child_object<Child: key>(parent: address, child: Child);
+
+    /// throws `EFieldDoesNotExist` if a child does not exist with that ID
+    /// or throws `EFieldTypeMismatch` if the type does not match
+    /// we need two versions to return a reference or a mutable reference
+    native fun borrow_child_object<Child: key>(object: &UID, id: address): &Child;
+
+    native fun borrow_child_object_mut<Child: key>(object: &mut UID, id: address): &mut Child;
+
+    /// throws `EFieldDoesNotExist` if a child does not exist with that ID
+    /// or throws `EFieldTypeMismatch` if the type does not match
+    native fun remove_child_object<Child: key>(parent: address, id: address): Child;
+
+    native fun has_child_object(parent: address, id: address): bool;
+
+    native fun has_child_object_with_ty<Child: key>(parent: address, id: address): bool;
+}
@@ -5,6 +5,7 @@ module sui::genesis {
     use std::vector;
 
     use sui::balance;
+    use sui::clock;
     use sui::sui;
     use sui::sui_system;
     use sui::tx_context::TxContext;
@@ -26,7 +27,7 @@ module sui::genesis {
 
     /// This function will be explicitly called once at genesis.
     /// It will create a singleton SuiSystemState object, which contains
-    /// all the information we need in the system.
+    /// all the information we need in the system, and the singleton Clock.
     fun create(
         validator_pubkeys: vector<vector<u8>>,
         validator_network_pubkeys: vector<vector<u8>>,
@@ -82,5 +83,6 @@ module sui::genesis {
             INIT_MIN_VALIDATOR_STAKE,
             INIT_STORAGE_GAS_PRICE,
         );
+        clock::create();
     }
 }
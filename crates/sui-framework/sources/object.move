@@ -6,6 +6,8 @@ module sui::object {
     use std::bcs;
     use sui::tx_context::{Self, TxContext};
 
+    friend sui::clock;
+    friend sui::dynamic_field;
     friend sui::sui_system;
     friend sui::transfer;
 
@@ -15,6 +17,9 @@ module sui::object {
     /// The hardcoded ID for the singleton Sui System State Object.
     const SUI_SYSTEM_STATE_OBJECT_ID: address = @0x5;
 
+    /// The hardcoded ID for the singleton Clock Object.
+    const SUI_CLOCK_OBJECT_ID: address = @0x6;
+
     /// An object ID. This is used to reference Sui Objects.
     /// This is *not* guaranteed to be globally unique--anyone can create an `ID` from a `UID` or
     /// from an object, and ID's can be freely copied and dropped.
@@ -61,6 +66,14 @@ module sui::object {
         }
     }
 
+    /// Create the `UID` for the singleton `Clock` object.
+    /// This should only be called once from `clock`.
+    public(friend) fun clock(): UID {
+        UID {
+            id: ID { bytes: SUI_CLOCK_OBJECT_ID },
+        }
+    }
+
     /// Get the inner `ID` of `uid`
     public fun uid_as_inner(uid: &UID): &ID {
         &uid.id
@@ -91,6 +104,14 @@ module sui::object {
         }
     }
 
+    /// Create the `UID` of a dynamic field, derived from its parent and its name.
+    /// This should only be called from `dynamic_field`, which guarantees the uniqueness of `bytes`.
+    public(friend) fun new_uid_from_hash(bytes: address): UID {
+        UID {
+            id: ID { bytes },
+        }
+    }
+
     /// Delete the object and it's `UID`. This is the only way to eliminate a `UID`.
     // This exists to inform Sui of object deletions. When an object
     // gets unpacked, the programmer will have to do something with its
@@ -0,0 +1,143 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+/// Upgrades of Move packages. Publishing a package transfers an `UpgradeCap` for it to the
+/// sender, which is needed to publish new versions of the package. Its owner can restrict the
+/// upgrades it allows, or destroy it to make the package immutable.
+///
+/// A module's one-time witness can also be exchanged for a `Publisher`, which proves authority
+/// over the types defined in that module and the rest of its package.
+module sui::package {
+    use std::ascii::{Self, String};
+    use sui::object::{Self, ID, UID};
+    use sui::transfer;
+    use sui::tx_context::{Self, TxContext};
+    use sui::types;
+
+    /// Struct layouts and public function signatures must be preserved.
+    const COMPATIBLE: u8 = 0;
+    /// New modules, structs and functions may be added, but existing ones must not change.
+    const ADDITIVE: u8 = 128;
+    /// Modules must not change, only the versions of the dependencies they link against.
+    const DEP_ONLY: u8 = 192;
+
+    /// Tried to set a less restrictive policy than currently in place.
+    const ETooPermissive: u64 = 0;
+    /// Tried to claim a `Publisher` with a type that is not a one-time witness.
+    const ENotOneTimeWitness: u64 = 1;
+
+    /// Capability to publish new versions of the package `package`, as allowed by `policy`.
+    /// Created by the runtime when the package is published, and updated on each upgrade.
+    struct UpgradeCap has key, store {
+        id: UID,
+        /// The latest version of the package.
+        package: ID,
+        /// The number of versions of the package, starting at 1.
+        version: u64,
+        /// The kind of upgrades allowed.
+        policy: u8,
+    }
+
+    /// Proof that its owner published the module `module_name` of the package at `package`.
+    /// Claimed with the module's one-time witness, usually in its `init` function.
+    struct Publisher has key, store {
+        id: UID,
+        /// The address of the package, as found in the types it defines.
+        package: address,
+        module_name: String,
+    }
+
+    /// Exchange the one-time witness `otw` for a `Publisher` of the module defining it.
+    public fun claim<OTW: drop>(otw: OTW, ctx: &mut TxContext): Publisher {
+        assert!(types::is_one_time_witness(&otw), ENotOneTimeWitness);
+        let (package, module_name) = types::type_origin<OTW>();
+        Publisher { id: object::new(ctx), package, module_name: ascii::string(module_name) }
+    }
+
+    /// Claim a `Publisher` with `otw` and send it to the transaction sender.
+    public fun claim_and_keep<OTW: drop>(otw: OTW, ctx: &mut TxContext) {
+        transfer::transfer(claim(otw, ctx), tx_context::sender(ctx))
+    }
+
+    /// Destroy `publisher`, giving up the authority it proves.
+    public entry fun burn_publisher(publisher: Publisher) {
+        let Publisher { id, package: _, module_name: _ } = publisher;
+        object::delete(id);
+    }
+
+    /// Whether `T` is defined in the package `publisher` was claimed for.
+    public fun from_package<T>(publisher: &Publisher): bool {
+        let (package, _) = types::type_origin<T>();
+        package == publisher.package
+    }
+
+    /// Whether `T` is defined in the module `publisher` was claimed for.
+    public fun from_module<T>(publisher: &Publisher): bool {
+        let (package, module_name) = types::type_origin<T>();
+        package == publisher.package && ascii::string(module_name) == publisher.module_name
+    }
+
+    /// The address of the package `publisher` was claimed for.
+    public fun published_package(publisher: &Publisher): &address {
+        &publisher.package
+    }
+
+    /// The name of the module `publisher` was claimed for.
+    public fun published_module(publisher: &Publisher): &String {
+        &publisher.module_name
+    }
+
+    /// The ID of the latest version of the package `cap` can upgrade.
+    public fun upgrade_package(cap: &UpgradeCap): ID {
+        cap.package
+    }
+
+    /// The version of the latest version of the package `cap` can upgrade.
+    public fun version(cap: &UpgradeCap): u64 {
+        cap.version
+    }
+
+    /// The kind of upgrades `cap` allows.
+    public fun upgrade_policy(cap: &UpgradeCap): u8 {
+        cap.policy
+    }
+
+    public fun compatible_policy(): u8 {
+        COMPATIBLE
+    }
+
+    public fun additive_policy(): u8 {
+        ADDITIVE
+    }
+
+    public fun dep_only_policy(): u8 {
+        DEP_ONLY
+    }
+
+    /// Only allow upgrades that add to the package.
+    public entry fun only_additive_upgrades(cap: &mut UpgradeCap) {
+        restrict(cap, ADDITIVE)
+    }
+
+    /// Only allow upgrades that change the dependencies of the package.
+    public entry fun only_dep_upgrades(cap: &mut UpgradeCap) {
+        restrict(cap, DEP_ONLY)
+    }
+
+    /// Discard `cap`, making the package immutable.
+    public entry fun make_immutable(cap: UpgradeCap) {
+        let UpgradeCap { id, package: _, version: _, policy: _ } = cap;
+        object::delete(id);
+    }
+
+    fun restrict(cap: &mut UpgradeCap, policy: u8) {
+        assert!(cap.policy <= policy, ETooPermissive);
+        cap.policy = policy;
+    }
+
+    #[test_only]
+    /// Create an `UpgradeCap` for `package` for testing purposes.
+    public fun test_publish(package: ID, ctx: &mut TxContext): UpgradeCap {
+        UpgradeCap { id: object::new(ctx), package, version: 1, policy: COMPATIBLE }
+    }
+}
@@ -0,0 +1,280 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+/// Transfer policies let the publisher of a type require conditions to be met for objects of the
+/// type to be transferred, e.g. paying a royalty or only sending them to approved addresses.
+///
+/// A transfer starts with `new_request`, which takes the object and the payment for it, if any,
+/// and returns a `TransferRequest`. The request cannot be dropped or stored, so it must be
+/// consumed by `confirm_request` in the same transaction, which transfers the object only once
+/// every rule of the type's `TransferPolicy` has added its receipt to the request. Rules that
+/// charge a fee take it out of the payment held by the request, and what is left of the payment
+/// is returned by `confirm_request`, to be passed on to the seller.
+///
+/// A rule is a module defining a witness type `Rule`, which it uses to add itself to a policy
+/// with `add_rule`, to read its configuration with `get_rule`, and to add its receipt to a request
+/// with `add_receipt` once its conditions are met. Only the module defining `Rule` can call these
+/// functions with it.
+///
+/// Objects can only be transferred by the module defining their type unless the type has `store`,
+/// but this module can transfer objects of any type. Declaring a type without `store` and creating
+/// a policy for it therefore makes the policy the only way for others to transfer its objects.
+/// Objects of types with `store` could be transferred without their policy, so policies can only
+/// be created for types without `store`, by the module defining the type, which the Sui bytecode
+/// verifier enforces.
+module sui::transfer_policy {
+    use std::ascii::String;
+    use std::option::{Self, Option};
+    use std::vector;
+    use sui::balance::{Self, Balance};
+    use sui::coin::{Self, Coin};
+    use sui::dynamic_field as df;
+    use sui::event;
+    use sui::object::{Self, ID, UID};
+    use sui::package::{Self, Publisher};
+    use sui::sui::SUI;
+    use sui::transfer;
+    use sui::tx_context::{Self, TxContext};
+    use sui::types;
+    use sui::vec_set::{Self, VecSet};
+
+    /// The `Publisher` was not claimed for the package defining `T`.
+    const ENotOwner: u64 = 0;
+    /// A rule of the policy did not add its receipt to the request.
+    const EPolicyNotSatisfied: u64 = 1;
+    /// The rule is not part of the policy.
+    const EUnknownRule: u64 = 2;
+    /// The rule is already part of the policy.
+    const ERuleAlreadySet: u64 = 3;
+    /// Tried to withdraw more than the policy's balance.
+    const ENotEnough: u64 = 4;
+    /// The `TransferPolicyCap` is for a different policy.
+    const ENotPolicyOwner: u64 = 5;
+    /// Tried to take more than the payment left in the request.
+    const EInsufficientPayment: u64 = 6;
+
+    /// A transfer of `item` to `recipient` waiting on the rules of the `TransferPolicy<T>` to be
+    /// satisfied. Must be consumed by `confirm_request`.
+    struct TransferRequest<T: key> {
+        item: T,
+        recipient: address,
+        /// The payment for `item` in the transfer, if it is a sale, which rules such as royalties
+        /// base their conditions on and take their fees from.
+        paid: Balance<SUI>,
+        /// The type names of the rules that have been satisfied.
+        receipts: VecSet<String>,
+    }
+
+    /// The rules transfers of objects of type `T` must satisfy, and the fees collected by them.
+    struct TransferPolicy<phantom T: key> has key, store {
+        id: UID,
+        balance: Balance<SUI>,
+        /// The type names of the rules, their configurations are stored as dynamic fields.
+        rules: VecSet<String>,
+    }
+
+    /// Capability to change the rules of the policy `policy_id` and withdraw from its balance.
+    struct TransferPolicyCap<phantom T: key> has key, store {
+        id: UID,
+        policy_id: ID,
+    }
+
+    /// The dynamic field key of the configuration of the rule `Rule`.
+    struct RuleKey<phantom Rule: drop> has copy, drop, store {}
+
+    /// Emitted when a `TransferPolicy<T>` is created, so policies can be discovered off chain.
+    struct TransferPolicyCreated<phantom T: key> has copy, drop {
+        id: ID,
+    }
+
+    /// Create a `TransferPolicy<T>` without rules, authorized by the publisher of `T`, and the
+    /// cap to manage it. Can only be called by the module defining `T`, which must not have
+    /// `store`.
+    public fun new<T: key>(
+        publisher: &Publisher,
+        ctx: &mut TxContext,
+    ): (TransferPolicy<T>, TransferPolicyCap<T>) {
+        assert!(package::from_package<T>(publisher), ENotOwner);
+        let id = object::new(ctx);
+        let policy_id = object::uid_to_inner(&id);
+        event::emit(TransferPolicyCreated<T> { id: policy_id });
+        (
+            TransferPolicy { id, balance: balance::zero(), rules: vec_set::empty() },
+            TransferPolicyCap { id: object::new(ctx), policy_id },
+        )
+    }
+
+    /// Create a `TransferPolicy<T>` without rules and share it, sending its cap to the sender.
+    public fun create_and_share<T: key>(publisher: &Publisher, ctx: &mut TxContext) {
+        let (policy, cap) = new<T>(publisher, ctx);
+        transfer::share_object(policy);
+        transfer::transfer(cap, tx_context::sender(ctx));
+    }
+
+    /// Withdraw `amount` from the fees collected by `policy`, or all of them if `amount` is none.
+    public fun withdraw<T: key>(
+        policy: &mut TransferPolicy<T>,
+        cap: &TransferPolicyCap<T>,
+        amount: Option<u64>,
+        ctx: &mut TxContext,
+    ): Coin<SUI> {
+        assert!(object::id(policy) == cap.policy_id, ENotPolicyOwner);
+        let available = balance::value(&policy.balance);
+        let amount = if (option::is_some(&amount)) {
+            let amount = option::destroy_some(amount);
+            assert!(amount <= available, ENotEnough);
+            amount
+        } else {
+            available
+        };
+        coin::take(&mut policy.balance, amount, ctx)
+    }
+
+    /// Withdraw all fees collected by `policy` and send them to the sender.
+    public entry fun withdraw_all<T: key>(
+        policy: &mut TransferPolicy<T>,
+        cap: &TransferPolicyCap<T>,
+        ctx: &mut TxContext,
+    ) {
+        let coin = withdraw(policy, cap, option::none(), ctx);
+        transfer::transfer(coin, tx_context::sender(ctx));
+    }
+
+    // === Transfers ===
+
+    /// Start the transfer of `item` to `recipient`, paid for with `payment`, which is a zero coin
+    /// if the transfer is not a sale. The transfer completes with `confirm_request` once the
+    /// rules of the policy for `T` are satisfied.
+    public fun new_request<T: key>(
+        item: T,
+        recipient: address,
+        payment: Coin<SUI>,
+    ): TransferRequest<T> {
+        TransferRequest {
+            item,
+            recipient,
+            paid: coin::into_balance(payment),
+            receipts: vec_set::empty(),
+        }
+    }
+
+    /// Complete `request`, transferring its item to its recipient, and return what is left of its
+    /// payment after the fees of the rules. Aborts unless every rule of `policy` has added its
+    /// receipt to `request`.
+    public fun confirm_request<T: key>(
+        policy: &TransferPolicy<T>,
+        request: TransferRequest<T>,
+        ctx: &mut TxContext,
+    ): Coin<SUI> {
+        let TransferRequest { item, recipient, paid, receipts } = request;
+        let rules = vec_set::into_keys(policy.rules);
+        let len = vector::length(&rules);
+        let i = 0;
+        while (i < len) {
+            assert!(vec_set::contains(&receipts, vector::borrow(&rules, i)), EPolicyNotSatisfied);
+            i = i + 1;
+        };
+        transfer::transfer(item, recipient);
+        coin::from_balance(paid, ctx)
+    }
+
+    /// The ID of the object being transferred by `request`.
+    public fun item<T: key>(request: &TransferRequest<T>): ID {
+        object::id(&request.item)
+    }
+
+    /// The address `request` transfers its object to.
+    public fun recipient<T: key>(request: &TransferRequest<T>): address {
+        request.recipient
+    }
+
+    /// The payment left in `request`, the amount paid for its object less the fees already taken
+    /// out of it.
+    public fun paid<T: key>(request: &TransferRequest<T>): u64 {
+        balance::value(&request.paid)
+    }
+
+    // === Rules ===
+    //
+    // The functions taking a `Rule` witness can only be called by the module defining `Rule`,
+    // which the Sui bytecode verifier enforces.
+
+    /// Add the rule `Rule` with its configuration `config` to `policy`. Transfers under `policy`
+    /// then require a receipt from `Rule`.
+    public fun add_rule<T: key, Rule: drop, Config: store + drop>(
+        _: Rule,
+        policy: &mut TransferPolicy<T>,
+        cap: &TransferPolicyCap<T>,
+        config: Config,
+    ) {
+        assert!(object::id(policy) == cap.policy_id, ENotPolicyOwner);
+        assert!(!has_rule<T, Rule>(policy), ERuleAlreadySet);
+        df::add(&mut policy.id, RuleKey<Rule> {}, config);
+        vec_set::insert(&mut policy.rules, types::type_name<Rule>());
+    }
+
+    /// The configuration of the rule `Rule` in `policy`.
+    public fun get_rule<T: key, Rule: drop, Config: store + drop>(
+        _: Rule,
+        policy: &TransferPolicy<T>,
+    ): &Config {
+        df::borrow(&policy.id, RuleKey<Rule> {})
+    }
+
+    /// Add `coin` to the balance of `policy`, to pay the fee of the rule `Rule`.
+    public fun add_to_balance<T: key, Rule: drop>(
+        _: Rule,
+        policy: &mut TransferPolicy<T>,
+        coin: Coin<SUI>,
+    ) {
+        assert!(has_rule<T, Rule>(policy), EUnknownRule);
+        coin::put(&mut policy.balance, coin)
+    }
+
+    /// Take `amount` out of the payment held by `request` and add it to the balance of `policy`,
+    /// to pay the fee of the rule `Rule`.
+    public fun take_fee<T: key, Rule: drop>(
+        _: Rule,
+        policy: &mut TransferPolicy<T>,
+        request: &mut TransferRequest<T>,
+        amount: u64,
+    ) {
+        assert!(has_rule<T, Rule>(policy), EUnknownRule);
+        assert!(amount <= balance::value(&request.paid), EInsufficientPayment);
+        balance::join(&mut policy.balance, balance::split(&mut request.paid, amount));
+    }
+
+    /// Record on `request` that the rule `Rule` is satisfied.
+    public fun add_receipt<T: key, Rule: drop>(_: Rule, request: &mut TransferRequest<T>) {
+        let rule = types::type_name<Rule>();
+        if (!vec_set::contains(&request.receipts, &rule)) {
+            vec_set::insert(&mut request.receipts, rule)
+        }
+    }
+
+    /// Whether the rule `Rule` is part of `policy`.
+    public fun has_rule<T: key, Rule: drop>(policy: &TransferPolicy<T>): bool {
+        df::exists_(&policy.id, RuleKey<Rule> {})
+    }
+
+    /// Remove the rule `Rule` from `policy`.
+    public fun remove_rule<T: key, Rule: drop, Config: store + drop>(
+        policy: &mut TransferPolicy<T>,
+        cap: &TransferPolicyCap<T>,
+    ) {
+        assert!(object::id(policy) == cap.policy_id, ENotPolicyOwner);
+        assert!(has_rule<T, Rule>(policy), EUnknownRule);
+        let _: Config = df::remove(&mut policy.id, RuleKey<Rule> {});
+        vec_set::remove(&mut policy.rules, &types::type_name<Rule>());
+    }
+
+    /// The type names of the rules of `policy`.
+    public fun rules<T: key>(policy: &TransferPolicy<T>): &VecSet<String> {
+        &policy.rules
+    }
+
+    /// The fees collected by `policy`.
+    public fun balance<T: key>(policy: &TransferPolicy<T>): u64 {
+        balance::value(&policy.balance)
+    }
+}
@@ -3,9 +3,28 @@
 
 /// Sui types helpers and utilities
 module sui::types {
+    use std::ascii::{Self, String};
+
     // === one-time witness ===
 
     /// Tests if the argument type is a one-time witness, that is a type with only one instantiation
     /// across the entire code base.
     public native fun is_one_time_witness<T: drop>(_: &T): bool;
+
+    // === type origin ===
+
+    /// The address of the package and the name of the module that define the struct type `T`.
+    /// Aborts if `T` is not a struct type. For types from upgraded packages, this is the address of
+    /// the package version that first defined the type.
+    public native fun type_origin<T>(): (address, vector<u8>);
+
+    // === type names ===
+
+    /// The fully qualified name of the type `T`, e.g. `0x2::coin::Coin<0x2::sui::SUI>`, which
+    /// identifies the type uniquely.
+    public fun type_name<T>(): String {
+        ascii::string(type_name_internal<T>())
+    }
+
+    native fun type_name_internal<T>(): vector<u8>;
 }
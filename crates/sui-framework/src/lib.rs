@@ -7,6 +7,7 @@ use move_cli::base::test::UnitTestResult;
 use move_core_types::gas_algebra::InternalGas;
 use move_package::{compilation::compiled_package::CompiledPackage, BuildConfig};
 use move_unit_test::UnitTestingConfig;
+use natives::dynamic_field::{ChildObjectStore, NoChildObjects};
 use num_enum::TryFromPrimitive;
 use once_cell::sync::Lazy;
 use std::path::Path;
@@ -17,6 +18,7 @@ use sui_types::{
 
 pub mod cost_calib;
 pub mod natives;
+pub mod test_profiler;
 
 pub use sui_framework_build::build_move_stdlib_modules as get_move_stdlib_modules;
 pub use sui_framework_build::verify_modules;
@@ -140,6 +142,11 @@ pub fn run_move_unit_tests(
     let config = config
         .unwrap_or_else(|| UnitTestingConfig::default_with_bound(Some(MAX_UNIT_TEST_INSTRUCTIONS)));
 
+    // Dynamic fields need a child object store in every Move session.
+    move_unit_test::extensions::set_extension_hook(Box::new(|extensions| {
+        extensions.add(ChildObjectStore::new(Box::new(NoChildObjects)))
+    }));
+
     move_cli::base::test::run_move_unit_tests(
         path,
         build_config,
@@ -157,9 +164,19 @@ pub fn build_move_package(
     path: &Path,
     build_config: BuildConfig,
 ) -> SuiResult<Vec<CompiledModule>> {
+    let pkg = compile_move_package_with_deps(path, build_config)?;
+    filter_package_modules(&pkg)
+}
+
+/// Given a `path` and a `build_config`, build the package in that path with its dependencies,
+/// checking that it depends on the same framework and stdlib as this binary.
+pub fn compile_move_package_with_deps(
+    path: &Path,
+    build_config: BuildConfig,
+) -> SuiResult<CompiledPackage> {
     let pkg = build_move_package_with_deps(path, build_config)?;
     verify_framework_version(&pkg)?;
-    filter_package_modules(&pkg)
+    Ok(pkg)
 }
 
 /// Version of the framework code that the binary used for compilation expects should be the same as
@@ -262,4 +279,25 @@ mod tests {
             run_move_unit_tests(&path, BuildConfig::default(), None, false).unwrap();
         }
     }
+
+    #[test]
+    #[cfg_attr(msim, ignore)]
+    fn profile_examples_move_unit_tests() {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("../../sui_programmability/examples")
+            .join("basics");
+        let profile =
+            test_profiler::profile_move_unit_tests(&path, BuildConfig::default(), Some("counter"))
+                .unwrap();
+
+        assert!(profile.total() > 0);
+        let stacks = profile.to_folded_stacks();
+        assert!(stacks
+            .lines()
+            .all(|stack| stack.contains("::counter_test::test_counter")));
+        assert!(stacks.contains("::counter_test::test_counter;0x2::test_scenario::begin"));
+        assert!(profile
+            .function_costs()
+            .contains_key("0x2::transfer::share_object"));
+    }
 }
@@ -1,21 +1,36 @@
 // Copyright (c) 2022, Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
+use blake2::{digest::consts::U32, Blake2b};
 use curve25519_dalek_ng::scalar::Scalar;
 use fastcrypto::{
-    bls12381::{BLS12381PublicKey, BLS12381Signature},
+    bls12381::{BLS12381AggregateSignature, BLS12381PublicKey, BLS12381Signature},
     bulletproofs::{BulletproofsRangeProof, PedersenCommitment},
     ed25519::{Ed25519PublicKey, Ed25519Signature},
     secp256k1::{Secp256k1PublicKey, Secp256k1Signature},
-    traits::ToFromBytes,
+    traits::{AggregateAuthenticator, ToFromBytes},
     Verifier,
 };
+use hmac::{Hmac, Mac};
 use move_binary_format::errors::PartialVMResult;
+use move_core_types::gas_algebra::{Byte, InternalGas, InternalGasUnit, NumBytes};
 use move_vm_runtime::native_functions::NativeContext;
 use move_vm_types::{
     loaded_data::runtime_types::Type, natives::function::NativeResult, pop_arg, values::Value,
 };
+use sha2::Sha256;
+use sha3::{Digest, Sha3_256};
 use smallvec::smallvec;
 use std::collections::VecDeque;
+use sui_cost_tables::{
+    natives_tables::{
+        bls12381_aggregate_verify_cost, CRYPTO_BLAKE2B256_COST_EQUATION,
+        CRYPTO_ED25519_VERIFY_COST_EQUATION, CRYPTO_HMAC_SHA3_256_COST_EQUATION,
+        CRYPTO_SECP256R1_VERIFY_COST_EQUATION, CRYPTO_SHA2_256_COST_EQUATION,
+        CRYPTO_SHA3_256_COST_EQUATION,
+    },
+    non_execution_tables::MAXIMUM_TX_GAS,
+    units_types::LinearEquation,
+};
 
 use crate::{legacy_emit_cost, legacy_empty_cost};
 
@@ -326,7 +341,7 @@ pub fn ed25519_verify(
     let public_key_bytes = pop_arg!(args, Vec<u8>);
     let signature_bytes = pop_arg!(args, Vec<u8>);
 
-    let cost = legacy_empty_cost();
+    let cost = input_cost(&CRYPTO_ED25519_VERIFY_COST_EQUATION, msg.len());
 
     let signature = match <Ed25519Signature as ToFromBytes>::from_bytes(&signature_bytes) {
         Ok(signature) => signature,
@@ -343,3 +358,158 @@ pub fn ed25519_verify(
         Err(_) => Ok(NativeResult::ok(cost, smallvec![Value::bool(false)])),
     }
 }
+
+/// The cost of a crypto native reading `input_len` bytes of input. Inputs too large for any
+/// transaction to pay for are charged the maximum transaction gas.
+fn input_cost(equation: &LinearEquation<InternalGasUnit, Byte>, input_len: usize) -> InternalGas {
+    equation
+        .calculate(NumBytes::new(input_len as u64))
+        .unwrap_or(MAXIMUM_TX_GAS)
+}
+
+/// Native implemention of sha2_256 in public Move API, see crypto.move for specifications.
+pub fn sha2_256(
+    _context: &mut NativeContext,
+    ty_args: Vec<Type>,
+    mut args: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(ty_args.is_empty());
+    debug_assert!(args.len() == 1);
+
+    let data = pop_arg!(args, Vec<u8>);
+    let cost = input_cost(&CRYPTO_SHA2_256_COST_EQUATION, data.len());
+    Ok(NativeResult::ok(
+        cost,
+        smallvec![Value::vector_u8(Sha256::digest(data).to_vec())],
+    ))
+}
+
+/// Native implemention of sha3_256 in public Move API, see crypto.move for specifications.
+pub fn sha3_256(
+    _context: &mut NativeContext,
+    ty_args: Vec<Type>,
+    mut args: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(ty_args.is_empty());
+    debug_assert!(args.len() == 1);
+
+    let data = pop_arg!(args, Vec<u8>);
+    let cost = input_cost(&CRYPTO_SHA3_256_COST_EQUATION, data.len());
+    Ok(NativeResult::ok(
+        cost,
+        smallvec![Value::vector_u8(Sha3_256::digest(data).to_vec())],
+    ))
+}
+
+/// Native implemention of blake2b256 in public Move API, see crypto.move for specifications.
+pub fn blake2b256(
+    _context: &mut NativeContext,
+    ty_args: Vec<Type>,
+    mut args: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(ty_args.is_empty());
+    debug_assert!(args.len() == 1);
+
+    let data = pop_arg!(args, Vec<u8>);
+    let cost = input_cost(&CRYPTO_BLAKE2B256_COST_EQUATION, data.len());
+    Ok(NativeResult::ok(
+        cost,
+        smallvec![Value::vector_u8(Blake2b::<U32>::digest(data).to_vec())],
+    ))
+}
+
+/// Native implemention of hmac_sha3_256 in public Move API, see crypto.move for specifications.
+pub fn hmac_sha3_256(
+    _context: &mut NativeContext,
+    ty_args: Vec<Type>,
+    mut args: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(ty_args.is_empty());
+    debug_assert!(args.len() == 2);
+
+    let msg = pop_arg!(args, Vec<u8>);
+    let key = pop_arg!(args, Vec<u8>);
+    let cost = input_cost(&CRYPTO_HMAC_SHA3_256_COST_EQUATION, key.len() + msg.len());
+
+    // HMAC accepts keys of any length, so this cannot fail.
+    let mut mac = <Hmac<Sha3_256> as Mac>::new_from_slice(&key).unwrap();
+    mac.update(&msg);
+    Ok(NativeResult::ok(
+        cost,
+        smallvec![Value::vector_u8(mac.finalize().into_bytes().to_vec())],
+    ))
+}
+
+/// Native implemention of secp256r1_verify in public Move API, see crypto.move for specifications.
+pub fn secp256r1_verify(
+    _context: &mut NativeContext,
+    ty_args: Vec<Type>,
+    mut args: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(ty_args.is_empty());
+    debug_assert!(args.len() == 3);
+
+    let msg = pop_arg!(args, Vec<u8>);
+    let public_key_bytes = pop_arg!(args, Vec<u8>);
+    let signature_bytes = pop_arg!(args, Vec<u8>);
+
+    let cost = input_cost(&CRYPTO_SECP256R1_VERIFY_COST_EQUATION, msg.len());
+
+    // Reject high-S signatures, otherwise anyone could derive a second valid signature (r, n - s)
+    // from a signature they have seen.
+    let signature = match p256::ecdsa::Signature::try_from(signature_bytes.as_slice()) {
+        Ok(signature) if signature.normalize_s().is_none() => signature,
+        _ => return Ok(NativeResult::ok(cost, smallvec![Value::bool(false)])),
+    };
+
+    let public_key = match p256::ecdsa::VerifyingKey::from_sec1_bytes(&public_key_bytes) {
+        Ok(public_key) => public_key,
+        Err(_) => return Ok(NativeResult::ok(cost, smallvec![Value::bool(false)])),
+    };
+
+    match p256::ecdsa::signature::Verifier::verify(&public_key, &msg, &signature) {
+        Ok(_) => Ok(NativeResult::ok(cost, smallvec![Value::bool(true)])),
+        Err(_) => Ok(NativeResult::ok(cost, smallvec![Value::bool(false)])),
+    }
+}
+
+/// Native implemention of bls12381_aggregate_verify in public Move API, see crypto.move for
+/// specifications. Like bls12381_verify_g1_sig, this only works for signatures in G1 and public
+/// keys in G2.
+pub fn bls12381_aggregate_verify(
+    _context: &mut NativeContext,
+    ty_args: Vec<Type>,
+    mut args: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(ty_args.is_empty());
+    debug_assert!(args.len() == 3);
+
+    let msg = pop_arg!(args, Vec<u8>);
+    let public_keys_bytes = pop_arg!(args, Vec<Value>)
+        .into_iter()
+        .map(|public_key| public_key.value_as::<Vec<u8>>())
+        .collect::<PartialVMResult<Vec<_>>>()?;
+    let signature_bytes = pop_arg!(args, Vec<u8>);
+
+    let cost = bls12381_aggregate_verify_cost(public_keys_bytes.len() as u64, msg.len() as u64);
+
+    let signature = match <BLS12381AggregateSignature as ToFromBytes>::from_bytes(&signature_bytes)
+    {
+        Ok(signature) => signature,
+        Err(_) => return Ok(NativeResult::ok(cost, smallvec![Value::bool(false)])),
+    };
+
+    let public_keys = match public_keys_bytes
+        .iter()
+        .map(|public_key| <BLS12381PublicKey as ToFromBytes>::from_bytes(public_key))
+        .collect::<Result<Vec<_>, _>>()
+    {
+        Ok(public_keys) if !public_keys.is_empty() => public_keys,
+        _ => return Ok(NativeResult::ok(cost, smallvec![Value::bool(false)])),
+    };
+
+    match signature.verify(&public_keys, &msg) {
+        Ok(_) => Ok(NativeResult::ok(cost, smallvec![Value::bool(true)])),
+        Err(_) => Ok(NativeResult::ok(cost, smallvec![Value::bool(false)])),
+    }
+}
@@ -0,0 +1,471 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::natives::{get_nested_struct_field, get_object_id};
+use better_any::{Tid, TidAble};
+use move_binary_format::errors::{PartialVMError, PartialVMResult};
+use move_core_types::{
+    account_address::AccountAddress,
+    effects::Op,
+    gas_algebra::{Byte, InternalGas, InternalGasUnit, NumBytes},
+    language_storage::{StructTag, TypeTag},
+    value::MoveTypeLayout,
+    vm_status::StatusCode,
+};
+use move_vm_runtime::native_functions::NativeContext;
+use move_vm_types::{
+    loaded_data::runtime_types::Type,
+    natives::function::NativeResult,
+    pop_arg,
+    values::{GlobalValue, StructRef, Value},
+};
+use sha3::{Digest, Sha3_256};
+use smallvec::smallvec;
+use std::collections::{btree_map::Entry, BTreeMap, VecDeque};
+use sui_cost_tables::{
+    natives_tables::{
+        DYNAMIC_FIELD_ADD_CHILD_OBJECT_COST_EQUATION,
+        DYNAMIC_FIELD_BORROW_CHILD_OBJECT_COST_EQUATION,
+        DYNAMIC_FIELD_HASH_TYPE_AND_KEY_COST_EQUATION,
+        DYNAMIC_FIELD_HAS_CHILD_OBJECT_COST_EQUATION,
+        DYNAMIC_FIELD_REMOVE_CHILD_OBJECT_COST_EQUATION,
+    },
+    non_execution_tables::MAXIMUM_TX_GAS,
+    units_types::LinearEquation,
+};
+use sui_types::{
+    base_types::ObjectID, error::SuiResult, object::Object, storage::ChildObjectResolver,
+};
+
+// Abort codes, must match the constants in dynamic_field.move
+pub const E_FIELD_ALREADY_EXISTS: u64 = 0;
+pub const E_FIELD_DOES_NOT_EXIST: u64 = 1;
+pub const E_FIELD_TYPE_MISMATCH: u64 = 2;
+pub const E_BCS_SERIALIZATION_FAILURE: u64 = 3;
+
+/// A child object accessed during execution, with its owner and type at the time it was
+/// loaded or added.
+struct ChildObject {
+    owner: ObjectID,
+    type_: StructTag,
+    layout: MoveTypeLayout,
+    value: GlobalValue,
+}
+
+/// How a child object changed during execution. `Op::Delete` means the child was removed from
+/// its parent, it is up to the adapter to find out whether it was then deleted, wrapped or
+/// transferred.
+pub struct ChildObjectEffect {
+    pub owner: ObjectID,
+    pub type_: StructTag,
+    pub effect: Op<Vec<u8>>,
+}
+
+/// Native context extension holding the child objects loaded on demand during a Move session.
+/// Child objects are read from the `resolver` the first time they are accessed, and the final
+/// state of every child is returned to the adapter by `into_effects`.
+#[derive(Tid)]
+pub struct ChildObjectStore<'a> {
+    resolver: Box<dyn ChildObjectResolver + 'a>,
+    children: BTreeMap<ObjectID, ChildObject>,
+    /// Child objects read from storage, as they were before execution.
+    loaded: BTreeMap<ObjectID, Object>,
+    /// Bytes read from storage since the last native call, which the call is charged for.
+    bytes_read: usize,
+}
+
+impl<'a> ChildObjectStore<'a> {
+    pub fn new(resolver: Box<dyn ChildObjectResolver + 'a>) -> Self {
+        Self {
+            resolver,
+            children: BTreeMap::new(),
+            loaded: BTreeMap::new(),
+            bytes_read: 0,
+        }
+    }
+
+    /// Returns the child objects loaded from storage, and the changes made to child objects
+    /// during execution.
+    pub fn into_effects(
+        self,
+    ) -> PartialVMResult<(
+        BTreeMap<ObjectID, Object>,
+        BTreeMap<ObjectID, ChildObjectEffect>,
+    )> {
+        let mut effects = BTreeMap::new();
+        for (id, child) in self.children {
+            let serialize = |value: Value| {
+                value.simple_serialize(&child.layout).ok_or_else(|| {
+                    PartialVMError::new(StatusCode::VALUE_SERIALIZATION_ERROR)
+                        .with_message(format!("Cannot serialize child object {id}"))
+                })
+            };
+            let effect = match child.value.into_effect() {
+                None => continue,
+                Some(Op::New(value)) => Op::New(serialize(value)?),
+                Some(Op::Modify(value)) => Op::Modify(serialize(value)?),
+                Some(Op::Delete) => Op::Delete,
+            };
+            effects.insert(
+                id,
+                ChildObjectEffect {
+                    owner: child.owner,
+                    type_: child.type_,
+                    effect,
+                },
+            );
+        }
+        Ok((self.loaded, effects))
+    }
+
+    /// Read `child` from storage, the first time it is accessed.
+    fn fetch(&mut self, parent: &ObjectID, child: &ObjectID) -> PartialVMResult<Option<&Object>> {
+        if !self.loaded.contains_key(child) {
+            match self
+                .resolver
+                .read_child_object(parent, child)
+                .map_err(storage_error)?
+            {
+                Some(object) => {
+                    self.bytes_read += object.object_size_for_gas_metering();
+                    self.loaded.insert(*child, object);
+                }
+                None => return Ok(None),
+            }
+        }
+        Ok(self.loaded.get(child))
+    }
+
+    /// The bytes read from storage since the last call.
+    fn take_bytes_read(&mut self) -> usize {
+        std::mem::take(&mut self.bytes_read)
+    }
+
+    fn exists(&mut self, parent: ObjectID, child: ObjectID) -> PartialVMResult<bool> {
+        if let Some(object) = self.children.get(&child) {
+            return Ok(object.owner == parent && object.value.exists()?);
+        }
+        Ok(self.fetch(&parent, &child)?.is_some())
+    }
+
+    fn exists_with_type(
+        &mut self,
+        parent: ObjectID,
+        child: ObjectID,
+        type_: &StructTag,
+    ) -> PartialVMResult<bool> {
+        if let Some(object) = self.children.get(&child) {
+            return Ok(object.owner == parent
+                && &object.type_ == type_
+                && object.value.exists()?);
+        }
+        Ok(matches!(
+            self.fetch(&parent, &child)?,
+            Some(object) if object.data.type_() == Some(type_)
+        ))
+    }
+
+    fn add(
+        &mut self,
+        parent: ObjectID,
+        child: ObjectID,
+        type_: StructTag,
+        layout: MoveTypeLayout,
+        value: Value,
+    ) -> PartialVMResult<Result<(), u64>> {
+        if !self.children.contains_key(&child) && self.fetch(&parent, &child)?.is_some() {
+            return Ok(Err(E_FIELD_ALREADY_EXISTS));
+        }
+        let object = match self.children.entry(child) {
+            Entry::Vacant(entry) => entry.insert(ChildObject {
+                owner: parent,
+                type_,
+                layout,
+                value: GlobalValue::none(),
+            }),
+            Entry::Occupied(entry) => {
+                let object = entry.into_mut();
+                if object.value.exists()? {
+                    return Ok(Err(E_FIELD_ALREADY_EXISTS));
+                }
+                // The child was removed earlier in this transaction, it can be added back with
+                // a new owner and type.
+                object.owner = parent;
+                object.type_ = type_;
+                object.layout = layout;
+                object
+            }
+        };
+        object.value.move_to(value).map_err(|(e, _)| e)?;
+        Ok(Ok(()))
+    }
+
+    fn get_or_load(
+        &mut self,
+        parent: ObjectID,
+        child: ObjectID,
+        type_: StructTag,
+        layout: MoveTypeLayout,
+    ) -> PartialVMResult<Result<&mut GlobalValue, u64>> {
+        if !self.children.contains_key(&child) {
+            let contents = match self.fetch(&parent, &child)? {
+                None => return Ok(Err(E_FIELD_DOES_NOT_EXIST)),
+                Some(object) => match object.data.try_as_move() {
+                    Some(move_object) if move_object.type_ == type_ => {
+                        move_object.contents().to_vec()
+                    }
+                    _ => return Ok(Err(E_FIELD_TYPE_MISMATCH)),
+                },
+            };
+            let value = Value::simple_deserialize(&contents, &layout).ok_or_else(|| {
+                PartialVMError::new(StatusCode::FAILED_TO_DESERIALIZE_RESOURCE)
+                    .with_message(format!("Cannot deserialize child object {child}"))
+            })?;
+            self.children.insert(
+                child,
+                ChildObject {
+                    owner: parent,
+                    type_: type_.clone(),
+                    layout,
+                    value: GlobalValue::cached(value)?,
+                },
+            );
+        }
+        // unwrap safe because the child was inserted above.
+        let object = self.children.get_mut(&child).unwrap();
+        if object.owner != parent || !object.value.exists()? {
+            return Ok(Err(E_FIELD_DOES_NOT_EXIST));
+        }
+        if object.type_ != type_ {
+            return Ok(Err(E_FIELD_TYPE_MISMATCH));
+        }
+        Ok(Ok(&mut object.value))
+    }
+}
+
+/// Implementation of Move native function
+/// `hash_type_and_key<K: copy + drop + store>(parent: address, k: K): address`
+/// The ID of a dynamic field is derived from its parent, and from the type and value of its key.
+pub fn hash_type_and_key(
+    context: &mut NativeContext,
+    mut ty_args: Vec<Type>,
+    mut args: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(ty_args.len() == 1);
+    debug_assert!(args.len() == 2);
+
+    let k_ty = ty_args.pop().unwrap();
+    let k = args.pop_back().unwrap();
+    let parent = pop_arg!(args, AccountAddress);
+
+    let k_tag = context.type_to_type_tag(&k_ty)?;
+    let k_bytes = match context
+        .type_to_type_layout(&k_ty)?
+        .and_then(|layout| k.simple_serialize(&layout))
+    {
+        Some(bytes) => bytes,
+        None => {
+            let cost = native_cost(&DYNAMIC_FIELD_HASH_TYPE_AND_KEY_COST_EQUATION, 0);
+            return Ok(NativeResult::err(cost, E_BCS_SERIALIZATION_FAILURE));
+        }
+    };
+    // unwrap safe because type tags are always serializable.
+    let k_tag_bytes = bcs::to_bytes(&k_tag).unwrap();
+    let cost = native_cost(
+        &DYNAMIC_FIELD_HASH_TYPE_AND_KEY_COST_EQUATION,
+        k_bytes.len() + k_tag_bytes.len(),
+    );
+
+    let mut hasher = Sha3_256::default();
+    hasher.update(parent.to_vec());
+    hasher.update(k_bytes);
+    hasher.update(k_tag_bytes);
+    let hash = hasher.finalize();
+    // unwrap safe because the hash is longer than an address.
+    let id = AccountAddress::from_bytes(&hash[..AccountAddress::LENGTH]).unwrap();
+
+    Ok(NativeResult::ok(cost, smallvec![Value::address(id)]))
+}
+
+/// Implementation of Move native function
+/// `add_child_object<Child: key>(parent: address, child: Child)`
+pub fn add_child_object(
+    context: &mut NativeContext,
+    mut ty_args: Vec<Type>,
+    mut args: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(ty_args.len() == 1);
+    debug_assert!(args.len() == 2);
+
+    let child_ty = ty_args.pop().unwrap();
+    let child = args.pop_back().unwrap();
+    let parent = pop_arg!(args, AccountAddress);
+
+    let (type_, layout) = child_type_info(context, &child_ty)?;
+    let child_id = get_object_id(child.copy_value()?)?.value_as::<AccountAddress>()?;
+    let store = context.extensions_mut().get_mut::<ChildObjectStore>();
+    let result = store.add(parent.into(), child_id.into(), type_, layout, child)?;
+    // Adding a child reads it from storage, to check that it does not exist yet.
+    let cost = native_cost(
+        &DYNAMIC_FIELD_ADD_CHILD_OBJECT_COST_EQUATION,
+        store.take_bytes_read(),
+    );
+    match result {
+        Ok(()) => Ok(NativeResult::ok(cost, smallvec![])),
+        Err(code) => Ok(NativeResult::err(cost, code)),
+    }
+}
+
+/// Implementation of Move native functions
+/// `borrow_child_object<Child: key>(object: &UID, id: address): &Child` and
+/// `borrow_child_object_mut<Child: key>(object: &mut UID, id: address): &mut Child`
+pub fn borrow_child_object(
+    context: &mut NativeContext,
+    mut ty_args: Vec<Type>,
+    mut args: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(ty_args.len() == 1);
+    debug_assert!(args.len() == 2);
+
+    let child_ty = ty_args.pop().unwrap();
+    let child_id = pop_arg!(args, AccountAddress);
+    let parent_uid = pop_arg!(args, StructRef).read_ref()?;
+    // UID { id: ID { bytes: address } }
+    let parent = get_nested_struct_field(parent_uid, &[0, 0])?.value_as::<AccountAddress>()?;
+
+    let (type_, layout) = child_type_info(context, &child_ty)?;
+    let store = context.extensions_mut().get_mut::<ChildObjectStore>();
+    let result = match store.get_or_load(parent.into(), child_id.into(), type_, layout)? {
+        Ok(global) => Ok(global.borrow_global()?),
+        Err(code) => Err(code),
+    };
+    let cost = native_cost(
+        &DYNAMIC_FIELD_BORROW_CHILD_OBJECT_COST_EQUATION,
+        store.take_bytes_read(),
+    );
+    match result {
+        Ok(child) => Ok(NativeResult::ok(cost, smallvec![child])),
+        Err(code) => Ok(NativeResult::err(cost, code)),
+    }
+}
+
+/// Implementation of Move native function
+/// `remove_child_object<Child: key>(parent: address, id: address): Child`
+pub fn remove_child_object(
+    context: &mut NativeContext,
+    mut ty_args: Vec<Type>,
+    mut args: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(ty_args.len() == 1);
+    debug_assert!(args.len() == 2);
+
+    let child_ty = ty_args.pop().unwrap();
+    let child_id = pop_arg!(args, AccountAddress);
+    let parent = pop_arg!(args, AccountAddress);
+
+    let (type_, layout) = child_type_info(context, &child_ty)?;
+    let store = context.extensions_mut().get_mut::<ChildObjectStore>();
+    let result = match store.get_or_load(parent.into(), child_id.into(), type_, layout)? {
+        Ok(global) => Ok(global.move_from()?),
+        Err(code) => Err(code),
+    };
+    let cost = native_cost(
+        &DYNAMIC_FIELD_REMOVE_CHILD_OBJECT_COST_EQUATION,
+        store.take_bytes_read(),
+    );
+    match result {
+        Ok(child) => Ok(NativeResult::ok(cost, smallvec![child])),
+        Err(code) => Ok(NativeResult::err(cost, code)),
+    }
+}
+
+/// Implementation of Move native function
+/// `has_child_object(parent: address, id: address): bool`
+pub fn has_child_object(
+    context: &mut NativeContext,
+    ty_args: Vec<Type>,
+    mut args: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(ty_args.is_empty());
+    debug_assert!(args.len() == 2);
+
+    let child_id = pop_arg!(args, AccountAddress);
+    let parent = pop_arg!(args, AccountAddress);
+
+    let store = context.extensions_mut().get_mut::<ChildObjectStore>();
+    let exists = store.exists(parent.into(), child_id.into())?;
+    let cost = native_cost(
+        &DYNAMIC_FIELD_HAS_CHILD_OBJECT_COST_EQUATION,
+        store.take_bytes_read(),
+    );
+    Ok(NativeResult::ok(cost, smallvec![Value::bool(exists)]))
+}
+
+/// Implementation of Move native function
+/// `has_child_object_with_ty<Child: key>(parent: address, id: address): bool`
+pub fn has_child_object_with_ty(
+    context: &mut NativeContext,
+    mut ty_args: Vec<Type>,
+    mut args: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(ty_args.len() == 1);
+    debug_assert!(args.len() == 2);
+
+    let child_ty = ty_args.pop().unwrap();
+    let child_id = pop_arg!(args, AccountAddress);
+    let parent = pop_arg!(args, AccountAddress);
+
+    let (type_, _) = child_type_info(context, &child_ty)?;
+    let store = context.extensions_mut().get_mut::<ChildObjectStore>();
+    let exists = store.exists_with_type(parent.into(), child_id.into(), &type_)?;
+    let cost = native_cost(
+        &DYNAMIC_FIELD_HAS_CHILD_OBJECT_COST_EQUATION,
+        store.take_bytes_read(),
+    );
+    Ok(NativeResult::ok(cost, smallvec![Value::bool(exists)]))
+}
+
+/// The cost of a dynamic field native, given the number of bytes it hashed or read from storage.
+fn native_cost(equation: &LinearEquation<InternalGasUnit, Byte>, num_bytes: usize) -> InternalGas {
+    equation
+        .calculate(NumBytes::new(num_bytes as u64))
+        .unwrap_or(MAXIMUM_TX_GAS)
+}
+
+fn child_type_info(
+    context: &NativeContext,
+    ty: &Type,
+) -> PartialVMResult<(StructTag, MoveTypeLayout)> {
+    let type_ = match context.type_to_type_tag(ty)? {
+        TypeTag::Struct(type_) => type_,
+        _ => {
+            return Err(
+                PartialVMError::new(StatusCode::UNKNOWN_INVARIANT_VIOLATION_ERROR)
+                    .with_message("Child objects must be structs".to_string()),
+            )
+        }
+    };
+    let layout = context.type_to_type_layout(ty)?.ok_or_else(|| {
+        PartialVMError::new(StatusCode::UNKNOWN_INVARIANT_VIOLATION_ERROR)
+            .with_message(format!("Cannot compute the layout of {type_}"))
+    })?;
+    Ok((type_, layout))
+}
+
+fn storage_error(e: sui_types::error::SuiError) -> PartialVMError {
+    PartialVMError::new(StatusCode::STORAGE_ERROR).with_message(e.to_string())
+}
+
+/// A resolver without any child object in storage. Child objects only live for the duration of
+/// a single Move unit test.
+pub struct NoChildObjects;
+
+impl ChildObjectResolver for NoChildObjects {
+    fn read_child_object(
+        &self,
+        _parent: &ObjectID,
+        _child: &ObjectID,
+    ) -> SuiResult<Option<Object>> {
+        Ok(None)
+    }
+}
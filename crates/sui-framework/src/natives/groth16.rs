@@ -0,0 +1,213 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use ark_bls12_381::Bls12_381;
+use ark_bn254::Bn254;
+use ark_ec::{AffineCurve, PairingEngine};
+use ark_groth16::{PreparedVerifyingKey, Proof, VerifyingKey};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use move_binary_format::errors::PartialVMResult;
+use move_core_types::gas_algebra::NumBytes;
+use move_vm_runtime::native_functions::NativeContext;
+use move_vm_types::{
+    loaded_data::runtime_types::Type, natives::function::NativeResult, pop_arg, values::Value,
+};
+use smallvec::smallvec;
+use std::collections::VecDeque;
+use sui_cost_tables::{
+    natives_tables::{
+        groth16_verify_cost, CRYPTO_GROTH16_PREPARE_VERIFYING_KEY_COST_EQUATION,
+        CRYPTO_GROTH16_VERIFY_BLS12381_COST_FIXED, CRYPTO_GROTH16_VERIFY_BLS12381_COST_PER_INPUT,
+        CRYPTO_GROTH16_VERIFY_BN254_COST_FIXED, CRYPTO_GROTH16_VERIFY_BN254_COST_PER_INPUT,
+    },
+    non_execution_tables::MAXIMUM_TX_GAS,
+};
+
+pub const INVALID_VERIFYING_KEY: u64 = 0;
+pub const INVALID_CURVE: u64 = 1;
+pub const INVALID_PUBLIC_INPUTS: u64 = 2;
+pub const INVALID_PROOF: u64 = 3;
+
+/// Curve identifiers, matching the ones in groth16.move.
+const BLS12381: u8 = 0;
+const BN254: u8 = 1;
+
+/// The size of a serialized scalar, the public inputs are a concatenation of these. This is the
+/// same for both supported curves.
+const SCALAR_SIZE: usize = 32;
+
+/// The size of the length prefix of a serialized vector.
+const LENGTH_PREFIX_SIZE: usize = 8;
+
+/// Native implemention of prepare_verifying_key in public Move API, see groth16.move for
+/// specifications.
+pub fn prepare_verifying_key_internal(
+    _context: &mut NativeContext,
+    ty_args: Vec<Type>,
+    mut args: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(ty_args.is_empty());
+    debug_assert!(args.len() == 2);
+
+    let verifying_key = pop_arg!(args, Vec<u8>);
+    let curve = pop_arg!(args, u8);
+
+    let cost = CRYPTO_GROTH16_PREPARE_VERIFYING_KEY_COST_EQUATION
+        .calculate(NumBytes::new(verifying_key.len() as u64))
+        .unwrap_or(MAXIMUM_TX_GAS);
+
+    let prepared = match curve {
+        BLS12381 => prepare::<Bls12_381>(&verifying_key),
+        BN254 => prepare::<Bn254>(&verifying_key),
+        _ => return Ok(NativeResult::err(cost, INVALID_CURVE)),
+    };
+
+    match prepared {
+        Some([vk_gamma_abc_g1, alpha_g1_beta_g2, gamma_g2_neg_pc, delta_g2_neg_pc]) => {
+            Ok(NativeResult::ok(
+                cost,
+                smallvec![
+                    Value::vector_u8(vk_gamma_abc_g1),
+                    Value::vector_u8(alpha_g1_beta_g2),
+                    Value::vector_u8(gamma_g2_neg_pc),
+                    Value::vector_u8(delta_g2_neg_pc),
+                ],
+            ))
+        }
+        None => Ok(NativeResult::err(cost, INVALID_VERIFYING_KEY)),
+    }
+}
+
+/// Native implemention of verify_groth16_proof in public Move API, see groth16.move for
+/// specifications.
+pub fn verify_groth16_proof_internal(
+    _context: &mut NativeContext,
+    ty_args: Vec<Type>,
+    mut args: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(ty_args.is_empty());
+    debug_assert!(args.len() == 7);
+
+    let proof_points = pop_arg!(args, Vec<u8>);
+    let public_proof_inputs = pop_arg!(args, Vec<u8>);
+    let delta_g2_neg_pc = pop_arg!(args, Vec<u8>);
+    let gamma_g2_neg_pc = pop_arg!(args, Vec<u8>);
+    let alpha_g1_beta_g2 = pop_arg!(args, Vec<u8>);
+    let vk_gamma_abc_g1 = pop_arg!(args, Vec<u8>);
+    let curve = pop_arg!(args, u8);
+
+    let num_inputs = ((public_proof_inputs.len() + SCALAR_SIZE - 1) / SCALAR_SIZE) as u64;
+    let prepared_verifying_key: [&[u8]; 4] = [
+        &vk_gamma_abc_g1,
+        &alpha_g1_beta_g2,
+        &gamma_g2_neg_pc,
+        &delta_g2_neg_pc,
+    ];
+    let bls12381_cost = groth16_verify_cost(
+        CRYPTO_GROTH16_VERIFY_BLS12381_COST_FIXED,
+        CRYPTO_GROTH16_VERIFY_BLS12381_COST_PER_INPUT,
+        num_inputs,
+    );
+    let (cost, verified) = match curve {
+        BLS12381 => (
+            bls12381_cost,
+            verify::<Bls12_381>(prepared_verifying_key, &public_proof_inputs, &proof_points),
+        ),
+        BN254 => (
+            groth16_verify_cost(
+                CRYPTO_GROTH16_VERIFY_BN254_COST_FIXED,
+                CRYPTO_GROTH16_VERIFY_BN254_COST_PER_INPUT,
+                num_inputs,
+            ),
+            verify::<Bn254>(prepared_verifying_key, &public_proof_inputs, &proof_points),
+        ),
+        // Charged like the most expensive curve, so that failing is never cheaper than verifying.
+        _ => return Ok(NativeResult::err(bls12381_cost, INVALID_CURVE)),
+    };
+
+    match verified {
+        Ok(verified) => Ok(NativeResult::ok(cost, smallvec![Value::bool(verified)])),
+        Err(code) => Ok(NativeResult::err(cost, code)),
+    }
+}
+
+/// Prepares the serialized verifying key `verifying_key` for verification, returning the
+/// serialized parts of the prepared key that `verify` needs. `None` if the key is invalid.
+fn prepare<E: PairingEngine>(verifying_key: &[u8]) -> Option<[Vec<u8>; 4]> {
+    let vk = VerifyingKey::<E>::deserialize(verifying_key).ok()?;
+    let pvk = ark_groth16::prepare_verifying_key(&vk);
+
+    let mut vk_gamma_abc_g1 = Vec::new();
+    vk.gamma_abc_g1.serialize(&mut vk_gamma_abc_g1).ok()?;
+    let mut alpha_g1_beta_g2 = Vec::new();
+    pvk.alpha_g1_beta_g2.serialize(&mut alpha_g1_beta_g2).ok()?;
+    // The pairing-friendly precomputations of these points are recomputed on verification, which
+    // is cheap compared to the pairing computed for `alpha_g1_beta_g2`.
+    let mut gamma_g2_neg_pc = Vec::new();
+    (-vk.gamma_g2).serialize(&mut gamma_g2_neg_pc).ok()?;
+    let mut delta_g2_neg_pc = Vec::new();
+    (-vk.delta_g2).serialize(&mut delta_g2_neg_pc).ok()?;
+
+    Some([
+        vk_gamma_abc_g1,
+        alpha_g1_beta_g2,
+        gamma_g2_neg_pc,
+        delta_g2_neg_pc,
+    ])
+}
+
+/// Verifies `proof_points` against `public_proof_inputs` with the parts of a prepared verifying
+/// key returned by `prepare`. Returns the abort code to fail with if any of them are invalid.
+fn verify<E: PairingEngine>(
+    [vk_gamma_abc_g1, alpha_g1_beta_g2, gamma_g2_neg_pc, delta_g2_neg_pc]: [&[u8]; 4],
+    public_proof_inputs: &[u8],
+    proof_points: &[u8],
+) -> Result<bool, u64> {
+    if public_proof_inputs.len() % SCALAR_SIZE != 0 {
+        return Err(INVALID_PUBLIC_INPUTS);
+    }
+
+    // The cost is charged per public input, so check that the key has one point more than there
+    // are public inputs before decoding any of its points.
+    let num_points = vk_gamma_abc_g1
+        .get(..LENGTH_PREFIX_SIZE)
+        .and_then(|prefix| u64::deserialize(prefix).ok())
+        .ok_or(INVALID_VERIFYING_KEY)?;
+    if num_points != (public_proof_inputs.len() / SCALAR_SIZE + 1) as u64 {
+        return Err(INVALID_PUBLIC_INPUTS);
+    }
+    let point_size = E::G1Affine::prime_subgroup_generator().serialized_size();
+    if vk_gamma_abc_g1.len() != LENGTH_PREFIX_SIZE + num_points as usize * point_size {
+        return Err(INVALID_VERIFYING_KEY);
+    }
+
+    let gamma_abc_g1 =
+        Vec::<E::G1Affine>::deserialize(vk_gamma_abc_g1).map_err(|_| INVALID_VERIFYING_KEY)?;
+    let alpha_g1_beta_g2 =
+        E::Fqk::deserialize(alpha_g1_beta_g2).map_err(|_| INVALID_VERIFYING_KEY)?;
+    let gamma_g2_neg =
+        E::G2Affine::deserialize(gamma_g2_neg_pc).map_err(|_| INVALID_VERIFYING_KEY)?;
+    let delta_g2_neg =
+        E::G2Affine::deserialize(delta_g2_neg_pc).map_err(|_| INVALID_VERIFYING_KEY)?;
+
+    let public_inputs = public_proof_inputs
+        .chunks(SCALAR_SIZE)
+        .map(E::Fr::deserialize)
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|_| INVALID_PUBLIC_INPUTS)?;
+
+    let proof = Proof::<E>::deserialize(proof_points).map_err(|_| INVALID_PROOF)?;
+
+    // Verification only uses `gamma_abc_g1` out of the verifying key itself.
+    let pvk = PreparedVerifyingKey {
+        vk: VerifyingKey {
+            gamma_abc_g1,
+            ..Default::default()
+        },
+        alpha_g1_beta_g2,
+        gamma_g2_neg_pc: gamma_g2_neg.into(),
+        delta_g2_neg_pc: delta_g2_neg.into(),
+    };
+
+    ark_groth16::verify_proof(&pvk, &proof, &public_inputs).map_err(|_| INVALID_PUBLIC_INPUTS)
+}
@@ -2,7 +2,9 @@
 // SPDX-License-Identifier: Apache-2.0
 
 mod crypto;
+pub mod dynamic_field;
 mod event;
+mod groth16;
 mod object;
 mod test_scenario;
 mod transfer;
@@ -27,6 +29,24 @@ pub fn all_natives(
     let sui_natives: &[(&str, &str, NativeFunction)] = &[
         ("crypto", "ecrecover", make_native!(crypto::ecrecover)),
         ("crypto", "keccak256", make_native!(crypto::keccak256)),
+        ("crypto", "sha2_256", make_native!(crypto::sha2_256)),
+        ("crypto", "sha3_256", make_native!(crypto::sha3_256)),
+        ("crypto", "blake2b256", make_native!(crypto::blake2b256)),
+        (
+            "crypto",
+            "hmac_sha3_256",
+            make_native!(crypto::hmac_sha3_256),
+        ),
+        (
+            "crypto",
+            "secp256r1_verify",
+            make_native!(crypto::secp256r1_verify),
+        ),
+        (
+            "crypto",
+            "bls12381_aggregate_verify",
+            make_native!(crypto::bls12381_aggregate_verify),
+        ),
         (
             "crypto",
             "secp256k1_verify",
@@ -37,7 +57,52 @@ pub fn all_natives(
             "bls12381_verify_g1_sig",
             make_native!(crypto::bls12381_verify_g1_sig),
         ),
+        (
+            "dynamic_field",
+            "hash_type_and_key",
+            make_native!(dynamic_field::hash_type_and_key),
+        ),
+        (
+            "dynamic_field",
+            "add_child_object",
+            make_native!(dynamic_field::add_child_object),
+        ),
+        (
+            "dynamic_field",
+            "borrow_child_object",
+            make_native!(dynamic_field::borrow_child_object),
+        ),
+        (
+            "dynamic_field",
+            "borrow_child_object_mut",
+            make_native!(dynamic_field::borrow_child_object),
+        ),
+        (
+            "dynamic_field",
+            "remove_child_object",
+            make_native!(dynamic_field::remove_child_object),
+        ),
+        (
+            "dynamic_field",
+            "has_child_object",
+            make_native!(dynamic_field::has_child_object),
+        ),
+        (
+            "dynamic_field",
+            "has_child_object_with_ty",
+            make_native!(dynamic_field::has_child_object_with_ty),
+        ),
         ("event", "emit", make_native!(event::emit)),
+        (
+            "groth16",
+            "prepare_verifying_key_internal",
+            make_native!(groth16::prepare_verifying_key_internal),
+        ),
+        (
+            "groth16",
+            "verify_groth16_proof_internal",
+            make_native!(groth16::verify_groth16_proof_internal),
+        ),
         (
             "object",
             "bytes_to_address",
@@ -145,6 +210,12 @@ pub fn all_natives(
             "is_one_time_witness",
             make_native!(types::is_one_time_witness),
         ),
+        ("types", "type_origin", make_native!(types::type_origin)),
+        (
+            "types",
+            "type_name_internal",
+            make_native!(types::type_name_internal),
+        ),
     ];
     sui_natives
         .iter()
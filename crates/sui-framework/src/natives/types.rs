@@ -3,13 +3,16 @@
 
 use crate::legacy_length_cost;
 use move_binary_format::errors::PartialVMResult;
-use move_core_types::language_storage::TypeTag;
+use move_core_types::{gas_algebra::NumBytes, language_storage::TypeTag};
 use move_vm_runtime::native_functions::NativeContext;
 use move_vm_types::{
     loaded_data::runtime_types::Type, natives::function::NativeResult, values::Value,
 };
 use smallvec::smallvec;
 use std::collections::VecDeque;
+use sui_cost_tables::{
+    natives_tables::TYPES_TYPE_NAME_COST_EQUATION, non_execution_tables::MAXIMUM_TX_GAS,
+};
 
 pub fn is_one_time_witness(
     context: &mut NativeContext,
@@ -36,3 +39,55 @@ pub fn is_one_time_witness(
         )],
     ))
 }
+
+pub const NOT_A_STRUCT_TYPE: u64 = 0;
+
+pub fn type_origin(
+    context: &mut NativeContext,
+    mut ty_args: Vec<Type>,
+    args: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(ty_args.len() == 1);
+    debug_assert!(args.is_empty());
+
+    // unwrap safe because the interface of native function guarantees it.
+    let type_tag = context.type_to_type_tag(&ty_args.pop().unwrap())?;
+
+    // TODO: what should the cost of this be?
+    let cost = legacy_length_cost();
+
+    // Types defined in upgraded packages keep the address of the package that first defined them,
+    // so the address returned here is stable across upgrades.
+    match type_tag {
+        TypeTag::Struct(struct_tag) => Ok(NativeResult::ok(
+            cost,
+            smallvec![
+                Value::address(struct_tag.address),
+                Value::vector_u8(struct_tag.module.as_bytes().to_vec()),
+            ],
+        )),
+        _ => Ok(NativeResult::err(cost, NOT_A_STRUCT_TYPE)),
+    }
+}
+
+pub fn type_name_internal(
+    context: &mut NativeContext,
+    mut ty_args: Vec<Type>,
+    args: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(ty_args.len() == 1);
+    debug_assert!(args.is_empty());
+
+    // unwrap safe because the interface of native function guarantees it.
+    let type_tag = context.type_to_type_tag(&ty_args.pop().unwrap())?;
+    let type_name = type_tag.to_string().into_bytes();
+
+    let cost = TYPES_TYPE_NAME_COST_EQUATION
+        .calculate(NumBytes::new(type_name.len() as u64))
+        .unwrap_or(MAXIMUM_TX_GAS);
+
+    Ok(NativeResult::ok(
+        cost,
+        smallvec![Value::vector_u8(type_name)],
+    ))
+}
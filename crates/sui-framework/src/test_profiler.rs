@@ -0,0 +1,125 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Profiling of the gas used by Move unit tests.
+//!
+//! The Move unit-test runner meters tests with its own gas status, which cannot be replaced from
+//! Sui. Tests are profiled by executing the package's test plan again on a VM with the Sui natives,
+//! metered by a `SuiGasStatus` with profiling enabled, as transactions are.
+
+use crate::natives::{
+    self,
+    dynamic_field::{ChildObjectStore, NoChildObjects},
+};
+use move_compiler::{
+    diagnostics,
+    unit_test::{plan_builder::construct_test_plan, TestPlan},
+    PASS_CFGIR,
+};
+use move_core_types::identifier::IdentStr;
+use move_package::{compilation::build_plan::BuildPlan, BuildConfig};
+use move_vm_runtime::{move_vm::MoveVM, native_extensions::NativeContextExtensions};
+use move_vm_test_utils::InMemoryStorage;
+use std::path::Path;
+use sui_types::{
+    gas::{GasProfile, SuiGasStatus, MAX_GAS_BUDGET},
+    MOVE_STDLIB_ADDRESS, SUI_FRAMEWORK_ADDRESS,
+};
+
+/// Execute the unit tests of the package in `path` whose name contains `filter`, and return where
+/// their gas went. Each test is the outermost frame of the stacks it charged gas in. Tests that
+/// abort, e.g. because they are expected to fail, are recorded up to the abort.
+pub fn profile_move_unit_tests(
+    path: &Path,
+    build_config: BuildConfig,
+    filter: Option<&str>,
+) -> anyhow::Result<GasProfile> {
+    let test_plan = build_test_plan(path, build_config)?;
+
+    let mut storage = InMemoryStorage::new();
+    for info in test_plan.module_info.values() {
+        let mut bytes = Vec::new();
+        info.module.serialize(&mut bytes)?;
+        storage.publish_or_overwrite_module(info.module.self_id(), bytes);
+    }
+    let vm = MoveVM::new(natives::all_natives(
+        MOVE_STDLIB_ADDRESS,
+        SUI_FRAMEWORK_ADDRESS,
+    ))?;
+
+    let mut profile = GasProfile::new();
+    for (module_id, module_plan) in &test_plan.module_tests {
+        for (test_name, test_case) in &module_plan.tests {
+            let qualified_name = format!("{}::{}", module_id.short_str_lossless(), test_name);
+            if matches!(filter, Some(filter) if !qualified_name.contains(filter)) {
+                continue;
+            }
+            let args = test_case
+                .arguments
+                .iter()
+                .map(|arg| {
+                    arg.simple_serialize().ok_or_else(|| {
+                        anyhow::anyhow!("Cannot serialize the arguments of {qualified_name}")
+                    })
+                })
+                .collect::<anyhow::Result<Vec<_>>>()?;
+
+            // Dynamic fields need a child object store in every Move session.
+            let mut extensions = NativeContextExtensions::default();
+            extensions.add(ChildObjectStore::new(Box::new(NoChildObjects)));
+            let mut session = vm.new_session_with_extensions(&storage, extensions);
+
+            let mut gas_status = SuiGasStatus::new_with_budget(*MAX_GAS_BUDGET, 1.into(), 1.into());
+            gas_status.enable_profiling();
+            let move_gas_status = gas_status.get_move_gas_status();
+            if let Some(test_profile) = move_gas_status.profile_mut() {
+                test_profile.enter(qualified_name.clone());
+            }
+            // The outcome of the test is reported by the Move unit-test runner.
+            let _ = session.execute_function_bypass_visibility(
+                module_id,
+                IdentStr::new(test_name)?,
+                vec![],
+                args,
+                move_gas_status,
+            );
+            if let Some(test_profile) = gas_status.take_gas_profile() {
+                profile.merge(test_profile);
+            }
+        }
+    }
+    Ok(profile)
+}
+
+/// Compile the package in `path` in test mode, collecting its unit tests like the Move unit-test
+/// runner does.
+fn build_test_plan(path: &Path, mut build_config: BuildConfig) -> anyhow::Result<TestPlan> {
+    build_config.dev_mode = true;
+    build_config.test_mode = true;
+
+    let resolution_graph = build_config.resolution_graph_for_package(path, &mut std::io::sink())?;
+    let root_package = resolution_graph.root_package.package.name;
+    let build_plan = BuildPlan::create(resolution_graph)?;
+
+    let mut test_plan = None;
+    build_plan.compile_with_driver(&mut std::io::sink(), |compiler| {
+        let (files, comments_and_compiler_res) = compiler.run::<PASS_CFGIR>()?;
+        let (_, compiler) =
+            diagnostics::unwrap_or_report_diagnostics(&files, comments_and_compiler_res);
+        let (mut compiler, cfgir) = compiler.into_ast();
+        let built_test_plan =
+            construct_test_plan(compiler.compilation_env(), Some(root_package), &cfgir);
+        let compilation_result = compiler.at_cfgir(cfgir).build();
+        let (units, _) = diagnostics::unwrap_or_report_diagnostics(&files, compilation_result);
+        test_plan = Some((built_test_plan, files.clone(), units.clone()));
+        Ok((files, units))
+    })?;
+
+    let (module_tests, files, units) =
+        test_plan.ok_or_else(|| anyhow::anyhow!("Package was not compiled"))?;
+    Ok(TestPlan::new(
+        module_tests.unwrap_or_default(),
+        files,
+        units,
+    ))
+}
@@ -0,0 +1,59 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+#[test_only]
+module sui::bag_tests {
+    use sui::bag::{Self, add};
+    use sui::tx_context;
+
+    #[test]
+    fun simple_all_functions() {
+        let ctx = tx_context::dummy();
+        let bag = bag::new(&mut ctx);
+        // add fields with different types
+        add(&mut bag, b"hello", 0u64);
+        add(&mut bag, 1u8, true);
+        assert!(bag::length(&bag) == 2, 0);
+        // check they exist
+        assert!(bag::contains(&bag, b"hello"), 0);
+        assert!(bag::contains_with_type<vector<u8>, u64>(&bag, b"hello"), 0);
+        assert!(!bag::contains_with_type<vector<u8>, bool>(&bag, b"hello"), 0);
+        assert!(bag::contains_with_type<u8, bool>(&bag, 1), 0);
+        // check the values
+        assert!(*bag::borrow(&bag, b"hello") == 0u64, 0);
+        assert!(*bag::borrow(&bag, 1u8), 0);
+        // mutate them
+        *bag::borrow_mut(&mut bag, b"hello") = 42u64;
+        *bag::borrow_mut(&mut bag, 1u8) = false;
+        // check the new value
+        assert!(*bag::borrow(&bag, b"hello") == 42u64, 0);
+        assert!(!*bag::borrow(&bag, 1u8), 0);
+        // remove the value and check it
+        assert!(bag::remove(&mut bag, b"hello") == 42u64, 0);
+        assert!(!bag::remove<u8, bool>(&mut bag, 1), 0);
+        // verify that they are not there
+        assert!(!bag::contains(&bag, b"hello"), 0);
+        assert!(!bag::contains(&bag, 1u8), 0);
+        assert!(bag::is_empty(&bag), 0);
+        bag::destroy_empty(bag);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 2)]
+    fun borrow_wrong_type() {
+        let ctx = tx_context::dummy();
+        let bag = bag::new(&mut ctx);
+        add(&mut bag, 0, 0u64);
+        bag::borrow<u64, bool>(&bag, 0);
+        abort 42
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 0)]
+    fun destroy_non_empty() {
+        let ctx = tx_context::dummy();
+        let bag = bag::new(&mut ctx);
+        add(&mut bag, 0, 0);
+        bag::destroy_empty(bag);
+    }
+}
@@ -0,0 +1,20 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+#[test_only]
+module sui::clock_tests {
+    use sui::clock;
+    use sui::tx_context;
+
+    #[test]
+    fun test_timestamp() {
+        let ctx = tx_context::dummy();
+        let clock = clock::create_for_testing(&mut ctx);
+        assert!(clock::timestamp_ms(&clock) == 0, 0);
+
+        clock::increment_for_testing(&mut clock, 1000);
+        clock::increment_for_testing(&mut clock, 42);
+        assert!(clock::timestamp_ms(&clock) == 1042, 1);
+        clock::destroy_for_testing(clock);
+    }
+}
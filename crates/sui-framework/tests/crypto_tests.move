@@ -240,4 +240,159 @@ module sui::crypto_tests {
         let verify = crypto::secp256k1_verify(sig, pk, msg);
         assert!(verify == false, 0)
     }
+
+    #[test]
+    fun test_sha2_256_hash() {
+        let msg = b"hello world!";
+        let hashed_msg_bytes = vector[117, 9, 229, 189, 160, 199, 98, 210, 186, 199, 249, 13, 117, 139, 91, 34, 99, 250, 1, 204, 188, 84, 42, 181, 227, 223, 22, 59, 224, 142, 108, 169];
+
+        let hashed_msg = crypto::sha2_256(msg);
+        assert!(hashed_msg == hashed_msg_bytes, 0);
+    }
+
+    #[test]
+    fun test_sha3_256_hash() {
+        let msg = b"hello world!";
+        let hashed_msg_bytes = vector[156, 36, 176, 97, 67, 192, 114, 36, 200, 151, 186, 201, 114, 230, 233, 43, 70, 207, 24, 6, 63, 26, 70, 158, 190, 47, 122, 9, 102, 48, 97, 5];
+
+        let hashed_msg = crypto::sha3_256(msg);
+        assert!(hashed_msg == hashed_msg_bytes, 0);
+    }
+
+    #[test]
+    fun test_blake2b256_hash() {
+        let msg = b"hello world!";
+        let hashed_msg_bytes = vector[79, 204, 251, 77, 152, 208, 105, 85, 138, 169, 62, 149, 101, 249, 151, 216, 28, 51, 176, 128, 54, 78, 253, 88, 110, 119, 164, 51, 221, 255, 197, 226];
+
+        let hashed_msg = crypto::blake2b256(msg);
+        assert!(hashed_msg == hashed_msg_bytes, 0);
+    }
+
+    #[test]
+    fun test_hmac_sha3_256() {
+        let key = b"sui";
+        let msg = b"hello world!";
+        let mac_bytes = vector[184, 235, 116, 113, 191, 17, 173, 244, 120, 132, 21, 78, 37, 111, 77, 215, 57, 144, 155, 248, 159, 178, 121, 19, 44, 140, 205, 219, 84, 103, 200, 255];
+
+        let mac = crypto::hmac_sha3_256(key, msg);
+        assert!(mac == mac_bytes, 0);
+    }
+
+    #[test]
+    fun test_ed25519_valid_sig() {
+        let msg = b"hello world!";
+        let pk = vector[3, 161, 7, 191, 243, 206, 16, 190, 29, 112, 221, 24, 231, 75, 192, 153, 103, 228, 214, 48, 155, 165, 13, 95, 29, 220, 134, 100, 18, 85, 49, 184];
+        let sig = vector[85, 214, 210, 166, 169, 89, 7, 215, 203, 79, 204, 159, 50, 80, 19, 93, 227, 233, 231, 196, 235, 86, 168, 42, 118, 179, 255, 82, 104, 188, 175, 20, 19, 65, 245, 163, 50, 25, 81, 218, 11, 62, 164, 198, 6, 162, 80, 247, 38, 196, 96, 184, 145, 63, 195, 35, 138, 143, 223, 160, 77, 53, 37, 3];
+        let verify = crypto::ed25519_verify(sig, pk, msg);
+        assert!(verify == true, 0)
+    }
+
+    #[test]
+    fun test_ed25519_invalid_sig() {
+        let msg = b"hello world?";
+        let pk = vector[3, 161, 7, 191, 243, 206, 16, 190, 29, 112, 221, 24, 231, 75, 192, 153, 103, 228, 214, 48, 155, 165, 13, 95, 29, 220, 134, 100, 18, 85, 49, 184];
+        let sig = vector[85, 214, 210, 166, 169, 89, 7, 215, 203, 79, 204, 159, 50, 80, 19, 93, 227, 233, 231, 196, 235, 86, 168, 42, 118, 179, 255, 82, 104, 188, 175, 20, 19, 65, 245, 163, 50, 25, 81, 218, 11, 62, 164, 198, 6, 162, 80, 247, 38, 196, 96, 184, 145, 63, 195, 35, 138, 143, 223, 160, 77, 53, 37, 3];
+        let verify = crypto::ed25519_verify(sig, pk, msg);
+        assert!(verify == false, 0)
+    }
+
+    #[test]
+    fun test_secp256r1_valid_sig() {
+        // test case generated with the Python cryptography package
+        let msg = b"hello world!";
+        let pk = vector[3, 159, 173, 132, 174, 174, 8, 187, 239, 127, 1, 0, 20, 216, 44, 239, 106, 9, 222, 43, 12, 248, 113, 181, 206, 12, 79, 29, 19, 165, 154, 89, 52];
+        let sig = vector[84, 160, 163, 224, 41, 238, 136, 1, 86, 85, 169, 177, 200, 24, 222, 14, 51, 64, 57, 38, 96, 144, 3, 115, 240, 25, 237, 9, 183, 2, 200, 247, 100, 3, 21, 114, 50, 55, 211, 122, 202, 81, 189, 122, 44, 112, 159, 144, 44, 126, 125, 193, 217, 86, 83, 101, 236, 225, 245, 100, 250, 40, 98, 105];
+        let verify = crypto::secp256r1_verify(sig, pk, msg);
+        assert!(verify == true, 0)
+    }
+
+    #[test]
+    fun test_secp256r1_invalid_sig() {
+        let msg = b"hello world?";
+        let pk = vector[3, 159, 173, 132, 174, 174, 8, 187, 239, 127, 1, 0, 20, 216, 44, 239, 106, 9, 222, 43, 12, 248, 113, 181, 206, 12, 79, 29, 19, 165, 154, 89, 52];
+        let sig = vector[84, 160, 163, 224, 41, 238, 136, 1, 86, 85, 169, 177, 200, 24, 222, 14, 51, 64, 57, 38, 96, 144, 3, 115, 240, 25, 237, 9, 183, 2, 200, 247, 100, 3, 21, 114, 50, 55, 211, 122, 202, 81, 189, 122, 44, 112, 159, 144, 44, 126, 125, 193, 217, 86, 83, 101, 236, 225, 245, 100, 250, 40, 98, 105];
+        let verify = crypto::secp256r1_verify(sig, pk, msg);
+        assert!(verify == false, 0)
+    }
+
+    #[test]
+    fun test_secp256r1_high_s_sig() {
+        // The valid signature above with s replaced by n - s, which p256 alone would accept.
+        let msg = b"hello world!";
+        let pk = vector[3, 159, 173, 132, 174, 174, 8, 187, 239, 127, 1, 0, 20, 216, 44, 239, 106, 9, 222, 43, 12, 248, 113, 181, 206, 12, 79, 29, 19, 165, 154, 89, 52];
+        let sig = vector[84, 160, 163, 224, 41, 238, 136, 1, 86, 85, 169, 177, 200, 24, 222, 14, 51, 64, 57, 38, 96, 144, 3, 115, 240, 25, 237, 9, 183, 2, 200, 247, 155, 252, 234, 140, 205, 200, 44, 134, 53, 174, 66, 133, 211, 143, 96, 111, 144, 104, 124, 235, 205, 193, 75, 31, 6, 215, 213, 94, 2, 58, 194, 232];
+        let verify = crypto::secp256r1_verify(sig, pk, msg);
+        assert!(verify == false, 0)
+    }
+
+    #[test]
+    fun test_secp256r1_invalid_public_key_length() {
+        let msg = b"hello world!";
+        let pk = vector[159, 173, 132, 174, 174, 8, 187, 239, 127, 1, 0, 20, 216, 44, 239, 106, 9, 222, 43, 12, 248, 113, 181, 206, 12, 79, 29, 19, 165, 154, 89, 52];
+        let sig = vector[84, 160, 163, 224, 41, 238, 136, 1, 86, 85, 169, 177, 200, 24, 222, 14, 51, 64, 57, 38, 96, 144, 3, 115, 240, 25, 237, 9, 183, 2, 200, 247, 100, 3, 21, 114, 50, 55, 211, 122, 202, 81, 189, 122, 44, 112, 159, 144, 44, 126, 125, 193, 217, 86, 83, 101, 236, 225, 245, 100, 250, 40, 98, 105];
+        let verify = crypto::secp256r1_verify(sig, pk, msg);
+        assert!(verify == false, 0)
+    }
+
+    #[test]
+    fun test_bls12381_aggregate_valid_sig() {
+        // The aggregate of a single signature is the signature itself.
+        let msg = vector[1, 1, 1, 1, 1];
+        let pk = vector[141, 241, 1, 96, 111, 145, 243, 202, 215, 245, 75, 138, 255, 15, 15, 100, 196, 28, 72, 45, 155, 159, 159, 232, 29, 43, 96, 123, 197, 246, 17, 189, 250, 128, 23, 207, 4, 180, 123, 68, 178, 34, 195, 86, 239, 85, 95, 189, 17, 5, 140, 82, 192, 119, 245, 167, 236, 106, 21, 204, 253, 99, 159, 220, 155, 212, 125, 0, 90, 17, 29, 214, 205, 184, 192, 47, 228, 150, 8, 223, 85, 163, 201, 130, 41, 134, 173, 11, 134, 189, 234, 58, 191, 223, 228, 100];
+        let sig = vector[144, 142, 52, 95, 46, 40, 3, 205, 148, 26, 232, 140, 33, 140, 150, 25, 66, 51, 201, 5, 63, 161, 188, 165, 33, 36, 120, 125, 60, 202, 20, 28, 54, 66, 157, 118, 82, 67, 90, 130, 12, 114, 153, 45, 94, 238, 99, 23];
+        let verify = crypto::bls12381_aggregate_verify(sig, vector[pk], msg);
+        assert!(verify == true, 0)
+    }
+
+    #[test]
+    fun test_bls12381_aggregate_multiple_signers() {
+        // The signers' secret keys are sk, 2 * sk and 3 * sk, where sk is the secret key of the
+        // single signer test above, so their aggregate signature is 6 times its signature.
+        let msg = vector[1, 1, 1, 1, 1];
+        let pk1 = vector[141, 241, 1, 96, 111, 145, 243, 202, 215, 245, 75, 138, 255, 15, 15, 100, 196, 28, 72, 45, 155, 159, 159, 232, 29, 43, 96, 123, 197, 246, 17, 189, 250, 128, 23, 207, 4, 180, 123, 68, 178, 34, 195, 86, 239, 85, 95, 189, 17, 5, 140, 82, 192, 119, 245, 167, 236, 106, 21, 204, 253, 99, 159, 220, 155, 212, 125, 0, 90, 17, 29, 214, 205, 184, 192, 47, 228, 150, 8, 223, 85, 163, 201, 130, 41, 134, 173, 11, 134, 189, 234, 58, 191, 223, 228, 100];
+        let pk2 = vector[146, 190, 5, 13, 235, 225, 79, 57, 207, 60, 209, 27, 197, 223, 42, 218, 121, 141, 174, 252, 103, 172, 113, 130, 143, 59, 78, 116, 11, 202, 36, 102, 214, 145, 186, 173, 174, 106, 50, 219, 247, 229, 146, 198, 120, 247, 218, 26, 15, 63, 109, 197, 251, 129, 55, 95, 63, 131, 10, 208, 255, 140, 175, 32, 122, 184, 252, 4, 128, 141, 48, 132, 73, 26, 72, 53, 147, 83, 163, 172, 69, 53, 74, 133, 86, 115, 18, 42, 159, 141, 239, 132, 125, 185, 71, 191];
+        let pk3 = vector[130, 174, 121, 205, 100, 16, 154, 193, 174, 201, 28, 63, 60, 113, 37, 138, 57, 244, 179, 70, 26, 227, 101, 182, 137, 63, 197, 146, 159, 228, 122, 85, 70, 166, 218, 163, 80, 220, 32, 96, 190, 195, 59, 127, 163, 36, 63, 183, 21, 200, 62, 151, 175, 11, 14, 152, 34, 197, 171, 245, 226, 195, 125, 165, 64, 78, 141, 183, 140, 31, 146, 8, 221, 213, 217, 150, 69, 43, 210, 139, 196, 192, 202, 44, 13, 20, 170, 148, 113, 64, 55, 62, 43, 32, 70, 49];
+        let sig = vector[130, 38, 56, 208, 219, 12, 192, 12, 58, 238, 76, 9, 49, 110, 49, 233, 108, 18, 64, 16, 187, 168, 158, 212, 7, 224, 69, 161, 65, 121, 239, 199, 15, 14, 4, 181, 90, 146, 235, 133, 186, 111, 197, 227, 75, 27, 1, 84];
+        let verify = crypto::bls12381_aggregate_verify(sig, vector[pk1, pk2, pk3], msg);
+        assert!(verify == true, 0)
+    }
+
+    #[test]
+    fun test_bls12381_aggregate_multiple_signers_missing_signer() {
+        let msg = vector[1, 1, 1, 1, 1];
+        let pk1 = vector[141, 241, 1, 96, 111, 145, 243, 202, 215, 245, 75, 138, 255, 15, 15, 100, 196, 28, 72, 45, 155, 159, 159, 232, 29, 43, 96, 123, 197, 246, 17, 189, 250, 128, 23, 207, 4, 180, 123, 68, 178, 34, 195, 86, 239, 85, 95, 189, 17, 5, 140, 82, 192, 119, 245, 167, 236, 106, 21, 204, 253, 99, 159, 220, 155, 212, 125, 0, 90, 17, 29, 214, 205, 184, 192, 47, 228, 150, 8, 223, 85, 163, 201, 130, 41, 134, 173, 11, 134, 189, 234, 58, 191, 223, 228, 100];
+        let pk2 = vector[146, 190, 5, 13, 235, 225, 79, 57, 207, 60, 209, 27, 197, 223, 42, 218, 121, 141, 174, 252, 103, 172, 113, 130, 143, 59, 78, 116, 11, 202, 36, 102, 214, 145, 186, 173, 174, 106, 50, 219, 247, 229, 146, 198, 120, 247, 218, 26, 15, 63, 109, 197, 251, 129, 55, 95, 63, 131, 10, 208, 255, 140, 175, 32, 122, 184, 252, 4, 128, 141, 48, 132, 73, 26, 72, 53, 147, 83, 163, 172, 69, 53, 74, 133, 86, 115, 18, 42, 159, 141, 239, 132, 125, 185, 71, 191];
+        let pk3 = vector[130, 174, 121, 205, 100, 16, 154, 193, 174, 201, 28, 63, 60, 113, 37, 138, 57, 244, 179, 70, 26, 227, 101, 182, 137, 63, 197, 146, 159, 228, 122, 85, 70, 166, 218, 163, 80, 220, 32, 96, 190, 195, 59, 127, 163, 36, 63, 183, 21, 200, 62, 151, 175, 11, 14, 152, 34, 197, 171, 245, 226, 195, 125, 165, 64, 78, 141, 183, 140, 31, 146, 8, 221, 213, 217, 150, 69, 43, 210, 139, 196, 192, 202, 44, 13, 20, 170, 148, 113, 64, 55, 62, 43, 32, 70, 49];
+        let sig = vector[130, 38, 56, 208, 219, 12, 192, 12, 58, 238, 76, 9, 49, 110, 49, 233, 108, 18, 64, 16, 187, 168, 158, 212, 7, 224, 69, 161, 65, 121, 239, 199, 15, 14, 4, 181, 90, 146, 235, 133, 186, 111, 197, 227, 75, 27, 1, 84];
+        let verify = crypto::bls12381_aggregate_verify(sig, vector[pk1, pk3], msg);
+        assert!(verify == false, 0)
+    }
+
+    #[test]
+    fun test_bls12381_aggregate_multiple_signers_wrong_msg() {
+        let msg = vector[2, 1, 1, 1, 1];
+        let pk1 = vector[141, 241, 1, 96, 111, 145, 243, 202, 215, 245, 75, 138, 255, 15, 15, 100, 196, 28, 72, 45, 155, 159, 159, 232, 29, 43, 96, 123, 197, 246, 17, 189, 250, 128, 23, 207, 4, 180, 123, 68, 178, 34, 195, 86, 239, 85, 95, 189, 17, 5, 140, 82, 192, 119, 245, 167, 236, 106, 21, 204, 253, 99, 159, 220, 155, 212, 125, 0, 90, 17, 29, 214, 205, 184, 192, 47, 228, 150, 8, 223, 85, 163, 201, 130, 41, 134, 173, 11, 134, 189, 234, 58, 191, 223, 228, 100];
+        let pk2 = vector[146, 190, 5, 13, 235, 225, 79, 57, 207, 60, 209, 27, 197, 223, 42, 218, 121, 141, 174, 252, 103, 172, 113, 130, 143, 59, 78, 116, 11, 202, 36, 102, 214, 145, 186, 173, 174, 106, 50, 219, 247, 229, 146, 198, 120, 247, 218, 26, 15, 63, 109, 197, 251, 129, 55, 95, 63, 131, 10, 208, 255, 140, 175, 32, 122, 184, 252, 4, 128, 141, 48, 132, 73, 26, 72, 53, 147, 83, 163, 172, 69, 53, 74, 133, 86, 115, 18, 42, 159, 141, 239, 132, 125, 185, 71, 191];
+        let pk3 = vector[130, 174, 121, 205, 100, 16, 154, 193, 174, 201, 28, 63, 60, 113, 37, 138, 57, 244, 179, 70, 26, 227, 101, 182, 137, 63, 197, 146, 159, 228, 122, 85, 70, 166, 218, 163, 80, 220, 32, 96, 190, 195, 59, 127, 163, 36, 63, 183, 21, 200, 62, 151, 175, 11, 14, 152, 34, 197, 171, 245, 226, 195, 125, 165, 64, 78, 141, 183, 140, 31, 146, 8, 221, 213, 217, 150, 69, 43, 210, 139, 196, 192, 202, 44, 13, 20, 170, 148, 113, 64, 55, 62, 43, 32, 70, 49];
+        let sig = vector[130, 38, 56, 208, 219, 12, 192, 12, 58, 238, 76, 9, 49, 110, 49, 233, 108, 18, 64, 16, 187, 168, 158, 212, 7, 224, 69, 161, 65, 121, 239, 199, 15, 14, 4, 181, 90, 146, 235, 133, 186, 111, 197, 227, 75, 27, 1, 84];
+        let verify = crypto::bls12381_aggregate_verify(sig, vector[pk1, pk2, pk3], msg);
+        assert!(verify == false, 0)
+    }
+
+    #[test]
+    fun test_bls12381_aggregate_missing_public_key() {
+        let msg = vector[1, 1, 1, 1, 1];
+        let pk = vector[141, 241, 1, 96, 111, 145, 243, 202, 215, 245, 75, 138, 255, 15, 15, 100, 196, 28, 72, 45, 155, 159, 159, 232, 29, 43, 96, 123, 197, 246, 17, 189, 250, 128, 23, 207, 4, 180, 123, 68, 178, 34, 195, 86, 239, 85, 95, 189, 17, 5, 140, 82, 192, 119, 245, 167, 236, 106, 21, 204, 253, 99, 159, 220, 155, 212, 125, 0, 90, 17, 29, 214, 205, 184, 192, 47, 228, 150, 8, 223, 85, 163, 201, 130, 41, 134, 173, 11, 134, 189, 234, 58, 191, 223, 228, 100];
+        let sig = vector[144, 142, 52, 95, 46, 40, 3, 205, 148, 26, 232, 140, 33, 140, 150, 25, 66, 51, 201, 5, 63, 161, 188, 165, 33, 36, 120, 125, 60, 202, 20, 28, 54, 66, 157, 118, 82, 67, 90, 130, 12, 114, 153, 45, 94, 238, 99, 23];
+        let verify = crypto::bls12381_aggregate_verify(sig, vector[pk, pk], msg);
+        assert!(verify == false, 0)
+    }
+
+    #[test]
+    fun test_bls12381_aggregate_no_public_keys() {
+        let msg = vector[1, 1, 1, 1, 1];
+        let sig = vector[144, 142, 52, 95, 46, 40, 3, 205, 148, 26, 232, 140, 33, 140, 150, 25, 66, 51, 201, 5, 63, 161, 188, 165, 33, 36, 120, 125, 60, 202, 20, 28, 54, 66, 157, 118, 82, 67, 90, 130, 12, 114, 153, 45, 94, 238, 99, 23];
+        let verify = crypto::bls12381_aggregate_verify(sig, vector[], msg);
+        assert!(verify == false, 0)
+    }
 }
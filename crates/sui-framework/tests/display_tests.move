@@ -0,0 +1,69 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+#[test_only]
+module sui::display_tests {
+    use std::string;
+    use sui::display;
+    use sui::object::UID;
+    use sui::package;
+    use sui::transfer;
+    use sui::tx_context;
+    use sui::vec_map;
+
+    /// One-time witness of this module.
+    struct DISPLAY_TESTS has drop {}
+
+    struct Capy has key {
+        id: UID,
+        name: string::String,
+    }
+
+    #[test]
+    fun test_publisher() {
+        let ctx = tx_context::dummy();
+        let publisher = package::claim(DISPLAY_TESTS {}, &mut ctx);
+        assert!(package::from_module<Capy>(&publisher), 0);
+        assert!(package::from_package<Capy>(&publisher), 0);
+        assert!(*package::published_package(&publisher) == @sui, 0);
+        assert!(!package::from_module<display::Display<Capy>>(&publisher), 0);
+        assert!(package::from_package<display::Display<Capy>>(&publisher), 0);
+        package::burn_publisher(publisher);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 1)]
+    fun test_claim_requires_one_time_witness() {
+        let ctx = tx_context::dummy();
+        let publisher = package::claim(string::utf8(b"not a witness"), &mut ctx);
+        package::burn_publisher(publisher);
+    }
+
+    #[test]
+    fun test_display() {
+        let ctx = tx_context::dummy();
+        let publisher = package::claim(DISPLAY_TESTS {}, &mut ctx);
+        let display = display::new_with_fields<Capy>(
+            &publisher,
+            vector[b"name", b"link"],
+            vector[b"Capy {name}", b"https://capy.art/{id}"],
+            &mut ctx,
+        );
+        display::add(&mut display, b"description", b"A capybara");
+        display::edit(&mut display, b"name", b"{name} the Capy");
+        display::remove(&mut display, b"link");
+        assert!(display::version(&display) == 0, 0);
+
+        display::update_version(&mut display);
+        let fields = display::fields(&display);
+        assert!(display::version(&display) == 1, 0);
+        assert!(vec_map::size(fields) == 2, 0);
+        assert!(
+            *vec_map::get(fields, &string::utf8(b"name")) == string::utf8(b"{name} the Capy"),
+            0
+        );
+
+        transfer::transfer(display, tx_context::sender(&ctx));
+        package::burn_publisher(publisher);
+    }
+}
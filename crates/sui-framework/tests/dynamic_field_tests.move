@@ -0,0 +1,132 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+#[test_only]
+module sui::dynamic_field_tests {
+    use sui::dynamic_field::{add, exists_with_type, borrow, borrow_mut, remove, exists_};
+    use sui::object;
+    use sui::tx_context;
+
+    #[test]
+    fun simple_all_functions() {
+        let ctx = tx_context::dummy();
+        let id = object::new(&mut ctx);
+        // add fields
+        add<u64, u64>(&mut id, 0, 0);
+        add<vector<u8>, u64>(&mut id, b"", 1);
+        add<bool, u64>(&mut id, false, 2);
+        // check they exist
+        assert!(exists_with_type<u64, u64>(&id, 0), 0);
+        assert!(exists_with_type<vector<u8>, u64>(&id, b""), 0);
+        assert!(exists_with_type<bool, u64>(&id, false), 0);
+        // check the values
+        assert!(*borrow(&id, 0) == 0, 0);
+        assert!(*borrow(&id, b"") == 1, 0);
+        assert!(*borrow(&id, false) == 2, 0);
+        // mutate them
+        *borrow_mut(&mut id, 0) = 3 + *borrow(&id, 0);
+        *borrow_mut(&mut id, b"") = 4 + *borrow(&id, b"");
+        *borrow_mut(&mut id, false) = 5 + *borrow(&id, false);
+        // check the new value
+        assert!(*borrow(&id, 0) == 3, 0);
+        assert!(*borrow(&id, b"") == 5, 0);
+        assert!(*borrow(&id, false) == 7, 0);
+        // remove the value and check it
+        assert!(remove(&mut id, 0) == 3, 0);
+        assert!(remove(&mut id, b"") == 5, 0);
+        assert!(remove(&mut id, false) == 7, 0);
+        // verify that they are not there
+        assert!(!exists_with_type<u64, u64>(&id, 0), 0);
+        assert!(!exists_with_type<vector<u8>, u64>(&id, b""), 0);
+        assert!(!exists_with_type<bool, u64>(&id, false), 0);
+        object::delete(id);
+    }
+
+    #[test]
+    fun fields_are_per_object() {
+        let ctx = tx_context::dummy();
+        let id1 = object::new(&mut ctx);
+        let id2 = object::new(&mut ctx);
+        add<u64, u64>(&mut id1, 0, 0);
+        add<u64, u64>(&mut id2, 0, 1);
+        assert!(*borrow(&id1, 0) == 0, 0);
+        assert!(*borrow(&id2, 0) == 1, 0);
+        assert!(remove<u64, u64>(&mut id1, 0) == 0, 0);
+        assert!(!exists_(&id1, 0), 0);
+        assert!(exists_(&id2, 0), 0);
+        object::delete(id1);
+        object::delete(id2);
+    }
+
+    #[test]
+    fun add_after_remove() {
+        let ctx = tx_context::dummy();
+        let id = object::new(&mut ctx);
+        add<u64, u64>(&mut id, 0, 0);
+        assert!(remove<u64, u64>(&mut id, 0) == 0, 0);
+        // the value can be re-added, with another type
+        add<u64, bool>(&mut id, 0, true);
+        assert!(*borrow(&id, 0), 0);
+        assert!(!exists_with_type<u64, u64>(&id, 0), 0);
+        assert!(exists_with_type<u64, bool>(&id, 0), 0);
+        object::delete(id);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 0)]
+    fun add_duplicate() {
+        let ctx = tx_context::dummy();
+        let id = object::new(&mut ctx);
+        add<u64, u64>(&mut id, 0, 0);
+        add<u64, u64>(&mut id, 0, 1);
+        abort 42
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 0)]
+    fun add_duplicate_mismatched_type() {
+        let ctx = tx_context::dummy();
+        let id = object::new(&mut ctx);
+        add<u64, u64>(&mut id, 0, 0u64);
+        add<u64, u8>(&mut id, 0, 1u8);
+        abort 42
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 1)]
+    fun borrow_missing() {
+        let ctx = tx_context::dummy();
+        let id = object::new(&mut ctx);
+        borrow<u64, u64>(&id, 0);
+        abort 42
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 2)]
+    fun borrow_wrong_type() {
+        let ctx = tx_context::dummy();
+        let id = object::new(&mut ctx);
+        add(&mut id, 0, 0);
+        borrow<u64, u8>(&id, 0);
+        abort 42
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 1)]
+    fun remove_missing() {
+        let ctx = tx_context::dummy();
+        let id = object::new(&mut ctx);
+        remove<u64, u64>(&mut id, 0);
+        abort 42
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 2)]
+    fun remove_wrong_type() {
+        let ctx = tx_context::dummy();
+        let id = object::new(&mut ctx);
+        add(&mut id, 0, 0);
+        remove<u64, u8>(&mut id, 0);
+        abort 42
+    }
+}
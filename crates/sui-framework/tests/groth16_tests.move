@@ -0,0 +1,74 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+#[test_only]
+module sui::groth16_tests {
+    use sui::groth16;
+
+    #[test]
+    #[expected_failure(abort_code = 0)]
+    fun test_prepare_invalid_bls12381_verifying_key() {
+        groth16::prepare_verifying_key(&groth16::bls12381(), &vector[1, 2, 3]);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 0)]
+    fun test_prepare_invalid_bn254_verifying_key() {
+        groth16::prepare_verifying_key(&groth16::bn254(), &vector[]);
+    }
+
+    // The verifying keys and proofs below are for circuits with random parameters, the proofs are
+    // computed from the secret parameters the keys were generated with. The BLS12-381 circuit has
+    // the single public input 894 and the BN254 one the two public inputs 2 and 491.
+
+    #[test]
+    fun test_verify_groth16_bls12381_valid_proof() {
+        let vk = vector[166, 93, 46, 138, 139, 10, 249, 80, 67, 253, 168, 209, 132, 66, 49, 146, 110, 21, 103, 82, 224, 103, 229, 211, 242, 29, 221, 149, 91, 227, 35, 229, 129, 159, 6, 41, 139, 114, 163, 67, 89, 162, 193, 160, 63, 60, 221, 12, 178, 89, 218, 179, 176, 26, 36, 195, 3, 153, 79, 192, 0, 20, 69, 129, 154, 35, 217, 53, 42, 86, 217, 153, 42, 117, 111, 214, 56, 140, 48, 200, 183, 254, 125, 201, 38, 35, 102, 12, 18, 187, 23, 102, 77, 233, 161, 6, 30, 1, 92, 45, 80, 12, 73, 144, 98, 254, 187, 250, 21, 230, 44, 228, 63, 100, 235, 44, 57, 109, 244, 219, 238, 229, 65, 77, 222, 31, 251, 14, 176, 93, 135, 66, 238, 102, 162, 189, 154, 94, 56, 136, 233, 132, 89, 145, 199, 98, 79, 7, 65, 207, 174, 247, 57, 207, 65, 56, 163, 193, 2, 194, 83, 188, 214, 122, 123, 152, 20, 239, 219, 172, 218, 44, 233, 100, 49, 174, 13, 82, 13, 124, 245, 92, 195, 127, 23, 177, 104, 45, 75, 213, 4, 25, 23, 83, 33, 110, 39, 9, 69, 178, 23, 213, 0, 241, 131, 19, 45, 206, 83, 223, 244, 60, 38, 101, 157, 19, 18, 118, 169, 206, 151, 205, 230, 82, 90, 140, 219, 32, 207, 126, 1, 241, 236, 213, 179, 158, 126, 187, 16, 142, 237, 177, 202, 242, 224, 101, 139, 140, 215, 185, 137, 62, 131, 36, 40, 191, 253, 62, 199, 159, 106, 97, 238, 255, 110, 160, 107, 75, 230, 15, 175, 83, 193, 112, 130, 222, 133, 7, 194, 129, 16, 60, 134, 169, 191, 148, 93, 2, 23, 79, 161, 153, 238, 126, 254, 46, 199, 40, 166, 174, 120, 230, 107, 179, 0, 75, 31, 58, 127, 86, 244, 93, 124, 248, 235, 198, 80, 193, 149, 117, 186, 158, 192, 15, 112, 52, 43, 38, 42, 78, 106, 84, 140, 209, 98, 11, 2, 0, 0, 0, 0, 0, 0, 0, 147, 90, 224, 248, 46, 153, 83, 156, 68, 187, 201, 130, 131, 92, 212, 34, 203, 160, 234, 192, 246, 106, 200, 237, 242, 189, 85, 33, 177, 204, 155, 49, 10, 9, 57, 107, 111, 92, 46, 238, 141, 51, 172, 177, 155, 79, 131, 148, 190, 32, 41, 125, 101, 177, 191, 20, 65, 160, 152, 123, 139, 251, 88, 240, 120, 19, 11, 114, 85, 147, 112, 43, 127, 225, 199, 44, 209, 152, 23, 162, 159, 88, 159, 38, 91, 25, 128, 126, 202, 169, 76, 185, 45, 133, 243, 4];
+        let pvk = groth16::prepare_verifying_key(&groth16::bls12381(), &vk);
+        let inputs = groth16::public_proof_inputs_from_bytes(vector[126, 3, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+        let proof = groth16::proof_points_from_bytes(vector[202, 51, 215, 59, 74, 197, 13, 243, 86, 175, 69, 114, 25, 126, 219, 219, 220, 34, 133, 250, 185, 61, 74, 109, 208, 33, 29, 188, 251, 143, 46, 24, 91, 242, 84, 37, 32, 66, 253, 46, 86, 129, 243, 238, 173, 170, 122, 149, 140, 162, 14, 128, 129, 212, 70, 16, 52, 245, 245, 72, 84, 118, 114, 117, 66, 230, 60, 155, 181, 169, 223, 4, 110, 175, 135, 224, 53, 45, 64, 174, 146, 68, 4, 84, 59, 57, 24, 204, 167, 9, 127, 149, 123, 205, 84, 17, 132, 108, 184, 73, 148, 78, 153, 67, 88, 210, 30, 49, 196, 67, 0, 45, 237, 112, 92, 244, 18, 2, 190, 129, 165, 93, 177, 196, 50, 168, 136, 211, 110, 149, 198, 179, 97, 180, 222, 30, 165, 59, 62, 163, 123, 56, 69, 136, 45, 236, 136, 68, 11, 244, 154, 118, 38, 134, 92, 74, 110, 113, 117, 188, 118, 223, 74, 0, 188, 51, 249, 234, 24, 58, 108, 20, 194, 165, 40, 235, 252, 69, 252, 57, 105, 90, 55, 226, 224, 230, 27, 11, 219, 152, 131, 151]);
+        assert!(groth16::verify_groth16_proof(&pvk, &inputs, &proof) == true, 0);
+    }
+
+    #[test]
+    fun test_verify_groth16_bls12381_wrong_inputs() {
+        let vk = vector[166, 93, 46, 138, 139, 10, 249, 80, 67, 253, 168, 209, 132, 66, 49, 146, 110, 21, 103, 82, 224, 103, 229, 211, 242, 29, 221, 149, 91, 227, 35, 229, 129, 159, 6, 41, 139, 114, 163, 67, 89, 162, 193, 160, 63, 60, 221, 12, 178, 89, 218, 179, 176, 26, 36, 195, 3, 153, 79, 192, 0, 20, 69, 129, 154, 35, 217, 53, 42, 86, 217, 153, 42, 117, 111, 214, 56, 140, 48, 200, 183, 254, 125, 201, 38, 35, 102, 12, 18, 187, 23, 102, 77, 233, 161, 6, 30, 1, 92, 45, 80, 12, 73, 144, 98, 254, 187, 250, 21, 230, 44, 228, 63, 100, 235, 44, 57, 109, 244, 219, 238, 229, 65, 77, 222, 31, 251, 14, 176, 93, 135, 66, 238, 102, 162, 189, 154, 94, 56, 136, 233, 132, 89, 145, 199, 98, 79, 7, 65, 207, 174, 247, 57, 207, 65, 56, 163, 193, 2, 194, 83, 188, 214, 122, 123, 152, 20, 239, 219, 172, 218, 44, 233, 100, 49, 174, 13, 82, 13, 124, 245, 92, 195, 127, 23, 177, 104, 45, 75, 213, 4, 25, 23, 83, 33, 110, 39, 9, 69, 178, 23, 213, 0, 241, 131, 19, 45, 206, 83, 223, 244, 60, 38, 101, 157, 19, 18, 118, 169, 206, 151, 205, 230, 82, 90, 140, 219, 32, 207, 126, 1, 241, 236, 213, 179, 158, 126, 187, 16, 142, 237, 177, 202, 242, 224, 101, 139, 140, 215, 185, 137, 62, 131, 36, 40, 191, 253, 62, 199, 159, 106, 97, 238, 255, 110, 160, 107, 75, 230, 15, 175, 83, 193, 112, 130, 222, 133, 7, 194, 129, 16, 60, 134, 169, 191, 148, 93, 2, 23, 79, 161, 153, 238, 126, 254, 46, 199, 40, 166, 174, 120, 230, 107, 179, 0, 75, 31, 58, 127, 86, 244, 93, 124, 248, 235, 198, 80, 193, 149, 117, 186, 158, 192, 15, 112, 52, 43, 38, 42, 78, 106, 84, 140, 209, 98, 11, 2, 0, 0, 0, 0, 0, 0, 0, 147, 90, 224, 248, 46, 153, 83, 156, 68, 187, 201, 130, 131, 92, 212, 34, 203, 160, 234, 192, 246, 106, 200, 237, 242, 189, 85, 33, 177, 204, 155, 49, 10, 9, 57, 107, 111, 92, 46, 238, 141, 51, 172, 177, 155, 79, 131, 148, 190, 32, 41, 125, 101, 177, 191, 20, 65, 160, 152, 123, 139, 251, 88, 240, 120, 19, 11, 114, 85, 147, 112, 43, 127, 225, 199, 44, 209, 152, 23, 162, 159, 88, 159, 38, 91, 25, 128, 126, 202, 169, 76, 185, 45, 133, 243, 4];
+        let pvk = groth16::prepare_verifying_key(&groth16::bls12381(), &vk);
+        let inputs = groth16::public_proof_inputs_from_bytes(vector[127, 3, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+        let proof = groth16::proof_points_from_bytes(vector[202, 51, 215, 59, 74, 197, 13, 243, 86, 175, 69, 114, 25, 126, 219, 219, 220, 34, 133, 250, 185, 61, 74, 109, 208, 33, 29, 188, 251, 143, 46, 24, 91, 242, 84, 37, 32, 66, 253, 46, 86, 129, 243, 238, 173, 170, 122, 149, 140, 162, 14, 128, 129, 212, 70, 16, 52, 245, 245, 72, 84, 118, 114, 117, 66, 230, 60, 155, 181, 169, 223, 4, 110, 175, 135, 224, 53, 45, 64, 174, 146, 68, 4, 84, 59, 57, 24, 204, 167, 9, 127, 149, 123, 205, 84, 17, 132, 108, 184, 73, 148, 78, 153, 67, 88, 210, 30, 49, 196, 67, 0, 45, 237, 112, 92, 244, 18, 2, 190, 129, 165, 93, 177, 196, 50, 168, 136, 211, 110, 149, 198, 179, 97, 180, 222, 30, 165, 59, 62, 163, 123, 56, 69, 136, 45, 236, 136, 68, 11, 244, 154, 118, 38, 134, 92, 74, 110, 113, 117, 188, 118, 223, 74, 0, 188, 51, 249, 234, 24, 58, 108, 20, 194, 165, 40, 235, 252, 69, 252, 57, 105, 90, 55, 226, 224, 230, 27, 11, 219, 152, 131, 151]);
+        assert!(groth16::verify_groth16_proof(&pvk, &inputs, &proof) == false, 0);
+    }
+
+    #[test]
+    fun test_verify_groth16_bn254_valid_proof() {
+        let vk = vector[82, 227, 59, 243, 32, 163, 254, 185, 233, 101, 105, 29, 149, 15, 14, 47, 185, 166, 76, 111, 246, 106, 52, 252, 54, 82, 44, 144, 199, 207, 96, 149, 123, 230, 22, 163, 167, 6, 4, 112, 158, 92, 216, 16, 60, 194, 103, 3, 65, 146, 42, 154, 55, 169, 90, 223, 125, 44, 209, 136, 58, 53, 29, 38, 15, 47, 233, 186, 93, 232, 62, 15, 208, 209, 30, 4, 108, 151, 197, 192, 150, 50, 189, 57, 121, 253, 144, 38, 23, 216, 85, 173, 8, 15, 79, 148, 97, 100, 31, 68, 55, 19, 202, 244, 122, 168, 19, 125, 37, 16, 20, 174, 222, 108, 240, 10, 237, 171, 139, 28, 126, 179, 86, 50, 171, 239, 225, 42, 161, 218, 128, 237, 97, 243, 103, 233, 66, 161, 137, 233, 129, 93, 112, 183, 83, 80, 123, 202, 79, 121, 187, 209, 137, 24, 79, 217, 146, 206, 199, 32, 222, 137, 54, 87, 38, 211, 21, 197, 61, 203, 137, 41, 194, 88, 113, 189, 16, 12, 81, 62, 253, 191, 36, 165, 105, 11, 174, 231, 59, 232, 163, 34, 219, 140, 134, 59, 151, 16, 162, 190, 69, 234, 67, 152, 213, 120, 27, 3, 101, 71, 244, 199, 10, 136, 210, 73, 154, 37, 251, 228, 93, 65, 245, 13, 3, 0, 0, 0, 0, 0, 0, 0, 226, 135, 225, 22, 75, 113, 97, 45, 58, 123, 38, 36, 69, 91, 62, 251, 158, 39, 50, 196, 16, 201, 8, 162, 172, 121, 8, 111, 194, 130, 232, 166, 84, 170, 11, 235, 214, 61, 216, 81, 89, 56, 53, 39, 54, 60, 62, 232, 153, 248, 157, 246, 221, 131, 150, 102, 66, 162, 51, 7, 20, 36, 253, 30, 99, 32, 78, 40, 158, 152, 82, 49, 103, 79, 125, 6, 194, 166, 214, 22, 128, 87, 169, 74, 193, 48, 187, 56, 161, 0, 208, 18, 19, 100, 128, 14];
+        let pvk = groth16::prepare_verifying_key(&groth16::bn254(), &vk);
+        let inputs = groth16::public_proof_inputs_from_bytes(vector[2, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 235, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+        let proof = groth16::proof_points_from_bytes(vector[45, 169, 214, 239, 213, 232, 95, 205, 198, 150, 36, 150, 110, 214, 132, 9, 183, 131, 227, 186, 149, 231, 3, 98, 191, 170, 184, 103, 201, 75, 113, 155, 94, 32, 236, 79, 146, 67, 152, 172, 108, 135, 69, 32, 162, 217, 25, 201, 37, 125, 65, 207, 59, 234, 40, 146, 29, 110, 234, 130, 141, 154, 25, 17, 175, 119, 95, 180, 21, 7, 69, 192, 158, 155, 212, 132, 86, 61, 230, 133, 74, 178, 77, 108, 212, 132, 93, 34, 22, 66, 215, 252, 14, 177, 33, 165, 24, 96, 191, 22, 166, 91, 26, 112, 28, 222, 254, 185, 166, 206, 132, 71, 104, 207, 173, 228, 188, 70, 110, 37, 102, 33, 48, 204, 92, 39, 4, 48]);
+        assert!(groth16::verify_groth16_proof(&pvk, &inputs, &proof) == true, 0);
+    }
+
+    #[test]
+    fun test_verify_groth16_bn254_wrong_inputs() {
+        let vk = vector[82, 227, 59, 243, 32, 163, 254, 185, 233, 101, 105, 29, 149, 15, 14, 47, 185, 166, 76, 111, 246, 106, 52, 252, 54, 82, 44, 144, 199, 207, 96, 149, 123, 230, 22, 163, 167, 6, 4, 112, 158, 92, 216, 16, 60, 194, 103, 3, 65, 146, 42, 154, 55, 169, 90, 223, 125, 44, 209, 136, 58, 53, 29, 38, 15, 47, 233, 186, 93, 232, 62, 15, 208, 209, 30, 4, 108, 151, 197, 192, 150, 50, 189, 57, 121, 253, 144, 38, 23, 216, 85, 173, 8, 15, 79, 148, 97, 100, 31, 68, 55, 19, 202, 244, 122, 168, 19, 125, 37, 16, 20, 174, 222, 108, 240, 10, 237, 171, 139, 28, 126, 179, 86, 50, 171, 239, 225, 42, 161, 218, 128, 237, 97, 243, 103, 233, 66, 161, 137, 233, 129, 93, 112, 183, 83, 80, 123, 202, 79, 121, 187, 209, 137, 24, 79, 217, 146, 206, 199, 32, 222, 137, 54, 87, 38, 211, 21, 197, 61, 203, 137, 41, 194, 88, 113, 189, 16, 12, 81, 62, 253, 191, 36, 165, 105, 11, 174, 231, 59, 232, 163, 34, 219, 140, 134, 59, 151, 16, 162, 190, 69, 234, 67, 152, 213, 120, 27, 3, 101, 71, 244, 199, 10, 136, 210, 73, 154, 37, 251, 228, 93, 65, 245, 13, 3, 0, 0, 0, 0, 0, 0, 0, 226, 135, 225, 22, 75, 113, 97, 45, 58, 123, 38, 36, 69, 91, 62, 251, 158, 39, 50, 196, 16, 201, 8, 162, 172, 121, 8, 111, 194, 130, 232, 166, 84, 170, 11, 235, 214, 61, 216, 81, 89, 56, 53, 39, 54, 60, 62, 232, 153, 248, 157, 246, 221, 131, 150, 102, 66, 162, 51, 7, 20, 36, 253, 30, 99, 32, 78, 40, 158, 152, 82, 49, 103, 79, 125, 6, 194, 166, 214, 22, 128, 87, 169, 74, 193, 48, 187, 56, 161, 0, 208, 18, 19, 100, 128, 14];
+        let pvk = groth16::prepare_verifying_key(&groth16::bn254(), &vk);
+        let inputs = groth16::public_proof_inputs_from_bytes(vector[3, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 235, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+        let proof = groth16::proof_points_from_bytes(vector[45, 169, 214, 239, 213, 232, 95, 205, 198, 150, 36, 150, 110, 214, 132, 9, 183, 131, 227, 186, 149, 231, 3, 98, 191, 170, 184, 103, 201, 75, 113, 155, 94, 32, 236, 79, 146, 67, 152, 172, 108, 135, 69, 32, 162, 217, 25, 201, 37, 125, 65, 207, 59, 234, 40, 146, 29, 110, 234, 130, 141, 154, 25, 17, 175, 119, 95, 180, 21, 7, 69, 192, 158, 155, 212, 132, 86, 61, 230, 133, 74, 178, 77, 108, 212, 132, 93, 34, 22, 66, 215, 252, 14, 177, 33, 165, 24, 96, 191, 22, 166, 91, 26, 112, 28, 222, 254, 185, 166, 206, 132, 71, 104, 207, 173, 228, 188, 70, 110, 37, 102, 33, 48, 204, 92, 39, 4, 48]);
+        assert!(groth16::verify_groth16_proof(&pvk, &inputs, &proof) == false, 0);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 2)]
+    fun test_verify_groth16_missing_public_inputs() {
+        let vk = vector[82, 227, 59, 243, 32, 163, 254, 185, 233, 101, 105, 29, 149, 15, 14, 47, 185, 166, 76, 111, 246, 106, 52, 252, 54, 82, 44, 144, 199, 207, 96, 149, 123, 230, 22, 163, 167, 6, 4, 112, 158, 92, 216, 16, 60, 194, 103, 3, 65, 146, 42, 154, 55, 169, 90, 223, 125, 44, 209, 136, 58, 53, 29, 38, 15, 47, 233, 186, 93, 232, 62, 15, 208, 209, 30, 4, 108, 151, 197, 192, 150, 50, 189, 57, 121, 253, 144, 38, 23, 216, 85, 173, 8, 15, 79, 148, 97, 100, 31, 68, 55, 19, 202, 244, 122, 168, 19, 125, 37, 16, 20, 174, 222, 108, 240, 10, 237, 171, 139, 28, 126, 179, 86, 50, 171, 239, 225, 42, 161, 218, 128, 237, 97, 243, 103, 233, 66, 161, 137, 233, 129, 93, 112, 183, 83, 80, 123, 202, 79, 121, 187, 209, 137, 24, 79, 217, 146, 206, 199, 32, 222, 137, 54, 87, 38, 211, 21, 197, 61, 203, 137, 41, 194, 88, 113, 189, 16, 12, 81, 62, 253, 191, 36, 165, 105, 11, 174, 231, 59, 232, 163, 34, 219, 140, 134, 59, 151, 16, 162, 190, 69, 234, 67, 152, 213, 120, 27, 3, 101, 71, 244, 199, 10, 136, 210, 73, 154, 37, 251, 228, 93, 65, 245, 13, 3, 0, 0, 0, 0, 0, 0, 0, 226, 135, 225, 22, 75, 113, 97, 45, 58, 123, 38, 36, 69, 91, 62, 251, 158, 39, 50, 196, 16, 201, 8, 162, 172, 121, 8, 111, 194, 130, 232, 166, 84, 170, 11, 235, 214, 61, 216, 81, 89, 56, 53, 39, 54, 60, 62, 232, 153, 248, 157, 246, 221, 131, 150, 102, 66, 162, 51, 7, 20, 36, 253, 30, 99, 32, 78, 40, 158, 152, 82, 49, 103, 79, 125, 6, 194, 166, 214, 22, 128, 87, 169, 74, 193, 48, 187, 56, 161, 0, 208, 18, 19, 100, 128, 14];
+        let pvk = groth16::prepare_verifying_key(&groth16::bn254(), &vk);
+        let inputs = groth16::public_proof_inputs_from_bytes(vector[2, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+        let proof = groth16::proof_points_from_bytes(vector[45, 169, 214, 239, 213, 232, 95, 205, 198, 150, 36, 150, 110, 214, 132, 9, 183, 131, 227, 186, 149, 231, 3, 98, 191, 170, 184, 103, 201, 75, 113, 155, 94, 32, 236, 79, 146, 67, 152, 172, 108, 135, 69, 32, 162, 217, 25, 201, 37, 125, 65, 207, 59, 234, 40, 146, 29, 110, 234, 130, 141, 154, 25, 17, 175, 119, 95, 180, 21, 7, 69, 192, 158, 155, 212, 132, 86, 61, 230, 133, 74, 178, 77, 108, 212, 132, 93, 34, 22, 66, 215, 252, 14, 177, 33, 165, 24, 96, 191, 22, 166, 91, 26, 112, 28, 222, 254, 185, 166, 206, 132, 71, 104, 207, 173, 228, 188, 70, 110, 37, 102, 33, 48, 204, 92, 39, 4, 48]);
+        assert!(groth16::verify_groth16_proof(&pvk, &inputs, &proof) == true, 0);
+    }
+
+    #[test]
+    fun test_curves() {
+        assert!(groth16::bls12381() != groth16::bn254(), 0);
+    }
+}
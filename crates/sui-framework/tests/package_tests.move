@@ -0,0 +1,35 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+#[test_only]
+module sui::package_tests {
+    use sui::object;
+    use sui::package;
+    use sui::tx_context;
+
+    #[test]
+    fun test_restrict_policy() {
+        let ctx = tx_context::dummy();
+        let package_id = object::id_from_address(@0x42);
+        let cap = package::test_publish(package_id, &mut ctx);
+        assert!(package::upgrade_package(&cap) == package_id, 0);
+        assert!(package::version(&cap) == 1, 0);
+        assert!(package::upgrade_policy(&cap) == package::compatible_policy(), 0);
+
+        package::only_additive_upgrades(&mut cap);
+        assert!(package::upgrade_policy(&cap) == package::additive_policy(), 0);
+        package::only_dep_upgrades(&mut cap);
+        assert!(package::upgrade_policy(&cap) == package::dep_only_policy(), 0);
+        package::make_immutable(cap);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 0)]
+    fun test_policy_cannot_be_relaxed() {
+        let ctx = tx_context::dummy();
+        let cap = package::test_publish(object::id_from_address(@0x42), &mut ctx);
+        package::only_dep_upgrades(&mut cap);
+        package::only_additive_upgrades(&mut cap);
+        package::make_immutable(cap);
+    }
+}
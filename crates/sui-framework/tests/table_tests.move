@@ -0,0 +1,66 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+#[test_only]
+module sui::table_tests {
+    use sui::table::{Self, add};
+    use sui::tx_context;
+
+    #[test]
+    fun simple_all_functions() {
+        let ctx = tx_context::dummy();
+        let table = table::new(&mut ctx);
+        // add fields
+        add(&mut table, b"hello", 0);
+        add(&mut table, b"goodbye", 1);
+        assert!(table::length(&table) == 2, 0);
+        // check they exist
+        assert!(table::contains(&table, b"hello"), 0);
+        assert!(table::contains(&table, b"goodbye"), 0);
+        // check the values
+        assert!(*table::borrow(&table, b"hello") == 0, 0);
+        assert!(*table::borrow(&table, b"goodbye") == 1, 0);
+        // mutate them
+        *table::borrow_mut(&mut table, b"hello") = *table::borrow(&table, b"hello") * 2;
+        *table::borrow_mut(&mut table, b"goodbye") = *table::borrow(&table, b"goodbye") * 2;
+        // check the new value
+        assert!(*table::borrow(&table, b"hello") == 0, 0);
+        assert!(*table::borrow(&table, b"goodbye") == 2, 0);
+        // remove the value and check it
+        assert!(table::remove(&mut table, b"hello") == 0, 0);
+        assert!(table::remove(&mut table, b"goodbye") == 2, 0);
+        // verify that they are not there
+        assert!(!table::contains(&table, b"hello"), 0);
+        assert!(!table::contains(&table, b"goodbye"), 0);
+        assert!(table::is_empty(&table), 0);
+        table::destroy_empty(table);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 0)]
+    fun add_duplicate() {
+        let ctx = tx_context::dummy();
+        let table = table::new(&mut ctx);
+        add(&mut table, b"hello", 0);
+        add(&mut table, b"hello", 1);
+        abort 42
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 1)]
+    fun borrow_missing() {
+        let ctx = tx_context::dummy();
+        let table = table::new<u64, u64>(&mut ctx);
+        table::borrow(&table, 0);
+        abort 42
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 0)]
+    fun destroy_non_empty() {
+        let ctx = tx_context::dummy();
+        let table = table::new(&mut ctx);
+        add(&mut table, 0, 0);
+        table::destroy_empty(table);
+    }
+}
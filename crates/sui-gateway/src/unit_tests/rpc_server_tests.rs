@@ -101,7 +101,8 @@ async fn test_publish() -> Result<(), anyhow::Error> {
     let tx_response = http_client
         .execute_transaction(tx_bytes, sig_scheme, signature_bytes, pub_key)
         .await?;
-    assert_eq!(6, tx_response.effects.created.len());
+    // The package, its UpgradeCap and the objects created by the module initializers.
+    assert_eq!(7, tx_response.effects.created.len());
     Ok(())
 }
 
@@ -9,6 +9,7 @@ use std::fmt;
 use std::fmt::Write;
 use std::fmt::{Display, Formatter};
 
+use anyhow::anyhow;
 use colored::Colorize;
 use itertools::Itertools;
 use move_binary_format::file_format::{Ability, AbilitySet, StructTypeParameter, Visibility};
@@ -34,18 +35,19 @@ use sui_types::base_types::{
     ObjectDigest, ObjectID, ObjectInfo, ObjectRef, SequenceNumber, SuiAddress, TransactionDigest,
     TransactionEffectsDigest,
 };
-use sui_types::committee::EpochId;
+use sui_types::committee::{EpochId, StakeUnit};
 use sui_types::crypto::{AuthorityStrongQuorumSignInfo, SignableBytes, Signature};
 use sui_types::error::SuiError;
 use sui_types::event::{Event, TransferType};
 use sui_types::event::{EventEnvelope, EventType};
 use sui_types::filter::{EventFilter, TransactionFilter};
-use sui_types::gas::GasCostSummary;
+use sui_types::gas::{GasCostSummary, GasProfile};
 use sui_types::gas_coin::GasCoin;
 use sui_types::messages::{
-    CallArg, CertifiedTransaction, CertifiedTransactionEffects, ExecuteTransactionResponse,
-    ExecutionStatus, InputObjectKind, MoveModulePublish, ObjectArg, SingleTransactionKind,
-    TransactionData, TransactionEffects, TransactionKind,
+    AuthenticatedEpoch, CallArg, CertifiedTransaction, CertifiedTransactionEffects,
+    ExecuteTransactionResponse, ExecutionStatus, InputObjectKind, MoveModulePublish,
+    MovePackageUpgrade, ObjectArg, SingleTransactionKind, TransactionData, TransactionEffects,
+    TransactionKind,
 };
 use sui_types::messages_checkpoint::CheckpointSequenceNumber;
 use sui_types::move_package::{disassemble_modules, MovePackage};
@@ -53,6 +55,10 @@ use sui_types::object::{
     Data, MoveObject, Object, ObjectFormatOptions, ObjectRead, Owner, PastObjectRead,
 };
 use sui_types::sui_serde::{Base64, Encoding};
+use sui_types::sui_system_state::{
+    Delegation, EpochRewardRecord, SuiSystemState, SystemParameters, Validator, ValidatorMetadata,
+    ValidatorSet,
+};
 
 #[cfg(test)]
 #[path = "unit_tests/rpc_types_tests.rs"]
@@ -363,9 +369,14 @@ pub enum SuiExecuteTransactionResponse {
         certificate: SuiCertifiedTransaction,
     },
     // TODO: Change to CertifiedTransactionEffects eventually.
+    #[serde(rename_all = "camelCase")]
     EffectsCert {
         certificate: SuiCertifiedTransaction,
         effects: SuiCertifiedTransactionEffects,
+        /// Whether the node serving the request executed the transaction before responding,
+        /// which is only waited for with `WaitForLocalExecution`.
+        #[serde(default)]
+        confirmed_local_execution: bool,
     },
 }
 
@@ -373,6 +384,7 @@ impl SuiExecuteTransactionResponse {
     pub fn from_execute_transaction_response(
         resp: ExecuteTransactionResponse,
         tx_digest: TransactionDigest,
+        confirmed_local_execution: bool,
         resolver: &impl GetModule,
     ) -> Result<Self, anyhow::Error> {
         Ok(match resp {
@@ -392,6 +404,7 @@ impl SuiExecuteTransactionResponse {
                 SuiExecuteTransactionResponse::EffectsCert {
                     certificate,
                     effects,
+                    confirmed_local_execution,
                 }
             }
         })
@@ -471,6 +484,9 @@ pub struct SuiObject<T: SuiData> {
     /// the present storage gas price.
     pub storage_rebate: u64,
     pub reference: SuiObjectRef,
+    /// The object rendered with the `sui::display::Display` of its type, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub display: Option<BTreeMap<String, String>>,
 }
 
 impl TryInto<Object> for SuiObject<SuiRawData> {
@@ -596,6 +612,7 @@ impl<T: SuiData> SuiObject<T> {
             previous_transaction: o.previous_transaction,
             storage_rebate: o.storage_rebate,
             reference: oref.into(),
+            display: None,
         })
     }
 }
@@ -781,6 +798,18 @@ impl SuiMoveObject for SuiParsedMoveObject {
 }
 
 impl SuiParsedMoveObject {
+    /// Renders this object with the `sui::display` templates of its type, keyed by display field
+    /// name. Display fields whose template does not apply to the object are left out.
+    pub fn render_display(&self, templates: &BTreeMap<String, String>) -> BTreeMap<String, String> {
+        templates
+            .iter()
+            .filter_map(|(name, template)| {
+                let rendered = self.fields.render(template).ok()?;
+                Some((name.clone(), rendered))
+            })
+            .collect()
+    }
+
     fn try_type_and_fields_from_move_struct(
         type_: &StructTag,
         move_struct: MoveStruct,
@@ -947,6 +976,29 @@ impl Display for SuiParsedPublishResponse {
 pub type GetObjectDataResponse = SuiObjectRead<SuiParsedData>;
 pub type GetRawObjectDataResponse = SuiObjectRead<SuiRawData>;
 
+/// A transaction with its effects, and the checkpoint proving the effects, in BCS.
+#[serde_as]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Eq, PartialEq)]
+#[serde(rename_all = "camelCase", rename = "RawTransaction")]
+pub struct SuiRawTransaction {
+    /// The certified transaction
+    #[serde_as(as = "Base64")]
+    #[schemars(with = "Base64")]
+    pub certificate: Vec<u8>,
+    /// The effects of the transaction, as executed by the node
+    #[serde_as(as = "Base64")]
+    #[schemars(with = "Base64")]
+    pub effects: Vec<u8>,
+    /// The certified checkpoint including the transaction, unset until the transaction is checkpointed
+    #[serde_as(as = "Option<Base64>")]
+    #[schemars(with = "Option<Base64>")]
+    pub checkpoint: Option<Vec<u8>>,
+    /// The contents of the checkpoint, set along with the checkpoint
+    #[serde_as(as = "Option<Base64>")]
+    #[schemars(with = "Option<Base64>")]
+    pub checkpoint_contents: Option<Vec<u8>>,
+}
+
 #[derive(Serialize, Deserialize, Debug, JsonSchema, Clone)]
 #[serde(tag = "status", content = "details", rename = "ObjectRead")]
 pub enum SuiObjectRead<T: SuiData> {
@@ -1152,6 +1204,27 @@ impl Display for SuiMoveValue {
     }
 }
 
+impl SuiMoveValue {
+    /// The text a `sui::display` template shows for this value. Only values with an obvious text
+    /// form can be displayed.
+    fn render(&self) -> Result<String, anyhow::Error> {
+        Ok(match self {
+            SuiMoveValue::Number(value) => value.to_string(),
+            SuiMoveValue::Bool(value) => value.to_string(),
+            SuiMoveValue::Address(value) => value.to_string(),
+            SuiMoveValue::String(value) => value.clone(),
+            SuiMoveValue::UID { id } => id.to_string(),
+            SuiMoveValue::Option(value) => match value.as_ref() {
+                Some(value) => value.render()?,
+                None => String::new(),
+            },
+            SuiMoveValue::Vector(_) | SuiMoveValue::Bytearray(_) | SuiMoveValue::Struct(_) => {
+                return Err(anyhow!("Only primitive values can be displayed"))
+            }
+        })
+    }
+}
+
 impl From<MoveValue> for SuiMoveValue {
     fn from(value: MoveValue) -> Self {
         match value {
@@ -1241,6 +1314,66 @@ impl SuiMoveStruct {
         }?;
         serde_json::to_value(&unwrapped)
     }
+
+    /// Returns the value at `path`, a `.` separated list of field names.
+    pub fn field(&self, path: &str) -> Option<&SuiMoveValue> {
+        let mut fields = self.fields()?;
+        let mut names = path.split('.').peekable();
+        while let Some(name) = names.next() {
+            let value = fields.get(name)?;
+            if names.peek().is_none() {
+                return Some(value);
+            }
+            fields = match value {
+                SuiMoveValue::Struct(move_struct) => move_struct.fields()?,
+                _ => return None,
+            };
+        }
+        None
+    }
+
+    fn fields(&self) -> Option<&BTreeMap<String, SuiMoveValue>> {
+        match self {
+            SuiMoveStruct::Runtime(_) => None,
+            SuiMoveStruct::WithTypes { fields, .. } | SuiMoveStruct::WithFields(fields) => {
+                Some(fields)
+            }
+        }
+    }
+
+    /// Renders a `sui::display` template, replacing each `{path}` with the value of the field at
+    /// `path`. `{{` and `}}` stand for literal braces.
+    pub fn render(&self, template: &str) -> Result<String, anyhow::Error> {
+        let mut rendered = String::new();
+        let mut chars = template.chars();
+        while let Some(c) = chars.next() {
+            match c {
+                '{' if chars.as_str().starts_with('{') => {
+                    chars.next();
+                    rendered.push('{');
+                }
+                '}' if chars.as_str().starts_with('}') => {
+                    chars.next();
+                    rendered.push('}');
+                }
+                '{' => {
+                    let rest = chars.as_str();
+                    let end = rest
+                        .find('}')
+                        .ok_or_else(|| anyhow!("Unclosed '{{' in template {template:?}"))?;
+                    let path = &rest[..end];
+                    let value = self
+                        .field(path)
+                        .ok_or_else(|| anyhow!("Field {path:?} not found"))?;
+                    rendered.push_str(&value.render()?);
+                    chars = rest[end + 1..].chars();
+                }
+                '}' => return Err(anyhow!("Unmatched '}}' in template {template:?}")),
+                c => rendered.push(c),
+            }
+        }
+        Ok(rendered)
+    }
 }
 
 impl Display for SuiMoveStruct {
@@ -1367,6 +1500,28 @@ impl TryFrom<MoveModulePublish> for SuiMovePackage {
     }
 }
 
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Eq, PartialEq)]
+#[serde(rename = "MovePackageUpgrade", rename_all = "camelCase")]
+pub struct SuiMovePackageUpgrade {
+    pub package: ObjectID,
+    pub upgrade_cap: SuiObjectRef,
+    pub disassembled: BTreeMap<String, Value>,
+    pub dependencies: Vec<ObjectID>,
+}
+
+impl TryFrom<MovePackageUpgrade> for SuiMovePackageUpgrade {
+    type Error = anyhow::Error;
+
+    fn try_from(u: MovePackageUpgrade) -> Result<Self, Self::Error> {
+        Ok(Self {
+            package: u.package,
+            upgrade_cap: u.upgrade_cap.into(),
+            disassembled: disassemble_modules(u.modules.iter())?,
+            dependencies: u.dependencies,
+        })
+    }
+}
+
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Clone)]
 #[serde(rename = "TransactionData", rename_all = "camelCase")]
 pub struct SuiTransactionData {
@@ -1427,6 +1582,8 @@ pub enum SuiTransactionKind {
     TransferSui(SuiTransferSui),
     /// A system transaction that will update epoch information on-chain.
     ChangeEpoch(SuiChangeEpoch),
+    /// Publish a new version of a Move package
+    Upgrade(SuiMovePackageUpgrade),
     // .. more transaction types go here
 }
 
@@ -1457,6 +1614,11 @@ impl Display for SuiTransactionKind {
             Self::Publish(_p) => {
                 write!(writer, "Transaction Kind : Publish")?;
             }
+            Self::Upgrade(u) => {
+                writeln!(writer, "Transaction Kind : Upgrade")?;
+                writeln!(writer, "Package ID : {}", u.package.to_hex_literal())?;
+                write!(writer, "Upgrade Cap : {}", u.upgrade_cap.object_id)?;
+            }
             Self::Call(c) => {
                 writeln!(writer, "Transaction Kind : Call")?;
                 writeln!(
@@ -1494,6 +1656,7 @@ impl TryFrom<SingleTransactionKind> for SuiTransactionKind {
                 amount: t.amount,
             }),
             SingleTransactionKind::Publish(p) => Self::Publish(p.try_into()?),
+            SingleTransactionKind::Upgrade(u) => Self::Upgrade(u.try_into()?),
             SingleTransactionKind::Call(c) => Self::Call(SuiMoveCall {
                 package: c.package.into(),
                 module: c.module.to_string(),
@@ -1814,6 +1977,38 @@ impl From<GasCostSummary> for SuiGasCostSummary {
     }
 }
 
+/// Where the gas of a transaction goes, in internal gas units.
+#[derive(Eq, PartialEq, Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename = "GasProfile", rename_all = "camelCase")]
+pub struct SuiGasProfile {
+    /// The gas charged by each Move function's own instructions and natives, excluding its callees.
+    pub function_costs: BTreeMap<String, u64>,
+    /// The gas charged by each instruction, across functions, and by each charge made outside of
+    /// Move, e.g. `[storage]`.
+    pub instruction_costs: BTreeMap<String, u64>,
+    /// The gas charged by each stack, in the folded format of flamegraph tools.
+    pub folded_stacks: String,
+}
+
+impl From<GasProfile> for SuiGasProfile {
+    fn from(profile: GasProfile) -> Self {
+        Self {
+            function_costs: profile.function_costs().clone(),
+            instruction_costs: profile.instruction_costs().clone(),
+            folded_stacks: profile.to_folded_stacks(),
+        }
+    }
+}
+
+/// The effects a transaction would have, from executing it without committing it.
+#[derive(Eq, PartialEq, Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename = "DryRunTransactionResponse", rename_all = "camelCase")]
+pub struct SuiDryRunTransactionResponse {
+    pub effects: SuiTransactionEffects,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gas_profile: Option<SuiGasProfile>,
+}
+
 #[derive(Eq, PartialEq, Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename = "ObjectRef")]
 pub struct OwnedObjectRef {
@@ -2338,3 +2533,275 @@ impl TransactionBytes {
         TransactionData::from_signable_bytes(&self.tx_bytes.to_vec()?)
     }
 }
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Eq, PartialEq)]
+#[serde(rename = "SystemParameters")]
+pub struct SuiSystemParameters {
+    pub min_validator_stake: u64,
+    pub max_validator_candidate_count: u64,
+    pub storage_gas_price: u64,
+}
+
+impl From<SystemParameters> for SuiSystemParameters {
+    fn from(parameters: SystemParameters) -> Self {
+        Self {
+            min_validator_stake: parameters.min_validator_stake,
+            max_validator_candidate_count: parameters.max_validator_candidate_count,
+            storage_gas_price: parameters.storage_gas_price,
+        }
+    }
+}
+
+#[serde_as]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Eq, PartialEq)]
+#[serde(rename = "ValidatorMetadata")]
+pub struct SuiValidatorMetadata {
+    pub sui_address: SuiAddress,
+    #[serde_as(as = "Base64")]
+    #[schemars(with = "Base64")]
+    pub pubkey_bytes: Vec<u8>,
+    #[serde_as(as = "Base64")]
+    #[schemars(with = "Base64")]
+    pub network_pubkey_bytes: Vec<u8>,
+    #[serde_as(as = "Base64")]
+    #[schemars(with = "Base64")]
+    pub proof_of_possession_bytes: Vec<u8>,
+    pub name: String,
+    #[serde_as(as = "Base64")]
+    #[schemars(with = "Base64")]
+    pub net_address: Vec<u8>,
+    /// Validator stake that will be active in the next epoch
+    pub next_epoch_stake: u64,
+    /// Delegated stake that will be active in the next epoch
+    pub next_epoch_delegation: u64,
+    /// Gas price quote for the next epoch
+    pub next_epoch_gas_price: u64,
+}
+
+impl From<ValidatorMetadata> for SuiValidatorMetadata {
+    fn from(metadata: ValidatorMetadata) -> Self {
+        Self {
+            sui_address: SuiAddress::from(ObjectID::from(metadata.sui_address)),
+            pubkey_bytes: metadata.pubkey_bytes,
+            network_pubkey_bytes: metadata.network_pubkey_bytes,
+            proof_of_possession_bytes: metadata.proof_of_possession_bytes,
+            name: String::from_utf8_lossy(&metadata.name).into_owned(),
+            net_address: metadata.net_address,
+            next_epoch_stake: metadata.next_epoch_stake,
+            next_epoch_delegation: metadata.next_epoch_delegation,
+            next_epoch_gas_price: metadata.next_epoch_gas_price,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Eq, PartialEq)]
+#[serde(rename = "Validator")]
+pub struct SuiValidator {
+    pub metadata: SuiValidatorMetadata,
+    /// Validator stake active in the current epoch
+    pub stake_amount: u64,
+    /// Delegated stake active in the current epoch
+    pub delegation: u64,
+    pub pending_stake: u64,
+    pub pending_withdraw: u64,
+    pub pending_delegation: u64,
+    pub pending_delegation_withdraw: u64,
+    pub delegator_count: u64,
+    pub pending_delegator_count: u64,
+    pub pending_delegator_withdraw_count: u64,
+    /// Gas price quote for the current epoch
+    pub gas_price: u64,
+}
+
+impl From<Validator> for SuiValidator {
+    fn from(validator: Validator) -> Self {
+        Self {
+            metadata: validator.metadata.into(),
+            stake_amount: validator.stake_amount,
+            delegation: validator.delegation,
+            pending_stake: validator.pending_stake,
+            pending_withdraw: validator.pending_withdraw,
+            pending_delegation: validator.pending_delegation,
+            pending_delegation_withdraw: validator.pending_delegation_withdraw,
+            delegator_count: validator.delegator_count,
+            pending_delegator_count: validator.pending_delegator_count,
+            pending_delegator_withdraw_count: validator.pending_delegator_withdraw_count,
+            gas_price: validator.gas_price,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Eq, PartialEq)]
+#[serde(rename = "ValidatorSet")]
+pub struct SuiValidatorSet {
+    pub total_validator_stake: u64,
+    pub total_delegation_stake: u64,
+    pub quorum_stake_threshold: u64,
+    pub active_validators: Vec<SuiValidator>,
+    /// Validators joining at the end of the epoch
+    pub pending_validators: Vec<SuiValidator>,
+    /// Indexes into `active_validators` of the validators leaving at the end of the epoch
+    pub pending_removals: Vec<u64>,
+    pub next_epoch_validators: Vec<SuiValidatorMetadata>,
+}
+
+impl From<ValidatorSet> for SuiValidatorSet {
+    fn from(validators: ValidatorSet) -> Self {
+        Self {
+            total_validator_stake: validators.validator_stake,
+            total_delegation_stake: validators.delegation_stake,
+            quorum_stake_threshold: validators.quorum_stake_threshold,
+            active_validators: validators
+                .active_validators
+                .into_iter()
+                .map(SuiValidator::from)
+                .collect(),
+            pending_validators: validators
+                .pending_validators
+                .into_iter()
+                .map(SuiValidator::from)
+                .collect(),
+            pending_removals: validators.pending_removals,
+            next_epoch_validators: validators
+                .next_epoch_validators
+                .into_iter()
+                .map(SuiValidatorMetadata::from)
+                .collect(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Eq, PartialEq)]
+#[serde(rename = "SystemState")]
+pub struct SuiSystemStateSummary {
+    pub epoch: EpochId,
+    pub validators: SuiValidatorSet,
+    pub total_supply: u64,
+    pub storage_fund: u64,
+    pub parameters: SuiSystemParameters,
+    /// Delegation rewards not claimed yet
+    pub delegation_reward: u64,
+    pub reference_gas_price: u64,
+}
+
+impl From<SuiSystemState> for SuiSystemStateSummary {
+    fn from(state: SuiSystemState) -> Self {
+        Self {
+            epoch: state.epoch,
+            validators: state.validators.into(),
+            total_supply: state.treasury_cap.value,
+            storage_fund: state.storage_fund.value(),
+            parameters: state.parameters.into(),
+            delegation_reward: state.delegation_reward.value(),
+            reference_gas_price: state.reference_gas_price,
+        }
+    }
+}
+
+#[serde_as]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Eq, PartialEq)]
+#[serde(rename = "CommitteeMember")]
+pub struct SuiCommitteeMember {
+    /// The public key of the authority
+    #[serde_as(as = "Base64")]
+    #[schemars(with = "Base64")]
+    pub authority_name: Vec<u8>,
+    pub stake: StakeUnit,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Eq, PartialEq)]
+#[serde(rename = "EpochInfo")]
+pub struct SuiEpochInfo {
+    pub epoch: EpochId,
+    pub committee: Vec<SuiCommitteeMember>,
+    pub first_checkpoint: CheckpointSequenceNumber,
+    /// False until a quorum of the previous committee signed the epoch
+    pub certified: bool,
+    /// Unset when the node does not know the economics of the epoch
+    pub reference_gas_price: Option<u64>,
+    /// Storage fund during the epoch, unset when the node does not know the economics of the epoch
+    pub storage_fund: Option<u64>,
+    /// Computation rewards distributed at the end of the epoch, unset while the epoch is in progress
+    pub rewards_distributed: Option<u64>,
+}
+
+impl From<AuthenticatedEpoch> for SuiEpochInfo {
+    fn from(epoch: AuthenticatedEpoch) -> Self {
+        let certified = !matches!(epoch, AuthenticatedEpoch::Signed(_));
+        let epoch_info = epoch.into_epoch_info();
+        Self {
+            epoch: epoch_info.epoch(),
+            first_checkpoint: *epoch_info.first_checkpoint(),
+            committee: epoch_info
+                .into_committee()
+                .voting_rights
+                .into_iter()
+                .map(|(name, stake)| SuiCommitteeMember {
+                    authority_name: name.as_ref().to_vec(),
+                    stake,
+                })
+                .collect(),
+            certified,
+            reference_gas_price: None,
+            storage_fund: None,
+            rewards_distributed: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Eq, PartialEq)]
+#[serde(rename = "Delegation")]
+pub struct SuiDelegation {
+    pub id: ObjectID,
+    pub validator_address: SuiAddress,
+    pub delegate_amount: u64,
+    /// False once the delegation has been withdrawn or switched to another validator
+    pub active: bool,
+    /// The epoch the delegation was withdrawn, if it is no longer active
+    pub ending_epoch: Option<EpochId>,
+    pub next_reward_unclaimed_epoch: EpochId,
+    /// Set if the delegation was made from a locked coin
+    pub coin_locked_until_epoch: Option<EpochId>,
+}
+
+impl From<Delegation> for SuiDelegation {
+    fn from(delegation: Delegation) -> Self {
+        Self {
+            id: *delegation.id.object_id(),
+            validator_address: SuiAddress::from(ObjectID::from(delegation.validator_address)),
+            delegate_amount: delegation.delegate_amount,
+            active: delegation.is_active(),
+            ending_epoch: delegation.ending_epoch.as_option().copied(),
+            next_reward_unclaimed_epoch: delegation.next_reward_unclaimed_epoch,
+            coin_locked_until_epoch: delegation
+                .coin_locked_until_epoch
+                .as_option()
+                .map(|lock| lock.epoch),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Eq, PartialEq)]
+#[serde(rename = "EpochRewardRecord")]
+pub struct SuiEpochRewardRecord {
+    pub id: ObjectID,
+    pub epoch: EpochId,
+    pub computation_charge: u64,
+    pub total_stake: u64,
+    /// The number of delegators that have not claimed their reward yet
+    pub delegator_count: u64,
+    pub validator: SuiAddress,
+}
+
+impl From<EpochRewardRecord> for SuiEpochRewardRecord {
+    fn from(record: EpochRewardRecord) -> Self {
+        Self {
+            id: *record.id.object_id(),
+            epoch: record.epoch,
+            computation_charge: record.computation_charge,
+            total_stake: record.total_stake,
+            delegator_count: record.delegator_count,
+            validator: SuiAddress::from(ObjectID::from(record.validator)),
+        }
+    }
+}
@@ -5,6 +5,7 @@ use anyhow::anyhow;
 use move_core_types::ident_str;
 use move_core_types::language_storage::StructTag;
 use move_core_types::value::{MoveStruct, MoveValue};
+use std::collections::BTreeMap;
 
 use crate::{SuiMoveStruct, SuiMoveValue};
 use sui_types::base_types::SequenceNumber;
@@ -129,3 +130,30 @@ fn test_serde() {
         )
     }
 }
+
+#[test]
+fn test_render_display_template() {
+    let id = ObjectID::random();
+    let capy = SuiMoveStruct::WithFields(BTreeMap::from([
+        ("id".to_string(), SuiMoveValue::UID { id }),
+        ("name".to_string(), SuiMoveValue::String("Capy".to_string())),
+        (
+            "attributes".to_string(),
+            SuiMoveValue::Struct(SuiMoveStruct::WithFields(BTreeMap::from([(
+                "level".to_string(),
+                SuiMoveValue::Number(3),
+            )]))),
+        ),
+    ]));
+
+    assert_eq!(capy.render("{name} #{id}").unwrap(), format!("Capy #{id}"));
+    assert_eq!(
+        capy.render("{name} is level {attributes.level}").unwrap(),
+        "Capy is level 3"
+    );
+    assert_eq!(capy.render("{{name}}").unwrap(), "{name}");
+    assert!(capy.render("{unknown}").is_err());
+    assert!(capy.render("{attributes}").is_err());
+    assert!(capy.render("{name").is_err());
+    assert!(capy.render("name}").is_err());
+}
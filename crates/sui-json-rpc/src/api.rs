@@ -8,13 +8,16 @@ use jsonrpsee_proc_macros::rpc;
 use sui_json::SuiJsonValue;
 use sui_json_rpc_types::{
     GatewayTxSeqNumber, GetObjectDataResponse, GetPastObjectDataResponse, GetRawObjectDataResponse,
-    MoveFunctionArgType, RPCTransactionRequestParams, SuiEventEnvelope, SuiEventFilter,
+    MoveFunctionArgType, RPCTransactionRequestParams, SuiDelegation, SuiDryRunTransactionResponse,
+    SuiEpochInfo, SuiEpochRewardRecord, SuiEventEnvelope, SuiEventFilter,
     SuiExecuteTransactionResponse, SuiGasCostSummary, SuiMoveNormalizedFunction,
-    SuiMoveNormalizedModule, SuiMoveNormalizedStruct, SuiObjectInfo, SuiTransactionFilter,
-    SuiTransactionResponse, SuiTypeTag, TransactionBytes,
+    SuiMoveNormalizedModule, SuiMoveNormalizedStruct, SuiObjectInfo, SuiRawTransaction,
+    SuiSystemStateSummary, SuiTransactionFilter, SuiTransactionResponse, SuiTypeTag, SuiValidator,
+    TransactionBytes,
 };
 use sui_open_rpc_macros::open_rpc;
 use sui_types::base_types::{ObjectID, SequenceNumber, SuiAddress, TransactionDigest};
+use sui_types::committee::EpochId;
 use sui_types::crypto::SignatureScheme;
 use sui_types::messages::ExecuteTransactionRequestType;
 use sui_types::object::Owner;
@@ -216,6 +219,23 @@ pub trait RpcFullNodeReadApi {
         /// the version of the queried object. If None, default to the latest known version
         version: SequenceNumber,
     ) -> RpcResult<GetPastObjectDataResponse>;
+
+    /// Execute the transaction against the latest state of this node without committing it,
+    /// and return the effects it would have. The signature is not checked.
+    #[method(name = "dryRunTransaction")]
+    async fn dry_run_transaction(
+        &self,
+        /// transaction data bytes, as base-64 encoded string
+        tx_bytes: Base64,
+        /// Flag of the signature scheme that is used.
+        sig_scheme: SignatureScheme,
+        /// transaction signature, as base-64 encoded string
+        signature: Base64,
+        /// signer's public key, as base-64 encoded string
+        pub_key: Base64,
+        /// whether to also return where the gas of the transaction goes
+        profile_gas: bool,
+    ) -> RpcResult<SuiDryRunTransactionResponse>;
 }
 
 #[open_rpc(namespace = "sui", tag = "Transaction Builder API")]
@@ -363,6 +383,23 @@ pub trait RpcBcsApi {
         /// the id of the object
         object_id: ObjectID,
     ) -> RpcResult<GetRawObjectDataResponse>;
+
+    /// Return the BCS serialized certificate and effects of a transaction, and the certified
+    /// checkpoint including the transaction once there is one.
+    #[method(name = "getRawTransaction")]
+    async fn get_raw_transaction(
+        &self,
+        /// the digest of the transaction
+        digest: TransactionDigest,
+    ) -> RpcResult<SuiRawTransaction>;
+
+    /// Return the BCS serialized authenticated epoch, signed by the committee of the previous epoch.
+    #[method(name = "getRawEpoch")]
+    async fn get_raw_epoch(
+        &self,
+        /// the epoch to query, the latest epoch known to the node if not set
+        epoch: Option<EpochId>,
+    ) -> RpcResult<Option<Base64>>;
 }
 
 #[open_rpc(namespace = "sui", tag = "Transaction Subscription")]
@@ -521,3 +558,49 @@ pub trait EstimatorApi {
         tx_bytes: Base64,
     ) -> RpcResult<SuiGasCostSummary>;
 }
+
+#[open_rpc(namespace = "sui", tag = "Governance Read API")]
+#[rpc(server, client, namespace = "sui")]
+pub trait GovernanceReadApi {
+    /// Return the state of the Sui system object, including the validator set.
+    #[method(name = "getSuiSystemState")]
+    async fn get_sui_system_state(&self) -> RpcResult<SuiSystemStateSummary>;
+
+    /// Return the active validators, with their stake, delegation, gas price and next epoch values.
+    #[method(name = "getValidators")]
+    async fn get_validators(&self) -> RpcResult<Vec<SuiValidator>>;
+
+    /// Return the delegations owned by an address.
+    #[method(name = "getDelegatedStakes")]
+    async fn get_delegated_stakes(
+        &self,
+        /// the owner's Sui address
+        owner: SuiAddress,
+    ) -> RpcResult<Vec<SuiDelegation>>;
+
+    /// Return the reward records of the epochs from `from_epoch` on, oldest first.
+    #[method(name = "getEpochRewardRecords")]
+    async fn get_epoch_reward_records(
+        &self,
+        /// only return the records of this validator, if set
+        validator: Option<SuiAddress>,
+        /// the first epoch to return records for
+        from_epoch: EpochId,
+    ) -> RpcResult<Vec<SuiEpochRewardRecord>>;
+
+    /// Return the committee and economics of an epoch.
+    #[method(name = "getEpochInfo")]
+    async fn get_epoch_info(
+        &self,
+        /// the epoch to query, the latest epoch known to the node if not set
+        epoch: Option<EpochId>,
+    ) -> RpcResult<SuiEpochInfo>;
+}
+
+#[open_rpc(namespace = "sui", tag = "Epoch Subscription")]
+#[rpc(server, client, namespace = "sui")]
+pub trait EpochStreamingApi {
+    /// Subscribe to epoch changes, receiving the information of every new epoch once the node moved to it
+    #[subscription(name = "subscribeEpochChange", item = SuiEpochInfo)]
+    fn subscribe_epoch_change(&self);
+}
@@ -11,9 +11,11 @@ use jsonrpsee::core::RpcResult;
 use jsonrpsee::RpcModule;
 use sui_core::authority::AuthorityState;
 use sui_core::gateway_state::GatewayClient;
-use sui_json_rpc_types::GetRawObjectDataResponse;
+use sui_json_rpc_types::{GetRawObjectDataResponse, SuiRawTransaction};
 use sui_open_rpc::Module;
-use sui_types::base_types::ObjectID;
+use sui_types::base_types::{ExecutionDigests, ObjectID, TransactionDigest};
+use sui_types::committee::EpochId;
+use sui_types::sui_serde::Base64;
 
 pub struct BcsApiImpl {
     client: ClientStateAdaptor,
@@ -52,6 +54,55 @@ impl ClientStateAdaptor {
                 .try_into(),
         }
     }
+
+    async fn get_raw_transaction(
+        &self,
+        digest: TransactionDigest,
+    ) -> Result<SuiRawTransaction, anyhow::Error> {
+        let state = match self {
+            ClientStateAdaptor::Gateway(_) => {
+                return Err(anyhow!("Raw transactions are not supported by the gateway"))
+            }
+            ClientStateAdaptor::FullNode(state) => state,
+        };
+        let (certificate, effects) = state.get_transaction(digest).await?;
+        let execution_digests = ExecutionDigests::new(digest, effects.digest());
+        let checkpoint = match state.checkpoints() {
+            Some(checkpoints) => checkpoints
+                .lock()
+                .get_checkpoint_of_transaction(&execution_digests)?,
+            None => None,
+        };
+        let (checkpoint, checkpoint_contents) = match checkpoint {
+            Some((checkpoint, contents)) => (
+                Some(bcs::to_bytes(&checkpoint)?),
+                Some(bcs::to_bytes(&contents)?),
+            ),
+            None => (None, None),
+        };
+        Ok(SuiRawTransaction {
+            certificate: bcs::to_bytes(&certificate)?,
+            effects: bcs::to_bytes(&effects)?,
+            checkpoint,
+            checkpoint_contents,
+        })
+    }
+
+    async fn get_raw_epoch(&self, epoch: Option<EpochId>) -> Result<Option<Base64>, anyhow::Error> {
+        let state = match self {
+            ClientStateAdaptor::Gateway(_) => {
+                return Err(anyhow!("Raw epochs are not supported by the gateway"))
+            }
+            ClientStateAdaptor::FullNode(state) => state,
+        };
+        let epoch = match epoch {
+            Some(epoch) => state.epoch_store().get_authenticated_epoch(&epoch)?,
+            None => Some(state.epoch_store().get_latest_authenticated_epoch()),
+        };
+        epoch
+            .map(|epoch| Ok(Base64::from_bytes(&bcs::to_bytes(&epoch)?)))
+            .transpose()
+    }
 }
 
 #[async_trait]
@@ -59,6 +110,14 @@ impl RpcBcsApiServer for BcsApiImpl {
     async fn get_raw_object(&self, object_id: ObjectID) -> RpcResult<GetRawObjectDataResponse> {
         Ok(self.client.get_raw_object(object_id).await?)
     }
+
+    async fn get_raw_transaction(&self, digest: TransactionDigest) -> RpcResult<SuiRawTransaction> {
+        Ok(self.client.get_raw_transaction(digest).await?)
+    }
+
+    async fn get_raw_epoch(&self, epoch: Option<EpochId>) -> RpcResult<Option<Base64>> {
+        Ok(self.client.get_raw_epoch(epoch).await?)
+    }
 }
 
 impl SuiRpcModule for BcsApiImpl {
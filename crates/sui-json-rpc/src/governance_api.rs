@@ -0,0 +1,250 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::api::{EpochStreamingApiServer, GovernanceReadApiServer};
+use crate::streaming_api::spawn_subscription;
+use crate::SuiRpcModule;
+use anyhow::anyhow;
+use async_trait::async_trait;
+use futures::StreamExt;
+use jsonrpsee::core::RpcResult;
+use jsonrpsee::types::SubscriptionResult;
+use jsonrpsee_core::server::rpc_module::{RpcModule, SubscriptionSink};
+use std::sync::Arc;
+use sui_core::authority::AuthorityState;
+use sui_json_rpc_types::{
+    SuiDelegation, SuiEpochInfo, SuiEpochRewardRecord, SuiSystemStateSummary, SuiValidator,
+};
+use sui_open_rpc::Module;
+use sui_types::base_types::SuiAddress;
+use sui_types::committee::EpochId;
+use sui_types::messages::{AuthenticatedEpoch, SingleTransactionKind};
+use sui_types::object::{ObjectRead, Owner};
+use sui_types::sui_system_state::{Delegation, EpochRewardRecord};
+use sui_types::SUI_SYSTEM_STATE_OBJECT_ID;
+use tokio::sync::broadcast::error::RecvError;
+use tracing::warn;
+
+pub struct GovernanceReadApi {
+    pub state: Arc<AuthorityState>,
+}
+
+impl GovernanceReadApi {
+    pub fn new(state: Arc<AuthorityState>) -> Self {
+        Self { state }
+    }
+}
+
+#[async_trait]
+impl GovernanceReadApiServer for GovernanceReadApi {
+    async fn get_sui_system_state(&self) -> RpcResult<SuiSystemStateSummary> {
+        Ok(self
+            .state
+            .get_sui_system_state_object()
+            .await
+            .map_err(|e| anyhow!("{e}"))?
+            .into())
+    }
+
+    async fn get_validators(&self) -> RpcResult<Vec<SuiValidator>> {
+        Ok(self
+            .state
+            .get_sui_system_state_object()
+            .await
+            .map_err(|e| anyhow!("{e}"))?
+            .validators
+            .active_validators
+            .into_iter()
+            .map(SuiValidator::from)
+            .collect())
+    }
+
+    async fn get_delegated_stakes(&self, owner: SuiAddress) -> RpcResult<Vec<SuiDelegation>> {
+        let delegation_type = Delegation::type_().to_string();
+        let mut delegations = Vec::new();
+        for info in self
+            .state
+            .get_owner_objects(Owner::AddressOwner(owner))
+            .map_err(|e| anyhow!("{e}"))?
+        {
+            if info.type_ != delegation_type {
+                continue;
+            }
+            // The object can be mutated or deleted after the owner index was read.
+            if let ObjectRead::Exists(_, object, _) = self
+                .state
+                .get_object_read(&info.object_id)
+                .await
+                .map_err(|e| anyhow!("{e}"))?
+            {
+                if let Some(move_object) = object.data.try_as_move() {
+                    let delegation: Delegation =
+                        bcs::from_bytes(move_object.contents()).map_err(|e| anyhow!("{e}"))?;
+                    delegations.push(delegation.into());
+                }
+            }
+        }
+        Ok(delegations)
+    }
+
+    async fn get_epoch_reward_records(
+        &self,
+        validator: Option<SuiAddress>,
+        from_epoch: EpochId,
+    ) -> RpcResult<Vec<SuiEpochRewardRecord>> {
+        Ok(get_epoch_reward_records(&self.state, validator, from_epoch).await?)
+    }
+
+    async fn get_epoch_info(&self, epoch: Option<EpochId>) -> RpcResult<SuiEpochInfo> {
+        let epoch_store = self.state.epoch_store();
+        let authenticated_epoch = match epoch {
+            Some(epoch) => epoch_store
+                .get_authenticated_epoch(&epoch)
+                .map_err(|e| anyhow!("{e}"))?
+                .ok_or_else(|| anyhow!("Epoch {epoch} is unknown to this node"))?,
+            None => epoch_store.get_latest_authenticated_epoch(),
+        };
+        Ok(get_epoch_info(&self.state, authenticated_epoch).await?)
+    }
+}
+
+/// Reward records are shared objects created by the epoch change transactions, which all mutate
+/// the system state object. Those are walked from the latest one back, until the records are
+/// older than `from_epoch`.
+async fn get_epoch_reward_records(
+    state: &AuthorityState,
+    validator: Option<SuiAddress>,
+    from_epoch: EpochId,
+) -> anyhow::Result<Vec<SuiEpochRewardRecord>> {
+    let record_type = EpochRewardRecord::type_();
+    let mut records = Vec::new();
+    for (_, digest) in state
+        .get_transactions_by_mutated_object(SUI_SYSTEM_STATE_OBJECT_ID)
+        .await?
+        .into_iter()
+        .rev()
+    {
+        let (cert, effects) = state.get_transaction(digest).await?;
+        let is_epoch_change = cert
+            .signed_data
+            .data
+            .kind
+            .single_transactions()
+            .any(|kind| matches!(kind, SingleTransactionKind::ChangeEpoch(_)));
+        if !is_epoch_change {
+            continue;
+        }
+
+        let mut reached_from_epoch = false;
+        for ((id, _, _), owner) in &effects.created {
+            if *owner != Owner::Shared {
+                continue;
+            }
+            let object = match state.get_object_read(id).await? {
+                ObjectRead::Exists(_, object, _) => object,
+                _ => continue,
+            };
+            let move_object = match object.data.try_as_move() {
+                Some(move_object) if move_object.type_ == record_type => move_object,
+                _ => continue,
+            };
+            let record: EpochRewardRecord = bcs::from_bytes(move_object.contents())?;
+            if record.epoch < from_epoch {
+                reached_from_epoch = true;
+                continue;
+            }
+            let record = SuiEpochRewardRecord::from(record);
+            if validator.map_or(true, |validator| validator == record.validator) {
+                records.push(record);
+            }
+        }
+        if reached_from_epoch {
+            break;
+        }
+    }
+    records.sort_by_key(|record| record.epoch);
+    Ok(records)
+}
+
+/// Complete the committee of an epoch with its economics, which the node only knows for the
+/// epochs it went through.
+async fn get_epoch_info(
+    state: &AuthorityState,
+    authenticated_epoch: AuthenticatedEpoch,
+) -> anyhow::Result<SuiEpochInfo> {
+    let mut epoch_info = SuiEpochInfo::from(authenticated_epoch);
+    if let Some(economics) = state.epoch_store().get_epoch_economics(&epoch_info.epoch)? {
+        epoch_info.reference_gas_price = Some(economics.reference_gas_price);
+        epoch_info.storage_fund = Some(economics.storage_fund);
+        epoch_info.rewards_distributed = economics.rewards_distributed;
+    } else {
+        // The epoch is in progress, its economics are those of the system state.
+        let system_state = state.get_sui_system_state_object().await?;
+        if system_state.epoch == epoch_info.epoch {
+            epoch_info.reference_gas_price = Some(system_state.reference_gas_price);
+            epoch_info.storage_fund = Some(system_state.storage_fund.value());
+        }
+    }
+    Ok(epoch_info)
+}
+
+impl SuiRpcModule for GovernanceReadApi {
+    fn rpc(self) -> RpcModule<Self> {
+        self.into_rpc()
+    }
+
+    fn rpc_doc_module() -> Module {
+        crate::api::GovernanceReadApiOpenRpc::module_doc()
+    }
+}
+
+pub struct EpochStreamingApiImpl {
+    state: Arc<AuthorityState>,
+}
+
+impl EpochStreamingApiImpl {
+    pub fn new(state: Arc<AuthorityState>) -> Self {
+        Self { state }
+    }
+}
+
+#[async_trait]
+impl EpochStreamingApiServer for EpochStreamingApiImpl {
+    fn subscribe_epoch_change(&self, sink: SubscriptionSink) -> SubscriptionResult {
+        let epochs =
+            futures::stream::unfold(self.state.subscribe_epoch_change(), |mut rx| async move {
+                loop {
+                    match rx.recv().await {
+                        Ok(epoch) => return Some((epoch, rx)),
+                        Err(RecvError::Lagged(skipped)) => {
+                            warn!(?skipped, "Epoch change subscriber lagged behind");
+                        }
+                        Err(RecvError::Closed) => return None,
+                    }
+                }
+            });
+        let state = self.state.clone();
+        let stream = epochs.then(move |epoch| {
+            let state = state.clone();
+            async move {
+                let authenticated_epoch = state
+                    .epoch_store()
+                    .get_authenticated_epoch(&epoch)?
+                    .ok_or_else(|| anyhow!("Epoch {epoch} is missing from the epoch store"))?;
+                get_epoch_info(&state, authenticated_epoch).await
+            }
+        });
+        spawn_subscription(sink, Box::pin(stream));
+        Ok(())
+    }
+}
+
+impl SuiRpcModule for EpochStreamingApiImpl {
+    fn rpc(self) -> RpcModule<Self> {
+        self.into_rpc()
+    }
+
+    fn rpc_doc_module() -> Module {
+        crate::api::EpochStreamingApiOpenRpc::module_doc()
+    }
+}
@@ -27,6 +27,7 @@ pub mod bcs_api;
 pub mod estimator_api;
 pub mod event_api;
 pub mod gateway_api;
+pub mod governance_api;
 pub mod quorum_driver_api;
 pub mod read_api;
 pub mod streaming_api;
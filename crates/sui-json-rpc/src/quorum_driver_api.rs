@@ -10,7 +10,8 @@ use jsonrpsee_core::server::rpc_module::RpcModule;
 use move_bytecode_utils::module_cache::SyncModuleCache;
 use signature::Signature;
 use std::sync::Arc;
-use sui_core::authority::{AuthorityStore, ResolverWrapper};
+use std::time::Duration;
+use sui_core::authority::{AuthorityState, AuthorityStore, ResolverWrapper};
 use sui_core::authority_client::NetworkAuthorityClient;
 use sui_json_rpc_types::SuiExecuteTransactionResponse;
 use sui_open_rpc::Module;
@@ -24,19 +25,25 @@ use sui_types::{
     messages::{Transaction, TransactionData},
 };
 
+// How long a WaitForLocalExecution request waits for the node to execute the transaction after
+// the effects certificate is formed. The effects are returned either way.
+const LOCAL_EXECUTION_TIMEOUT: Duration = Duration::from_secs(10);
+
 pub struct FullNodeQuorumDriverApi {
     pub quorum_driver: Arc<QuorumDriver<NetworkAuthorityClient>>,
+    pub state: Arc<AuthorityState>,
     pub module_cache: Arc<SyncModuleCache<ResolverWrapper<AuthorityStore>>>,
 }
 
 impl FullNodeQuorumDriverApi {
     pub fn new(
         quorum_driver: Arc<QuorumDriver<NetworkAuthorityClient>>,
-        module_cache: Arc<SyncModuleCache<ResolverWrapper<AuthorityStore>>>,
+        state: Arc<AuthorityState>,
     ) -> Self {
         Self {
             quorum_driver,
-            module_cache,
+            module_cache: state.module_cache.clone(),
+            state,
         }
     }
 }
@@ -59,6 +66,10 @@ impl QuorumDriverApiServer for FullNodeQuorumDriverApi {
         .map_err(|e| anyhow!(e))?;
         let txn = Transaction::new(data, signature);
         let txn_digest = *txn.digest();
+        let wait_for_local_execution = matches!(
+            request_type,
+            ExecuteTransactionRequestType::WaitForLocalExecution
+        );
         let response = self
             .quorum_driver
             .execute_transaction(ExecuteTransactionRequest {
@@ -67,9 +78,16 @@ impl QuorumDriverApiServer for FullNodeQuorumDriverApi {
             })
             .await
             .map_err(|e| anyhow!(e))?;
+        let confirmed_local_execution = wait_for_local_execution
+            && self
+                .state
+                .wait_for_local_execution(&txn_digest, LOCAL_EXECUTION_TIMEOUT)
+                .await
+                .map_err(|e| anyhow!(e))?;
         SuiExecuteTransactionResponse::from_execute_transaction_response(
             response,
             txn_digest,
+            confirmed_local_execution,
             self.module_cache.as_ref(),
         )
         .map_err(jsonrpsee_core::Error::from)
@@ -10,20 +10,25 @@ use jsonrpsee::core::RpcResult;
 use jsonrpsee_core::server::rpc_module::RpcModule;
 use move_binary_format::normalized::{Module as NormalizedModule, Type};
 use move_core_types::identifier::Identifier;
+use signature::Signature;
 use std::collections::BTreeMap;
 use std::sync::Arc;
 use sui_core::authority::AuthorityState;
 use sui_core::gateway_state::GatewayTxSeqNumber;
 use sui_json_rpc_types::{
     GetObjectDataResponse, GetPastObjectDataResponse, MoveFunctionArgType, ObjectValueKind,
-    SuiMoveNormalizedFunction, SuiMoveNormalizedModule, SuiMoveNormalizedStruct, SuiObjectInfo,
-    SuiTransactionEffects, SuiTransactionResponse,
+    SuiDryRunTransactionResponse, SuiMoveNormalizedFunction, SuiMoveNormalizedModule,
+    SuiMoveNormalizedStruct, SuiObjectInfo, SuiObjectRead, SuiParsedData, SuiTransactionEffects,
+    SuiTransactionResponse,
 };
 use sui_open_rpc::Module;
 use sui_types::base_types::SequenceNumber;
 use sui_types::base_types::{ObjectID, SuiAddress, TransactionDigest};
+use sui_types::crypto::{self, SignableBytes, SignatureScheme};
+use sui_types::messages::{Transaction, TransactionData};
 use sui_types::move_package::normalize_modules;
 use sui_types::object::{Data, ObjectRead, Owner};
+use sui_types::sui_serde::Base64;
 
 // An implementation of the read portion of the Gateway JSON-RPC interface intended for use in
 // Fullnodes.
@@ -76,12 +81,27 @@ impl RpcReadApiServer for ReadApi {
     }
 
     async fn get_object(&self, object_id: ObjectID) -> RpcResult<GetObjectDataResponse> {
-        Ok(self
+        let object_read = self
             .state
             .get_object_read(&object_id)
             .await
-            .map_err(|e| anyhow!("{e}"))?
-            .try_into()?)
+            .map_err(|e| anyhow!("{e}"))?;
+        // Display fields are indexed from transaction events, so they are only rendered by nodes
+        // that keep indexes.
+        let templates = match &object_read {
+            ObjectRead::Exists(_, object, _) => match object.data.type_() {
+                Some(type_) => self.state.get_display_fields(type_).await.ok().flatten(),
+                None => None,
+            },
+            _ => None,
+        };
+        let mut response: GetObjectDataResponse = object_read.try_into()?;
+        if let (Some(templates), SuiObjectRead::Exists(object)) = (templates, &mut response) {
+            if let SuiParsedData::MoveObject(move_object) = &object.data {
+                object.display = Some(move_object.render_display(&templates));
+            }
+        }
+        Ok(response)
     }
 
     async fn get_total_transaction_number(&self) -> RpcResult<u64> {
@@ -291,6 +311,32 @@ impl RpcFullNodeReadApiServer for FullNodeApi {
             .map_err(|e| anyhow!("{e}"))?
             .try_into()?)
     }
+
+    async fn dry_run_transaction(
+        &self,
+        tx_bytes: Base64,
+        sig_scheme: SignatureScheme,
+        signature: Base64,
+        pub_key: Base64,
+        profile_gas: bool,
+    ) -> RpcResult<SuiDryRunTransactionResponse> {
+        let data = TransactionData::from_signable_bytes(&tx_bytes.to_vec()?)?;
+        let flag = vec![sig_scheme.flag()];
+        let signature = crypto::Signature::from_bytes(
+            &[&*flag, &*signature.to_vec()?, &pub_key.to_vec()?].concat(),
+        )
+        .map_err(|e| anyhow!(e))?;
+        let txn = Transaction::new(data, signature);
+        let (effects, gas_profile) = self
+            .state
+            .dry_exec_transaction(&txn, profile_gas)
+            .await
+            .map_err(|e| anyhow!("{e}"))?;
+        Ok(SuiDryRunTransactionResponse {
+            effects: SuiTransactionEffects::try_from(effects, self.state.module_cache.as_ref())?,
+            gas_profile: gas_profile.map(Into::into),
+        })
+    }
 }
 
 impl SuiRpcModule for FullNodeApi {
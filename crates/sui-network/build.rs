@@ -94,9 +94,42 @@ fn main() -> Result<()> {
         )
         .build();
 
+    let state_sync_service = Service::builder()
+        .name("StateSync")
+        .package("sui.state_sync")
+        .comment("The interface full nodes use to serve synced state to each other")
+        .method(
+            Method::builder()
+                .name("checkpoint")
+                .route_name("Checkpoint")
+                .input_type("sui_types::messages_checkpoint::CheckpointRequest")
+                .output_type("sui_types::messages_checkpoint::CheckpointResponse")
+                .codec_path(codec_path)
+                .build(),
+        )
+        .method(
+            Method::builder()
+                .name("transaction")
+                .route_name("Transaction")
+                .input_type("sui_types::messages::StateSyncTransactionRequest")
+                .output_type("sui_types::messages::StateSyncTransactionResponse")
+                .codec_path(codec_path)
+                .build(),
+        )
+        .method(
+            Method::builder()
+                .name("object")
+                .route_name("Object")
+                .input_type("sui_types::messages::StateSyncObjectRequest")
+                .output_type("sui_types::messages::StateSyncObjectResponse")
+                .codec_path(codec_path)
+                .build(),
+        )
+        .build();
+
     Builder::new()
         .out_dir(&out_dir)
-        .compile(&[validator_service]);
+        .compile(&[validator_service, state_sync_service]);
 
     println!("cargo:rerun-if-changed=build.rs");
     println!("cargo:rerun-if-env-changed=DUMP_GENERATED_GRPC");
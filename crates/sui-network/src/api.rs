@@ -9,3 +9,12 @@ pub use validator::{
     validator_client::ValidatorClient,
     validator_server::{Validator, ValidatorServer},
 };
+
+mod state_sync {
+    include!(concat!(env!("OUT_DIR"), "/sui.state_sync.StateSync.rs"));
+}
+
+pub use state_sync::{
+    state_sync_client::StateSyncClient,
+    state_sync_server::{StateSync, StateSyncServer},
+};
@@ -1,39 +1,160 @@
 // Copyright (c) 2022, Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use anyhow::bail;
 use axum::{
     extract::Extension,
-    http::StatusCode,
+    http::{header::AUTHORIZATION, Request, StatusCode},
+    middleware::{self, Next},
+    response::{IntoResponse, Response},
     routing::{get, post},
-    Router,
+    Json, Router,
 };
+use serde::Serialize;
 use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::sync::Arc;
+use sui_config::NodeConfig;
+use sui_core::{
+    authority::AuthorityState, authority_active::ActiveAuthority,
+    authority_client::NetworkAuthorityClient,
+};
+use sui_storage::LockServiceStats;
+use sui_types::committee::{EpochId, StakeUnit};
 use telemetry_subscribers::FilterHandle;
+use tokio::sync::OnceCell;
 use tracing::info;
 
+use crate::SuiNode;
+
 const LOGGING_ROUTE: &str = "/logging";
+const STATUS_ROUTE: &str = "/status";
+const PAUSE_GOSSIP_ROUTE: &str = "/gossip/pause";
+const RESUME_GOSSIP_ROUTE: &str = "/gossip/resume";
+const PAUSE_NODE_SYNC_ROUTE: &str = "/node-sync/pause";
+const RESUME_NODE_SYNC_ROUTE: &str = "/node-sync/resume";
+const CHECKPOINT_PROPOSAL_ROUTE: &str = "/checkpoint/propose";
+
+/// The parts of a started node that the admin interface inspects and controls.
+struct AdminNode {
+    state: Arc<AuthorityState>,
+    active: Arc<ActiveAuthority<NetworkAuthorityClient>>,
+}
+
+#[derive(Clone)]
+struct AdminState {
+    node: Arc<OnceCell<AdminNode>>,
+    is_validator: bool,
+}
+
+impl AdminState {
+    fn node(&self) -> Result<&AdminNode, (StatusCode, String)> {
+        self.node.get().ok_or((
+            StatusCode::SERVICE_UNAVAILABLE,
+            "the node is still starting".into(),
+        ))
+    }
+}
+
+/// Handle to a running admin server, to hand it the node once the node has started.
+#[derive(Clone)]
+pub struct AdminServerHandle {
+    node: Arc<OnceCell<AdminNode>>,
+}
+
+impl AdminServerHandle {
+    /// Enable the routes that inspect or control `node`. Until then they respond with
+    /// `503 Service Unavailable`, while the log filter can already be changed.
+    pub fn set_node(&self, node: &SuiNode) {
+        let _ = self.node.set(AdminNode {
+            state: node.state(),
+            active: node.active().clone(),
+        });
+    }
+}
+
+#[derive(Clone)]
+struct AdminToken(Option<String>);
 
-pub fn start_admin_server(port: u16, filter_handle: FilterHandle) {
+/// Start the admin interface of the node configured by `config`. It only listens on a loopback
+/// address, and requires the configured admin token, if any. It is started before the node, so
+/// that logging can be adjusted during startup, and controls the node once it is passed to the
+/// returned handle.
+pub fn start_admin_server(
+    config: &NodeConfig,
+    filter_handle: FilterHandle,
+) -> anyhow::Result<AdminServerHandle> {
+    let address = config
+        .admin_interface_address
+        .unwrap_or(IpAddr::V4(Ipv4Addr::LOCALHOST));
+    if !address.is_loopback() {
+        bail!(
+            "the admin interface can only listen on a loopback address, not {}",
+            address
+        );
+    }
     let filter = filter_handle.get().unwrap();
 
-    let app = Router::new()
-        .route(LOGGING_ROUTE, get(get_filter))
-        .route(LOGGING_ROUTE, post(set_filter))
-        .layer(Extension(filter_handle));
+    let node = Arc::new(OnceCell::new());
+    let app = admin_router(config, filter_handle, node.clone());
 
-    let socket_address = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), port);
+    let socket_address = SocketAddr::new(address, config.admin_interface_port);
     info!(
         filter =% filter,
         address =% socket_address,
+        token_required = config.admin_interface_token.is_some(),
         "starting admin server"
     );
 
+    let server = axum::Server::try_bind(&socket_address)?;
     tokio::spawn(async move {
-        axum::Server::bind(&socket_address)
-            .serve(app.into_make_service())
-            .await
-            .unwrap();
+        server.serve(app.into_make_service()).await.unwrap();
     });
+
+    Ok(AdminServerHandle { node })
+}
+
+fn admin_router(
+    config: &NodeConfig,
+    filter_handle: FilterHandle,
+    node: Arc<OnceCell<AdminNode>>,
+) -> Router {
+    let admin_state = AdminState {
+        node,
+        is_validator: config.consensus_config().is_some(),
+    };
+
+    Router::new()
+        .route(LOGGING_ROUTE, get(get_filter))
+        .route(LOGGING_ROUTE, post(set_filter))
+        .route(STATUS_ROUTE, get(get_status))
+        .route(PAUSE_GOSSIP_ROUTE, post(pause_gossip))
+        .route(RESUME_GOSSIP_ROUTE, post(resume_gossip))
+        .route(PAUSE_NODE_SYNC_ROUTE, post(pause_node_sync))
+        .route(RESUME_NODE_SYNC_ROUTE, post(resume_node_sync))
+        .route(CHECKPOINT_PROPOSAL_ROUTE, post(trigger_checkpoint_proposal))
+        .route_layer(middleware::from_fn(check_token))
+        .layer(Extension(filter_handle))
+        .layer(Extension(admin_state))
+        .layer(Extension(AdminToken(config.admin_interface_token.clone())))
+}
+
+async fn check_token<B>(req: Request<B>, next: Next<B>) -> Result<Response, StatusCode> {
+    let expected = match req.extensions().get::<AdminToken>() {
+        Some(AdminToken(Some(token))) => format!("Bearer {}", token),
+        _ => return Ok(next.run(req).await),
+    };
+
+    let authorized = req
+        .headers()
+        .get(AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .map_or(false, |value| value == expected);
+
+    if authorized {
+        Ok(next.run(req).await)
+    } else {
+        Err(StatusCode::UNAUTHORIZED)
+    }
 }
 
 async fn get_filter(Extension(filter_handle): Extension<FilterHandle>) -> (StatusCode, String) {
@@ -55,3 +176,314 @@ async fn set_filter(
         Err(err) => (StatusCode::BAD_REQUEST, err.to_string()),
     }
 }
+
+#[derive(Serialize)]
+struct CommitteeMember {
+    name: String,
+    stake: StakeUnit,
+}
+
+#[derive(Serialize)]
+struct NodeStatus {
+    epoch: EpochId,
+    committee: Vec<CommitteeMember>,
+    pending_certificates: usize,
+    lock_service: LockServiceStats,
+    gossip_paused: bool,
+    node_sync_paused: bool,
+}
+
+async fn get_status(Extension(admin): Extension<AdminState>) -> Response {
+    let admin = match admin.node() {
+        Ok(node) => node,
+        Err(err) => return err.into_response(),
+    };
+    let pending_certificates = match admin.state.db().get_pending_digests() {
+        Ok(digests) => digests.len(),
+        Err(err) => return (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()).into_response(),
+    };
+
+    let committee = admin.state.committee.load();
+    let status = NodeStatus {
+        epoch: committee.epoch,
+        committee: committee
+            .members()
+            .map(|(name, stake)| CommitteeMember {
+                name: name.to_string(),
+                stake: *stake,
+            })
+            .collect(),
+        pending_certificates,
+        lock_service: admin.state.db().lock_service_stats(),
+        gossip_paused: admin.active.is_gossip_paused(),
+        node_sync_paused: admin.active.is_node_sync_paused().await,
+    };
+
+    (StatusCode::OK, Json(status)).into_response()
+}
+
+async fn pause_gossip(Extension(admin): Extension<AdminState>) -> (StatusCode, String) {
+    let admin = match admin.node() {
+        Ok(node) => node,
+        Err(err) => return err,
+    };
+    admin.active.pause_gossip();
+    (StatusCode::OK, "".into())
+}
+
+async fn resume_gossip(Extension(admin): Extension<AdminState>) -> (StatusCode, String) {
+    let admin = match admin.node() {
+        Ok(node) => node,
+        Err(err) => return err,
+    };
+    admin.active.resume_gossip();
+    (StatusCode::OK, "".into())
+}
+
+async fn pause_node_sync(Extension(admin): Extension<AdminState>) -> (StatusCode, String) {
+    let admin = match admin.node() {
+        Ok(node) => node,
+        Err(err) => return err,
+    };
+    if admin.active.pause_node_sync_process().await {
+        (StatusCode::OK, "".into())
+    } else {
+        (StatusCode::BAD_REQUEST, "node sync is not running".into())
+    }
+}
+
+async fn resume_node_sync(Extension(admin): Extension<AdminState>) -> (StatusCode, String) {
+    let admin = match admin.node() {
+        Ok(node) => node,
+        Err(err) => return err,
+    };
+    if admin.active.resume_node_sync_process().await {
+        (StatusCode::OK, "".into())
+    } else {
+        (StatusCode::BAD_REQUEST, "node sync is not paused".into())
+    }
+}
+
+async fn trigger_checkpoint_proposal(
+    Extension(admin): Extension<AdminState>,
+) -> (StatusCode, String) {
+    let is_validator = admin.is_validator;
+    let admin = match admin.node() {
+        Ok(node) => node,
+        Err(err) => return err,
+    };
+    if !is_validator || admin.state.checkpoints.is_none() {
+        return (
+            StatusCode::BAD_REQUEST,
+            "checkpoint proposals are only made by validators".into(),
+        );
+    }
+    admin.active.trigger_checkpoint_proposal();
+    (StatusCode::OK, "".into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, http::Method};
+    use prometheus::Registry;
+    use std::num::NonZeroUsize;
+    use std::path::Path;
+    use std::time::Duration;
+    use sui_config::{builder::ConfigBuilder, ConsensusProtocol, NetworkConfig};
+    use tower::ServiceExt;
+
+    const TOKEN: &str = "secret";
+
+    static FILTER_HANDLE: OnceCell<FilterHandle> = OnceCell::const_new();
+
+    /// Logging can only be initialized once per process, so the tests share its filter handle.
+    async fn filter_handle() -> FilterHandle {
+        FILTER_HANDLE
+            .get_or_init(|| async {
+                let (guards, filter_handle) =
+                    telemetry_subscribers::TelemetryConfig::new("admin_tests").init();
+                std::mem::forget(guards);
+                filter_handle
+            })
+            .await
+            .clone()
+    }
+
+    fn network_config(dir: &Path) -> NetworkConfig {
+        ConfigBuilder::new(dir)
+            .committee_size(NonZeroUsize::new(1).unwrap())
+            .consensus_protocol(ConsensusProtocol::SingleNode)
+            .build()
+    }
+
+    /// The admin interface of the node configured by `config`, controlling `node` if set.
+    async fn admin_app(config: &NodeConfig, node: Option<&SuiNode>) -> Router {
+        let admin_node = Arc::new(OnceCell::new());
+        let app = admin_router(config, filter_handle().await, admin_node.clone());
+        if let Some(node) = node {
+            AdminServerHandle { node: admin_node }.set_node(node);
+        }
+        app
+    }
+
+    async fn request(app: &Router, method: Method, route: &str, token: Option<&str>) -> StatusCode {
+        let mut request = Request::builder().method(method).uri(route);
+        if let Some(token) = token {
+            request = request.header(AUTHORIZATION, format!("Bearer {}", token));
+        }
+        app.clone()
+            .oneshot(request.body(Body::empty()).unwrap())
+            .await
+            .unwrap()
+            .status()
+    }
+
+    #[tokio::test]
+    async fn requires_token() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut config = network_config(dir.path()).validator_configs[0].clone();
+        config.admin_interface_token = Some(TOKEN.into());
+        let app = admin_app(&config, None).await;
+
+        for token in [None, Some("wrong"), Some("")] {
+            assert_eq!(
+                request(&app, Method::GET, LOGGING_ROUTE, token).await,
+                StatusCode::UNAUTHORIZED
+            );
+            assert_eq!(
+                request(&app, Method::POST, PAUSE_GOSSIP_ROUTE, token).await,
+                StatusCode::UNAUTHORIZED
+            );
+        }
+        assert_eq!(
+            request(&app, Method::GET, LOGGING_ROUTE, Some(TOKEN)).await,
+            StatusCode::OK
+        );
+    }
+
+    #[tokio::test]
+    async fn unavailable_before_node_starts() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = network_config(dir.path()).validator_configs[0].clone();
+        let app = admin_app(&config, None).await;
+
+        assert_eq!(
+            request(&app, Method::GET, STATUS_ROUTE, None).await,
+            StatusCode::SERVICE_UNAVAILABLE
+        );
+        for route in [
+            PAUSE_GOSSIP_ROUTE,
+            RESUME_GOSSIP_ROUTE,
+            PAUSE_NODE_SYNC_ROUTE,
+            RESUME_NODE_SYNC_ROUTE,
+            CHECKPOINT_PROPOSAL_ROUTE,
+        ] {
+            assert_eq!(
+                request(&app, Method::POST, route, None).await,
+                StatusCode::SERVICE_UNAVAILABLE
+            );
+        }
+        // The log filter can be changed while the node starts.
+        assert_eq!(
+            request(&app, Method::GET, LOGGING_ROUTE, None).await,
+            StatusCode::OK
+        );
+    }
+
+    #[tokio::test]
+    async fn controls_node() {
+        let dir = tempfile::tempdir().unwrap();
+        let network = network_config(dir.path());
+
+        let mut validator_config = network.validator_configs[0].clone();
+        // Without a checkpoint process to take it, a triggered proposal stays pending.
+        validator_config.enable_checkpoint = false;
+        let validator = SuiNode::start(&validator_config, Registry::new())
+            .await
+            .unwrap();
+        let app = admin_app(&validator_config, Some(&validator)).await;
+        assert_eq!(
+            request(&app, Method::GET, STATUS_ROUTE, None).await,
+            StatusCode::OK
+        );
+
+        assert!(!validator.active().is_gossip_paused());
+        assert_eq!(
+            request(&app, Method::POST, PAUSE_GOSSIP_ROUTE, None).await,
+            StatusCode::OK
+        );
+        assert!(validator.active().is_gossip_paused());
+        assert_eq!(
+            request(&app, Method::POST, RESUME_GOSSIP_ROUTE, None).await,
+            StatusCode::OK
+        );
+        assert!(!validator.active().is_gossip_paused());
+
+        assert_eq!(
+            request(&app, Method::POST, CHECKPOINT_PROPOSAL_ROUTE, None).await,
+            StatusCode::OK
+        );
+        tokio::time::timeout(
+            Duration::from_secs(1),
+            validator.active().wait_for_checkpoint_trigger(),
+        )
+        .await
+        .expect("checkpoint proposal was not triggered");
+
+        // Validators do not run node sync.
+        assert_eq!(
+            request(&app, Method::POST, PAUSE_NODE_SYNC_ROUTE, None).await,
+            StatusCode::BAD_REQUEST
+        );
+
+        let fullnode_config = network.generate_fullnode_config();
+        let fullnode = SuiNode::start(&fullnode_config, Registry::new())
+            .await
+            .unwrap();
+        let app = admin_app(&fullnode_config, Some(&fullnode)).await;
+
+        assert!(!fullnode.active().is_node_sync_paused().await);
+        assert_eq!(
+            request(&app, Method::POST, PAUSE_NODE_SYNC_ROUTE, None).await,
+            StatusCode::OK
+        );
+        assert!(fullnode.active().is_node_sync_paused().await);
+        assert_eq!(
+            request(&app, Method::POST, PAUSE_NODE_SYNC_ROUTE, None).await,
+            StatusCode::BAD_REQUEST
+        );
+        assert_eq!(
+            request(&app, Method::POST, RESUME_NODE_SYNC_ROUTE, None).await,
+            StatusCode::OK
+        );
+        assert!(!fullnode.active().is_node_sync_paused().await);
+        assert_eq!(
+            request(&app, Method::POST, RESUME_NODE_SYNC_ROUTE, None).await,
+            StatusCode::BAD_REQUEST
+        );
+
+        // Only validators propose checkpoints.
+        assert_eq!(
+            request(&app, Method::POST, CHECKPOINT_PROPOSAL_ROUTE, None).await,
+            StatusCode::BAD_REQUEST
+        );
+    }
+
+    #[tokio::test]
+    async fn only_listens_on_loopback() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut config = network_config(dir.path()).validator_configs[0].clone();
+
+        for address in ["0.0.0.0", "10.0.0.1", "::"] {
+            config.admin_interface_address = Some(address.parse().unwrap());
+            let err = start_admin_server(&config, filter_handle().await)
+                .err()
+                .unwrap();
+            assert!(err.to_string().contains("loopback"));
+        }
+
+        config.admin_interface_address = Some("127.0.0.1".parse().unwrap());
+        start_admin_server(&config, filter_handle().await).unwrap();
+    }
+}
@@ -24,11 +24,15 @@ use sui_core::{
         make_network_authority_client_sets_from_genesis,
         make_network_authority_client_sets_from_system_state, NetworkAuthorityClient,
     },
-    checkpoints::CheckpointStore,
+    checkpoints::{
+        archive::{checkpoint_archive_process, CheckpointArchiveReader, LocalFsArchiveStore},
+        CheckpointStore,
+    },
+    state_sync::{StateSyncPeer, StateSyncService},
 };
 use sui_json_rpc::bcs_api::BcsApiImpl;
 use sui_json_rpc::streaming_api::TransactionStreamingApiImpl;
-use sui_network::api::ValidatorServer;
+use sui_network::api::{StateSyncServer, ValidatorServer};
 use sui_quorum_driver::QuorumDriverMetrics;
 use sui_quorum_driver::{QuorumDriver, QuorumDriverHandler};
 use sui_storage::{
@@ -45,6 +49,7 @@ use sui_core::authority_client::NetworkAuthorityClientMetrics;
 use sui_core::epoch::epoch_store::EpochStore;
 use sui_json_rpc::event_api::EventReadApiImpl;
 use sui_json_rpc::event_api::EventStreamingApiImpl;
+use sui_json_rpc::governance_api::{EpochStreamingApiImpl, GovernanceReadApi};
 use sui_json_rpc::http_server::HttpServerHandle;
 use sui_json_rpc::quorum_driver_api::FullNodeQuorumDriverApi;
 use sui_json_rpc::read_api::FullNodeApi;
@@ -65,6 +70,8 @@ pub struct SuiNode {
     _gossip_handle: Option<tokio::task::JoinHandle<()>>,
     _execute_driver_handle: tokio::task::JoinHandle<()>,
     _checkpoint_process_handle: Option<tokio::task::JoinHandle<()>>,
+    _epoch_sync_handle: Option<tokio::task::JoinHandle<()>>,
+    _checkpoint_archive_handle: Option<tokio::task::JoinHandle<()>>,
     state: Arc<AuthorityState>,
     active: Arc<ActiveAuthority<NetworkAuthorityClient>>,
     quorum_driver_handler: Option<QuorumDriverHandler<NetworkAuthorityClient>>,
@@ -186,15 +193,51 @@ impl SuiNode {
             None,
             None,
         ));
-        let active_authority = Arc::new(ActiveAuthority::new(
-            state.clone(),
-            pending_store,
-            net,
-            GossipMetrics::new(&prometheus_registry),
-            network_metrics.clone(),
-        )?);
+        let state_sync_peers = config
+            .state_sync_peers
+            .iter()
+            .map(StateSyncPeer::connect_lazy)
+            .collect::<Result<Vec<_>>>()?;
+        let active_authority = Arc::new(
+            ActiveAuthority::new(
+                state.clone(),
+                pending_store.clone(),
+                net,
+                GossipMetrics::new(&prometheus_registry),
+                network_metrics.clone(),
+            )?
+            .with_state_sync_peers(state_sync_peers),
+        );
 
-        let gossip_handle = if is_full_node {
+        let gossip_handle = if is_full_node && config.enable_checkpoint_sync {
+            if let Some(path) = &config.checkpoint_archive_path {
+                // Seed checkpoint sync with what is already archived, so that it only has to
+                // download the checkpoints that are newer than the archive.
+                let reader =
+                    CheckpointArchiveReader::new(Arc::new(LocalFsArchiveStore::new(path.clone())));
+                match reader
+                    .restore_checkpoints(&pending_store, state.epoch_store())
+                    .await
+                {
+                    Ok(restored) => info!(
+                        "Restored {} checkpoints from the archive at {}",
+                        restored,
+                        path.display()
+                    ),
+                    Err(err) => error!(
+                        "Failed to restore checkpoints from the archive at {}: {}",
+                        path.display(),
+                        err
+                    ),
+                }
+            }
+            info!("Starting full node checkpoint sync");
+            active_authority
+                .clone()
+                .spawn_checkpoint_sync_process()
+                .await;
+            None
+        } else if is_full_node {
             info!("Starting full node sync to latest checkpoint (this may take a while)");
             let now = Instant::now();
             if let Err(err) = active_authority.sync_to_latest_checkpoint().await {
@@ -232,6 +275,20 @@ impl SuiNode {
             None
         };
 
+        // Validators move to new epochs in the checkpoint process, full nodes follow the epochs
+        // certified by the validators.
+        let epoch_sync_handle = if is_full_node && config.enable_reconfig {
+            Some(active_authority.clone().spawn_epoch_sync_process().await)
+        } else {
+            None
+        };
+
+        let checkpoint_archive_handle = config.checkpoint_archive_path.as_ref().map(|path| {
+            info!("Archiving certified checkpoints to {}", path.display());
+            let archive_store = Arc::new(LocalFsArchiveStore::new(path.clone()));
+            tokio::task::spawn(checkpoint_archive_process(state.clone(), archive_store))
+        });
+
         let batch_subsystem_handle = {
             // Start batch system so that this node can be followed
             let batch_state = state.clone();
@@ -278,6 +335,11 @@ impl SuiNode {
                     server_builder.add_service(ValidatorServer::new(validator_service));
             }
 
+            if is_full_node {
+                server_builder = server_builder
+                    .add_service(StateSyncServer::new(StateSyncService::new(state.clone())));
+            }
+
             let server = server_builder
                 .bind(config.network_address())
                 .await
@@ -302,6 +364,8 @@ impl SuiNode {
             _gossip_handle: gossip_handle,
             _execute_driver_handle: execute_driver_handle,
             _checkpoint_process_handle: checkpoint_process_handle,
+            _epoch_sync_handle: epoch_sync_handle,
+            _checkpoint_archive_handle: checkpoint_archive_handle,
             _batch_subsystem_handle: batch_subsystem_handle,
             _post_processing_subsystem_handle: post_processing_subsystem_handle,
             state,
@@ -370,11 +434,12 @@ pub async fn build_http_servers(
     server.register_module(ReadApi::new(state.clone()))?;
     server.register_module(FullNodeApi::new(state.clone()))?;
     server.register_module(BcsApiImpl::new(state.clone()))?;
+    server.register_module(GovernanceReadApi::new(state.clone()))?;
 
     if let Some(quorum_driver_handler_) = quorum_driver_handler {
         server.register_module(FullNodeQuorumDriverApi::new(
             quorum_driver_handler_.clone_quorum_driver(),
-            state.module_cache.clone(),
+            state.clone(),
         ))?;
     }
 
@@ -402,6 +467,7 @@ pub async fn build_http_servers(
             if let Some(event_handler) = state.event_handler.clone() {
                 server.register_module(EventStreamingApiImpl::new(state.clone(), event_handler))?;
             }
+            server.register_module(EpochStreamingApiImpl::new(state.clone()))?;
             Some(
                 server
                     .start(ws_addr)
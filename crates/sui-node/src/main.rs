@@ -129,9 +129,9 @@ async fn main() -> Result<()> {
         }
     });
 
-    sui_node::admin::start_admin_server(config.admin_interface_port, filter_handle);
-
+    let admin = sui_node::admin::start_admin_server(&config, filter_handle)?;
     let node = sui_node::SuiNode::start(&config, prometheus_registry).await?;
+    admin.set_node(&node);
     node.wait().await?;
 
     Ok(())
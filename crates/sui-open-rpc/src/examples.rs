@@ -213,6 +213,7 @@ impl RpcExampleProvider {
                 SequenceNumber::from_u64(1),
                 ObjectDigest::new(self.rng.gen()),
             )),
+            display: None,
         });
 
         Examples::new(
@@ -244,6 +245,7 @@ impl RpcExampleProvider {
                 SequenceNumber::from_u64(4),
                 ObjectDigest::new(self.rng.gen()),
             )),
+            display: None,
         });
 
         Examples::new(
@@ -322,6 +324,7 @@ impl RpcExampleProvider {
                 SequenceNumber::from_u64(1),
                 ObjectDigest::new(self.rng.gen()),
             )),
+            display: None,
         });
 
         Examples::new(
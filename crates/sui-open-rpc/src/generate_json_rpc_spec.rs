@@ -22,12 +22,14 @@ use sui::client_commands::{EXAMPLE_NFT_DESCRIPTION, EXAMPLE_NFT_NAME, EXAMPLE_NF
 use sui_config::genesis_config::GenesisConfig;
 use sui_config::SUI_CLIENT_CONFIG;
 use sui_json::SuiJsonValue;
+use sui_json_rpc::api::EpochStreamingApiOpenRpc;
 use sui_json_rpc::api::EventStreamingApiOpenRpc;
 use sui_json_rpc::api::RpcReadApiClient;
 use sui_json_rpc::api::RpcTransactionBuilderClient;
 use sui_json_rpc::api::WalletSyncApiClient;
 use sui_json_rpc::bcs_api::BcsApiImpl;
 use sui_json_rpc::gateway_api::{GatewayWalletSyncApiImpl, RpcGatewayImpl, TransactionBuilderImpl};
+use sui_json_rpc::governance_api::GovernanceReadApi;
 use sui_json_rpc::read_api::{FullNodeApi, ReadApi};
 use sui_json_rpc::sui_rpc_doc;
 use sui_json_rpc::SuiRpcModule;
@@ -83,6 +85,8 @@ async fn main() {
     open_rpc.add_module(EventStreamingApiOpenRpc::module_doc());
     open_rpc.add_module(EventReadApiOpenRpc::module_doc());
     open_rpc.add_module(GatewayWalletSyncApiImpl::rpc_doc_module());
+    open_rpc.add_module(GovernanceReadApi::rpc_doc_module());
+    open_rpc.add_module(EpochStreamingApiOpenRpc::module_doc());
 
     open_rpc.add_examples(RpcExampleProvider::new().examples());
 
@@ -330,6 +334,7 @@ async fn create_hero_response(
             args: vec![game_info, coin],
             gas: None,
             gas_budget: 10000,
+            profile_output: None,
         }
         .execute(context)
         .await?;
@@ -442,6 +447,7 @@ async fn get_nft_response(
         args,
         gas: None,
         gas_budget: 10000,
+        profile_output: None,
     }
     .execute(context)
     .await?;
@@ -2,7 +2,10 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use arc_swap::ArcSwap;
+use std::collections::BTreeMap;
+use std::future::Future;
 use std::sync::Arc;
+use std::time::Duration;
 
 use tokio::sync::mpsc::{self, Receiver, Sender};
 use tokio::task::JoinHandle;
@@ -12,11 +15,19 @@ use tracing::{debug, error, warn};
 pub use metrics::QuorumDriverMetrics;
 use sui_core::authority_aggregator::AuthorityAggregator;
 use sui_core::authority_client::AuthorityAPI;
+use sui_types::base_types::{ObjectRef, TransactionDigest};
 use sui_types::error::{SuiError, SuiResult};
 use sui_types::messages::{
     CertifiedTransaction, CertifiedTransactionEffects, ExecuteTransactionRequest,
-    ExecuteTransactionRequestType, ExecuteTransactionResponse, Transaction,
+    ExecuteTransactionRequestType, ExecuteTransactionResponse, InputObjectKind, Transaction,
 };
+
+/// Number of times a transaction or certificate is submitted before a transient error is returned.
+const MAX_ATTEMPTS: u32 = 3;
+/// Delay before the first retry, doubled after each further failed attempt.
+const INITIAL_BACKOFF: Duration = Duration::from_millis(200);
+const MAX_BACKOFF: Duration = Duration::from_secs(2);
+
 pub enum QuorumTask<A> {
     ProcessTransaction(Transaction),
     ProcessCertificate(CertifiedTransaction),
@@ -112,6 +123,24 @@ where
 
                 (&self.metrics.total_ok_responses_wait_for_effects_cert, res)
             }
+            // Waiting for local execution is up to the node serving the request, the quorum
+            // driver only needs to get the effects certificate.
+            ExecuteTransactionRequestType::WaitForLocalExecution => {
+                self.metrics.total_requests_wait_for_local_execution.inc();
+                let _timer = self
+                    .metrics
+                    .latency_sec_wait_for_local_execution
+                    .start_timer();
+
+                let res = self
+                    .execute_transaction_wait_for_effects_cert(transaction)
+                    .await;
+
+                (
+                    &self.metrics.total_ok_responses_wait_for_local_execution,
+                    res,
+                )
+            }
         };
         if result.is_ok() {
             ok_metric.inc()
@@ -164,26 +193,50 @@ where
         Ok(ExecuteTransactionResponse::EffectsCert(Box::new(response)))
     }
 
+    /// Submit a transaction to the validators to obtain a certificate. Transient failures are
+    /// retried with backoff, and returned as `SuiError::QuorumDriverError` once all attempts
+    /// failed. Other errors, such as input objects locked by conflicting transactions, are
+    /// returned as is.
     pub async fn process_transaction(
         &self,
         transaction: Transaction,
     ) -> SuiResult<CertifiedTransaction> {
-        self.validators
-            .load()
-            .process_transaction(transaction)
-            .instrument(tracing::debug_span!("process_tx"))
-            .await
+        let transaction = &transaction;
+        self.with_retries(transaction.digest(), || async move {
+            let validators = self.validators.load_full();
+            match validators
+                .process_transaction(transaction.clone())
+                .instrument(tracing::debug_span!("process_tx"))
+                .await
+            {
+                Ok(certificate) => Ok(certificate),
+                Err(err) => Err(self
+                    .classify_transaction_error(&validators, transaction, err)
+                    .await),
+            }
+        })
+        .await
     }
 
+    /// Submit a certificate to the validators to obtain its effects. Transient failures are
+    /// retried with backoff.
     pub async fn process_certificate(
         &self,
         certificate: CertifiedTransaction,
     ) -> SuiResult<(CertifiedTransaction, CertifiedTransactionEffects)> {
+        let certificate_ref = &certificate;
         let effects = self
-            .validators
-            .load()
-            .process_certificate(certificate.clone())
-            .instrument(tracing::debug_span!("process_cert"))
+            .with_retries(certificate.digest(), || async move {
+                self.validators
+                    .load_full()
+                    .process_certificate(certificate_ref.clone())
+                    .instrument(tracing::debug_span!("process_cert"))
+                    .await
+                    .map_err(|err| {
+                        let retryable = err.is_transient();
+                        (err, retryable)
+                    })
+            })
             .await?;
         let response = (certificate, effects);
         // An error to send the result to subscribers should not block returning the result.
@@ -193,6 +246,101 @@ where
         }
         Ok(response)
     }
+
+    /// Run `attempt` until it succeeds, it fails with an error that is not retryable, or
+    /// `MAX_ATTEMPTS` attempts have been made, doubling the delay between attempts each time.
+    /// Errors that are not retryable are returned directly.
+    async fn with_retries<T, F, Fut>(
+        &self,
+        tx_digest: &TransactionDigest,
+        mut attempt: F,
+    ) -> SuiResult<T>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, (SuiError, bool)>>,
+    {
+        let mut backoff = INITIAL_BACKOFF;
+        let mut attempts = 1;
+        loop {
+            match attempt().await {
+                Ok(result) => return Ok(result),
+                Err((error, false)) => return Err(error),
+                Err((error, true)) if attempts >= MAX_ATTEMPTS => {
+                    return Err(SuiError::QuorumDriverError {
+                        error: Box::new(error),
+                        attempts,
+                    });
+                }
+                Err((error, _)) => {
+                    debug!(
+                        ?tx_digest,
+                        attempts,
+                        ?backoff,
+                        "Retrying after transient error: {:?}",
+                        error
+                    );
+                    self.metrics.total_attempts_retried.inc();
+                    tokio::time::sleep(backoff).await;
+                    backoff = (backoff * 2).min(MAX_BACKOFF);
+                    attempts += 1;
+                }
+            }
+        }
+    }
+
+    /// Decide whether a transaction that failed to get a certificate can be retried.
+    /// If some of its owned input objects are locked by other transactions, the error is replaced
+    /// by a report of which transaction holds which object. Submitting the transaction again
+    /// cannot release those locks, so the error is not retried.
+    async fn classify_transaction_error(
+        &self,
+        validators: &AuthorityAggregator<A>,
+        transaction: &Transaction,
+        error: SuiError,
+    ) -> (SuiError, bool) {
+        if error.is_transient() {
+            return (error, true);
+        }
+        let owned_objects: Vec<ObjectRef> = match transaction.signed_data.data.input_objects() {
+            Ok(input_objects) => input_objects
+                .into_iter()
+                .filter_map(|kind| match kind {
+                    InputObjectKind::ImmOrOwnedMoveObject(object_ref) => Some(object_ref),
+                    _ => None,
+                })
+                .collect(),
+            Err(_) => return (error, false),
+        };
+
+        let tx_digest = transaction.digest();
+        let mut conflicting_transactions: BTreeMap<TransactionDigest, Vec<ObjectRef>> =
+            BTreeMap::new();
+        for (object_ref, locks) in validators.get_object_locks(&owned_objects).await {
+            for (digest, _) in locks.into_iter().filter(|(digest, _)| digest != tx_digest) {
+                conflicting_transactions
+                    .entry(digest)
+                    .or_default()
+                    .push(object_ref);
+            }
+        }
+        if conflicting_transactions.is_empty() {
+            return (error, false);
+        }
+
+        self.metrics.total_conflicting_transactions_detected.inc();
+        warn!(
+            ?tx_digest,
+            ?conflicting_transactions,
+            "Input objects locked by conflicting transactions: {:?}",
+            error
+        );
+        (
+            SuiError::ObjectsLockedByConflictingTransactions {
+                conflicting_transactions,
+            },
+            false,
+        )
+    }
 }
 
 impl<A> QuorumDriverHandler<A>
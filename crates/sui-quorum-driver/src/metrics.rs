@@ -15,12 +15,18 @@ pub struct QuorumDriverMetrics {
     pub(crate) total_ok_responses_wait_for_tx_cert: IntCounter,
     pub(crate) total_requests_wait_for_effects_cert: IntCounter,
     pub(crate) total_ok_responses_wait_for_effects_cert: IntCounter,
+    pub(crate) total_requests_wait_for_local_execution: IntCounter,
+    pub(crate) total_ok_responses_wait_for_local_execution: IntCounter,
 
     pub(crate) latency_sec_immediate_return: Histogram,
     pub(crate) latency_sec_wait_for_tx_cert: Histogram,
     pub(crate) latency_sec_wait_for_effects_cert: Histogram,
+    pub(crate) latency_sec_wait_for_local_execution: Histogram,
 
     pub(crate) current_requests_in_flight: IntGauge,
+
+    pub(crate) total_attempts_retried: IntCounter,
+    pub(crate) total_conflicting_transactions_detected: IntCounter,
 }
 
 impl QuorumDriverMetrics {
@@ -62,6 +68,18 @@ impl QuorumDriverMetrics {
                 registry,
             )
             .unwrap(),
+            total_requests_wait_for_local_execution: register_int_counter_with_registry!(
+                "quorum_driver_total_requests_wait_for_local_execution",
+                "Total number of wait_for_local_execution requests received",
+                registry,
+            )
+            .unwrap(),
+            total_ok_responses_wait_for_local_execution: register_int_counter_with_registry!(
+                "quorum_driver_total_ok_responses_wait_for_local_execution",
+                "Total number of wait_for_local_execution requests with an Ok effects certificate",
+                registry,
+            )
+            .unwrap(),
             latency_sec_immediate_return: register_histogram_with_registry!(
                 "quorum_driver_latency_sec_immediate_return",
                 "Latency of processing an immdediate_return execution request, in sec",
@@ -80,12 +98,30 @@ impl QuorumDriverMetrics {
                 registry,
             )
             .unwrap(),
+            latency_sec_wait_for_local_execution: register_histogram_with_registry!(
+                "quorum_driver_latency_sec_wait_for_local_execution",
+                "Latency of getting an effects certificate for a wait_for_local_execution request, in sec",
+                registry,
+            )
+            .unwrap(),
             current_requests_in_flight: register_int_gauge_with_registry!(
                 "current_requests_in_flight",
                 "Current number of requests being processed in QuorumDriver",
                 registry,
             )
             .unwrap(),
+            total_attempts_retried: register_int_counter_with_registry!(
+                "quorum_driver_total_attempts_retried",
+                "Total number of transaction or certificate submissions retried after a transient failure",
+                registry,
+            )
+            .unwrap(),
+            total_conflicting_transactions_detected: register_int_counter_with_registry!(
+                "quorum_driver_total_conflicting_transactions_detected",
+                "Total number of failed transactions whose input objects were locked by conflicting transactions",
+                registry,
+            )
+            .unwrap(),
         }
     }
 
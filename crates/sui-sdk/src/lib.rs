@@ -15,12 +15,15 @@ use serde::Deserialize;
 use serde::Serialize;
 use serde_json::Value;
 
-use rpc_types::{GetPastObjectDataResponse, SuiExecuteTransactionResponse};
+use rpc_types::{
+    GetPastObjectDataResponse, SuiDryRunTransactionResponse, SuiExecuteTransactionResponse,
+};
 pub use sui_config::gateway;
 use sui_config::gateway::GatewayConfig;
 use sui_core::gateway_state::{GatewayClient, GatewayState};
 pub use sui_json as json;
 use sui_json_rpc::api::EventStreamingApiClient;
+use sui_json_rpc::api::GovernanceReadApiClient;
 use sui_json_rpc::api::QuorumDriverApiClient;
 use sui_json_rpc::api::RpcBcsApiClient;
 use sui_json_rpc::api::RpcFullNodeReadApiClient;
@@ -29,11 +32,13 @@ use sui_json_rpc::api::RpcReadApiClient;
 use sui_json_rpc::api::WalletSyncApiClient;
 pub use sui_json_rpc_types as rpc_types;
 use sui_json_rpc_types::{
-    GatewayTxSeqNumber, GetObjectDataResponse, GetRawObjectDataResponse, SuiEventEnvelope,
-    SuiEventFilter, SuiObjectInfo, SuiTransactionResponse,
+    GatewayTxSeqNumber, GetObjectDataResponse, GetRawObjectDataResponse, SuiDelegation,
+    SuiEpochRewardRecord, SuiEventEnvelope, SuiEventFilter, SuiObjectInfo, SuiSystemStateSummary,
+    SuiTransactionResponse, SuiValidator,
 };
 pub use sui_types as types;
 use sui_types::base_types::{ObjectID, SuiAddress, TransactionDigest};
+use sui_types::committee::EpochId;
 use sui_types::messages::Transaction;
 use types::base_types::SequenceNumber;
 use types::messages::ExecuteTransactionRequestType;
@@ -42,6 +47,7 @@ use crate::transaction_builder::TransactionBuilder;
 
 // re-export essential sui crates
 pub mod crypto;
+pub mod light_client;
 mod transaction_builder;
 
 pub struct SuiClient {
@@ -52,6 +58,7 @@ pub struct SuiClient {
     event_api: EventApi,
     quorum_driver: QuorumDriver,
     wallet_sync_api: WalletSyncApi,
+    governance_api: GovernanceApi,
 }
 
 #[allow(clippy::large_enum_variant)]
@@ -159,6 +166,7 @@ impl SuiClient {
         let event_api = EventApi(api.clone());
         let transaction_builder = TransactionBuilder(read_api.clone());
         let wallet_sync_api = WalletSyncApi(api.clone());
+        let governance_api = GovernanceApi(api.clone());
 
         SuiClient {
             api,
@@ -168,6 +176,7 @@ impl SuiClient {
             event_api,
             quorum_driver,
             wallet_sync_api,
+            governance_api,
         }
     }
 
@@ -368,6 +377,30 @@ impl FullNodeApi {
         }
         .await?)
     }
+
+    pub async fn dry_run_transaction(
+        &self,
+        tx: Transaction,
+        profile_gas: bool,
+    ) -> anyhow::Result<SuiDryRunTransactionResponse> {
+        Ok(match &*self.0 {
+            SuiClientApi::Rpc(c) => {
+                let (tx_bytes, flag, signature, pub_key) = tx.to_network_data_for_execution();
+                RpcFullNodeReadApiClient::dry_run_transaction(
+                    &c.http,
+                    tx_bytes,
+                    flag,
+                    signature,
+                    pub_key,
+                    profile_gas,
+                )
+                .await?
+            }
+            SuiClientApi::Embedded(_) => {
+                return Err(anyhow!("Method not supported by embedded gateway client."))
+            }
+        })
+    }
 }
 pub struct EventApi(Arc<SuiClientApi>);
 
@@ -447,6 +480,57 @@ impl WalletSyncApi {
     }
 }
 
+pub struct GovernanceApi(Arc<SuiClientApi>);
+
+impl GovernanceApi {
+    pub async fn get_sui_system_state(&self) -> anyhow::Result<SuiSystemStateSummary> {
+        Ok(match &*self.0 {
+            SuiClientApi::Rpc(c) => c.http.get_sui_system_state(),
+            SuiClientApi::Embedded(_) => {
+                return Err(anyhow!("Method not supported by embedded gateway client."))
+            }
+        }
+        .await?)
+    }
+
+    pub async fn get_validators(&self) -> anyhow::Result<Vec<SuiValidator>> {
+        Ok(match &*self.0 {
+            SuiClientApi::Rpc(c) => c.http.get_validators(),
+            SuiClientApi::Embedded(_) => {
+                return Err(anyhow!("Method not supported by embedded gateway client."))
+            }
+        }
+        .await?)
+    }
+
+    pub async fn get_delegated_stakes(
+        &self,
+        owner: SuiAddress,
+    ) -> anyhow::Result<Vec<SuiDelegation>> {
+        Ok(match &*self.0 {
+            SuiClientApi::Rpc(c) => c.http.get_delegated_stakes(owner),
+            SuiClientApi::Embedded(_) => {
+                return Err(anyhow!("Method not supported by embedded gateway client."))
+            }
+        }
+        .await?)
+    }
+
+    pub async fn get_epoch_reward_records(
+        &self,
+        validator: Option<SuiAddress>,
+        from_epoch: EpochId,
+    ) -> anyhow::Result<Vec<SuiEpochRewardRecord>> {
+        Ok(match &*self.0 {
+            SuiClientApi::Rpc(c) => c.http.get_epoch_reward_records(validator, from_epoch),
+            SuiClientApi::Embedded(_) => {
+                return Err(anyhow!("Method not supported by embedded gateway client."))
+            }
+        }
+        .await?)
+    }
+}
+
 impl SuiClient {
     pub fn transaction_builder(&self) -> &TransactionBuilder {
         &self.transaction_builder
@@ -466,6 +550,9 @@ impl SuiClient {
     pub fn wallet_sync_api(&self) -> &WalletSyncApi {
         &self.wallet_sync_api
     }
+    pub fn governance_api(&self) -> &GovernanceApi {
+        &self.governance_api
+    }
 }
 
 #[derive(Serialize, Deserialize)]
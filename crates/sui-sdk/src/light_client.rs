@@ -0,0 +1,255 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::BTreeMap;
+use std::sync::RwLock;
+
+use anyhow::bail;
+use jsonrpsee::http_client::{HttpClient, HttpClientBuilder};
+
+use sui_config::genesis::Genesis;
+use sui_json_rpc::api::RpcBcsApiClient;
+use sui_json_rpc_types::SuiObjectRead;
+use sui_types::base_types::{ExecutionDigests, ObjectID, TransactionDigest};
+use sui_types::committee::{Committee, EpochId};
+use sui_types::error::{SuiError, SuiResult};
+use sui_types::fp_ensure;
+use sui_types::messages::{
+    AuthenticatedEpoch, CertifiedEpoch, CertifiedTransaction, CertifiedTransactionEffects,
+    EpochInfo, GenesisEpoch, TransactionEffects,
+};
+use sui_types::messages_checkpoint::{CertifiedCheckpointSummary, CheckpointContents};
+use sui_types::object::Object;
+
+/// The epochs verified so far, starting from a trusted epoch. Every following epoch is only
+/// accepted once certified by the committee of the epoch before it.
+pub struct VerifiedEpochs {
+    epochs: BTreeMap<EpochId, EpochInfo>,
+}
+
+impl VerifiedEpochs {
+    /// Start from an epoch trusted without verification, usually the genesis epoch or an epoch
+    /// obtained out of band.
+    pub fn new(trusted_epoch: AuthenticatedEpoch) -> Self {
+        let epoch_info = trusted_epoch.into_epoch_info();
+        Self {
+            epochs: BTreeMap::from([(epoch_info.epoch(), epoch_info)]),
+        }
+    }
+
+    pub fn latest_epoch(&self) -> EpochId {
+        // unwrap safe because there is at least the trusted epoch.
+        *self.epochs.keys().next_back().unwrap()
+    }
+
+    /// The committee of a verified epoch.
+    pub fn committee(&self, epoch: EpochId) -> SuiResult<&Committee> {
+        self.epochs
+            .get(&epoch)
+            .map(EpochInfo::committee)
+            .ok_or_else(|| {
+                SuiError::InvalidAuthenticatedEpoch(format!("Epoch {epoch} is not verified"))
+            })
+    }
+
+    /// Verify the certificate of the epoch following the latest verified epoch, and add the epoch.
+    pub fn add_certified_epoch(&mut self, certified_epoch: CertifiedEpoch) -> SuiResult {
+        let latest_epoch = self.latest_epoch();
+        let latest = &self.epochs[&latest_epoch];
+        fp_ensure!(
+            certified_epoch.epoch_info.epoch() == latest_epoch + 1,
+            SuiError::InvalidAuthenticatedEpoch(format!(
+                "Expected epoch {}, got epoch {}",
+                latest_epoch + 1,
+                certified_epoch.epoch_info.epoch()
+            ))
+        );
+        fp_ensure!(
+            *certified_epoch.epoch_info.prev_epoch_info_digest() == latest.digest(),
+            SuiError::InvalidAuthenticatedEpoch(
+                "Epoch does not follow the latest verified epoch".to_string()
+            )
+        );
+        certified_epoch.verify(latest.committee())?;
+        self.epochs
+            .insert(latest_epoch + 1, certified_epoch.epoch_info);
+        Ok(())
+    }
+
+    pub fn verify_certificate(&self, certificate: &CertifiedTransaction) -> SuiResult {
+        certificate.verify(self.committee(certificate.auth_sign_info.epoch)?)
+    }
+
+    pub fn verify_effects(&self, effects: &CertifiedTransactionEffects) -> SuiResult {
+        effects.verify(self.committee(effects.auth_signature.epoch)?)
+    }
+
+    pub fn verify_checkpoint(
+        &self,
+        checkpoint: &CertifiedCheckpointSummary,
+        contents: &CheckpointContents,
+    ) -> SuiResult {
+        checkpoint.verify(self.committee(checkpoint.summary.epoch)?, Some(contents))
+    }
+}
+
+/// A client reading from an untrusted full node, which verifies everything it reads against the
+/// committee. It follows the epoch changes from a trusted epoch, and verifies that transactions
+/// are certified and that their effects are included in a certified checkpoint.
+pub struct LightClient {
+    http: HttpClient,
+    epochs: RwLock<VerifiedEpochs>,
+}
+
+impl LightClient {
+    pub fn new(http_url: &str, trusted_epoch: AuthenticatedEpoch) -> Result<Self, anyhow::Error> {
+        Ok(Self {
+            http: HttpClientBuilder::default().build(http_url)?,
+            epochs: RwLock::new(VerifiedEpochs::new(trusted_epoch)),
+        })
+    }
+
+    pub fn new_from_genesis(http_url: &str, genesis: &Genesis) -> Result<Self, anyhow::Error> {
+        let genesis_epoch = GenesisEpoch::new(genesis.committee()?);
+        Self::new(http_url, AuthenticatedEpoch::Genesis(genesis_epoch))
+    }
+
+    pub fn latest_epoch(&self) -> EpochId {
+        self.epochs.read().unwrap().latest_epoch()
+    }
+
+    /// Follow the epoch changes certified since the latest verified epoch, and return the new
+    /// latest verified epoch.
+    pub async fn sync_epochs(&self) -> Result<EpochId, anyhow::Error> {
+        loop {
+            let next_epoch = self.latest_epoch() + 1;
+            let certified_epoch = match self.http.get_raw_epoch(Some(next_epoch)).await? {
+                Some(bytes) => match bcs::from_bytes(&bytes.to_vec()?)? {
+                    AuthenticatedEpoch::Certified(certified_epoch) => certified_epoch,
+                    // The next epoch is not certified yet.
+                    AuthenticatedEpoch::Signed(_) => break,
+                    AuthenticatedEpoch::Genesis(_) => {
+                        bail!("Received a genesis epoch for epoch {next_epoch}")
+                    }
+                },
+                None => break,
+            };
+            self.epochs
+                .write()
+                .unwrap()
+                .add_certified_epoch(certified_epoch)?;
+        }
+        Ok(self.latest_epoch())
+    }
+
+    /// Make sure the committee of `epoch` is verified, following epoch changes if needed.
+    async fn sync_to_epoch(&self, epoch: EpochId) -> Result<(), anyhow::Error> {
+        if epoch > self.latest_epoch() && self.sync_epochs().await? < epoch {
+            bail!("Epoch {epoch} is not certified yet");
+        }
+        Ok(())
+    }
+
+    pub async fn verify_certificate(
+        &self,
+        certificate: &CertifiedTransaction,
+    ) -> Result<(), anyhow::Error> {
+        self.sync_to_epoch(certificate.auth_sign_info.epoch).await?;
+        Ok(self
+            .epochs
+            .read()
+            .unwrap()
+            .verify_certificate(certificate)?)
+    }
+
+    pub async fn verify_effects(
+        &self,
+        effects: &CertifiedTransactionEffects,
+    ) -> Result<(), anyhow::Error> {
+        self.sync_to_epoch(effects.auth_signature.epoch).await?;
+        Ok(self.epochs.read().unwrap().verify_effects(effects)?)
+    }
+
+    pub async fn verify_checkpoint(
+        &self,
+        checkpoint: &CertifiedCheckpointSummary,
+        contents: &CheckpointContents,
+    ) -> Result<(), anyhow::Error> {
+        self.sync_to_epoch(checkpoint.summary.epoch).await?;
+        Ok(self
+            .epochs
+            .read()
+            .unwrap()
+            .verify_checkpoint(checkpoint, contents)?)
+    }
+
+    /// Read a transaction and its effects. The transaction must be certified, and its effects
+    /// included in a certified checkpoint, so this fails until the transaction is checkpointed.
+    pub async fn get_transaction(
+        &self,
+        digest: TransactionDigest,
+    ) -> Result<(CertifiedTransaction, TransactionEffects), anyhow::Error> {
+        let raw = self.http.get_raw_transaction(digest).await?;
+
+        let certificate: CertifiedTransaction = bcs::from_bytes(&raw.certificate)?;
+        if *certificate.digest() != digest {
+            bail!(
+                "Received the certificate of transaction {:?}",
+                certificate.digest()
+            );
+        }
+        self.verify_certificate(&certificate).await?;
+
+        let effects: TransactionEffects = bcs::from_bytes(&raw.effects)?;
+        if effects.transaction_digest != digest {
+            bail!(
+                "Received the effects of transaction {:?}",
+                effects.transaction_digest
+            );
+        }
+        let (checkpoint, contents) = match (raw.checkpoint, raw.checkpoint_contents) {
+            (Some(checkpoint), Some(contents)) => (
+                bcs::from_bytes::<CertifiedCheckpointSummary>(&checkpoint)?,
+                bcs::from_bytes::<CheckpointContents>(&contents)?,
+            ),
+            _ => bail!(
+                "Transaction {digest:?} is not checkpointed yet, its effects cannot be verified"
+            ),
+        };
+        self.verify_checkpoint(&checkpoint, &contents).await?;
+        let execution_digests = ExecutionDigests::new(digest, effects.digest());
+        if !contents.iter().any(|digests| *digests == execution_digests) {
+            bail!(
+                "Checkpoint {} does not include the effects of transaction {digest:?}",
+                checkpoint.summary.sequence_number
+            );
+        }
+        Ok((certificate, effects))
+    }
+
+    /// Read an object, and check that it is the output of the verified effects of the transaction
+    /// that last wrote it. This proves that the object existed at this version, not that no later
+    /// transaction modified it.
+    pub async fn get_object(&self, object_id: ObjectID) -> Result<Object, anyhow::Error> {
+        let object: Object = match self.http.get_raw_object(object_id).await? {
+            SuiObjectRead::Exists(object) => object.try_into()?,
+            SuiObjectRead::NotExists(id) => bail!("Object {id} does not exist"),
+            SuiObjectRead::Deleted(object_ref) => {
+                bail!("Object {} was deleted", object_ref.object_id)
+            }
+        };
+        let object_ref = object.compute_object_reference();
+        let (_, effects) = self.get_transaction(object.previous_transaction).await?;
+        if !effects
+            .all_mutated()
+            .any(|(written_ref, owner)| *written_ref == object_ref && *owner == object.owner)
+        {
+            bail!(
+                "Object {:?} is not written by its previous transaction {:?}",
+                object_ref,
+                object.previous_transaction
+            );
+        }
+        Ok(object)
+    }
+}
@@ -5,8 +5,16 @@ use std::str::FromStr;
 use sha3::{Digest, Sha3_256};
 use tempfile::TempDir;
 
+use std::collections::BTreeMap;
+
 use sui_sdk::crypto::KeystoreType;
-use sui_types::crypto::{SignatureScheme, SuiSignatureInner};
+use sui_sdk::light_client::VerifiedEpochs;
+use sui_types::committee::Committee;
+use sui_types::crypto::{
+    random_key_pairs, AuthorityKeyPair, AuthorityPublicKeyBytes, KeypairTraits, SignatureScheme,
+    SuiSignatureInner,
+};
+use sui_types::messages::{AuthenticatedEpoch, CertifiedEpoch, GenesisEpoch, SignedEpoch};
 use sui_types::{
     base_types::{SuiAddress, SUI_ADDRESS_LENGTH},
     crypto::Ed25519SuiSignature,
@@ -57,3 +65,62 @@ fn sui_wallet_address_mnemonic_test() -> Result<(), anyhow::Error> {
 
     Ok(())
 }
+
+#[test]
+fn light_client_verified_epochs_test() {
+    let keys: Vec<AuthorityKeyPair> = random_key_pairs(4);
+    let voting_rights: BTreeMap<_, _> = keys
+        .iter()
+        .map(|key| (AuthorityPublicKeyBytes::from(key.public()), 1))
+        .collect();
+    let committee0 = Committee::new(0, voting_rights.clone()).unwrap();
+    let committee1 = Committee::new(1, voting_rights).unwrap();
+
+    let genesis = GenesisEpoch::new(committee0.clone());
+    let mut epochs = VerifiedEpochs::new(AuthenticatedEpoch::Genesis(genesis.clone()));
+    assert_eq!(epochs.latest_epoch(), 0);
+
+    // Certify epoch 1 with the keys of the epoch 0 committee.
+    let certify = |committee: Committee, keys: &[AuthorityKeyPair]| {
+        let signatures: Vec<_> = keys
+            .iter()
+            .map(|key| {
+                let signed = SignedEpoch::new(
+                    committee.clone(),
+                    AuthorityPublicKeyBytes::from(key.public()),
+                    key,
+                    10,
+                    &genesis.epoch_info,
+                );
+                (
+                    signed.auth_sign_info.authority,
+                    signed.auth_sign_info.signature,
+                )
+            })
+            .collect();
+        let epoch_info = SignedEpoch::new(
+            committee,
+            AuthorityPublicKeyBytes::from(keys[0].public()),
+            &keys[0],
+            10,
+            &genesis.epoch_info,
+        )
+        .epoch_info;
+        CertifiedEpoch::new(&epoch_info, signatures, &committee0).unwrap()
+    };
+
+    // An epoch skipping epoch 1 is rejected.
+    let committee2 = Committee::new(2, committee1.voting_rights.iter().cloned().collect()).unwrap();
+    assert!(epochs
+        .add_certified_epoch(certify(committee2, &keys[..3]))
+        .is_err());
+
+    let certified = certify(committee1.clone(), &keys[..3]);
+    epochs.add_certified_epoch(certified.clone()).unwrap();
+    assert_eq!(epochs.latest_epoch(), 1);
+    assert_eq!(epochs.committee(1).unwrap(), &committee1);
+
+    // The same epoch cannot be added twice.
+    assert!(epochs.add_certified_epoch(certified).is_err());
+    assert!(epochs.committee(2).is_err());
+}
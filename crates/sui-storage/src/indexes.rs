@@ -6,16 +6,19 @@
 
 use rocksdb::Options;
 use serde::{de::DeserializeOwned, Serialize};
+use std::collections::BTreeMap;
 use typed_store_derive::DBMapUtils;
 
 use sui_types::base_types::{ObjectID, SuiAddress, TransactionDigest};
 use sui_types::batch::TxSequenceNumber;
+use sui_types::display::DisplayVersionUpdatedEvent;
 use sui_types::error::SuiResult;
 
 use sui_types::base_types::ObjectRef;
 use sui_types::object::Owner;
 
 use move_core_types::identifier::Identifier;
+use move_core_types::language_storage::StructTag;
 use typed_store::rocks::DBMap;
 use typed_store::traits::Map;
 use typed_store::traits::TypedStoreDebug;
@@ -51,6 +54,11 @@ pub struct IndexStore {
     /// The timestamping happens when the node sees a txn certificate for the first time.
     #[default_options_override_fn = "timestamps_table_default_config"]
     timestamps: DBMap<TransactionDigest, u64>,
+
+    /// Index from object type to the version and fields to display its objects with, as announced
+    /// by the `sui::display::VersionUpdated` event with the highest version for the type.
+    #[default_options_override_fn = "display_table_default_config"]
+    display: DBMap<StructTag, (u64, BTreeMap<String, String>)>,
 }
 
 // These functions are used to initialize the DB tables
@@ -72,6 +80,9 @@ fn transactions_by_move_function_table_default_config() -> Options {
 fn timestamps_table_default_config() -> Options {
     default_db_options(None, Some(1_000_000)).1
 }
+fn display_table_default_config() -> Options {
+    default_db_options(None, None).1
+}
 
 impl IndexStore {
     pub fn index_tx<'a>(
@@ -80,6 +91,7 @@ impl IndexStore {
         active_inputs: impl Iterator<Item = ObjectID>,
         mutated_objects: impl Iterator<Item = &'a (ObjectRef, Owner)> + Clone,
         move_functions: impl Iterator<Item = (ObjectID, Identifier, Identifier)> + Clone,
+        display_updates: impl Iterator<Item = (StructTag, DisplayVersionUpdatedEvent)>,
         sequence: TxSequenceNumber,
         digest: &TransactionDigest,
         timestamp_ms: u64,
@@ -126,6 +138,20 @@ impl IndexStore {
         let batch =
             batch.insert_batch(&self.timestamps, std::iter::once((*digest, timestamp_ms)))?;
 
+        // A type can have several `Display` objects, whose updates may come in any order, so only
+        // updates with a higher version than the indexed one are kept.
+        let mut displays: BTreeMap<StructTag, (u64, BTreeMap<String, String>)> = BTreeMap::new();
+        for (type_, update) in display_updates {
+            let indexed_version = match displays.get(&type_) {
+                Some((version, _)) => Some(*version),
+                None => self.display.get(&type_)?.map(|(version, _)| version),
+            };
+            if indexed_version.map_or(true, |version| update.version > version) {
+                displays.insert(type_, (update.version, update.fields()));
+            }
+        }
+        let batch = batch.insert_batch(&self.display, displays)?;
+
         batch.write()?;
 
         Ok(())
@@ -140,6 +166,11 @@ impl IndexStore {
         Ok(ts)
     }
 
+    /// Returns the fields to display objects of type `type_` with, if its publisher set any.
+    pub fn get_display(&self, type_: &StructTag) -> SuiResult<Option<BTreeMap<String, String>>> {
+        Ok(self.display.get(type_)?.map(|(_, fields)| fields))
+    }
+
     fn get_transactions_by_object<
         KeyT: Clone + Serialize + DeserializeOwned + std::cmp::PartialEq,
     >(
@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 pub mod lock_service;
-pub use lock_service::LockService;
+pub use lock_service::{LockService, LockServiceStats};
 
 pub mod indexes;
 pub use indexes::IndexStore;
@@ -17,6 +17,7 @@
 
 use futures::channel::oneshot;
 use rocksdb::Options;
+use serde::Serialize;
 use std::path::PathBuf;
 use std::sync::Arc;
 use std::thread::JoinHandle;
@@ -396,6 +397,17 @@ impl LockServiceImpl {
 
 const LOCKSERVICE_QUEUE_LEN: usize = 500;
 
+/// A snapshot of the LockService queues, as reported to node operators.
+#[derive(Clone, Debug, Serialize)]
+pub struct LockServiceStats {
+    /// Number of lock mutations waiting to be processed.
+    pub queued_commands: usize,
+    /// Number of lock queries waiting to be processed.
+    pub queued_queries: usize,
+    /// Maximum length of each queue, beyond which callers are blocked.
+    pub queue_capacity: usize,
+}
+
 /// Atomic Sui Object locking service.
 /// Primary abstraction is an atomic op to acquire a lock on a given set of objects.
 /// Atomicity relies on single threaded loop and only one instance per authority.
@@ -626,6 +638,15 @@ impl LockService {
         })
         .await
     }
+
+    /// Returns how many commands and queries are currently queued for the LockService.
+    pub fn stats(&self) -> LockServiceStats {
+        LockServiceStats {
+            queued_commands: LOCKSERVICE_QUEUE_LEN - self.inner.sender().capacity(),
+            queued_queries: LOCKSERVICE_QUEUE_LEN - self.inner.query_sender().capacity(),
+            queue_capacity: LOCKSERVICE_QUEUE_LEN,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -785,4 +806,21 @@ mod tests {
             .filter(|r| r.is_err())
             .all(|r| matches!(r, Err(SuiError::ConflictingTransaction { .. }))));
     }
+
+    #[tokio::test]
+    async fn test_lockservice_stats() {
+        let ls = init_lockservice();
+
+        let ref1: ObjectRef = (ObjectID::random(), 1.into(), ObjectDigest::random());
+        ls.initialize_locks(&[ref1], false /* is_force_reset */)
+            .await
+            .unwrap();
+        assert_eq!(ls.get_lock(ref1).await, Ok(Some(None)));
+
+        // All requests above have been answered, so nothing is left in the queues.
+        let stats = ls.stats();
+        assert_eq!(stats.queued_commands, 0);
+        assert_eq!(stats.queued_queries, 0);
+        assert_eq!(stats.queue_capacity, LOCKSERVICE_QUEUE_LEN);
+    }
 }
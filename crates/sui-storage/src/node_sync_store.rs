@@ -9,6 +9,9 @@ use sui_types::{
     committee::StakeUnit,
     error::SuiResult,
     messages::{CertifiedTransaction, SignedTransactionEffects},
+    messages_checkpoint::{
+        CertifiedCheckpointSummary, CheckpointContents, CheckpointDigest, CheckpointSequenceNumber,
+    },
 };
 
 use typed_store::rocks::DBMap;
@@ -42,6 +45,16 @@ pub struct NodeSyncStore {
 
     /// Which peers have claimed to have executed which effects?
     effects_votes: DBMap<(TransactionDigest, TransactionEffectsDigest, AuthorityName), StakeUnit>,
+
+    /// Certified checkpoints (and their contents) that have been downloaded and verified, but
+    /// whose transactions have not all been executed yet.
+    /// Entries are cleared once the checkpoint is synced.
+    pending_checkpoints:
+        DBMap<CheckpointSequenceNumber, (CertifiedCheckpointSummary, CheckpointContents)>,
+
+    /// Checkpoints whose transactions have all been executed locally, mapped to their digest.
+    /// The highest entry is the point at which checkpoint sync resumes after a restart.
+    synced_checkpoints: DBMap<CheckpointSequenceNumber, CheckpointDigest>,
 }
 
 impl NodeSyncStore {
@@ -209,6 +222,49 @@ impl NodeSyncStore {
                 .map(|(k, _)| k),
         )?)
     }
+
+    pub fn store_pending_checkpoint(
+        &self,
+        checkpoint: &CertifiedCheckpointSummary,
+        contents: &CheckpointContents,
+    ) -> SuiResult {
+        trace!(seq = ?checkpoint.summary.sequence_number, "persisting pending checkpoint");
+        Ok(self.pending_checkpoints.insert(
+            &checkpoint.summary.sequence_number,
+            &(checkpoint.clone(), contents.clone()),
+        )?)
+    }
+
+    pub fn get_pending_checkpoint(
+        &self,
+        seq: CheckpointSequenceNumber,
+    ) -> SuiResult<Option<(CertifiedCheckpointSummary, CheckpointContents)>> {
+        Ok(self.pending_checkpoints.get(&seq)?)
+    }
+
+    /// Record that all transactions in `checkpoint` have been executed, and drop the pending
+    /// copy of the checkpoint in the same write.
+    pub fn mark_checkpoint_synced(&self, checkpoint: &CertifiedCheckpointSummary) -> SuiResult {
+        let seq = checkpoint.summary.sequence_number;
+        trace!(?seq, "marking checkpoint as synced");
+        let write_batch = self
+            .pending_checkpoints
+            .batch()
+            .delete_batch(&self.pending_checkpoints, std::iter::once(seq))?
+            .insert_batch(
+                &self.synced_checkpoints,
+                std::iter::once((seq, checkpoint.summary.digest())),
+            )?;
+        write_batch.write()?;
+        Ok(())
+    }
+
+    /// Returns the sequence number and digest of the highest synced checkpoint, if any.
+    pub fn latest_synced_checkpoint(
+        &self,
+    ) -> SuiResult<Option<(CheckpointSequenceNumber, CheckpointDigest)>> {
+        Ok(self.synced_checkpoints.iter().skip_to_last().next())
+    }
 }
 
 #[cfg(test)]
@@ -41,6 +41,11 @@ pub trait TxGuard<'a>: Drop {
 
     /// Mark the TX as abandoned/aborted but not requiring any recovery or rollback.
     fn release(self);
+
+    /// Move the TX out of the log and into quarantine, where it is kept for inspection and is
+    /// no longer recovered. A resubmitted certificate releases it from quarantine for another
+    /// attempt, at most `MAX_QUARANTINE_RELEASES` times.
+    fn quarantine(self);
 }
 
 // WriteAheadLog is parameterized on the value type (C) because:
@@ -115,6 +120,13 @@ where
         }
     }
 
+    fn quarantine_impl(mut self) {
+        self.dead = true;
+        if let Err(e) = self.wal.quarantine_tx_impl(&self.tx) {
+            warn!(digest = ?self.tx, "Couldn't quarantine tx in WriteAheadLog: {}", e);
+        }
+    }
+
     fn commit_tx_impl(mut self, is_commit: bool) {
         self.dead = true;
         // Note: if commit_tx fails, the tx will still be in the log and will re-enter
@@ -146,6 +158,10 @@ where
     fn release(self) {
         self.commit_tx_impl(false)
     }
+
+    fn quarantine(self) {
+        self.quarantine_impl()
+    }
 }
 
 impl<C> Drop for DBTxGuard<'_, C>
@@ -167,6 +183,11 @@ pub struct DBWriteAheadLogTables<C> {
     // We use two tables, because if we instead have one table mapping digest -> (C, u32), we have
     // to clone C to make a tuple ref to pass to insert.
     retry_count: DBMap<TransactionDigest, u32>,
+    // Txes that were moved out of the log, either because they kept failing recovery or by an
+    // operator, with their retry count at that point. These are never recovered.
+    quarantine: DBMap<TransactionDigest, (C, u32)>,
+    // How many times each tx was released from quarantine by being resubmitted.
+    quarantine_releases: DBMap<TransactionDigest, u32>,
 }
 
 // A WriteAheadLog implementation built on rocksdb.
@@ -181,6 +202,10 @@ pub struct DBWriteAheadLog<C> {
     mutex_table: MutexTable<TransactionDigest>,
 }
 
+/// How many times a quarantined tx is released for another attempt when its certificate is
+/// resubmitted. After that, it stays quarantined until it is dropped with `drop_tx`.
+pub const MAX_QUARANTINE_RELEASES: u32 = 3;
+
 const MUTEX_TABLE_SIZE: usize = 1024;
 const MUTEX_TABLE_SHARD_SIZE: usize = 128;
 
@@ -215,9 +240,130 @@ where
         let write_batch = write_batch.delete_batch(&self.tables.log, std::iter::once(tx))?;
         let write_batch =
             write_batch.delete_batch(&self.tables.retry_count, std::iter::once(tx))?;
+        let write_batch =
+            write_batch.delete_batch(&self.tables.quarantine_releases, std::iter::once(tx))?;
         write_batch.write().map_err(SuiError::from)
     }
 
+    /// List the txes in the log together with the number of times each one has been retried.
+    pub fn entries(&self) -> SuiResult<Vec<(TransactionDigest, u32)>> {
+        self.tables
+            .log
+            .iter()
+            .map(|(tx, _)| Ok((tx, self.get_retry_count(&tx)?)))
+            .collect()
+    }
+
+    /// Returns the cert and retry count of a tx in the log.
+    pub fn get_entry(&self, tx: &TransactionDigest) -> SuiResult<Option<(C, u32)>> {
+        match self.tables.log.get(tx)? {
+            Some(cert) => Ok(Some((cert, self.get_retry_count(tx)?))),
+            None => Ok(None),
+        }
+    }
+
+    /// List the quarantined txes, with the retry count they had when they were quarantined.
+    pub fn quarantined_entries(&self) -> Vec<(TransactionDigest, u32)> {
+        self.tables
+            .quarantine
+            .iter()
+            .map(|(tx, (_, retry_count))| (tx, retry_count))
+            .collect()
+    }
+
+    /// Returns the cert and retry count of a quarantined tx.
+    pub fn get_quarantined_entry(&self, tx: &TransactionDigest) -> SuiResult<Option<(C, u32)>> {
+        Ok(self.tables.quarantine.get(tx)?)
+    }
+
+    /// Move a tx from the log into quarantine. Returns false if the tx is not in the log.
+    pub async fn quarantine_tx(&self, tx: &TransactionDigest) -> SuiResult<bool> {
+        let _mutex_guard = self.mutex_table.acquire_lock(*tx).await;
+        if !self.tables.log.contains_key(tx)? {
+            return Ok(false);
+        }
+        self.quarantine_tx_impl(tx)?;
+        Ok(true)
+    }
+
+    /// Remove a tx from both the log and quarantine, without recovering it, and forget how many
+    /// times it was released from quarantine. Returns false if the tx was in neither.
+    pub async fn drop_tx(&self, tx: &TransactionDigest) -> SuiResult<bool> {
+        let _mutex_guard = self.mutex_table.acquire_lock(*tx).await;
+        let found = self.tables.log.contains_key(tx)? || self.tables.quarantine.contains_key(tx)?;
+
+        warn!(digest = ?tx, "dropping tx from WriteAheadLog");
+        let write_batch = self.tables.log.batch();
+        let write_batch = write_batch.delete_batch(&self.tables.log, std::iter::once(tx))?;
+        let write_batch =
+            write_batch.delete_batch(&self.tables.retry_count, std::iter::once(tx))?;
+        let write_batch = write_batch.delete_batch(&self.tables.quarantine, std::iter::once(tx))?;
+        let write_batch =
+            write_batch.delete_batch(&self.tables.quarantine_releases, std::iter::once(tx))?;
+        write_batch.write()?;
+
+        self.forget_recoverable_tx(tx);
+        Ok(found)
+    }
+
+    // Callers must hold the lock for tx.
+    fn quarantine_tx_impl(&self, tx: &TransactionDigest) -> SuiResult {
+        let cert = self
+            .tables
+            .log
+            .get(tx)?
+            .ok_or(SuiError::TransactionNotFound { digest: *tx })?;
+        let retry_count = self.get_retry_count(tx)?;
+        warn!(digest = ?tx, ?retry_count, "quarantining tx in WriteAheadLog");
+
+        let write_batch = self.tables.log.batch();
+        let write_batch = write_batch.insert_batch(
+            &self.tables.quarantine,
+            std::iter::once((tx, (cert, retry_count))),
+        )?;
+        let write_batch = write_batch.delete_batch(&self.tables.log, std::iter::once(tx))?;
+        let write_batch =
+            write_batch.delete_batch(&self.tables.retry_count, std::iter::once(tx))?;
+        write_batch.write()?;
+
+        self.forget_recoverable_tx(tx);
+        Ok(())
+    }
+
+    // Move a quarantined tx back into the log for a fresh attempt, unless it has already been
+    // released too many times. Callers must hold the lock for tx.
+    fn release_quarantined_tx(&self, tx: &TransactionDigest, cert: &C) -> SuiResult {
+        let releases = self.tables.quarantine_releases.get(tx)?.unwrap_or(0);
+        if releases >= MAX_QUARANTINE_RELEASES {
+            return Err(SuiError::ErrorWhileProcessingConfirmationTransaction {
+                err: format!(
+                    "tx is quarantined in the WriteAheadLog after {} releases, \
+                    it must be dropped before it can be executed again",
+                    releases
+                ),
+            });
+        }
+        warn!(digest = ?tx, releases, "releasing quarantined tx in WriteAheadLog");
+
+        let write_batch = self.tables.log.batch();
+        let write_batch = write_batch.delete_batch(&self.tables.quarantine, std::iter::once(tx))?;
+        let write_batch = write_batch.insert_batch(
+            &self.tables.quarantine_releases,
+            std::iter::once((tx, releases + 1)),
+        )?;
+        let write_batch =
+            write_batch.insert_batch(&self.tables.log, std::iter::once((tx, cert)))?;
+        write_batch.write()?;
+        Ok(())
+    }
+
+    fn forget_recoverable_tx(&self, tx: &TransactionDigest) {
+        self.recoverable_txes
+            .lock()
+            .unwrap()
+            .retain(|digest| digest != tx);
+    }
+
     fn get_retry_count(&self, tx: &TransactionDigest) -> SuiResult<u32> {
         Ok(self.tables.retry_count.get(tx)?.unwrap_or(0))
     }
@@ -263,6 +409,11 @@ where
         let mutex_guard = self.mutex_table.acquire_lock(*tx).await;
         trace!(digest = ?tx, "acquired tx lock");
 
+        if self.tables.quarantine.contains_key(tx)? {
+            self.release_quarantined_tx(tx, cert)?;
+            return Ok(DBTxGuard::new(tx, 0, mutex_guard, self));
+        }
+
         let retry_count = if self.tables.log.contains_key(tx)? {
             self.increment_retry_count(tx).tap_err(|e| {
                 error!(digest = ?tx,
@@ -307,7 +458,9 @@ where
 #[cfg(test)]
 mod tests {
 
-    use crate::write_ahead_log::{DBWriteAheadLog, TxGuard, WriteAheadLog};
+    use crate::write_ahead_log::{
+        DBWriteAheadLog, TxGuard, WriteAheadLog, MAX_QUARANTINE_RELEASES,
+    };
     use anyhow;
     use sui_types::base_types::TransactionDigest;
 
@@ -369,4 +522,78 @@ mod tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_write_ahead_log_quarantine() -> Result<(), anyhow::Error> {
+        let working_dir = tempfile::tempdir()?;
+
+        let tx1_id = TransactionDigest::random();
+        let tx2_id = TransactionDigest::random();
+
+        {
+            let log: DBWriteAheadLog<u32> = DBWriteAheadLog::new(working_dir.path().to_path_buf());
+            {
+                let _tx1 = log.begin_tx(&tx1_id, &1).await.unwrap();
+                let _tx2 = log.begin_tx(&tx2_id, &2).await.unwrap();
+                // implicit drop
+            }
+            let mut entries = log.entries()?;
+            entries.sort();
+            let mut expected = vec![(tx1_id, 0), (tx2_id, 0)];
+            expected.sort();
+            assert_eq!(entries, expected);
+
+            // The first recovered tx, which may be either of them, is quarantined through its
+            // guard, as recovery does once a tx has been retried too many times.
+            let (_, guard) = log.read_one_recoverable_tx().await.unwrap().unwrap();
+            let quarantined_id = guard.tx_id();
+            assert_eq!(guard.retry_num(), 1);
+            guard.quarantine();
+
+            // The other tx is quarantined by an operator.
+            let other_id = if quarantined_id == tx1_id {
+                tx2_id
+            } else {
+                tx1_id
+            };
+            assert!(log.quarantine_tx(&other_id).await?);
+            assert!(!log.quarantine_tx(&other_id).await?);
+
+            assert!(log.entries()?.is_empty());
+            assert_eq!(log.quarantined_entries().len(), 2);
+            assert!(recover_queue_empty(&log).await);
+            assert_eq!(log.get_quarantined_entry(&quarantined_id)?.unwrap().1, 1);
+
+            // Resubmitting a quarantined tx releases it for a fresh attempt, a bounded number
+            // of times.
+            for _ in 0..MAX_QUARANTINE_RELEASES {
+                let guard = log.begin_tx(&tx1_id, &1).await.unwrap();
+                assert_eq!(guard.retry_num(), 0);
+                assert_eq!(log.get_entry(&tx1_id)?.unwrap(), (1, 0));
+                assert!(log.get_quarantined_entry(&tx1_id)?.is_none());
+                guard.quarantine();
+            }
+            assert!(log.begin_tx(&tx1_id, &1).await.is_err());
+        }
+
+        {
+            // Quarantine survives restarts, and dropping a tx makes it usable again.
+            let log: DBWriteAheadLog<u32> = DBWriteAheadLog::new(working_dir.path().to_path_buf());
+            assert!(recover_queue_empty(&log).await);
+            assert!(log.drop_tx(&tx1_id).await?);
+            assert!(!log.drop_tx(&tx1_id).await?);
+            let remaining: Vec<_> = log
+                .quarantined_entries()
+                .into_iter()
+                .map(|(digest, _)| digest)
+                .collect();
+            assert_eq!(remaining, vec![tx2_id]);
+
+            let tx1 = log.begin_tx(&tx1_id, &1).await.unwrap();
+            assert_eq!(tx1.retry_num(), 0);
+            tx1.commit_tx();
+        }
+
+        Ok(())
+    }
 }
@@ -14,7 +14,7 @@ use std::{
 };
 use sui_config::builder::{CommitteeConfig, ConfigBuilder};
 use sui_config::genesis_config::{GenesisConfig, ValidatorGenesisInfo};
-use sui_config::NetworkConfig;
+use sui_config::{ConsensusProtocol, NetworkConfig};
 use sui_types::base_types::SuiAddress;
 use tempfile::TempDir;
 
@@ -27,6 +27,7 @@ pub struct SwarmBuilder<R = OsRng> {
     fullnode_count: usize,
     fullnode_rpc_addr: Option<SocketAddr>,
     websocket_rpc_addr: Option<SocketAddr>,
+    consensus_protocol: ConsensusProtocol,
 }
 
 impl SwarmBuilder {
@@ -40,6 +41,7 @@ impl SwarmBuilder {
             fullnode_count: 0,
             fullnode_rpc_addr: None,
             websocket_rpc_addr: None,
+            consensus_protocol: ConsensusProtocol::Narwhal,
         }
     }
 }
@@ -54,6 +56,7 @@ impl<R> SwarmBuilder<R> {
             fullnode_count: self.fullnode_count,
             fullnode_rpc_addr: self.fullnode_rpc_addr,
             websocket_rpc_addr: self.websocket_rpc_addr,
+            consensus_protocol: self.consensus_protocol,
         }
     }
 
@@ -99,6 +102,15 @@ impl<R> SwarmBuilder<R> {
         self.websocket_rpc_addr = Some(websocket_rpc_addr);
         self
     }
+
+    /// Set the consensus protocol run by the validators. `ConsensusProtocol::SingleNode`
+    /// requires a committee of size 1.
+    ///
+    /// Defaults to Narwhal.
+    pub fn with_consensus_protocol(mut self, consensus_protocol: ConsensusProtocol) -> Self {
+        self.consensus_protocol = consensus_protocol;
+        self
+    }
 }
 
 impl<R: ::rand::RngCore + ::rand::CryptoRng> SwarmBuilder<R> {
@@ -118,6 +130,7 @@ impl<R: ::rand::RngCore + ::rand::CryptoRng> SwarmBuilder<R> {
 
         let network_config = config_builder
             .committee(self.committee)
+            .consensus_protocol(self.consensus_protocol)
             .with_swarm()
             .rng(self.rng)
             .build();
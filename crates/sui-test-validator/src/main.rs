@@ -21,7 +21,8 @@ use sui_types::base_types::SuiAddress;
 use tower::ServiceBuilder;
 use tower_http::cors::{Any, CorsLayer};
 
-/// Start a Sui validator and fullnode for easy testing.
+/// Start a Sui validator and fullnode for easy testing. The validator sequences transactions
+/// in-process, so no Narwhal primaries or workers are started.
 #[derive(Parser, Debug)]
 #[clap(author, version, about, long_about = None)]
 struct Args {
@@ -56,6 +57,7 @@ async fn main() -> Result<()> {
         fullnode_address: Some(format!("127.0.0.1:{}", args.fullnode_rpc_port)),
         websocket_address: Some(format!("127.0.0.1:{}", args.websocket_rpc_port)),
         faucet_address: None,
+        single_node: true,
     })
     .await?;
 
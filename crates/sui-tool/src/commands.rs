@@ -10,6 +10,7 @@ use std::sync::Arc;
 use std::time::Duration;
 use sui_config::genesis::Genesis;
 use sui_tool::db_tool::{execute_db_tool_command, print_db_all_tables, DbToolCommand};
+use sui_tool::wal_tool::{execute_wal_tool_command, WalToolCommand};
 
 use sui_core::authority_client::{
     AuthorityAPI, NetworkAuthorityClient, NetworkAuthorityClientMetrics,
@@ -89,6 +90,16 @@ pub enum ToolCommand {
         cmd: Option<DbToolCommand>,
     },
 
+    /// Inspect and repair the write ahead log of a stopped validator or full node.
+    #[clap(name = "wal")]
+    Wal {
+        /// Path of the write ahead log, i.e. the `recovery_log` directory of the authority store
+        #[clap(long = "db-path")]
+        db_path: PathBuf,
+        #[clap(subcommand)]
+        cmd: WalToolCommand,
+    },
+
     /// Pull down the batch stream for a validator(s).
     /// Note that this command currently operates sequentially, so it will block on the first
     /// validator indefinitely. Therefore you should generally use this with a --validator=
@@ -499,6 +510,7 @@ impl ToolCommand {
                     None => print_db_all_tables(path)?,
                 }
             }
+            ToolCommand::Wal { db_path, cmd } => execute_wal_tool_command(db_path, cmd).await?,
 
             ToolCommand::DumpValidators { genesis } => {
                 let genesis = Genesis::load(genesis).unwrap();
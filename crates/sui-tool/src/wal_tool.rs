@@ -0,0 +1,86 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::anyhow;
+use clap::Parser;
+use std::path::PathBuf;
+use sui_storage::write_ahead_log::DBWriteAheadLog;
+use sui_types::{base_types::TransactionDigest, messages::CertifiedTransaction};
+
+/// Inspect and repair the write ahead log (`recovery_log`) of an authority store.
+/// The node must be stopped while these commands run.
+#[derive(Parser)]
+#[clap(rename_all = "kebab-case")]
+pub enum WalToolCommand {
+    /// List the certificates in the log, and the quarantined ones, with their retry counts.
+    List,
+    /// Show a single certificate from the log or from quarantine.
+    Show {
+        #[clap(long)]
+        digest: TransactionDigest,
+    },
+    /// Move a certificate out of the log so that it is no longer recovered. Resubmitting it
+    /// releases it for another attempt a few times, after which it has to be dropped.
+    Quarantine {
+        #[clap(long)]
+        digest: TransactionDigest,
+    },
+    /// Remove a certificate from the log and from quarantine. A dropped certificate is executed
+    /// again if it is resubmitted.
+    Drop {
+        #[clap(long)]
+        digest: TransactionDigest,
+    },
+}
+
+pub async fn execute_wal_tool_command(
+    wal_path: PathBuf,
+    cmd: WalToolCommand,
+) -> anyhow::Result<()> {
+    let wal: DBWriteAheadLog<CertifiedTransaction> = DBWriteAheadLog::new(wal_path);
+
+    match cmd {
+        WalToolCommand::List => {
+            println!("{:<45} {:<8} status", "digest", "retries");
+            for (digest, retry_count) in wal.entries()? {
+                println!("{:<45} {:<8} pending", format!("{:?}", digest), retry_count);
+            }
+            for (digest, retry_count) in wal.quarantined_entries() {
+                println!(
+                    "{:<45} {:<8} quarantined",
+                    format!("{:?}", digest),
+                    retry_count
+                );
+            }
+        }
+        WalToolCommand::Show { digest } => {
+            let (cert, retry_count, status) =
+                if let Some((cert, retry_count)) = wal.get_entry(&digest)? {
+                    (cert, retry_count, "pending")
+                } else if let Some((cert, retry_count)) = wal.get_quarantined_entry(&digest)? {
+                    (cert, retry_count, "quarantined")
+                } else {
+                    return Err(anyhow!("{:?} is not in the write ahead log", digest));
+                };
+            println!("status: {}", status);
+            println!("retries: {}", retry_count);
+            println!("{}", cert);
+        }
+        WalToolCommand::Quarantine { digest } => {
+            if !wal.quarantine_tx(&digest).await? {
+                return Err(anyhow!(
+                    "{:?} is not pending in the write ahead log",
+                    digest
+                ));
+            }
+            println!("quarantined {:?}", digest);
+        }
+        WalToolCommand::Drop { digest } => {
+            if !wal.drop_tx(&digest).await? {
+                return Err(anyhow!("{:?} is not in the write ahead log", digest));
+            }
+            println!("dropped {:?}", digest);
+        }
+    }
+    Ok(())
+}
@@ -463,7 +463,7 @@ impl<'a> SuiTestAdapter<'a> {
         transaction: Transaction,
         gas_budget: u64,
     ) -> anyhow::Result<TxnSummary> {
-        let gas_status = gas::start_gas_metering(gas_budget, 1, 1).unwrap();
+        let mut gas_status = gas::start_gas_metering(gas_budget, 1, 1).unwrap();
         let transaction_digest = TransactionDigest::new(self.rng.gen());
         let objects_by_kind = transaction
             .signed_data
@@ -507,7 +507,7 @@ impl<'a> SuiTestAdapter<'a> {
             transaction_dependencies,
             &self.vm,
             &self.native_functions,
-            gas_status,
+            &mut gas_status,
             // TODO: Support different epochs in transactional tests.
             0,
         );
@@ -0,0 +1,54 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use move_core_types::{ident_str, identifier::IdentStr, language_storage::StructTag};
+use serde::{Deserialize, Serialize};
+
+use crate::{id::UID, object::Object, SUI_CLOCK_OBJECT_ID, SUI_FRAMEWORK_ADDRESS};
+
+pub const CLOCK_MODULE_NAME: &IdentStr = ident_str!("clock");
+pub const CLOCK_STRUCT_NAME: &IdentStr = ident_str!("Clock");
+
+/// Rust version of the Move sui::clock::Clock type
+#[derive(Debug, Serialize, Deserialize, Clone, Eq, PartialEq)]
+pub struct Clock {
+    pub id: UID,
+    pub timestamp_ms: u64,
+}
+
+impl Clock {
+    pub fn type_() -> StructTag {
+        StructTag {
+            address: SUI_FRAMEWORK_ADDRESS,
+            module: CLOCK_MODULE_NAME.to_owned(),
+            name: CLOCK_STRUCT_NAME.to_owned(),
+            type_params: Vec::new(),
+        }
+    }
+
+    pub fn to_bcs_bytes(&self) -> Vec<u8> {
+        bcs::to_bytes(&self).unwrap()
+    }
+
+    /// Read the clock from the singleton Clock object.
+    pub fn from_object(object: &Object) -> Self {
+        debug_assert_eq!(object.id(), SUI_CLOCK_OBJECT_ID);
+        let move_object = object
+            .data
+            .try_as_move()
+            .expect("Clock object must be a Move object");
+        bcs::from_bytes(move_object.contents()).expect("Clock object deserialization cannot fail")
+    }
+
+    /// Set the timestamp of the singleton Clock object. Transactions can only read the Clock, so
+    /// its version is left unchanged.
+    pub fn set_timestamp(object: &mut Object, timestamp_ms: u64) {
+        let mut clock = Self::from_object(object);
+        clock.timestamp_ms = timestamp_ms;
+        object
+            .data
+            .try_as_move_mut()
+            .expect("Clock object must be a Move object")
+            .update_contents_without_version_change(clock.to_bcs_bytes());
+    }
+}
@@ -1151,6 +1151,7 @@ mod bcs_signable {
     impl BcsSignable for crate::messages::TransactionEffects {}
     impl BcsSignable for crate::messages::TransactionData {}
     impl BcsSignable for crate::messages::SenderSignedData {}
+    impl BcsSignable for crate::messages::ClockTimestamp {}
     impl BcsSignable for crate::messages::EpochInfo {}
     impl BcsSignable for crate::object::Object {}
 
@@ -0,0 +1,72 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use move_core_types::{
+    ident_str,
+    identifier::IdentStr,
+    language_storage::{StructTag, TypeTag},
+};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use crate::{base_types::ObjectID, SUI_FRAMEWORK_ADDRESS};
+
+pub const DISPLAY_MODULE_NAME: &IdentStr = ident_str!("display");
+pub const DISPLAY_STRUCT_NAME: &IdentStr = ident_str!("Display");
+pub const DISPLAY_VERSION_UPDATED_EVENT_NAME: &IdentStr = ident_str!("VersionUpdated");
+
+/// Rust version of the Move sui::vec_map::VecMap type
+#[derive(Debug, Serialize, Deserialize, Clone, Eq, PartialEq)]
+pub struct VecMap<K, V> {
+    pub contents: Vec<Entry<K, V>>,
+}
+
+/// Rust version of the Move sui::vec_map::Entry type
+#[derive(Debug, Serialize, Deserialize, Clone, Eq, PartialEq)]
+pub struct Entry<K, V> {
+    pub key: K,
+    pub value: V,
+}
+
+/// Rust version of the Move sui::display::VersionUpdated<T> event, emitted with the display
+/// fields of the objects of type `T`.
+#[derive(Debug, Serialize, Deserialize, Clone, Eq, PartialEq)]
+pub struct DisplayVersionUpdatedEvent {
+    pub id: ObjectID,
+    pub version: u64,
+    pub fields: VecMap<String, String>,
+}
+
+impl DisplayVersionUpdatedEvent {
+    pub fn type_(inner: StructTag) -> StructTag {
+        StructTag {
+            address: SUI_FRAMEWORK_ADDRESS,
+            module: DISPLAY_MODULE_NAME.to_owned(),
+            name: DISPLAY_VERSION_UPDATED_EVENT_NAME.to_owned(),
+            type_params: vec![TypeTag::Struct(inner)],
+        }
+    }
+
+    /// If `type_` is the type of a `VersionUpdated<T>` event, the type `T` it updates the display
+    /// of.
+    pub fn displayed_type(type_: &StructTag) -> Option<&StructTag> {
+        if type_.address != SUI_FRAMEWORK_ADDRESS
+            || type_.module.as_ident_str() != DISPLAY_MODULE_NAME
+            || type_.name.as_ident_str() != DISPLAY_VERSION_UPDATED_EVENT_NAME
+        {
+            return None;
+        }
+        match type_.type_params.as_slice() {
+            [TypeTag::Struct(inner)] => Some(inner),
+            _ => None,
+        }
+    }
+
+    pub fn fields(&self) -> BTreeMap<String, String> {
+        self.fields
+            .contents
+            .iter()
+            .map(|entry| (entry.key.clone(), entry.value.clone()))
+            .collect()
+    }
+}
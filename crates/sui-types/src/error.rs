@@ -2,11 +2,12 @@
 // Copyright (c) 2022, Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{base_types::*, committee::EpochId, messages::ExecutionFailureStatus};
+use crate::{base_types::*, committee::EpochId, messages::ExecutionFailureStatus, object::Owner};
 use move_binary_format::errors::{Location, PartialVMError, VMError};
 use move_core_types::vm_status::{StatusCode, StatusType};
 use narwhal_executor::SubscriberError;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fmt::Debug;
 use thiserror::Error;
 use typed_store::rocks::TypedStoreError;
@@ -75,6 +76,12 @@ pub enum SuiError {
         child_id: ObjectID,
         parent_id: ObjectID,
     },
+    #[error("Object {child_id:?} is not a child of object {parent_id:?}, it is owned by {actual_owner:?}")]
+    InvalidChildObjectAccess {
+        child_id: ObjectID,
+        parent_id: ObjectID,
+        actual_owner: Owner,
+    },
 
     // Signature verification
     #[error("Signature is not valid: {}", error)]
@@ -197,6 +204,10 @@ pub enum SuiError {
     SubscriptionServiceClosed,
     #[error("Checkpointing error: {}", error)]
     CheckpointingError { error: String },
+    #[error("Checkpoint archive error: {error}")]
+    CheckpointArchiveError { error: String },
+    #[error("State sync peer {peer} returned invalid data: {error}")]
+    StateSyncPeerError { peer: String, error: String },
     #[error(
         "ExecutionDriver error for {:?}: {} - Caused by : {:#?}",
         digest,
@@ -381,6 +392,11 @@ pub enum SuiError {
     SharedObjectLockingFailure(String),
     #[error("Consensus listener is out of capacity")]
     ListenerCapacityExceeded,
+    #[error("Too many transactions touching shared object {object_id} are being sequenced (at most {max_pending_transactions}), retry later")]
+    SharedObjectCongested {
+        object_id: ObjectID,
+        max_pending_transactions: usize,
+    },
     #[error("Failed to serialize/deserialize Narwhal message: {0}")]
     ConsensusSuiSerializationError(String),
     #[error("Only shared object transactions need to be sequenced")]
@@ -415,6 +431,17 @@ pub enum SuiError {
     #[error("Unable to communicate with the Quorum Driver channel: {:?}", error)]
     QuorumDriverCommunicationError { error: String },
 
+    #[error("Quorum Driver failed to execute transaction after {attempts} attempts: {error}")]
+    QuorumDriverError { error: Box<SuiError>, attempts: u32 },
+
+    #[error(
+        "Input objects are locked by conflicting transactions: {:?}",
+        conflicting_transactions
+    )]
+    ObjectsLockedByConflictingTransactions {
+        conflicting_transactions: BTreeMap<TransactionDigest, Vec<ObjectRef>>,
+    },
+
     #[error("Operation timed out")]
     TimeoutError,
 
@@ -462,6 +489,27 @@ impl std::convert::From<SubscriberError> for SuiError {
     }
 }
 
+impl SuiError {
+    /// Whether the error may go away by itself, e.g. a network failure or a timeout, so that
+    /// sending the same request again may succeed.
+    pub fn is_transient(&self) -> bool {
+        match self {
+            SuiError::TimeoutError | SuiError::SharedObjectCongested { .. } => true,
+            SuiError::RpcError(_, code) => [
+                tonic::Code::Unknown,
+                tonic::Code::Cancelled,
+                tonic::Code::DeadlineExceeded,
+                tonic::Code::ResourceExhausted,
+                tonic::Code::Unavailable,
+            ]
+            .iter()
+            .any(|transient| transient.description() == *code),
+            SuiError::QuorumNotReached { errors } => errors.iter().all(SuiError::is_transient),
+            _ => false,
+        }
+    }
+}
+
 impl From<tonic::Status> for SuiError {
     fn from(status: tonic::Status) -> Self {
         Self::RpcError(status.message().to_owned(), status.code().description())
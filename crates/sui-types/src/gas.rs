@@ -28,6 +28,8 @@ use sui_cost_tables::{
     units_types::GasUnit,
 };
 
+pub use sui_cost_tables::gas_profiler::GasProfile;
+
 pub type GasUnits = GasQuantity<GasUnit>;
 pub enum GasPriceUnit {}
 pub enum SuiGasUnit {}
@@ -214,24 +216,50 @@ impl<'a> SuiGasStatus<'a> {
         &mut self.gas_status
     }
 
+    /// Start recording where the gas goes. The gas charged before, i.e. the fixed costs of the
+    /// transaction, is recorded as a whole.
+    pub fn enable_profiling(&mut self) {
+        if self.gas_status.profile().is_some() {
+            return;
+        }
+        let charged = to_internal(self.init_budget)
+            .checked_sub(to_internal(self.gas_status.remaining_gas()))
+            .unwrap_or_else(|| InternalGas::new(0));
+        self.gas_status.enable_profiling();
+        self.record_gas("[transaction]", &charged);
+    }
+
+    /// The gas recorded so far, if profiling is enabled.
+    pub fn gas_profile(&self) -> Option<&GasProfile> {
+        self.gas_status.profile()
+    }
+
+    /// Stop profiling, returning the gas recorded.
+    pub fn take_gas_profile(&mut self) -> Option<GasProfile> {
+        self.gas_status.take_profile()
+    }
+
     pub fn charge_min_tx_gas(&mut self) -> Result<(), ExecutionError> {
-        self.deduct_computation_cost(INIT_SUI_COST_TABLE.min_transaction_cost.deref())
+        self.deduct_computation_cost(
+            "[transaction]",
+            INIT_SUI_COST_TABLE.min_transaction_cost.deref(),
+        )
     }
 
     pub fn charge_consensus(&mut self) -> Result<(), ExecutionError> {
-        self.deduct_computation_cost(&INIT_SUI_COST_TABLE.consensus_cost)
+        self.deduct_computation_cost("[consensus]", &INIT_SUI_COST_TABLE.consensus_cost)
     }
 
     pub fn charge_publish_package(&mut self, size: usize) -> Result<(), ExecutionError> {
         let computation_cost =
             NumBytes::new(size as u64).mul(*INIT_SUI_COST_TABLE.package_publish_per_byte_cost);
 
-        self.deduct_computation_cost(&computation_cost)
+        self.deduct_computation_cost("[publish]", &computation_cost)
     }
 
     pub fn charge_storage_read(&mut self, size: usize) -> Result<(), ExecutionError> {
         let cost = NumBytes::new(size as u64).mul(*INIT_SUI_COST_TABLE.object_read_per_byte_cost);
-        self.deduct_computation_cost(&cost)
+        self.deduct_computation_cost("[storage_read]", &cost)
     }
 
     pub fn charge_storage_mutation(
@@ -249,7 +277,7 @@ impl<'a> SuiGasStatus<'a> {
         // write a new one.
         let cost = NumBytes::new((old_size + new_size) as u64)
             .mul(*INIT_SUI_COST_TABLE.object_mutation_per_byte_cost);
-        self.deduct_computation_cost(&cost)?;
+        self.deduct_computation_cost("[storage_mutation]", &cost)?;
 
         self.storage_rebate += storage_rebate;
 
@@ -321,11 +349,24 @@ impl<'a> SuiGasStatus<'a> {
         }
     }
 
-    fn deduct_computation_cost(&mut self, cost: &InternalGas) -> Result<(), ExecutionError> {
-        self.gas_status.deduct_gas(*cost).map_err(|e| {
-            debug_assert_eq!(e.major_status(), StatusCode::OUT_OF_GAS);
-            ExecutionErrorKind::InsufficientGas.into()
-        })
+    /// Record `cost`, charged outside of the Move VM, under `label` in the gas profile.
+    fn record_gas(&mut self, label: &str, cost: &InternalGas) {
+        if let Some(profile) = self.gas_status.profile_mut() {
+            profile.record(label, u64::from(*cost));
+        }
+    }
+
+    fn deduct_computation_cost(
+        &mut self,
+        label: &str,
+        cost: &InternalGas,
+    ) -> Result<(), ExecutionError> {
+        self.gas_status
+            .deduct_gas_recorded(label, *cost)
+            .map_err(|e| {
+                debug_assert_eq!(e.major_status(), StatusCode::OUT_OF_GAS);
+                ExecutionErrorKind::InsufficientGas.into()
+            })
     }
 
     fn deduct_storage_cost(&mut self, cost: &InternalGas) -> Result<GasUnits, ExecutionError> {
@@ -335,7 +376,11 @@ impl<'a> SuiGasStatus<'a> {
         let ext_cost = to_external(NumBytes::new(1).mul(InternalGasPerByte::new(u64::from(*cost))));
         let charge_amount = to_internal(ext_cost);
         let remaining_gas = self.gas_status.remaining_gas();
-        if self.gas_status.deduct_gas(charge_amount).is_err() {
+        if self
+            .gas_status
+            .deduct_gas_recorded("[storage]", charge_amount)
+            .is_err()
+        {
             debug_assert_eq!(u64::from(self.gas_status.remaining_gas()), 0);
             // Even when we run out of gas, we still keep track of the storage_cost change,
             // so that at the end, we could still use it to accurately derive the
@@ -19,9 +19,11 @@ pub mod error;
 pub mod balance;
 pub mod base_types;
 pub mod batch;
+pub mod clock;
 pub mod coin;
 pub mod committee;
 pub mod crypto;
+pub mod display;
 pub mod event;
 pub mod gas;
 pub mod gas_coin;
@@ -53,6 +55,9 @@ pub const SUI_FRAMEWORK_OBJECT_ID: ObjectID = ObjectID::from_single_byte(2);
 /// 0x5: hardcoded object ID for the singleton sui system state object.
 pub const SUI_SYSTEM_STATE_OBJECT_ID: ObjectID = ObjectID::from_single_byte(5);
 
+/// 0x6: hardcoded object ID for the singleton clock object.
+pub const SUI_CLOCK_OBJECT_ID: ObjectID = ObjectID::from_single_byte(6);
+
 const fn get_hex_address_two() -> AccountAddress {
     let mut addr = [0u8; AccountAddress::LENGTH];
     addr[AccountAddress::LENGTH - 1] = 2u8;
@@ -2,6 +2,7 @@
 // Copyright (c) 2022, Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 use super::{base_types::*, batch::*, committee::Committee, error::*, event::Event};
+use crate::clock::Clock;
 use crate::committee::{EpochId, StakeUnit};
 use crate::crypto::{
     sha3_hash, AuthoritySignInfo, AuthoritySignInfoTrait, AuthoritySignature,
@@ -14,7 +15,7 @@ use crate::messages_checkpoint::{CheckpointFragment, CheckpointSequenceNumber};
 use crate::object::{Object, ObjectFormatOptions, Owner, OBJECT_START_VERSION};
 use crate::storage::DeleteKind;
 use crate::sui_serde::Base64;
-use crate::SUI_SYSTEM_STATE_OBJECT_ID;
+use crate::{SUI_CLOCK_OBJECT_ID, SUI_SYSTEM_STATE_OBJECT_ID};
 use base64ct::Encoding;
 use byteorder::{BigEndian, ReadBytesExt};
 use itertools::Either;
@@ -90,6 +91,20 @@ pub struct MoveModulePublish {
     pub modules: Vec<Vec<u8>>,
 }
 
+#[serde_as]
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Serialize, Deserialize)]
+pub struct MovePackageUpgrade {
+    /// The package to upgrade, which must be the latest version of the package the
+    /// `UpgradeCap` was issued for.
+    pub package: ObjectID,
+    pub upgrade_cap: ObjectRef,
+    #[serde_as(as = "Vec<Bytes>")]
+    pub modules: Vec<Vec<u8>>,
+    /// Upgraded versions of the dependencies of the package to link the new version against.
+    /// Other dependencies are linked against the versions the previous version links against.
+    pub dependencies: Vec<ObjectID>,
+}
+
 #[derive(Debug, PartialEq, Eq, Hash, Clone, Serialize, Deserialize)]
 pub struct TransferSui {
     pub recipient: SuiAddress,
@@ -125,6 +140,8 @@ pub enum SingleTransactionKind {
     /// A validator will not sign a transaction of this kind from outside. It only
     /// signs internally during epoch changes.
     ChangeEpoch(ChangeEpoch),
+    /// Publish a new version of a Move package, authorized by its `UpgradeCap`
+    Upgrade(MovePackageUpgrade),
     // .. more transaction types go here
 }
 
@@ -221,6 +238,29 @@ impl SingleTransactionKind {
                     .collect::<Vec<_>>();
                 Transaction::input_objects_in_compiled_modules(&compiled_modules)
             }
+            Self::Upgrade(MovePackageUpgrade {
+                package,
+                upgrade_cap,
+                modules,
+                dependencies,
+            }) => {
+                // Same as for publishing, and in addition the package being upgraded and the
+                // upgraded dependencies are needed to check and link the new version.
+                let compiled_modules = modules
+                    .iter()
+                    .filter_map(|bytes| CompiledModule::deserialize(bytes).ok())
+                    .collect::<Vec<_>>();
+                let mut packages: BTreeSet<_> =
+                    Transaction::input_objects_in_compiled_modules(&compiled_modules)
+                        .iter()
+                        .map(InputObjectKind::object_id)
+                        .collect();
+                packages.insert(*package);
+                packages.extend(dependencies);
+                std::iter::once(InputObjectKind::ImmOrOwnedMoveObject(*upgrade_cap))
+                    .chain(packages.into_iter().map(InputObjectKind::MovePackage))
+                    .collect()
+            }
             Self::TransferSui(_) => {
                 vec![]
             }
@@ -269,6 +309,11 @@ impl Display for SingleTransactionKind {
             Self::Publish(_p) => {
                 writeln!(writer, "Transaction Kind : Publish")?;
             }
+            Self::Upgrade(u) => {
+                writeln!(writer, "Transaction Kind : Upgrade")?;
+                writeln!(writer, "Package ID : {}", u.package.to_hex_literal())?;
+                writeln!(writer, "Upgrade Cap : {}", u.upgrade_cap.0)?;
+            }
             Self::Call(c) => {
                 writeln!(writer, "Transaction Kind : Call")?;
                 writeln!(writer, "Package ID : {}", c.package.0.to_hex_literal())?;
@@ -344,6 +389,7 @@ impl TransactionKind {
                     SingleTransactionKind::TransferSui(_) => false,
                     SingleTransactionKind::ChangeEpoch(_) => false,
                     SingleTransactionKind::Publish(_) => false,
+                    SingleTransactionKind::Upgrade(_) => false,
                 });
                 fp_ensure!(
                     valid,
@@ -479,6 +525,24 @@ impl TransactionData {
         Self::new(kind, sender, gas_payment, gas_budget)
     }
 
+    pub fn new_upgrade(
+        sender: SuiAddress,
+        gas_payment: ObjectRef,
+        package: ObjectID,
+        upgrade_cap: ObjectRef,
+        modules: Vec<Vec<u8>>,
+        dependencies: Vec<ObjectID>,
+        gas_budget: u64,
+    ) -> Self {
+        let kind = TransactionKind::Single(SingleTransactionKind::Upgrade(MovePackageUpgrade {
+            package,
+            upgrade_cap,
+            modules,
+            dependencies,
+        }));
+        Self::new(kind, sender, gas_payment, gas_budget)
+    }
+
     /// Returns the transaction kind as a &str (variant name, no fields)
     pub fn kind_as_str(&self) -> &'static str {
         self.kind.variant_name()
@@ -1060,6 +1124,11 @@ pub enum ExecutionFailureStatus {
     MoveAbort(ModuleId, u64), // TODO func def + offset?
     VMVerificationOrDeserializationError,
     VMInvariantViolation,
+
+    //
+    // MoveUpgrade errors
+    //
+    PackageUpgradeError(PackageUpgradeError),
 }
 
 #[derive(Eq, PartialEq, Clone, Copy, Debug, Serialize, Deserialize, Hash)]
@@ -1078,6 +1147,27 @@ pub enum EntryArgumentErrorKind {
     ArityMismatch,
 }
 
+#[derive(Eq, PartialEq, Clone, Copy, Debug, Serialize, Deserialize, Hash)]
+pub enum PackageUpgradeError {
+    NotAPackage {
+        object_id: ObjectID,
+    },
+    InvalidUpgradeCap {
+        object_id: ObjectID,
+    },
+    PackageIdDoesNotMatch {
+        package_id: ObjectID,
+        cap_package_id: ObjectID,
+    },
+    UnknownUpgradePolicy {
+        policy: u8,
+    },
+    IncompatibleUpgrade,
+    UpgradeCapNotOwnedBySender {
+        object_id: ObjectID,
+    },
+}
+
 #[derive(Eq, PartialEq, Clone, Copy, Debug, Serialize, Deserialize, Hash)]
 pub struct CircularObjectOwnership {
     pub object: ObjectID,
@@ -1222,6 +1312,9 @@ impl std::fmt::Display for ExecutionFailureStatus {
                 "Sui Move Bytecode Verification Error. \
                 Please run the Sui Move Verifier for more information."
             ),
+            ExecutionFailureStatus::PackageUpgradeError(error) => {
+                write!(f, "Package Upgrade Error. {error}")
+            }
             ExecutionFailureStatus::MovePrimitiveRuntimeError => write!(
                 f,
                 "Move Primitive Runtime Error. \
@@ -1280,6 +1373,37 @@ impl Display for EntryArgumentErrorKind {
     }
 }
 
+impl Display for PackageUpgradeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PackageUpgradeError::NotAPackage { object_id } => {
+                write!(f, "Object {object_id} is not a package.")
+            }
+            PackageUpgradeError::InvalidUpgradeCap { object_id } => {
+                write!(f, "Object {object_id} is not an UpgradeCap.")
+            }
+            PackageUpgradeError::PackageIdDoesNotMatch {
+                package_id,
+                cap_package_id,
+            } => write!(
+                f,
+                "Package {package_id} is not the package {cap_package_id} the UpgradeCap can \
+                upgrade."
+            ),
+            PackageUpgradeError::UnknownUpgradePolicy { policy } => {
+                write!(f, "Unknown upgrade policy {policy}.")
+            }
+            PackageUpgradeError::IncompatibleUpgrade => write!(
+                f,
+                "The new version of the package is not allowed by the upgrade policy."
+            ),
+            PackageUpgradeError::UpgradeCapNotOwnedBySender { object_id } => {
+                write!(f, "UpgradeCap {object_id} is not owned by the sender.")
+            }
+        }
+    }
+}
+
 impl Display for CircularObjectOwnership {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         let CircularObjectOwnership { object } = self;
@@ -1363,6 +1487,12 @@ impl From<EntryArgumentError> for ExecutionFailureStatus {
     }
 }
 
+impl From<PackageUpgradeError> for ExecutionFailureStatus {
+    fn from(error: PackageUpgradeError) -> Self {
+        Self::PackageUpgradeError(error)
+    }
+}
+
 impl From<CircularObjectOwnership> for ExecutionFailureStatus {
     fn from(error: CircularObjectOwnership) -> Self {
         Self::CircularObjectOwnership(error)
@@ -1416,6 +1546,8 @@ pub struct TransactionEffects {
     pub events: Vec<Event>,
     /// The set of transaction digests this transaction depends on.
     pub dependencies: Vec<TransactionDigest>,
+    /// The timestamp of the Clock observed by this transaction, if it read the Clock.
+    pub clock_timestamp_ms: Option<u64>,
 }
 
 impl TransactionEffects {
@@ -1580,6 +1712,11 @@ impl CertifiedTransactionEffects {
         })
     }
 
+    /// Verify the signature of this effects certificate against the committee of its epoch.
+    pub fn verify(&self, committee: &Committee) -> SuiResult {
+        self.auth_signature.verify(&self.effects, committee)
+    }
+
     pub fn to_unsigned_effects(self) -> UnsignedTransactionEffects {
         UnsignedTransactionEffects {
             transaction_effects_digest: self.transaction_effects_digest,
@@ -1694,11 +1831,24 @@ impl InputObjects {
                         Some(*object_ref)
                     }
                 }
+                // The Clock is read-only, it is never mutated by transactions.
+                InputObjectKind::SharedMoveObject(id) if *id == SUI_CLOCK_OBJECT_ID => None,
                 InputObjectKind::SharedMoveObject(_) => Some(object.compute_object_reference()),
             })
             .collect()
     }
 
+    /// Set the timestamp of the Clock, if it is one of the inputs. Its version is unchanged.
+    pub fn set_clock_timestamp(&mut self, timestamp_ms: u64) {
+        if let Some((_, clock)) = self
+            .objects
+            .iter_mut()
+            .find(|(kind, _)| kind.object_id() == SUI_CLOCK_OBJECT_ID)
+        {
+            Clock::set_timestamp(clock, timestamp_ms);
+        }
+    }
+
     pub fn into_object_map(self) -> BTreeMap<ObjectID, Object> {
         self.objects
             .into_iter()
@@ -1859,6 +2009,63 @@ pub struct ConsensusTransaction {
 pub enum ConsensusTransactionKind {
     UserTransaction(Box<CertifiedTransaction>),
     Checkpoint(Box<CheckpointFragment>),
+    ClockTimestamp(Box<SignedClockTimestamp>),
+}
+
+/// The wall-clock time of an authority, submitted to consensus. The timestamp of the Clock
+/// is derived from the latest timestamps sequenced for each authority of the committee.
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct ClockTimestamp {
+    pub epoch: EpochId,
+    pub timestamp_ms: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct SignedClockTimestamp {
+    pub timestamp: ClockTimestamp,
+    pub auth_signature: AuthoritySignInfo,
+}
+
+impl SignedClockTimestamp {
+    pub fn new(
+        epoch: EpochId,
+        timestamp_ms: u64,
+        authority: AuthorityName,
+        secret: &dyn signature::Signer<AuthoritySignature>,
+    ) -> Self {
+        let timestamp = ClockTimestamp {
+            epoch,
+            timestamp_ms,
+        };
+        let signature = AuthoritySignature::new(&timestamp, secret);
+        Self {
+            timestamp,
+            auth_signature: AuthoritySignInfo {
+                epoch,
+                authority,
+                signature,
+            },
+        }
+    }
+
+    pub fn authority(&self) -> &AuthorityName {
+        &self.auth_signature.authority
+    }
+
+    pub fn timestamp_ms(&self) -> u64 {
+        self.timestamp.timestamp_ms
+    }
+
+    pub fn verify(&self, committee: &Committee) -> SuiResult {
+        fp_ensure!(
+            self.timestamp.epoch == committee.epoch()
+                && self.auth_signature.epoch == committee.epoch(),
+            SuiError::WrongEpoch {
+                expected_epoch: committee.epoch()
+            }
+        );
+        self.auth_signature.verify(&self.timestamp, committee)
+    }
 }
 
 impl ConsensusTransaction {
@@ -1892,6 +2099,17 @@ impl ConsensusTransaction {
         }
     }
 
+    pub fn new_clock_timestamp_message(timestamp: SignedClockTimestamp) -> Self {
+        let mut hasher = DefaultHasher::new();
+        timestamp.authority().hash(&mut hasher);
+        timestamp.timestamp.hash(&mut hasher);
+        let tracking_id = hasher.finish().to_be_bytes();
+        Self {
+            tracking_id,
+            kind: ConsensusTransactionKind::ClockTimestamp(Box::new(timestamp)),
+        }
+    }
+
     pub fn get_tracking_id(&self) -> u64 {
         (&self.tracking_id[..])
             .read_u64::<BigEndian>()
@@ -1902,6 +2120,7 @@ impl ConsensusTransaction {
         match &self.kind {
             ConsensusTransactionKind::UserTransaction(certificate) => certificate.verify(committee),
             ConsensusTransactionKind::Checkpoint(fragment) => fragment.verify(committee),
+            ConsensusTransactionKind::ClockTimestamp(timestamp) => timestamp.verify(committee),
         }
     }
 }
@@ -1911,6 +2130,9 @@ pub enum ExecuteTransactionRequestType {
     ImmediateReturn,
     WaitForTxCert,
     WaitForEffectsCert,
+    /// Like `WaitForEffectsCert`, and also wait for the full node serving the request to execute
+    /// the transaction, so that its reads reflect the effects.
+    WaitForLocalExecution,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
@@ -2137,3 +2359,28 @@ pub struct EpochRequest {
 pub struct EpochResponse {
     pub epoch_info: Option<AuthenticatedEpoch>,
 }
+
+/// Request the certificate and effects of a checkpointed transaction from a state sync peer.
+/// The effects digest comes from a certified checkpoint, and is used to verify the response.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StateSyncTransactionRequest {
+    pub digests: ExecutionDigests,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StateSyncTransactionResponse {
+    pub certified_transaction: Option<CertifiedTransaction>,
+    pub signed_effects: Option<SignedTransactionEffects>,
+}
+
+/// Request a specific version of an object from a state sync peer. The object reference is
+/// expected to come from verified effects, so the returned object can be checked against it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StateSyncObjectRequest {
+    pub object_ref: ObjectRef,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StateSyncObjectResponse {
+    pub object: Option<Object>,
+}
@@ -4,20 +4,28 @@
 use crate::{
     base_types::ObjectID,
     error::{SuiError, SuiResult},
+    id::{ID, UID},
+    SUI_FRAMEWORK_ADDRESS,
 };
 use move_binary_format::access::ModuleAccess;
 use move_binary_format::binary_views::BinaryIndexedView;
 use move_binary_format::file_format::CompiledModule;
 use move_binary_format::normalized;
-use move_core_types::identifier::Identifier;
+use move_core_types::ident_str;
+use move_core_types::identifier::{IdentStr, Identifier};
+use move_core_types::language_storage::StructTag;
 use move_disassembler::disassembler::Disassembler;
 use move_ir_types::location::Spanned;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use serde_with::serde_as;
 use serde_with::Bytes;
+use sha3::{Digest, Sha3_256};
 use std::collections::BTreeMap;
 
+pub const PACKAGE_MODULE_NAME: &IdentStr = ident_str!("package");
+pub const UPGRADE_CAP_STRUCT_NAME: &IdentStr = ident_str!("UpgradeCap");
+
 // TODO: robust MovePackage tests
 // #[cfg(test)]
 // #[path = "unit_tests/move_package.rs"]
@@ -28,16 +36,60 @@ use std::collections::BTreeMap;
 #[derive(Eq, PartialEq, Debug, Clone, Deserialize, Serialize, Hash)]
 pub struct MovePackage {
     id: ObjectID,
+    /// The ID of the first version of this package. The modules of every version of a package
+    /// are addressed by it, so that types defined by the package are the same across upgrades.
+    original_id: ObjectID,
+    /// The version of this package, starting at 1 and incremented on each upgrade.
+    version: u64,
     // TODO use session cache
     #[serde_as(as = "BTreeMap<_, Bytes>")]
     module_map: BTreeMap<String, Vec<u8>>,
+    /// For each (transitive) dependency that has been upgraded, maps its original ID to the
+    /// version this package links against. Dependencies missing from the table are linked
+    /// against their original version.
+    linkage_table: BTreeMap<ObjectID, UpgradeInfo>,
+}
+
+#[derive(Eq, PartialEq, Debug, Clone, Copy, Deserialize, Serialize, Hash)]
+pub struct UpgradeInfo {
+    /// ID of the upgraded version of the package.
+    pub upgraded_id: ObjectID,
+    /// Version of the upgraded package.
+    pub upgraded_version: u64,
 }
 
 impl MovePackage {
     pub fn new(id: ObjectID, module_map: &BTreeMap<String, Vec<u8>>) -> Self {
         Self {
             id,
+            original_id: id,
+            version: 1,
             module_map: module_map.clone(),
+            linkage_table: BTreeMap::new(),
+        }
+    }
+
+    /// Create a new version of the package `previous`, stored at `id`.
+    pub fn new_upgraded(
+        id: ObjectID,
+        previous: &MovePackage,
+        modules: Vec<CompiledModule>,
+        linkage_table: BTreeMap<ObjectID, UpgradeInfo>,
+    ) -> Self {
+        Self {
+            id,
+            original_id: previous.original_id,
+            version: previous.version + 1,
+            module_map: serialize_modules(modules),
+            linkage_table,
+        }
+    }
+
+    /// Link the package against the upgraded versions of its dependencies in `linkage_table`.
+    pub fn with_linkage_table(self, linkage_table: BTreeMap<ObjectID, UpgradeInfo>) -> Self {
+        Self {
+            linkage_table,
+            ..self
         }
     }
 
@@ -45,6 +97,33 @@ impl MovePackage {
         self.id
     }
 
+    pub fn original_id(&self) -> ObjectID {
+        self.original_id
+    }
+
+    pub fn version(&self) -> u64 {
+        self.version
+    }
+
+    pub fn linkage_table(&self) -> &BTreeMap<ObjectID, UpgradeInfo> {
+        &self.linkage_table
+    }
+
+    /// Returns the package versions to resolve modules against when running code from this
+    /// package, keyed by the original ID of each package. Returns an empty map if this package
+    /// and all of its dependencies are original versions.
+    pub fn linkage(&self) -> BTreeMap<ObjectID, ObjectID> {
+        let mut linkage: BTreeMap<_, _> = self
+            .linkage_table
+            .iter()
+            .map(|(original_id, info)| (*original_id, info.upgraded_id))
+            .collect();
+        if self.original_id != self.id {
+            linkage.insert(self.original_id, self.id);
+        }
+        linkage
+    }
+
     pub fn serialized_module_map(&self) -> &BTreeMap<String, Vec<u8>> {
         &self.module_map
     }
@@ -70,6 +149,15 @@ impl MovePackage {
     }
 }
 
+/// The digest of the bytecode of the modules in `module_map`, keyed by module name as in
+/// `MovePackage::serialized_module_map`. Used to check that sources compile to the bytecode of a
+/// published package.
+pub fn module_map_digest(module_map: &BTreeMap<String, Vec<u8>>) -> [u8; 32] {
+    let mut hasher = Sha3_256::default();
+    hasher.update(bcs::to_bytes(module_map).unwrap());
+    hasher.finalize().into()
+}
+
 pub fn disassemble_modules<'a, I>(modules: I) -> SuiResult<BTreeMap<String, Value>>
 where
     I: Iterator<Item = &'a Vec<u8>>,
@@ -125,15 +213,83 @@ impl FromIterator<CompiledModule> for MovePackage {
                 .address(),
         );
 
-        Self::new(
-            id,
-            &iter
-                .map(|module| {
-                    let mut bytes = Vec::new();
-                    module.serialize(&mut bytes).unwrap();
-                    (module.self_id().name().to_string(), bytes)
-                })
-                .collect(),
-        )
+        Self::new(id, &serialize_modules(iter))
+    }
+}
+
+fn serialize_modules<I: IntoIterator<Item = CompiledModule>>(
+    modules: I,
+) -> BTreeMap<String, Vec<u8>> {
+    modules
+        .into_iter()
+        .map(|module| {
+            let mut bytes = Vec::new();
+            module.serialize(&mut bytes).unwrap();
+            (module.self_id().name().to_string(), bytes)
+        })
+        .collect()
+}
+
+/// The policies an `UpgradeCap` can restrict upgrades of its package to, from the most to the
+/// least permissive. A package whose `UpgradeCap` has been destroyed is immutable.
+#[derive(Eq, PartialEq, PartialOrd, Ord, Debug, Clone, Copy)]
+pub enum UpgradePolicy {
+    /// Struct layouts and public function signatures must be preserved.
+    Compatible,
+    /// New modules, structs and functions may be added, but existing ones must not change.
+    Additive,
+    /// Modules must not change, only the versions of the dependencies they link against.
+    DepOnly,
+}
+
+impl UpgradePolicy {
+    pub const COMPATIBLE: u8 = 0;
+    pub const ADDITIVE: u8 = 128;
+    pub const DEP_ONLY: u8 = 192;
+}
+
+impl TryFrom<u8> for UpgradePolicy {
+    type Error = u8;
+
+    fn try_from(policy: u8) -> Result<Self, Self::Error> {
+        match policy {
+            Self::COMPATIBLE => Ok(Self::Compatible),
+            Self::ADDITIVE => Ok(Self::Additive),
+            Self::DEP_ONLY => Ok(Self::DepOnly),
+            _ => Err(policy),
+        }
+    }
+}
+
+// Rust version of the Move sui::package::UpgradeCap type
+#[derive(Debug, Serialize, Deserialize, Clone, Eq, PartialEq)]
+pub struct UpgradeCap {
+    pub id: UID,
+    pub package: ID,
+    pub version: u64,
+    pub policy: u8,
+}
+
+impl UpgradeCap {
+    pub fn new(id: ObjectID, package_id: ObjectID) -> Self {
+        Self {
+            id: UID::new(id),
+            package: ID { bytes: package_id },
+            version: 1,
+            policy: UpgradePolicy::COMPATIBLE,
+        }
+    }
+
+    pub fn type_() -> StructTag {
+        StructTag {
+            address: SUI_FRAMEWORK_ADDRESS,
+            module: PACKAGE_MODULE_NAME.to_owned(),
+            name: UPGRADE_CAP_STRUCT_NAME.to_owned(),
+            type_params: Vec::new(),
+        }
+    }
+
+    pub fn to_bcs_bytes(&self) -> Vec<u8> {
+        bcs::to_bytes(&self).unwrap()
     }
 }
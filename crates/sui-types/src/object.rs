@@ -19,7 +19,7 @@ use serde_with::Bytes;
 use crate::crypto::sha3_hash;
 use crate::error::{ExecutionError, ExecutionErrorKind};
 use crate::error::{SuiError, SuiResult};
-use crate::move_package::MovePackage;
+use crate::move_package::{MovePackage, UpgradeCap};
 use crate::{
     base_types::{
         ObjectDigest, ObjectID, ObjectRef, SequenceNumber, SuiAddress, TransactionDigest,
@@ -96,6 +96,10 @@ impl MoveObject {
         unsafe { Self::new_from_execution(GasCoin::type_(), true, version, None, contents) }
     }
 
+    pub fn new_upgrade_cap(version: SequenceNumber, contents: Vec<u8>) -> Self {
+        unsafe { Self::new_from_execution(UpgradeCap::type_(), true, version, None, contents) }
+    }
+
     pub fn has_public_transfer(&self) -> bool {
         self.has_public_transfer
     }
@@ -421,6 +425,18 @@ impl Object {
         }
     }
 
+    pub fn new_package_from_data(
+        package: MovePackage,
+        previous_transaction: TransactionDigest,
+    ) -> Self {
+        Object {
+            data: Data::Package(package),
+            owner: Owner::Immutable,
+            previous_transaction,
+            storage_rebate: 0,
+        }
+    }
+
     pub fn is_immutable(&self) -> bool {
         self.owner.is_immutable()
     }
@@ -3,9 +3,9 @@
 
 use crate::{
     base_types::{ObjectID, ObjectRef, SequenceNumber},
-    error::SuiResult,
+    error::{SuiError, SuiResult},
     event::Event,
-    object::Object,
+    object::{Object, Owner},
 };
 use serde::{Deserialize, Serialize};
 use std::collections::{BTreeMap, BTreeSet};
@@ -40,6 +40,14 @@ pub trait Storage {
     fn log_event(&mut self, event: Event);
 
     fn apply_object_changes(&mut self, changes: BTreeMap<ObjectID, ObjectChange>);
+
+    /// Record the child objects read from storage during execution, which are not among the
+    /// inputs of the transaction.
+    fn save_loaded_child_objects(&mut self, loaded_child_objects: BTreeMap<ObjectID, Object>);
+
+    /// Set the versions of packages to load modules from, keyed by the original ID of each
+    /// package. Packages missing from `linkage` are loaded at their original version.
+    fn set_linkage(&mut self, linkage: BTreeMap<ObjectID, ObjectID>);
 }
 
 pub trait BackingPackageStore {
@@ -85,3 +93,40 @@ impl<S: ParentSync> ParentSync for &mut S {
         ParentSync::get_latest_parent_entry_ref(*self, object_id)
     }
 }
+
+/// Read access to the children of an object, loaded on demand during execution (e.g. by dynamic
+/// fields) rather than passed as transaction inputs.
+pub trait ChildObjectResolver {
+    /// Read the latest version of `child`. Fails if `child` exists but is not owned by `parent`.
+    fn read_child_object(&self, parent: &ObjectID, child: &ObjectID) -> SuiResult<Option<Object>>;
+}
+
+impl<S: ChildObjectResolver> ChildObjectResolver for std::sync::Arc<S> {
+    fn read_child_object(&self, parent: &ObjectID, child: &ObjectID) -> SuiResult<Option<Object>> {
+        ChildObjectResolver::read_child_object(self.as_ref(), parent, child)
+    }
+}
+
+impl<S: ChildObjectResolver> ChildObjectResolver for &S {
+    fn read_child_object(&self, parent: &ObjectID, child: &ObjectID) -> SuiResult<Option<Object>> {
+        ChildObjectResolver::read_child_object(*self, parent, child)
+    }
+}
+
+impl<S: ChildObjectResolver> ChildObjectResolver for &mut S {
+    fn read_child_object(&self, parent: &ObjectID, child: &ObjectID) -> SuiResult<Option<Object>> {
+        ChildObjectResolver::read_child_object(*self, parent, child)
+    }
+}
+
+/// Check that `child` is owned by `parent`, for implementations of `ChildObjectResolver`.
+pub fn check_child_object_owner(parent: &ObjectID, child: Object) -> SuiResult<Object> {
+    match child.owner {
+        Owner::ObjectOwner(owner) if ObjectID::from(owner) == *parent => Ok(child),
+        actual_owner => Err(SuiError::InvalidChildObjectAccess {
+            child_id: child.id(),
+            parent_id: *parent,
+            actual_owner,
+        }),
+    }
+}
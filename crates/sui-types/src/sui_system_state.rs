@@ -15,6 +15,24 @@ use crate::{
 const SUI_SYSTEM_STATE_STRUCT_NAME: &IdentStr = ident_str!("SuiSystemState");
 pub const SUI_SYSTEM_MODULE_NAME: &IdentStr = ident_str!("sui_system");
 pub const ADVANCE_EPOCH_FUNCTION_NAME: &IdentStr = ident_str!("advance_epoch");
+pub const REQUEST_ADD_STAKE_FUNCTION_NAME: &IdentStr = ident_str!("request_add_stake");
+pub const REQUEST_ADD_DELEGATION_FUNCTION_NAME: &IdentStr = ident_str!("request_add_delegation");
+pub const REQUEST_SWITCH_DELEGATION_FUNCTION_NAME: &IdentStr =
+    ident_str!("request_switch_delegation");
+pub const REQUEST_REMOVE_DELEGATION_FUNCTION_NAME: &IdentStr =
+    ident_str!("request_remove_delegation");
+pub const CLAIM_DELEGATION_REWARD_FUNCTION_NAME: &IdentStr = ident_str!("claim_delegation_reward");
+pub const REQUEST_ADD_VALIDATOR_FUNCTION_NAME: &IdentStr = ident_str!("request_add_validator");
+pub const REQUEST_REMOVE_VALIDATOR_FUNCTION_NAME: &IdentStr =
+    ident_str!("request_remove_validator");
+pub const REQUEST_SET_GAS_PRICE_FUNCTION_NAME: &IdentStr = ident_str!("request_set_gas_price");
+
+const DELEGATION_MODULE_NAME: &IdentStr = ident_str!("delegation");
+const DELEGATION_STRUCT_NAME: &IdentStr = ident_str!("Delegation");
+const STAKE_MODULE_NAME: &IdentStr = ident_str!("stake");
+const STAKE_STRUCT_NAME: &IdentStr = ident_str!("Stake");
+const EPOCH_REWARD_RECORD_MODULE_NAME: &IdentStr = ident_str!("epoch_reward_record");
+const EPOCH_REWARD_RECORD_STRUCT_NAME: &IdentStr = ident_str!("EpochRewardRecord");
 
 /// Rust version of the Move sui::sui_system::SystemParameters type
 #[derive(Debug, Serialize, Deserialize, Clone, Eq, PartialEq)]
@@ -31,6 +49,12 @@ pub struct MoveOption<T> {
     pub vec: Vec<T>,
 }
 
+impl<T> MoveOption<T> {
+    pub fn as_option(&self) -> Option<&T> {
+        self.vec.first()
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, Eq, PartialEq)]
 pub struct ValidatorMetadata {
     pub sui_address: AccountAddress,
@@ -96,3 +120,98 @@ impl SuiSystemState {
         }
     }
 }
+
+/// Rust version of the Move sui::epoch_time_lock::EpochTimeLock type
+#[derive(Debug, Serialize, Deserialize, Clone, Eq, PartialEq)]
+pub struct EpochTimeLock {
+    pub epoch: u64,
+}
+
+/// Rust version of the Move sui::stake::Stake type
+#[derive(Debug, Serialize, Deserialize, Clone, Eq, PartialEq)]
+pub struct Stake {
+    pub id: UID,
+    pub balance: Balance,
+    pub locked_until_epoch: MoveOption<EpochTimeLock>,
+}
+
+impl Stake {
+    pub fn type_() -> StructTag {
+        StructTag {
+            address: SUI_FRAMEWORK_ADDRESS,
+            name: STAKE_STRUCT_NAME.to_owned(),
+            module: STAKE_MODULE_NAME.to_owned(),
+            type_params: vec![],
+        }
+    }
+}
+
+/// Rust version of the Move sui::delegation::Delegation type
+#[derive(Debug, Serialize, Deserialize, Clone, Eq, PartialEq)]
+pub struct Delegation {
+    pub id: UID,
+    pub active_delegation: MoveOption<Balance>,
+    pub ending_epoch: MoveOption<u64>,
+    pub delegate_amount: u64,
+    pub next_reward_unclaimed_epoch: u64,
+    pub coin_locked_until_epoch: MoveOption<EpochTimeLock>,
+    pub validator_address: AccountAddress,
+}
+
+impl Delegation {
+    pub fn type_() -> StructTag {
+        StructTag {
+            address: SUI_FRAMEWORK_ADDRESS,
+            name: DELEGATION_STRUCT_NAME.to_owned(),
+            module: DELEGATION_MODULE_NAME.to_owned(),
+            type_params: vec![],
+        }
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.active_delegation.as_option().is_some()
+    }
+
+    /// Mirrors `delegation::can_claim_reward`.
+    pub fn can_claim_reward(&self, epoch_to_claim: u64, validator: AccountAddress) -> bool {
+        if validator != self.validator_address || self.next_reward_unclaimed_epoch > epoch_to_claim
+        {
+            return false;
+        }
+        match self.ending_epoch.as_option() {
+            Some(ending_epoch) => *ending_epoch > epoch_to_claim,
+            None => true,
+        }
+    }
+}
+
+/// Rust version of the Move sui::epoch_reward_record::EpochRewardRecord type
+#[derive(Debug, Serialize, Deserialize, Clone, Eq, PartialEq)]
+pub struct EpochRewardRecord {
+    pub id: UID,
+    pub epoch: u64,
+    pub computation_charge: u64,
+    pub total_stake: u64,
+    pub delegator_count: u64,
+    pub validator: AccountAddress,
+}
+
+impl EpochRewardRecord {
+    pub fn type_() -> StructTag {
+        StructTag {
+            address: SUI_FRAMEWORK_ADDRESS,
+            name: EPOCH_REWARD_RECORD_STRUCT_NAME.to_owned(),
+            module: EPOCH_REWARD_RECORD_MODULE_NAME.to_owned(),
+            type_params: vec![],
+        }
+    }
+
+    /// Mirrors `epoch_reward_record::claim_reward`, without updating the record.
+    pub fn reward_for(&self, delegate_amount: u64) -> u64 {
+        if self.total_stake == 0 {
+            return 0;
+        }
+        (delegate_amount as u128 * self.computation_charge as u128 / self.total_stake as u128)
+            as u64
+    }
+}
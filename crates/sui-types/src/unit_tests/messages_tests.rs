@@ -425,6 +425,7 @@ fn test_digest_caching() {
         gas_object: (random_object_ref(), Owner::AddressOwner(a1)),
         events: Vec::new(),
         dependencies: Vec::new(),
+        clock_timestamp_ms: None,
     };
 
     let mut signed_effects = effects.to_sign_effects(
@@ -577,6 +578,7 @@ fn test_user_signature_committed_in_checkpoints() {
         gas_object: (random_object_ref(), Owner::AddressOwner(a1)),
         events: Vec::new(),
         dependencies: Vec::new(),
+        clock_timestamp_ms: None,
     };
 
     let mut effects_b = effects_a.clone();
@@ -0,0 +1,57 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+// tests transfer policies can only be created by the module defining their type, for types
+// without store
+
+//# init --addresses a=0x0 t1=0x0 t2=0x0 t3=0x0 t4=0x0
+
+//# publish
+module a::m {
+    struct S has key { id: sui::object::UID }
+}
+
+//# publish
+module t1::m {
+    use sui::package::Publisher;
+    use sui::transfer_policy::{Self, TransferPolicy, TransferPolicyCap};
+    use sui::tx_context::TxContext;
+    struct S has key { id: sui::object::UID }
+    fun t(p: &Publisher, ctx: &mut TxContext): (TransferPolicy<S>, TransferPolicyCap<S>) {
+        transfer_policy::new(p, ctx)
+    }
+}
+
+//# publish
+module t2::m {
+    use sui::package::Publisher;
+    use sui::transfer_policy::{Self, TransferPolicy, TransferPolicyCap};
+    use sui::tx_context::TxContext;
+    struct S has key, store { id: sui::object::UID }
+    fun t(p: &Publisher, ctx: &mut TxContext): (TransferPolicy<S>, TransferPolicyCap<S>) {
+        transfer_policy::new(p, ctx)
+    }
+}
+
+//# publish
+module t3::m {
+    use sui::package::Publisher;
+    use sui::transfer_policy::{Self, TransferPolicy, TransferPolicyCap};
+    use sui::tx_context::TxContext;
+    fun t(
+        p: &Publisher,
+        ctx: &mut TxContext,
+    ): (TransferPolicy<a::m::S>, TransferPolicyCap<a::m::S>) {
+        transfer_policy::new(p, ctx)
+    }
+}
+
+//# publish
+module t4::m {
+    use sui::package::Publisher;
+    use sui::transfer_policy::{Self, TransferPolicy, TransferPolicyCap};
+    use sui::tx_context::TxContext;
+    fun t<T: key>(p: &Publisher, ctx: &mut TxContext): (TransferPolicy<T>, TransferPolicyCap<T>) {
+        transfer_policy::new(p, ctx)
+    }
+}
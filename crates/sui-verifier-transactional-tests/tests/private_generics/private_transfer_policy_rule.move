@@ -0,0 +1,36 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+// tests modules cannot use transfer policy rules not defined in the current module
+
+//# init --addresses a=0x0 test=0x0
+
+//# publish
+module a::m {
+    struct S has key { id: sui::object::UID }
+    struct R has drop {}
+}
+
+//# publish
+module test::m {
+    use sui::transfer_policy::{Self, TransferRequest};
+    fun t(r: a::m::R, request: &mut TransferRequest<a::m::S>) {
+        transfer_policy::add_receipt(r, request)
+    }
+}
+
+//# publish
+module test::m {
+    use sui::transfer_policy::{Self, TransferRequest};
+    fun t<R: drop>(r: R, request: &mut TransferRequest<a::m::S>) {
+        transfer_policy::add_receipt(r, request)
+    }
+}
+
+//# publish
+module test::m {
+    use sui::transfer_policy::{Self, TransferPolicy, TransferPolicyCap};
+    fun t(r: a::m::R, policy: &mut TransferPolicy<a::m::S>, cap: &TransferPolicyCap<a::m::S>) {
+        transfer_policy::add_rule(r, policy, cap, true)
+    }
+}
@@ -25,6 +25,16 @@ use crate::{format_signature_token, verification_failure};
 /// is no relaxation for `store`
 /// Concretely, with `event::emit<T>(...)`:
 /// - `T` must be a type declared in the current module
+///
+/// The rule functions of `sui::transfer_policy` are "private" to the module declaring the rule.
+/// For example, with `transfer_policy::add_receipt<T, Rule>(...)`:
+/// - `Rule` must be a type declared in the current module
+/// `sui::transfer_policy` itself can transfer objects of any type, as it only does so once the
+/// rules of the type's policy are satisfied. Its policies are only binding for types without
+/// `store`, whose objects cannot be transferred by other modules otherwise, so with
+/// `transfer_policy::new<T>(...)`:
+/// - `T` must be a type declared in the current module and
+/// - `T` must not have `store`
 pub fn verify_module(module: &CompiledModule) -> Result<(), ExecutionError> {
     let view = &BinaryIndexedView::Module(module);
     // do not need to check the sui::transfer module itself
@@ -68,6 +78,12 @@ fn verify_function(view: &BinaryIndexedView, fdef: &FunctionDefinition) -> Resul
                 (SUI_FRAMEWORK_ADDRESS, "event") => {
                     verify_private_event_emit(view, fhandle, type_arguments)?
                 }
+                (SUI_FRAMEWORK_ADDRESS, "transfer_policy") => verify_private_transfer_policy(
+                    view,
+                    function_type_parameters,
+                    fhandle,
+                    type_arguments,
+                )?,
                 _ => (),
             }
         }
@@ -82,7 +98,10 @@ fn verify_private_transfer(
     type_arguments: &[SignatureToken],
 ) -> Result<(), String> {
     let self_handle = view.module_handle_at(view.self_handle_idx().unwrap());
-    if addr_module(view, self_handle) == (SUI_FRAMEWORK_ADDRESS, "transfer") {
+    if matches!(
+        addr_module(view, self_handle),
+        (SUI_FRAMEWORK_ADDRESS, "transfer") | (SUI_FRAMEWORK_ADDRESS, "transfer_policy")
+    ) {
         return Ok(());
     }
     let fident = view.identifier_at(fhandle.name);
@@ -157,6 +176,55 @@ fn verify_private_event_emit(
     Ok(())
 }
 
+fn verify_private_transfer_policy(
+    view: &BinaryIndexedView,
+    function_type_parameters: &[AbilitySet],
+    fhandle: &FunctionHandle,
+    type_arguments: &[SignatureToken],
+) -> Result<(), String> {
+    let self_handle = view.module_handle_at(view.self_handle_idx().unwrap());
+    if addr_module(view, self_handle) == (SUI_FRAMEWORK_ADDRESS, "transfer_policy") {
+        return Ok(());
+    }
+    let fident = view.identifier_at(fhandle.name);
+    match fident.as_str() {
+        // functions creating a policy, the policy's type is the first type argument
+        "new" | "create_and_share" => {
+            let type_arg = &type_arguments[0];
+            let has_store = view
+                .abilities(type_arg, function_type_parameters)
+                .map_err(|vm_err| vm_err.to_string())?
+                .has_store();
+            if has_store || !is_defined_in_current_module(view, type_arg) {
+                return Err(format!(
+                    "Invalid call to '{}::transfer_policy::{}' on an object type '{}'. \
+                    The policy's type must be defined in the current module, \
+                    and must not have the 'store' type ability",
+                    SUI_FRAMEWORK_ADDRESS,
+                    fident,
+                    format_signature_token(view, type_arg),
+                ));
+            }
+            return Ok(());
+        }
+        // functions taking a rule witness, the rule is the second type argument
+        "add_rule" | "get_rule" | "add_to_balance" | "take_fee" | "add_receipt" => (),
+        // the other functions are not restricted
+        _ => return Ok(()),
+    };
+    let rule = &type_arguments[1];
+    if !is_defined_in_current_module(view, rule) {
+        return Err(format!(
+            "Invalid call to '{}::transfer_policy::{}' with a rule type '{}'. \
+            The rule's type must be defined in the current module",
+            SUI_FRAMEWORK_ADDRESS,
+            fident,
+            format_signature_token(view, rule),
+        ));
+    }
+    Ok(())
+}
+
 fn is_defined_in_current_module(view: &BinaryIndexedView, type_arg: &SignatureToken) -> bool {
     match type_arg {
         SignatureToken::Struct(idx) | SignatureToken::StructInstantiation(idx, _) => {
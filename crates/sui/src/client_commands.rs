@@ -3,7 +3,7 @@
 
 use core::fmt;
 use std::{
-    collections::BTreeSet,
+    collections::{BTreeMap, BTreeSet},
     fmt::{Debug, Display, Formatter, Write},
     path::{Path, PathBuf},
     time::Instant,
@@ -12,34 +12,49 @@ use std::{
 use anyhow::anyhow;
 use clap::*;
 use colored::Colorize;
-use move_core_types::language_storage::TypeTag;
+use move_core_types::identifier::IdentStr;
+use move_core_types::language_storage::{StructTag, TypeTag};
 use move_package::BuildConfig;
+use serde::de::DeserializeOwned;
 use serde::Serialize;
 use serde_json::json;
 use tracing::info;
 
-use sui_framework::build_move_package_to_bytes;
 use sui_json::SuiJsonValue;
 use sui_json_rpc_types::SuiData;
 use sui_json_rpc_types::{
     GetObjectDataResponse, SuiExecuteTransactionResponse, SuiObjectInfo, SuiParsedObject,
-    SuiTransactionResponse,
+    SuiParsedTransactionResponse, SuiTransactionResponse,
+};
+use sui_json_rpc_types::{
+    SuiCertifiedTransaction, SuiExecutionStatus, SuiGasProfile, SuiRawData, SuiTransactionEffects,
 };
-use sui_json_rpc_types::{SuiCertifiedTransaction, SuiExecutionStatus, SuiTransactionEffects};
 use sui_sdk::crypto::SuiKeystore;
 use sui_sdk::{ClientType, SuiClient};
 use sui_types::crypto::SignatureScheme;
 use sui_types::sui_serde::{Base64, Encoding};
 use sui_types::{
     base_types::{ObjectID, SuiAddress},
+    committee::EpochId,
     gas_coin::GasCoin,
+    id::UID,
     messages::ExecuteTransactionRequestType,
     messages::Transaction,
     object::Owner,
-    parse_sui_type_tag, SUI_FRAMEWORK_ADDRESS,
+    parse_sui_type_tag,
+    sui_system_state::{
+        Delegation, EpochRewardRecord, Stake, CLAIM_DELEGATION_REWARD_FUNCTION_NAME,
+        REQUEST_ADD_DELEGATION_FUNCTION_NAME, REQUEST_ADD_STAKE_FUNCTION_NAME,
+        REQUEST_REMOVE_DELEGATION_FUNCTION_NAME, REQUEST_SWITCH_DELEGATION_FUNCTION_NAME,
+        SUI_SYSTEM_MODULE_NAME,
+    },
+    SUI_FRAMEWORK_ADDRESS, SUI_FRAMEWORK_OBJECT_ID, SUI_SYSTEM_STATE_OBJECT_ID,
 };
 
 use crate::config::{Config, PersistedConfig, SuiClientConfig};
+use crate::published_package::{
+    build_with_published_deps, network_name, PublishedDependency, PublishedPackage,
+};
 
 pub const EXAMPLE_NFT_NAME: &str = "Example NFT";
 pub const EXAMPLE_NFT_DESCRIPTION: &str = "An NFT created by the Sui Command Line Tool";
@@ -136,6 +151,10 @@ pub enum SuiClientCommands {
         /// Gas budget for this call
         #[clap(long)]
         gas_budget: u64,
+        /// Dry run the call on the full node instead of executing it, and write where its gas
+        /// goes to this file, as folded stacks for flamegraph tools
+        #[clap(long)]
+        profile_output: Option<PathBuf>,
     },
 
     /// Transfer object
@@ -272,6 +291,120 @@ pub enum SuiClientCommands {
         #[clap(long)]
         gas_budget: Option<u64>,
     },
+
+    /// Stake SUI as a validator, or add to an existing validator stake.
+    #[clap(name = "stake")]
+    Stake {
+        /// SUI coin to stake, in 20 bytes Hex string.
+        /// If not provided, a coin with at least `amount` value will be selected
+        #[clap(long)]
+        coin: Option<ObjectID>,
+
+        /// The amount to stake. If not specified, the entire coin is staked, otherwise a coin
+        /// of exactly this amount is split off first.
+        #[clap(long)]
+        amount: Option<u64>,
+
+        /// ID of the gas object for gas payment, in 20 bytes Hex string
+        /// If not provided, a gas object with at least gas_budget value will be selected
+        #[clap(long)]
+        gas: Option<ObjectID>,
+
+        /// Gas budget for this call
+        #[clap(long)]
+        gas_budget: u64,
+    },
+
+    /// Delegate SUI to a validator.
+    #[clap(name = "delegate")]
+    Delegate {
+        /// Address of the validator to delegate to
+        #[clap(long)]
+        validator: SuiAddress,
+
+        /// SUI coin to delegate, in 20 bytes Hex string.
+        /// If not provided, a coin with at least `amount` value will be selected
+        #[clap(long)]
+        coin: Option<ObjectID>,
+
+        /// The amount to delegate. If not specified, the entire coin is delegated, otherwise a
+        /// coin of exactly this amount is split off first.
+        #[clap(long)]
+        amount: Option<u64>,
+
+        /// ID of the gas object for gas payment, in 20 bytes Hex string
+        /// If not provided, a gas object with at least gas_budget value will be selected
+        #[clap(long)]
+        gas: Option<ObjectID>,
+
+        /// Gas budget for this call
+        #[clap(long)]
+        gas_budget: u64,
+    },
+
+    /// Move an active delegation to another validator.
+    #[clap(name = "switch-delegation")]
+    SwitchDelegation {
+        /// Delegation object to switch, in 20 bytes Hex string
+        #[clap(long)]
+        delegation: ObjectID,
+
+        /// Address of the new validator
+        #[clap(long)]
+        validator: SuiAddress,
+
+        /// ID of the gas object for gas payment, in 20 bytes Hex string
+        /// If not provided, a gas object with at least gas_budget value will be selected
+        #[clap(long)]
+        gas: Option<ObjectID>,
+
+        /// Gas budget for this call
+        #[clap(long)]
+        gas_budget: u64,
+    },
+
+    /// Withdraw an active delegation. The delegated SUI is returned to the delegation owner.
+    #[clap(name = "undelegate")]
+    Undelegate {
+        /// Delegation object to withdraw, in 20 bytes Hex string
+        #[clap(long)]
+        delegation: ObjectID,
+
+        /// ID of the gas object for gas payment, in 20 bytes Hex string
+        /// If not provided, a gas object with at least gas_budget value will be selected
+        #[clap(long)]
+        gas: Option<ObjectID>,
+
+        /// Gas budget for this call
+        #[clap(long)]
+        gas_budget: u64,
+    },
+
+    /// Claim the rewards of every epoch the delegation has not claimed yet.
+    /// Each epoch is claimed in its own transaction.
+    #[clap(name = "claim-rewards")]
+    ClaimRewards {
+        /// Delegation object to claim the rewards of, in 20 bytes Hex string
+        #[clap(long)]
+        delegation: ObjectID,
+
+        /// ID of the gas object for gas payment, in 20 bytes Hex string
+        /// If not provided, a gas object with at least gas_budget value will be selected
+        #[clap(long)]
+        gas: Option<ObjectID>,
+
+        /// Gas budget for each claim
+        #[clap(long)]
+        gas_budget: u64,
+    },
+
+    /// Obtain the Stake and Delegation objects owned by the address, with their pending rewards.
+    #[clap(name = "stakes")]
+    Stakes {
+        /// Address owning the objects
+        #[clap(long)]
+        address: Option<SuiAddress>,
+    },
 }
 
 impl SuiClientCommands {
@@ -289,17 +422,31 @@ impl SuiClientCommands {
                 let sender = context.try_get_object_owner(&gas).await?;
                 let sender = sender.unwrap_or(context.active_address()?);
 
-                let compiled_modules = build_move_package_to_bytes(&package_path, build_config)?;
+                let network = network_name(&context.config.client_type);
+                let package =
+                    build_with_published_deps(&package_path, build_config, Some(&network))?;
+                check_published_dependencies(context, &package.published_dependencies).await?;
+
                 let data = context
                     .client
                     .transaction_builder()
-                    .publish(sender, compiled_modules, gas, gas_budget)
+                    .publish(sender, package.module_bytes(), gas, gas_budget)
                     .await?;
                 let signature = context.keystore.sign(&sender, &data.to_bytes())?;
                 let response = context
                     .execute_transaction(Transaction::new(data, signature))
                     .await?;
 
+                if let Some(SuiParsedTransactionResponse::Publish(published)) =
+                    &response.parsed_data
+                {
+                    let package_ref = &published.package;
+                    let module_map =
+                        read_package_module_map(context, package_ref.object_id).await?;
+                    PublishedPackage::new(package_ref.object_id, package_ref.version, &module_map)
+                        .write(&package_path, &network)?;
+                }
+
                 SuiClientCommandResult::Publish(response)
             }
 
@@ -316,12 +463,24 @@ impl SuiClientCommands {
                 gas,
                 gas_budget,
                 args,
+                profile_output,
             } => {
-                let (cert, effects) = call_move(
-                    package, &module, &function, type_args, gas, gas_budget, args, context,
-                )
-                .await?;
-                SuiClientCommandResult::Call(cert, effects)
+                if let Some(profile_output) = profile_output {
+                    let (effects, profile) = profile_call_move(
+                        package, &module, &function, type_args, gas, gas_budget, args, context,
+                    )
+                    .await?;
+                    std::fs::write(&profile_output, &profile.folded_stacks).map_err(|e| {
+                        anyhow!("Cannot write the gas profile to {profile_output:?}: {e}")
+                    })?;
+                    SuiClientCommandResult::ProfileCall(effects, profile)
+                } else {
+                    let (cert, effects) = call_move(
+                        package, &module, &function, type_args, gas, gas_budget, args, context,
+                    )
+                    .await?;
+                    SuiClientCommandResult::Call(cert, effects)
+                }
             }
 
             SuiClientCommands::Transfer {
@@ -530,6 +689,189 @@ impl SuiClientCommands {
                 let object_read = context.client.read_api().get_parsed_object(nft_id).await?;
                 SuiClientCommandResult::CreateExampleNFT(object_read)
             }
+
+            SuiClientCommands::Stake {
+                coin,
+                amount,
+                gas,
+                gas_budget,
+            } => {
+                let sender = context.try_get_object_owner(&coin).await?;
+                let sender = sender.unwrap_or(context.active_address()?);
+                let coin = select_sui_coin(context, sender, coin, amount, gas, gas_budget).await?;
+                let (cert, effects) = call_sui_system(
+                    context,
+                    sender,
+                    REQUEST_ADD_STAKE_FUNCTION_NAME,
+                    vec![SuiJsonValue::from_object_id(coin)],
+                    gas,
+                    gas_budget,
+                )
+                .await?;
+                SuiClientCommandResult::Stake(cert, effects)
+            }
+
+            SuiClientCommands::Delegate {
+                validator,
+                coin,
+                amount,
+                gas,
+                gas_budget,
+            } => {
+                let sender = context.try_get_object_owner(&coin).await?;
+                let sender = sender.unwrap_or(context.active_address()?);
+                let coin = select_sui_coin(context, sender, coin, amount, gas, gas_budget).await?;
+                let (cert, effects) = call_sui_system(
+                    context,
+                    sender,
+                    REQUEST_ADD_DELEGATION_FUNCTION_NAME,
+                    vec![
+                        SuiJsonValue::from_object_id(coin),
+                        SuiJsonValue::new(json!(validator.to_string()))?,
+                    ],
+                    gas,
+                    gas_budget,
+                )
+                .await?;
+                SuiClientCommandResult::Delegate(cert, effects)
+            }
+
+            SuiClientCommands::SwitchDelegation {
+                delegation,
+                validator,
+                gas,
+                gas_budget,
+            } => {
+                let sender = context.get_object_owner(&delegation).await?;
+                let (cert, effects) = call_sui_system(
+                    context,
+                    sender,
+                    REQUEST_SWITCH_DELEGATION_FUNCTION_NAME,
+                    vec![
+                        SuiJsonValue::from_object_id(delegation),
+                        SuiJsonValue::new(json!(validator.to_string()))?,
+                    ],
+                    gas,
+                    gas_budget,
+                )
+                .await?;
+                SuiClientCommandResult::SwitchDelegation(cert, effects)
+            }
+
+            SuiClientCommands::Undelegate {
+                delegation,
+                gas,
+                gas_budget,
+            } => {
+                let sender = context.get_object_owner(&delegation).await?;
+                let (cert, effects) = call_sui_system(
+                    context,
+                    sender,
+                    REQUEST_REMOVE_DELEGATION_FUNCTION_NAME,
+                    vec![SuiJsonValue::from_object_id(delegation)],
+                    gas,
+                    gas_budget,
+                )
+                .await?;
+                SuiClientCommandResult::Undelegate(cert, effects)
+            }
+
+            SuiClientCommands::ClaimRewards {
+                delegation: delegation_id,
+                gas,
+                gas_budget,
+            } => {
+                let sender = context.get_object_owner(&delegation_id).await?;
+                let delegation: Delegation =
+                    read_move_object(context, delegation_id, &Delegation::type_())
+                        .await?
+                        .ok_or_else(|| anyhow!("{delegation_id} is not a Delegation object"))?;
+                let records = context
+                    .epoch_reward_records(
+                        Some(SuiAddress::from(ObjectID::from(
+                            delegation.validator_address,
+                        ))),
+                        delegation.next_reward_unclaimed_epoch,
+                    )
+                    .await?;
+                let records = claimable_reward_records(&delegation, &records);
+                if records.is_empty() {
+                    return Err(anyhow!(
+                        "Delegation {delegation_id} has no unclaimed rewards"
+                    ));
+                }
+
+                let mut claims = Vec::new();
+                for (record_id, _) in records {
+                    claims.push(
+                        call_sui_system(
+                            context,
+                            sender,
+                            CLAIM_DELEGATION_REWARD_FUNCTION_NAME,
+                            vec![
+                                SuiJsonValue::from_object_id(delegation_id),
+                                SuiJsonValue::from_object_id(*record_id),
+                            ],
+                            gas,
+                            gas_budget,
+                        )
+                        .await?,
+                    );
+                }
+                SuiClientCommandResult::ClaimRewards(claims)
+            }
+
+            SuiClientCommands::Stakes { address } => {
+                let address = address.unwrap_or(context.active_address()?);
+                let objects = context
+                    .client
+                    .read_api()
+                    .get_objects_owned_by_address(address)
+                    .await?;
+                let mut stakes = Vec::new();
+                let mut delegation_objects = Vec::new();
+                for info in objects {
+                    if info.type_ == Stake::type_().to_string() {
+                        if let Some(stake) =
+                            read_move_object::<Stake>(context, info.object_id, &Stake::type_())
+                                .await?
+                        {
+                            stakes.push(StakeInfo::new(info.object_id, &stake));
+                        }
+                    } else if info.type_ == Delegation::type_().to_string() {
+                        if let Some(delegation) = read_move_object::<Delegation>(
+                            context,
+                            info.object_id,
+                            &Delegation::type_(),
+                        )
+                        .await?
+                        {
+                            delegation_objects.push((info.object_id, delegation));
+                        }
+                    }
+                }
+
+                // The records of all unclaimed epochs are read at once. They can only be found
+                // through a full node, pending rewards are left out when it is not available.
+                let from_epoch = delegation_objects
+                    .iter()
+                    .map(|(_, delegation)| delegation.next_reward_unclaimed_epoch)
+                    .min();
+                let records = match from_epoch {
+                    Some(from_epoch) => context.epoch_reward_records(None, from_epoch).await.ok(),
+                    None => Some(vec![]),
+                };
+                let delegations = delegation_objects
+                    .iter()
+                    .map(|(object_id, delegation)| {
+                        DelegationInfo::new(*object_id, delegation, records.as_deref())
+                    })
+                    .collect();
+                SuiClientCommandResult::Stakes(StakesResponse {
+                    stakes,
+                    delegations,
+                })
+            }
         });
         ret
     }
@@ -674,10 +1016,64 @@ impl WalletContext {
         ))
     }
 
+    /// Get the `EpochRewardRecord`s of the epochs from `from_epoch` on, of all validators or of
+    /// a single one, ordered by epoch. This is only supported by full nodes.
+    pub async fn epoch_reward_records(
+        &self,
+        validator: Option<SuiAddress>,
+        from_epoch: EpochId,
+    ) -> Result<Vec<(ObjectID, EpochRewardRecord)>, anyhow::Error> {
+        Ok(self
+            .client
+            .governance_api()
+            .get_epoch_reward_records(validator, from_epoch)
+            .await?
+            .into_iter()
+            .map(|record| {
+                (
+                    record.id,
+                    EpochRewardRecord {
+                        id: UID::new(record.id),
+                        epoch: record.epoch,
+                        computation_charge: record.computation_charge,
+                        total_stake: record.total_stake,
+                        delegator_count: record.delegator_count,
+                        validator: record.validator.into(),
+                    },
+                )
+            })
+            .collect())
+    }
+
     /// A backward-compatible migration of transaction execution from gateway to fullnode
     pub async fn execute_transaction(
         &self,
         tx: Transaction,
+    ) -> anyhow::Result<SuiTransactionResponse> {
+        self.execute_transaction_with_request_type(
+            tx,
+            ExecuteTransactionRequestType::WaitForEffectsCert,
+        )
+        .await
+    }
+
+    /// Like `execute_transaction`, and also wait for the full node to execute the transaction,
+    /// for callers reading objects written by the transaction right after.
+    pub async fn execute_transaction_and_wait_for_local_execution(
+        &self,
+        tx: Transaction,
+    ) -> anyhow::Result<SuiTransactionResponse> {
+        self.execute_transaction_with_request_type(
+            tx,
+            ExecuteTransactionRequestType::WaitForLocalExecution,
+        )
+        .await
+    }
+
+    async fn execute_transaction_with_request_type(
+        &self,
+        tx: Transaction,
+        request_type: ExecuteTransactionRequestType,
     ) -> anyhow::Result<SuiTransactionResponse> {
         let tx_digest = *tx.digest();
         if self.client.is_gateway() {
@@ -686,15 +1082,13 @@ impl WalletContext {
             let result = self
                 .client
                 .quorum_driver()
-                .execute_transaction_by_fullnode(
-                    tx,
-                    ExecuteTransactionRequestType::WaitForEffectsCert,
-                )
+                .execute_transaction_by_fullnode(tx, request_type)
                 .await;
             match result {
                 Ok(SuiExecuteTransactionResponse::EffectsCert {
                     certificate,
                     effects,
+                    ..
                 }) => Ok(SuiTransactionResponse {
                     certificate,
                     effects: effects.effects,
@@ -733,6 +1127,14 @@ impl Display for SuiClientCommandResult {
             SuiClientCommandResult::Call(cert, effects) => {
                 write!(writer, "{}", write_cert_and_effects(cert, effects)?)?;
             }
+            SuiClientCommandResult::ProfileCall(effects, profile) => {
+                writeln!(writer, "{}", "----- Transaction Effects ----".bold())?;
+                write!(writer, "{}", effects)?;
+                writeln!(writer, "{}", "----- Gas By Function ----".bold())?;
+                for (function, gas) in &profile.function_costs {
+                    writeln!(writer, "{function}: {gas}")?;
+                }
+            }
             SuiClientCommandResult::Transfer(time_elapsed, cert, effects) => {
                 writeln!(writer, "Transfer confirmed after {} us", time_elapsed)?;
                 write!(writer, "{}", write_cert_and_effects(cert, effects)?)?;
@@ -829,6 +1231,20 @@ impl Display for SuiClientCommandResult {
                 writeln!(writer, "{}\n", "Successfully created an ExampleNFT:".bold())?;
                 writeln!(writer, "{}", object)?;
             }
+            SuiClientCommandResult::Stake(cert, effects)
+            | SuiClientCommandResult::Delegate(cert, effects)
+            | SuiClientCommandResult::SwitchDelegation(cert, effects)
+            | SuiClientCommandResult::Undelegate(cert, effects) => {
+                write!(writer, "{}", write_cert_and_effects(cert, effects)?)?;
+            }
+            SuiClientCommandResult::ClaimRewards(claims) => {
+                for (cert, effects) in claims {
+                    write!(writer, "{}", write_cert_and_effects(cert, effects)?)?;
+                }
+            }
+            SuiClientCommandResult::Stakes(response) => {
+                write!(writer, "{}", response)?;
+            }
         }
         write!(f, "{}", writer.trim_end_matches('\n'))
     }
@@ -844,6 +1260,59 @@ pub async fn call_move(
     args: Vec<SuiJsonValue>,
     context: &mut WalletContext,
 ) -> Result<(SuiCertifiedTransaction, SuiTransactionEffects), anyhow::Error> {
+    let transaction = move_call_transaction(
+        package, module, function, type_args, gas, gas_budget, args, context,
+    )
+    .await?;
+
+    let response = context.execute_transaction(transaction).await?;
+    let cert = response.certificate;
+    let effects = response.effects;
+
+    if matches!(effects.status, SuiExecutionStatus::Failure { .. }) {
+        return Err(anyhow!("Error calling module: {:#?}", effects.status));
+    }
+    Ok((cert, effects))
+}
+
+/// Dry run a Move call on the full node, returning the effects it would have and where its gas
+/// would go.
+pub async fn profile_call_move(
+    package: ObjectID,
+    module: &str,
+    function: &str,
+    type_args: Vec<TypeTag>,
+    gas: Option<ObjectID>,
+    gas_budget: u64,
+    args: Vec<SuiJsonValue>,
+    context: &mut WalletContext,
+) -> Result<(SuiTransactionEffects, SuiGasProfile), anyhow::Error> {
+    let transaction = move_call_transaction(
+        package, module, function, type_args, gas, gas_budget, args, context,
+    )
+    .await?;
+
+    let response = context
+        .client
+        .full_node_api()
+        .dry_run_transaction(transaction, true)
+        .await?;
+    let profile = response
+        .gas_profile
+        .ok_or_else(|| anyhow!("The full node did not return a gas profile"))?;
+    Ok((response.effects, profile))
+}
+
+async fn move_call_transaction(
+    package: ObjectID,
+    module: &str,
+    function: &str,
+    type_args: Vec<TypeTag>,
+    gas: Option<ObjectID>,
+    gas_budget: u64,
+    args: Vec<SuiJsonValue>,
+    context: &mut WalletContext,
+) -> Result<Transaction, anyhow::Error> {
     let gas_owner = context.try_get_object_owner(&gas).await?;
     let sender = gas_owner.unwrap_or(context.active_address()?);
 
@@ -865,18 +1334,186 @@ pub async fn call_move(
         )
         .await?;
     let signature = context.keystore.sign(&sender, &data.to_bytes())?;
-    let transaction = Transaction::new(data, signature);
+    Ok(Transaction::new(data, signature))
+}
 
-    let response = context.execute_transaction(transaction).await?;
+pub(crate) async fn call_sui_system(
+    context: &mut WalletContext,
+    sender: SuiAddress,
+    function: &IdentStr,
+    args: Vec<SuiJsonValue>,
+    gas: Option<ObjectID>,
+    gas_budget: u64,
+) -> Result<(SuiCertifiedTransaction, SuiTransactionEffects), anyhow::Error> {
+    let args = std::iter::once(SuiJsonValue::from_object_id(SUI_SYSTEM_STATE_OBJECT_ID))
+        .chain(args)
+        .collect();
+    let data = context
+        .client
+        .transaction_builder()
+        .move_call(
+            sender,
+            SUI_FRAMEWORK_OBJECT_ID,
+            SUI_SYSTEM_MODULE_NAME.as_str(),
+            function.as_str(),
+            vec![],
+            args,
+            gas,
+            gas_budget,
+        )
+        .await?;
+    let signature = context.keystore.sign(&sender, &data.to_bytes())?;
+    let response = context
+        .execute_transaction(Transaction::new(data, signature))
+        .await?;
     let cert = response.certificate;
     let effects = response.effects;
 
     if matches!(effects.status, SuiExecutionStatus::Failure { .. }) {
-        return Err(anyhow!("Error calling module: {:#?}", effects.status));
+        return Err(anyhow!("Error calling {function}: {:#?}", effects.status));
     }
     Ok((cert, effects))
 }
 
+/// Pick a SUI coin owned by `owner` holding exactly `amount`. When `coin` is given it is the
+/// source of the SUI, and is used whole if no amount is specified. Otherwise the smallest coin
+/// large enough is picked, keeping the larger coins available for gas. A coin of the exact
+/// amount is split off the source if needed.
+pub(crate) async fn select_sui_coin(
+    context: &mut WalletContext,
+    owner: SuiAddress,
+    coin: Option<ObjectID>,
+    amount: Option<u64>,
+    gas: Option<ObjectID>,
+    gas_budget: u64,
+) -> Result<ObjectID, anyhow::Error> {
+    let amount = match (coin, amount) {
+        (Some(coin), None) => return Ok(coin),
+        (None, None) => return Err(anyhow!("Either a coin or an amount must be specified")),
+        (_, Some(amount)) => amount,
+    };
+
+    let (coin, value) = if let Some(coin) = coin {
+        let object = context
+            .client
+            .read_api()
+            .get_parsed_object(coin)
+            .await?
+            .into_object()?;
+        (coin, GasCoin::try_from(&object)?.value())
+    } else {
+        context
+            .gas_objects(owner)
+            .await?
+            .into_iter()
+            .filter(|(value, object, _)| *value >= amount && Some(object.id()) != gas)
+            .min_by_key(|(value, _, _)| *value)
+            .map(|(value, object, _)| (object.id(), value))
+            .ok_or_else(|| anyhow!("No SUI coin owned by {owner} holds at least {amount}"))?
+    };
+
+    if value < amount {
+        return Err(anyhow!("Coin {coin} holds {value}, less than {amount}"));
+    }
+    if value == amount {
+        return Ok(coin);
+    }
+
+    let data = context
+        .client
+        .transaction_builder()
+        .split_coin(owner, coin, vec![amount], gas, gas_budget)
+        .await?;
+    let signature = context.keystore.sign(&owner, &data.to_bytes())?;
+    // The split coin is an input of the caller's next transaction, built from the full node.
+    let response = context
+        .execute_transaction_and_wait_for_local_execution(Transaction::new(data, signature))
+        .await?;
+    if matches!(response.effects.status, SuiExecutionStatus::Failure { .. }) {
+        return Err(anyhow!(
+            "Error splitting coin {coin}: {:#?}",
+            response.effects.status
+        ));
+    }
+    Ok(response
+        .effects
+        .created
+        .first()
+        .ok_or_else(|| anyhow!("Failed to split coin {coin}"))?
+        .reference
+        .object_id)
+}
+
+/// Read the Move object `id` and decode it, if it is of type `type_`.
+pub(crate) async fn read_move_object<T: DeserializeOwned>(
+    context: &WalletContext,
+    id: ObjectID,
+    type_: &StructTag,
+) -> Result<Option<T>, anyhow::Error> {
+    let object = context
+        .client
+        .read_api()
+        .get_object(id)
+        .await?
+        .into_object()?;
+    match object.data {
+        SuiRawData::MoveObject(o) if o.type_ == type_.to_string() => {
+            Ok(Some(bcs::from_bytes(&o.bcs_bytes)?))
+        }
+        _ => Ok(None),
+    }
+}
+
+/// The serialized modules of the package `id`, keyed by module name.
+pub async fn read_package_module_map(
+    context: &WalletContext,
+    id: ObjectID,
+) -> Result<BTreeMap<String, Vec<u8>>, anyhow::Error> {
+    let object = context
+        .client
+        .read_api()
+        .get_object(id)
+        .await?
+        .into_object()?;
+    match object.data {
+        SuiRawData::Package(p) => Ok(p.module_map),
+        SuiRawData::MoveObject(_) => Err(anyhow!("{id} is not a package")),
+    }
+}
+
+/// Check that the packages recorded for `dependencies` are the ones on chain. The recorded digests
+/// were read from the chain when the dependencies were published, and the sources of each
+/// dependency were checked against them when it was built.
+pub async fn check_published_dependencies(
+    context: &WalletContext,
+    dependencies: &[PublishedDependency],
+) -> Result<(), anyhow::Error> {
+    for dependency in dependencies {
+        let module_map = read_package_module_map(context, dependency.published.package_id).await?;
+        if !dependency.published.matches(&module_map) {
+            return Err(anyhow!(
+                "The bytecode published at {} differs from the bytecode compiled from dependency \
+                {} at {}",
+                dependency.published.package_id,
+                dependency.name,
+                dependency.path.display(),
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// The reward records `delegation` is able to claim, in the order they must be claimed.
+fn claimable_reward_records<'a>(
+    delegation: &Delegation,
+    records: &'a [(ObjectID, EpochRewardRecord)],
+) -> Vec<&'a (ObjectID, EpochRewardRecord)> {
+    records
+        .iter()
+        .filter(|(_, record)| delegation.can_claim_reward(record.epoch, record.validator))
+        .collect()
+}
+
 fn unwrap_or<'a>(val: &'a Option<String>, default: &'a str) -> &'a str {
     match val {
         Some(v) => v,
@@ -938,6 +1575,7 @@ pub enum SuiClientCommandResult {
     Publish(SuiTransactionResponse),
     Object(GetObjectDataResponse),
     Call(SuiCertifiedTransaction, SuiTransactionEffects),
+    ProfileCall(SuiTransactionEffects, SuiGasProfile),
     Transfer(
         // Skipping serialisation for elapsed time.
         #[serde(skip)] u128,
@@ -955,6 +1593,12 @@ pub enum SuiClientCommandResult {
     Switch(SwitchResponse),
     ActiveAddress(Option<SuiAddress>),
     CreateExampleNFT(GetObjectDataResponse),
+    Stake(SuiCertifiedTransaction, SuiTransactionEffects),
+    Delegate(SuiCertifiedTransaction, SuiTransactionEffects),
+    SwitchDelegation(SuiCertifiedTransaction, SuiTransactionEffects),
+    Undelegate(SuiCertifiedTransaction, SuiTransactionEffects),
+    ClaimRewards(Vec<(SuiCertifiedTransaction, SuiTransactionEffects)>),
+    Stakes(StakesResponse),
 }
 
 #[derive(Serialize, Clone, Debug)]
@@ -981,3 +1625,111 @@ impl Display for SwitchResponse {
         write!(f, "{}", writer)
     }
 }
+
+#[derive(Serialize, Clone, Debug)]
+pub struct StakeInfo {
+    pub object_id: ObjectID,
+    pub amount: u64,
+    pub locked_until_epoch: Option<u64>,
+}
+
+impl StakeInfo {
+    fn new(object_id: ObjectID, stake: &Stake) -> Self {
+        Self {
+            object_id,
+            amount: stake.balance.value(),
+            locked_until_epoch: stake.locked_until_epoch.as_option().map(|lock| lock.epoch),
+        }
+    }
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct DelegationInfo {
+    pub object_id: ObjectID,
+    pub validator: SuiAddress,
+    pub delegate_amount: u64,
+    pub active: bool,
+    pub next_reward_unclaimed_epoch: u64,
+    pub ending_epoch: Option<u64>,
+    /// Epochs with rewards which can be claimed, None if the reward records could not be read.
+    pub unclaimed_epochs: Option<Vec<u64>>,
+    /// Total reward of `unclaimed_epochs`.
+    pub pending_reward: Option<u64>,
+}
+
+impl DelegationInfo {
+    fn new(
+        object_id: ObjectID,
+        delegation: &Delegation,
+        records: Option<&[(ObjectID, EpochRewardRecord)]>,
+    ) -> Self {
+        let claimable = records.map(|records| claimable_reward_records(delegation, records));
+        Self {
+            object_id,
+            validator: SuiAddress::from(ObjectID::from(delegation.validator_address)),
+            delegate_amount: delegation.delegate_amount,
+            active: delegation.is_active(),
+            next_reward_unclaimed_epoch: delegation.next_reward_unclaimed_epoch,
+            ending_epoch: delegation.ending_epoch.as_option().copied(),
+            unclaimed_epochs: claimable
+                .as_ref()
+                .map(|records| records.iter().map(|(_, record)| record.epoch).collect()),
+            pending_reward: claimable.as_ref().map(|records| {
+                records
+                    .iter()
+                    .map(|(_, record)| record.reward_for(delegation.delegate_amount))
+                    .sum()
+            }),
+        }
+    }
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct StakesResponse {
+    pub stakes: Vec<StakeInfo>,
+    pub delegations: Vec<DelegationInfo>,
+}
+
+impl Display for StakesResponse {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut writer = String::new();
+        writeln!(
+            writer,
+            " {0: ^42} | {1: ^15} | {2: ^12}",
+            "Stake ID", "Amount", "Locked Until"
+        )?;
+        writeln!(writer, "{}", ["-"; 75].join(""))?;
+        for stake in &self.stakes {
+            let locked_until = stake
+                .locked_until_epoch
+                .map_or_else(|| "-".to_string(), |epoch| epoch.to_string());
+            writeln!(
+                writer,
+                " {0: ^42} | {1: ^15} | {2: ^12}",
+                stake.object_id, stake.amount, locked_until
+            )?;
+        }
+        writeln!(writer)?;
+        writeln!(
+            writer,
+            " {0: ^42} | {1: ^42} | {2: ^15} | {3: ^8} | {4: ^15}",
+            "Delegation ID", "Validator", "Amount", "Active", "Pending Reward"
+        )?;
+        writeln!(writer, "{}", ["-"; 135].join(""))?;
+        for delegation in &self.delegations {
+            let pending_reward = delegation
+                .pending_reward
+                .map_or_else(|| "unknown".to_string(), |reward| reward.to_string());
+            writeln!(
+                writer,
+                " {0: ^42} | {1: ^42} | {2: ^15} | {3: ^8} | {4: ^15}",
+                delegation.object_id,
+                delegation.validator,
+                delegation.delegate_amount,
+                delegation.active,
+                pending_reward
+            )?;
+        }
+        write!(f, "{}", writer)
+    }
+}
@@ -6,8 +6,10 @@ pub mod client_commands;
 pub mod config;
 pub mod console;
 pub mod keytool;
+pub mod published_package;
 pub mod shell;
 pub mod sui_commands;
 pub mod sui_move;
+pub mod validator_commands;
 
 pub mod genesis_ceremony;
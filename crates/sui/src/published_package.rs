@@ -0,0 +1,292 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Records of published packages, and resolution of package dependencies against them.
+//!
+//! `sui client publish` records the ID, version and bytecode digest of the package it publishes in
+//! a `Published.toml` file next to the package's `Move.toml`, keyed by the network it was published
+//! on. Packages depending on it can keep its address as `0x0` in their manifest: when they are
+//! built for a network, references to its modules resolve to the ID recorded for that network, and
+//! the build fails if its sources no longer compile to the recorded bytecode.
+
+use anyhow::{anyhow, bail};
+use move_binary_format::CompiledModule;
+use move_compiler::compiled_unit::{CompiledUnit, NamedCompiledModule};
+use move_core_types::{account_address::AccountAddress, language_storage::ModuleId};
+use move_package::BuildConfig;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, HashSet},
+    fs,
+    path::{Path, PathBuf},
+};
+use sui_adapter::bytecode_rewriter::ModuleHandleRewriter;
+use sui_config::{sui_config_dir, PersistedConfig, SUI_CLIENT_CONFIG};
+use sui_sdk::ClientType;
+use sui_types::{
+    base_types::{ObjectID, SequenceNumber},
+    move_package::module_map_digest,
+};
+
+use crate::config::SuiClientConfig;
+
+pub const PUBLISHED_FILE_NAME: &str = "Published.toml";
+
+const MANIFEST_FILE_NAME: &str = "Move.toml";
+
+/// Where and as what a package was published.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub struct PublishedPackage {
+    pub package_id: ObjectID,
+    pub version: SequenceNumber,
+    /// Hex-encoded `module_map_digest` of the published modules.
+    pub digest: String,
+}
+
+impl PublishedPackage {
+    pub fn new(
+        package_id: ObjectID,
+        version: SequenceNumber,
+        module_map: &BTreeMap<String, Vec<u8>>,
+    ) -> Self {
+        Self {
+            package_id,
+            version,
+            digest: hex::encode(module_map_digest(module_map)),
+        }
+    }
+
+    /// Read the record of the package at `package_path` on `network`, if it was published there.
+    pub fn read(package_path: &Path, network: &str) -> anyhow::Result<Option<Self>> {
+        Ok(read_records(package_path)?.remove(network))
+    }
+
+    /// Record that the package at `package_path` was published on `network` as `self`, keeping the
+    /// records of the other networks it was published on.
+    pub fn write(&self, package_path: &Path, network: &str) -> anyhow::Result<()> {
+        let path = package_path.join(PUBLISHED_FILE_NAME);
+        let mut records = read_records(package_path)?;
+        records.insert(network.to_string(), self.clone());
+        fs::write(&path, toml::to_string(&records)?)
+            .map_err(|e| anyhow!("Cannot write {}: {e}", path.display()))
+    }
+
+    /// Whether the modules in `module_map` are the ones that were published.
+    pub fn matches(&self, module_map: &BTreeMap<String, Vec<u8>>) -> bool {
+        self.digest == hex::encode(module_map_digest(module_map))
+    }
+}
+
+/// The records of the package at `package_path`, keyed by network.
+fn read_records(package_path: &Path) -> anyhow::Result<BTreeMap<String, PublishedPackage>> {
+    let path = package_path.join(PUBLISHED_FILE_NAME);
+    if !path.exists() {
+        return Ok(BTreeMap::new());
+    }
+    let contents = fs::read_to_string(&path)?;
+    toml::from_str(&contents).map_err(|e| anyhow!("Cannot parse {}: {e}", path.display()))
+}
+
+/// The network a client of type `client_type` publishes to, which packages are recorded per: the
+/// URL of its RPC server, or the validators of its embedded gateway.
+pub fn network_name(client_type: &ClientType) -> String {
+    match client_type {
+        ClientType::RPC(url, _) => url.clone(),
+        ClientType::Embedded(config) => {
+            let mut addresses = config
+                .validator_set
+                .iter()
+                .map(|info| info.network_address().to_string())
+                .collect::<Vec<_>>();
+            addresses.sort();
+            format!("embedded:{}", addresses.join(","))
+        }
+    }
+}
+
+/// The client config at `config`, or in the Sui config directory if not set. `None` if not set and
+/// there is no client config.
+pub fn client_config_path(config: Option<PathBuf>) -> anyhow::Result<Option<PathBuf>> {
+    match config {
+        Some(config) => Ok(Some(config)),
+        None => {
+            let config = sui_config_dir()?.join(SUI_CLIENT_CONFIG);
+            Ok(config.exists().then_some(config))
+        }
+    }
+}
+
+/// The network of the client config at `config`, or in the Sui config directory if not set, to
+/// resolve published dependencies on. `None` if not set and there is no client config.
+pub fn client_network(config: Option<PathBuf>) -> anyhow::Result<Option<String>> {
+    let config = match client_config_path(config)? {
+        Some(config) => config,
+        None => return Ok(None),
+    };
+    let config: SuiClientConfig = PersistedConfig::read(&config)
+        .map_err(|e| anyhow!("Cannot read client config {}: {e}", config.display()))?;
+    Ok(Some(network_name(&config.client_type)))
+}
+
+/// A dependency of a package that was resolved to its published version.
+#[derive(Debug, Clone)]
+pub struct PublishedDependency {
+    pub name: String,
+    pub path: PathBuf,
+    pub published: PublishedPackage,
+}
+
+/// The modules of a package built against the published versions of its dependencies.
+pub struct CompiledPackageWithPublishedDeps {
+    /// The modules of the package itself, in dependency order.
+    pub modules: Vec<CompiledModule>,
+    pub published_dependencies: Vec<PublishedDependency>,
+}
+
+impl CompiledPackageWithPublishedDeps {
+    pub fn module_bytes(&self) -> Vec<Vec<u8>> {
+        self.modules
+            .iter()
+            .map(|m| {
+                let mut bytes = Vec::new();
+                m.serialize(&mut bytes).unwrap();
+                bytes
+            })
+            .collect()
+    }
+}
+
+/// Given a `path` and a `build_config`, build the package in that path for `network`. Dependencies
+/// with `0x0` as their address must have been published on `network` with their `Published.toml`
+/// recorded, and references to their modules are resolved to the recorded package IDs. Fails if
+/// the sources of a dependency do not compile to the bytecode that was published.
+pub fn build_with_published_deps(
+    path: &Path,
+    build_config: BuildConfig,
+    network: Option<&str>,
+) -> anyhow::Result<CompiledPackageWithPublishedDeps> {
+    let package = sui_framework::compile_move_package_with_deps(path, build_config)?;
+    let root_modules: HashSet<ModuleId> = package
+        .root_modules_map()
+        .iter_modules()
+        .iter()
+        .map(|m| m.self_id())
+        .collect();
+
+    // The unpublished-looking modules of each dependency, keyed by package name.
+    let mut dependencies: BTreeMap<String, (PathBuf, Vec<CompiledModule>)> = BTreeMap::new();
+    for (package_name, unit) in &package.deps_compiled_units {
+        if let CompiledUnit::Module(NamedCompiledModule { module, .. }) = &unit.unit {
+            let id = module.self_id();
+            if root_modules.contains(&id) || id.address() != &AccountAddress::ZERO {
+                continue;
+            }
+            let package_path = find_package_root(&unit.source_path)?;
+            dependencies
+                .entry(package_name.to_string())
+                .or_insert_with(|| (package_path, Vec::new()))
+                .1
+                .push(module.clone());
+        }
+    }
+
+    let mut published_dependencies = Vec::new();
+    let mut sub_map = BTreeMap::new();
+    for (name, (path, modules)) in &dependencies {
+        let network = network.ok_or_else(|| {
+            anyhow!(
+                "Dependency {name} can only be resolved for a network: its modules have 0x0 as \
+                their address and no client config was found to choose the network"
+            )
+        })?;
+        let published = PublishedPackage::read(path, network)?.ok_or_else(|| {
+            anyhow!(
+                "Dependency {name} has not been published on {network}: its modules have 0x0 as \
+                their address and there is no record for {network} in {PUBLISHED_FILE_NAME} in {}",
+                path.display()
+            )
+        })?;
+        for module in modules {
+            sub_map.insert(
+                module.self_id(),
+                ModuleId::new(
+                    published.package_id.into(),
+                    module.self_id().name().to_owned(),
+                ),
+            );
+        }
+        published_dependencies.push(PublishedDependency {
+            name: name.clone(),
+            path: path.clone(),
+            published,
+        });
+    }
+
+    for (dependency, (_, modules)) in published_dependencies
+        .iter()
+        .zip(dependencies.into_values())
+    {
+        let module_map = published_module_map(modules, &sub_map)?;
+        if !dependency.published.matches(&module_map) {
+            bail!(
+                "The sources of dependency {} at {} do not compile to the bytecode published at {}",
+                dependency.name,
+                dependency.path.display(),
+                dependency.published.package_id,
+            );
+        }
+    }
+
+    let rewriter = ModuleHandleRewriter::new(sub_map)?;
+    let modules = package
+        .all_modules_map()
+        .compute_dependency_graph()
+        .compute_topological_order()?
+        .filter(|m| root_modules.contains(&m.self_id()))
+        .map(|m| {
+            let mut module = m.clone();
+            rewriter.sub_module_ids(&mut module);
+            module
+        })
+        .collect();
+
+    Ok(CompiledPackageWithPublishedDeps {
+        modules,
+        published_dependencies,
+    })
+}
+
+/// The serialized modules of a dependency as they were published: references to its own
+/// dependencies are substituted before publishing, and its own ID is substituted on publish.
+fn published_module_map(
+    mut modules: Vec<CompiledModule>,
+    sub_map: &BTreeMap<ModuleId, ModuleId>,
+) -> anyhow::Result<BTreeMap<String, Vec<u8>>> {
+    let own_ids: HashSet<ModuleId> = modules.iter().map(|m| m.self_id()).collect();
+    let (own_sub_map, deps_sub_map): (BTreeMap<_, _>, BTreeMap<_, _>) = sub_map
+        .clone()
+        .into_iter()
+        .partition(|(id, _)| own_ids.contains(id));
+
+    let deps_rewriter = ModuleHandleRewriter::new(deps_sub_map)?;
+    let own_rewriter = ModuleHandleRewriter::new(own_sub_map)?;
+    let mut module_map = BTreeMap::new();
+    for module in &mut modules {
+        deps_rewriter.sub_module_ids(module);
+        own_rewriter.sub_module_ids(module);
+        let mut bytes = Vec::new();
+        module.serialize(&mut bytes)?;
+        module_map.insert(module.self_id().name().to_string(), bytes);
+    }
+    Ok(module_map)
+}
+
+/// The root of the package containing the source file `source_path`.
+fn find_package_root(source_path: &Path) -> anyhow::Result<PathBuf> {
+    source_path
+        .ancestors()
+        .find(|p| p.join(MANIFEST_FILE_NAME).exists())
+        .map(Path::to_path_buf)
+        .ok_or_else(|| anyhow!("No package contains {}", source_path.display()))
+}
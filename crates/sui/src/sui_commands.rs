@@ -29,6 +29,7 @@ use crate::console::start_console;
 use crate::genesis_ceremony::{run, Ceremony};
 use crate::keytool::KeyToolCommand;
 use crate::sui_move::{self, execute_move_command};
+use crate::validator_commands::SuiValidatorCommand;
 
 #[allow(clippy::large_enum_variant)]
 #[derive(Parser)]
@@ -98,6 +99,19 @@ pub enum SuiCommand {
         json: bool,
     },
 
+    /// Commands for validators to join, leave and manage their membership of the committee.
+    #[clap(name = "validator")]
+    Validator {
+        /// Sets the file storing the state of our user accounts (an empty one will be created if missing)
+        #[clap(long = "client.config")]
+        config: Option<PathBuf>,
+        #[clap(subcommand)]
+        cmd: Option<SuiValidatorCommand>,
+        /// Return command outputs in json format.
+        #[clap(long, global = true)]
+        json: bool,
+    },
+
     /// Tool to build and test Move applications.
     #[clap(name = "move")]
     Move {
@@ -353,11 +367,27 @@ impl SuiCommand {
                 }
                 Ok(())
             }
+            SuiCommand::Validator { config, cmd, json } => {
+                let config_path = config.unwrap_or(sui_config_dir()?.join(SUI_CLIENT_CONFIG));
+                prompt_if_no_config(&config_path).await?;
+                let mut context = WalletContext::new(&config_path).await?;
+
+                if let Some(cmd) = cmd {
+                    sync_accounts(&mut context).await?;
+                    cmd.execute(&mut context).await?.print(!json);
+                } else {
+                    // Print help
+                    let mut app: Command = SuiCommand::command();
+                    app.build();
+                    app.find_subcommand_mut("validator").unwrap().print_help()?;
+                }
+                Ok(())
+            }
             SuiCommand::Move {
                 package_path,
                 build_config,
                 cmd,
-            } => execute_move_command(package_path, build_config, cmd),
+            } => execute_move_command(package_path, build_config, cmd).await,
         }
     }
 }
@@ -5,6 +5,12 @@ use clap::Parser;
 use move_cli::base::{self, build};
 use move_package::BuildConfig;
 use std::path::{Path, PathBuf};
+use sui_types::sui_serde::{Base64, Encoding};
+
+use crate::client_commands::{check_published_dependencies, WalletContext};
+use crate::published_package::{
+    build_with_published_deps, client_config_path, client_network, CompiledPackageWithPublishedDeps,
+};
 
 #[derive(Parser)]
 pub struct Build {
@@ -13,25 +19,58 @@ pub struct Build {
     /// Whether we are printing in base64.
     #[clap(long, global = true)]
     pub dump_bytecode_as_base64: bool,
+    /// Sets the file storing the state of our user accounts, to find the network to resolve
+    /// published dependencies on and check them against. Defaults to the client config in the
+    /// Sui config directory.
+    #[clap(long = "client.config")]
+    pub config: Option<PathBuf>,
 }
 
 impl Build {
-    pub fn execute(&self, path: Option<PathBuf>, build_config: BuildConfig) -> anyhow::Result<()> {
+    pub async fn execute(
+        &self,
+        path: Option<PathBuf>,
+        build_config: BuildConfig,
+    ) -> anyhow::Result<()> {
         let rerooted_path = base::reroot_path(path)?;
-        Self::execute_internal(&rerooted_path, build_config, self.dump_bytecode_as_base64)
+        let config = client_config_path(self.config.clone())?;
+        let network = client_network(config.clone())?;
+        let package = build_with_published_deps(&rerooted_path, build_config, network.as_deref())?;
+        // Dependencies only resolve to published packages when there is a client config, whose
+        // network the packages recorded for them are read from.
+        if let Some(config) = config {
+            if !package.published_dependencies.is_empty() {
+                let context = WalletContext::new(&config).await?;
+                check_published_dependencies(&context, &package.published_dependencies).await?;
+            }
+        }
+        Self::output(&package, self.dump_bytecode_as_base64)
     }
+
     pub fn execute_internal(
         rerooted_path: &Path,
         build_config: BuildConfig,
         dump_bytecode_as_base64: bool,
+        network: Option<&str>,
     ) -> anyhow::Result<()> {
         // find manifest file directory from a given path or (if missing) from current dir
+        let package = build_with_published_deps(rerooted_path, build_config, network)?;
+        Self::output(&package, dump_bytecode_as_base64)
+    }
+
+    fn output(
+        package: &CompiledPackageWithPublishedDeps,
+        dump_bytecode_as_base64: bool,
+    ) -> anyhow::Result<()> {
         if dump_bytecode_as_base64 {
-            let compiled_modules =
-                sui_framework::build_move_package_to_base64(rerooted_path, build_config)?;
+            let compiled_modules = package
+                .module_bytes()
+                .iter()
+                .map(Base64::encode)
+                .collect::<Vec<_>>();
             println!("{:?}", compiled_modules);
         } else {
-            sui_framework::build_and_verify_package(rerooted_path, build_config)?;
+            sui_framework::verify_modules(&package.modules)?;
         }
         Ok(())
     }
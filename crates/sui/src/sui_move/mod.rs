@@ -13,6 +13,7 @@ pub mod disassemble;
 pub mod new;
 pub mod prove;
 pub mod unit_test;
+pub mod verify_source;
 
 #[derive(Parser)]
 pub enum Command {
@@ -22,6 +23,7 @@ pub enum Command {
     New(new::New),
     Prove(prove::Prove),
     Test(unit_test::Test),
+    VerifySource(verify_source::VerifySource),
     CalibrateCosts(Calib),
 }
 #[derive(Parser)]
@@ -32,13 +34,13 @@ pub struct Calib {
     summarize: bool,
 }
 
-pub fn execute_move_command(
+pub async fn execute_move_command(
     package_path: Option<PathBuf>,
     build_config: BuildConfig,
     command: Command,
 ) -> anyhow::Result<()> {
     match command {
-        Command::Build(c) => c.execute(package_path, build_config),
+        Command::Build(c) => c.execute(package_path, build_config).await,
         Command::Coverage(c) => c.execute(package_path, build_config),
         Command::Disassemble(c) => c.execute(package_path, build_config),
         Command::New(c) => c.execute(package_path),
@@ -65,6 +67,7 @@ pub fn execute_move_command(
 
             Ok(())
         }
+        Command::VerifySource(c) => c.execute(package_path, build_config).await,
         Command::CalibrateCosts(c) => {
             sui_framework::cost_calib::run_calibration(c.runs, c.summarize);
             Ok(())
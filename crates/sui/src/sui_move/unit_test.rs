@@ -9,12 +9,21 @@ use move_cli::base::{
 };
 use move_package::BuildConfig;
 use move_unit_test::UnitTestingConfig;
-use std::path::PathBuf;
+use std::{fs, path::PathBuf};
+
+use crate::published_package::client_network;
 
 #[derive(Parser)]
 pub struct Test {
     #[clap(flatten)]
     pub test: test::Test,
+    /// Profile the gas used by the tests, writing it to the `--profile-output` file as folded
+    /// stacks for flamegraph tools
+    #[clap(long)]
+    pub profile: bool,
+    /// The file `--profile` writes the folded stacks to
+    #[clap(long, value_name = "FILE", default_value = "gas_profile.folded")]
+    pub profile_output: PathBuf,
 }
 impl Test {
     pub fn execute(
@@ -23,20 +32,39 @@ impl Test {
         build_config: BuildConfig,
         unit_test_config: UnitTestingConfig,
     ) -> anyhow::Result<UnitTestResult> {
+        // resolve the profile output before the working directory moves to the package
+        let profile_output = std::env::current_dir()?.join(&self.profile_output);
         // find manifest file directory from a given path or (if missing) from current dir
         let rerooted_path = base::reroot_path(path)?;
         // pre build for Sui-specific verifications
         let dump_bytecode_as_base64 = false;
+        let network = client_network(None)?;
         build::Build::execute_internal(
             &rerooted_path,
             build_config.clone(),
             dump_bytecode_as_base64,
+            network.as_deref(),
         )?;
-        sui_framework::run_move_unit_tests(
+        let filter = unit_test_config.filter.clone();
+        let result = sui_framework::run_move_unit_tests(
             &rerooted_path,
-            build_config,
+            build_config.clone(),
             Some(unit_test_config),
             self.test.compute_coverage,
-        )
+        )?;
+        if self.profile {
+            let profile = sui_framework::test_profiler::profile_move_unit_tests(
+                &rerooted_path,
+                build_config,
+                filter.as_deref(),
+            )?;
+            fs::write(&profile_output, profile.to_folded_stacks())?;
+            println!(
+                "Wrote the gas profile of the tests ({} gas) to {}",
+                profile.total(),
+                profile_output.display()
+            );
+        }
+        Ok(result)
     }
 }
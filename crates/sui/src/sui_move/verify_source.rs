@@ -0,0 +1,156 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{anyhow, bail};
+use clap::Parser;
+use move_binary_format::CompiledModule;
+use move_cli::base;
+use move_core_types::language_storage::ModuleId;
+use move_package::BuildConfig;
+use std::{
+    collections::BTreeMap,
+    fmt::{self, Display, Formatter},
+    path::{Path, PathBuf},
+};
+use sui_adapter::bytecode_rewriter::ModuleHandleRewriter;
+use sui_config::{sui_config_dir, SUI_CLIENT_CONFIG};
+use sui_types::base_types::ObjectID;
+
+use crate::client_commands::{read_package_module_map, WalletContext};
+use crate::published_package::{build_with_published_deps, network_name};
+
+#[derive(Parser)]
+pub struct VerifySource {
+    /// ID of the published package to verify the sources against.
+    #[clap(long)]
+    pub package_id: ObjectID,
+    /// Sets the file storing the state of our user accounts, to find the network to read the
+    /// package from.
+    #[clap(long = "client.config")]
+    pub config: Option<PathBuf>,
+}
+
+impl VerifySource {
+    pub async fn execute(
+        self,
+        path: Option<PathBuf>,
+        build_config: BuildConfig,
+    ) -> anyhow::Result<()> {
+        let rerooted_path = base::reroot_path(path)?;
+        let config = match self.config {
+            Some(config) => config,
+            None => sui_config_dir()?.join(SUI_CLIENT_CONFIG),
+        };
+        let context = WalletContext::new(&config).await?;
+
+        let on_chain = read_package_module_map(&context, self.package_id).await?;
+        let network = network_name(&context.config.client_type);
+        let report = verify_source(&rerooted_path, build_config, &network, &on_chain)?;
+        print!("{report}");
+        if !report.is_verified() {
+            bail!(
+                "The sources at {} do not match package {}",
+                rerooted_path.display(),
+                self.package_id
+            );
+        }
+        println!(
+            "The sources at {} match package {}",
+            rerooted_path.display(),
+            self.package_id
+        );
+        Ok(())
+    }
+}
+
+/// The result of comparing a module built from source with the module published on chain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ModuleVerification {
+    /// The bytecode is identical.
+    Verified,
+    /// The bytecode differs.
+    Mismatch,
+    /// The module is in the sources but not in the published package.
+    MissingOnChain,
+    /// The module is in the published package but not in the sources.
+    MissingFromSources,
+}
+
+/// The comparison of each module of a package built from source with the published package.
+#[derive(Debug)]
+pub struct SourceVerificationReport {
+    pub modules: BTreeMap<String, ModuleVerification>,
+}
+
+impl SourceVerificationReport {
+    /// Whether the sources compile to exactly the published modules.
+    pub fn is_verified(&self) -> bool {
+        self.modules
+            .values()
+            .all(|v| *v == ModuleVerification::Verified)
+    }
+}
+
+impl Display for SourceVerificationReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for (name, verification) in &self.modules {
+            let status = match verification {
+                ModuleVerification::Verified => "verified",
+                ModuleVerification::Mismatch => "bytecode differs from the published module",
+                ModuleVerification::MissingOnChain => "not in the published package",
+                ModuleVerification::MissingFromSources => "not in the sources",
+            };
+            writeln!(f, "{name}: {status}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Build the package at `path` and compare the bytecode of each of its modules byte for byte with
+/// `on_chain`, the serialized modules of a package published on `network` keyed by name. The
+/// package must be built with the same `build_config` it was published with.
+pub fn verify_source(
+    path: &Path,
+    build_config: BuildConfig,
+    network: &str,
+    on_chain: &BTreeMap<String, Vec<u8>>,
+) -> anyhow::Result<SourceVerificationReport> {
+    // Modules are published at address 0x0 and get the package's address on publish, which the
+    // modules of upgraded packages keep from the first version.
+    let package_address = match on_chain.values().next() {
+        Some(bytes) => *CompiledModule::deserialize(bytes)?.self_id().address(),
+        None => return Err(anyhow!("The published package has no modules")),
+    };
+
+    let mut modules = build_with_published_deps(path, build_config, Some(network))?.modules;
+    let sub_map = modules
+        .iter()
+        .map(|m| {
+            let id = m.self_id();
+            let published_id = ModuleId::new(package_address, id.name().to_owned());
+            (id, published_id)
+        })
+        .collect();
+    let rewriter = ModuleHandleRewriter::new(sub_map)?;
+
+    let mut report = BTreeMap::new();
+    for module in &mut modules {
+        rewriter.sub_module_ids(module);
+        let mut bytes = Vec::new();
+        module.serialize(&mut bytes)?;
+        let name = module.self_id().name().to_string();
+        let verification = match on_chain.get(&name) {
+            Some(published) if *published == bytes => ModuleVerification::Verified,
+            Some(_) => ModuleVerification::Mismatch,
+            None => ModuleVerification::MissingOnChain,
+        };
+        report.insert(name, verification);
+    }
+    for name in on_chain.keys() {
+        report
+            .entry(name.clone())
+            .or_insert(ModuleVerification::MissingFromSources);
+    }
+
+    Ok(SourceVerificationReport { modules: report })
+}
@@ -1,18 +1,38 @@
 // Copyright (c) 2022, Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use std::{fmt::Write, fs::read_dir, path::PathBuf, str, time::Duration};
+use std::{
+    collections::BTreeMap,
+    fmt::Write,
+    fs::read_dir,
+    path::{Path, PathBuf},
+    str,
+    time::Duration,
+};
 
 use anyhow::anyhow;
+use move_core_types::language_storage::ModuleId;
 use move_package::BuildConfig;
 use serde_json::json;
 
 use sui::client_commands::SwitchResponse;
 use sui::{
-    client_commands::{SuiClientCommandResult, SuiClientCommands, WalletContext},
+    client_commands::{
+        check_published_dependencies, read_package_module_map, SuiClientCommandResult,
+        SuiClientCommands, WalletContext,
+    },
     config::SuiClientConfig,
+    keytool::read_authority_keypair_from_file,
+    published_package::{
+        build_with_published_deps, network_name, PublishedPackage, PUBLISHED_FILE_NAME,
+    },
     sui_commands::SuiCommand,
+    sui_move::verify_source::{verify_source, ModuleVerification},
+    validator_commands::{
+        SuiValidatorCommand, SuiValidatorCommandResponse, ValidatorState, PROTOCOL_KEY_FILE,
+    },
 };
+use sui_adapter::bytecode_rewriter::ModuleHandleRewriter;
 use sui_config::gateway::GatewayConfig;
 use sui_config::genesis_config::{AccountConfig, GenesisConfig, ObjectConfig};
 use sui_config::{
@@ -33,6 +53,40 @@ use test_utils::network::{setup_network_and_wallet, start_test_network};
 
 const TEST_DATA_DIR: &str = "src/unit_tests/data/";
 
+/// Copy the test package `name` into `dir`, so that publishing it records it in the copy rather
+/// than in the test data, with its Sui dependency pointing at the framework sources.
+fn copy_test_package(name: &str, dir: &Path) -> Result<PathBuf, anyhow::Error> {
+    let package_path = dir.join(name);
+    copy_dir(&PathBuf::from(TEST_DATA_DIR).join(name), &package_path)?;
+
+    let framework_path =
+        std::fs::canonicalize(PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../sui-framework"))?;
+    let manifest_path = package_path.join("Move.toml");
+    let manifest = std::fs::read_to_string(&manifest_path)?.replace(
+        r#"local = "../../../../../sui-framework""#,
+        &format!("local = {:?}", framework_path.display().to_string()),
+    );
+    std::fs::write(&manifest_path, manifest)?;
+    Ok(package_path)
+}
+
+/// Copy the sources of the package at `from` to `to`, leaving out its build and publish outputs.
+fn copy_dir(from: &Path, to: &Path) -> Result<(), anyhow::Error> {
+    std::fs::create_dir_all(to)?;
+    for entry in read_dir(from)? {
+        let entry = entry?;
+        let name = entry.file_name();
+        if entry.file_type()?.is_dir() {
+            if name != "build" {
+                copy_dir(&entry.path(), &to.join(&name))?;
+            }
+        } else if name != PUBLISHED_FILE_NAME {
+            std::fs::copy(entry.path(), to.join(&name))?;
+        }
+    }
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_genesis() -> Result<(), anyhow::Error> {
     let temp_dir = tempfile::tempdir()?;
@@ -330,8 +384,8 @@ async fn test_move_call_args_linter_command() -> Result<(), anyhow::Error> {
         .get_objects_owned_by_address(address1)
         .await?;
     let gas_obj_id = object_refs.first().unwrap().object_id;
-    let mut package_path = PathBuf::from(TEST_DATA_DIR);
-    package_path.push("move_call_args_linter");
+    let temp_dir = tempfile::tempdir()?;
+    let package_path = copy_test_package("move_call_args_linter", temp_dir.path())?;
     let build_config = BuildConfig::default();
     let resp = SuiClientCommands::Publish {
         package_path,
@@ -393,6 +447,7 @@ async fn test_move_call_args_linter_command() -> Result<(), anyhow::Error> {
         args,
         gas: None,
         gas_budget: 1000,
+        profile_output: None,
     }
     .execute(&mut context)
     .await?;
@@ -433,6 +488,7 @@ async fn test_move_call_args_linter_command() -> Result<(), anyhow::Error> {
         args: args.to_vec(),
         gas: Some(gas),
         gas_budget: 1000,
+        profile_output: None,
     }
     .execute(&mut context)
     .await;
@@ -457,6 +513,7 @@ async fn test_move_call_args_linter_command() -> Result<(), anyhow::Error> {
         args: args.to_vec(),
         gas: Some(gas),
         gas_budget: 1000,
+        profile_output: None,
     }
     .execute(&mut context)
     .await;
@@ -482,6 +539,7 @@ async fn test_move_call_args_linter_command() -> Result<(), anyhow::Error> {
         args: args.to_vec(),
         gas: Some(gas),
         gas_budget: 1000,
+        profile_output: None,
     }
     .execute(&mut context)
     .await?;
@@ -504,8 +562,8 @@ async fn test_package_publish_command() -> Result<(), anyhow::Error> {
     let gas_obj_id = object_refs.first().unwrap().object_id;
 
     // Provide path to well formed package sources
-    let mut package_path = PathBuf::from(TEST_DATA_DIR);
-    package_path.push("dummy_modules_publish");
+    let temp_dir = tempfile::tempdir()?;
+    let package_path = copy_test_package("dummy_modules_publish", temp_dir.path())?;
     let build_config = BuildConfig::default();
     let resp = SuiClientCommands::Publish {
         package_path,
@@ -553,6 +611,269 @@ async fn test_package_publish_command() -> Result<(), anyhow::Error> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_publish_with_published_dependency() -> Result<(), anyhow::Error> {
+    let (_network, mut context, address) = setup_network_and_wallet().await?;
+    let gas_obj_id = context
+        .client
+        .read_api()
+        .get_objects_owned_by_address(address)
+        .await?
+        .first()
+        .unwrap()
+        .object_id;
+
+    let temp_dir = tempfile::tempdir()?;
+    let dependency_path = copy_test_package("published_dependency", temp_dir.path())?;
+    let dependent_path = copy_test_package("published_dependent", temp_dir.path())?;
+    let network = network_name(&context.config.client_type);
+
+    // The dependency has not been published yet.
+    let err = build_with_published_deps(&dependent_path, BuildConfig::default(), Some(&network))
+        .err()
+        .unwrap();
+    assert!(err.to_string().contains("has not been published"));
+
+    let resp = SuiClientCommands::Publish {
+        package_path: dependency_path.clone(),
+        build_config: BuildConfig::default(),
+        gas: Some(gas_obj_id),
+        gas_budget: 1000,
+    }
+    .execute(&mut context)
+    .await?;
+    let package = if let SuiClientCommandResult::Publish(response) = resp {
+        response.parsed_data.unwrap().to_publish_response()?.package
+    } else {
+        unreachable!("Invalid response");
+    };
+    let published = PublishedPackage::read(&dependency_path, &network)?.unwrap();
+    assert_eq!(published.package_id, package.object_id);
+    assert_eq!(published.version, package.version);
+    assert!(PublishedPackage::read(&dependency_path, "another network")?.is_none());
+
+    // The dependency is only resolved for the network it was published on.
+    let err = build_with_published_deps(
+        &dependent_path,
+        BuildConfig::default(),
+        Some("another network"),
+    )
+    .err()
+    .unwrap();
+    assert!(err
+        .to_string()
+        .contains("has not been published on another network"));
+    let err = build_with_published_deps(&dependent_path, BuildConfig::default(), None)
+        .err()
+        .unwrap();
+    assert!(err
+        .to_string()
+        .contains("can only be resolved for a network"));
+
+    // References to the dependency resolve to where it was published.
+    let built = build_with_published_deps(&dependent_path, BuildConfig::default(), Some(&network))?;
+    assert_eq!(built.published_dependencies.len(), 1);
+    assert!(built.modules[0]
+        .address_identifiers
+        .contains(&package.object_id.into()));
+
+    let resp = SuiClientCommands::Publish {
+        package_path: dependent_path.clone(),
+        build_config: BuildConfig::default(),
+        gas: Some(gas_obj_id),
+        gas_budget: 1000,
+    }
+    .execute(&mut context)
+    .await?;
+    assert!(matches!(resp, SuiClientCommandResult::Publish(_)));
+
+    // Sources that do not match the published bytecode fail the build.
+    PublishedPackage {
+        digest: hex::encode([0; 32]),
+        ..published
+    }
+    .write(&dependency_path, &network)?;
+    let err = build_with_published_deps(&dependent_path, BuildConfig::default(), Some(&network))
+        .err()
+        .unwrap();
+    assert!(err.to_string().contains("do not compile to the bytecode"));
+
+    // Nothing was written into the test data.
+    for name in ["published_dependency", "published_dependent"] {
+        assert!(!PathBuf::from(TEST_DATA_DIR)
+            .join(name)
+            .join(PUBLISHED_FILE_NAME)
+            .exists());
+    }
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_published_dependency_checked_against_chain() -> Result<(), anyhow::Error> {
+    let (_network, mut context, address) = setup_network_and_wallet().await?;
+    let gas_obj_id = context
+        .client
+        .read_api()
+        .get_objects_owned_by_address(address)
+        .await?
+        .first()
+        .unwrap()
+        .object_id;
+
+    let temp_dir = tempfile::tempdir()?;
+    let dependency_path = copy_test_package("published_dependency", temp_dir.path())?;
+    let dependent_path = copy_test_package("published_dependent", temp_dir.path())?;
+    let network = network_name(&context.config.client_type);
+
+    // Publish modified sources of the dependency.
+    let modified_dir = tempfile::tempdir()?;
+    let modified_path = copy_test_package("published_dependency", modified_dir.path())?;
+    let source_path = modified_path.join("sources").join("counter.move");
+    let source = std::fs::read_to_string(&source_path)?;
+    std::fs::write(
+        &source_path,
+        source.replace("counter.value + 1", "counter.value + 2"),
+    )?;
+    let resp = SuiClientCommands::Publish {
+        package_path: modified_path,
+        build_config: BuildConfig::default(),
+        gas: Some(gas_obj_id),
+        gas_budget: 1000,
+    }
+    .execute(&mut context)
+    .await?;
+    let package = if let SuiClientCommandResult::Publish(response) = resp {
+        response.parsed_data.unwrap().to_publish_response()?.package
+    } else {
+        unreachable!("Invalid response");
+    };
+
+    // Record the unmodified sources as published there.
+    let mut modules =
+        build_with_published_deps(&dependency_path, BuildConfig::default(), Some(&network))?
+            .modules;
+    let rewriter = ModuleHandleRewriter::new(
+        modules
+            .iter()
+            .map(|m| {
+                let id = m.self_id();
+                let published_id = ModuleId::new(package.object_id.into(), id.name().to_owned());
+                (id, published_id)
+            })
+            .collect(),
+    )?;
+    let mut module_map = BTreeMap::new();
+    for module in &mut modules {
+        rewriter.sub_module_ids(module);
+        let mut bytes = Vec::new();
+        module.serialize(&mut bytes)?;
+        module_map.insert(module.self_id().name().to_string(), bytes);
+    }
+    PublishedPackage::new(package.object_id, package.version, &module_map)
+        .write(&dependency_path, &network)?;
+
+    // The sources agree with the record, so the dependent builds offline...
+    let built = build_with_published_deps(&dependent_path, BuildConfig::default(), Some(&network))?;
+    assert_eq!(built.published_dependencies.len(), 1);
+
+    // ...but the chain does not, which the build and publish commands check.
+    let err = check_published_dependencies(&context, &built.published_dependencies)
+        .await
+        .err()
+        .unwrap();
+    assert!(err
+        .to_string()
+        .contains("differs from the bytecode compiled from dependency"));
+
+    let err = SuiClientCommands::Publish {
+        package_path: dependent_path,
+        build_config: BuildConfig::default(),
+        gas: Some(gas_obj_id),
+        gas_budget: 1000,
+    }
+    .execute(&mut context)
+    .await
+    .err()
+    .unwrap();
+    assert!(err
+        .to_string()
+        .contains("differs from the bytecode compiled from dependency"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_verify_source() -> Result<(), anyhow::Error> {
+    let (_network, mut context, address) = setup_network_and_wallet().await?;
+    let gas_obj_id = context
+        .client
+        .read_api()
+        .get_objects_owned_by_address(address)
+        .await?
+        .first()
+        .unwrap()
+        .object_id;
+
+    let temp_dir = tempfile::tempdir()?;
+    let package_path = copy_test_package("dummy_modules_publish", temp_dir.path())?;
+    let resp = SuiClientCommands::Publish {
+        package_path: package_path.clone(),
+        build_config: BuildConfig::default(),
+        gas: Some(gas_obj_id),
+        gas_budget: 1000,
+    }
+    .execute(&mut context)
+    .await?;
+    let package = if let SuiClientCommandResult::Publish(response) = resp {
+        response.parsed_data.unwrap().to_publish_response()?.package
+    } else {
+        unreachable!("Invalid response");
+    };
+    let on_chain = read_package_module_map(&context, package.object_id).await?;
+
+    let network = network_name(&context.config.client_type);
+    let report = verify_source(&package_path, BuildConfig::default(), &network, &on_chain)?;
+    assert!(report.is_verified());
+    assert_eq!(
+        report.modules.get("trusted_coin"),
+        Some(&ModuleVerification::Verified)
+    );
+
+    // Modified sources of the package do not match.
+    let modified_dir = tempfile::tempdir()?;
+    let modified_path = copy_test_package("dummy_modules_publish", modified_dir.path())?;
+    let source_path = modified_path.join("sources").join("trusted_coin.move");
+    let source = std::fs::read_to_string(&source_path)?;
+    std::fs::write(
+        &source_path,
+        source.replace(
+            "coin::mint<TRUSTED_COIN>(treasury_cap, amount, ctx)",
+            "coin::mint<TRUSTED_COIN>(treasury_cap, amount * 2, ctx)",
+        ),
+    )?;
+    let report = verify_source(&modified_path, BuildConfig::default(), &network, &on_chain)?;
+    assert!(!report.is_verified());
+    assert_eq!(
+        report.modules.get("trusted_coin"),
+        Some(&ModuleVerification::Mismatch)
+    );
+
+    // The sources of another package do not match.
+    let other_path = PathBuf::from(TEST_DATA_DIR).join("move_call_args_linter");
+    let report = verify_source(&other_path, BuildConfig::default(), &network, &on_chain)?;
+    assert!(!report.is_verified());
+    assert_eq!(
+        report.modules.get("trusted_coin"),
+        Some(&ModuleVerification::MissingFromSources)
+    );
+    assert_eq!(
+        report.modules.get("object_basics"),
+        Some(&ModuleVerification::MissingOnChain)
+    );
+
+    Ok(())
+}
+
 #[allow(clippy::assertions_on_constants)]
 #[tokio::test]
 async fn test_native_transfer() -> Result<(), anyhow::Error> {
@@ -1141,3 +1462,197 @@ async fn test_signature_flag() -> Result<(), anyhow::Error> {
     assert!(res.is_err());
     Ok(())
 }
+
+#[tokio::test]
+async fn test_delegation_commands() -> Result<(), anyhow::Error> {
+    let (network, mut context, address) = setup_network_and_wallet().await?;
+    let validator = network.config().validator_set()[0].sui_address();
+
+    // A coin of exactly the delegated amount is split off one of the gas coins.
+    let resp = SuiClientCommands::Delegate {
+        validator,
+        coin: None,
+        amount: Some(1000),
+        gas: None,
+        gas_budget: 1000,
+    }
+    .execute(&mut context)
+    .await?;
+    assert!(matches!(resp, SuiClientCommandResult::Delegate(..)));
+
+    let resp = SuiClientCommands::Stakes {
+        address: Some(address),
+    }
+    .execute(&mut context)
+    .await?;
+    let delegation = if let SuiClientCommandResult::Stakes(r) = resp {
+        assert!(r.stakes.is_empty());
+        assert_eq!(r.delegations.len(), 1);
+        r.delegations[0].clone()
+    } else {
+        panic!("Command failed")
+    };
+    assert_eq!(delegation.validator, validator);
+    assert_eq!(delegation.delegate_amount, 1000);
+    assert!(delegation.active);
+
+    SuiClientCommands::Undelegate {
+        delegation: delegation.object_id,
+        gas: None,
+        gas_budget: 1000,
+    }
+    .execute(&mut context)
+    .await?;
+
+    let resp = SuiClientCommands::Stakes {
+        address: Some(address),
+    }
+    .execute(&mut context)
+    .await?;
+    if let SuiClientCommandResult::Stakes(r) = resp {
+        assert_eq!(r.delegations.len(), 1);
+        assert!(!r.delegations[0].active);
+    } else {
+        panic!("Command failed")
+    };
+
+    // Nothing can be claimed before an epoch has passed.
+    assert!(SuiClientCommands::ClaimRewards {
+        delegation: delegation.object_id,
+        gas: None,
+        gas_budget: 1000,
+    }
+    .execute(&mut context)
+    .await
+    .is_err());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_validator_commands() -> Result<(), anyhow::Error> {
+    let (network, mut context, address) = setup_network_and_wallet().await?;
+    let validator = network.config().validator_set()[0].sui_address();
+    let temp_dir = tempfile::tempdir()?;
+
+    let resp = SuiValidatorCommand::MakeValidatorKeys {
+        dir: temp_dir.path().to_path_buf(),
+    }
+    .execute(&mut context)
+    .await?;
+    assert!(
+        matches!(resp, SuiValidatorCommandResponse::MakeValidatorKeys(files) if files.len() == 3)
+    );
+    read_authority_keypair_from_file(temp_dir.path().join(PROTOCOL_KEY_FILE))?;
+
+    // Existing keys are never overwritten.
+    assert!(SuiValidatorCommand::MakeValidatorKeys {
+        dir: temp_dir.path().to_path_buf(),
+    }
+    .execute(&mut context)
+    .await
+    .is_err());
+
+    let resp = SuiValidatorCommand::Status {
+        address: Some(validator),
+    }
+    .execute(&mut context)
+    .await?;
+    if let SuiValidatorCommandResponse::Status(status) = resp {
+        assert_eq!(status.state, ValidatorState::Active);
+        assert_eq!(status.current.unwrap().metadata.sui_address, validator);
+        assert!(status.next_epoch.is_some());
+    } else {
+        panic!("Command failed")
+    }
+
+    let resp = SuiValidatorCommand::Status {
+        address: Some(address),
+    }
+    .execute(&mut context)
+    .await?;
+    if let SuiValidatorCommandResponse::Status(status) = resp {
+        assert_eq!(status.state, ValidatorState::Inactive);
+        assert!(status.current.is_none());
+        assert!(status.next_epoch.is_none());
+    } else {
+        panic!("Command failed")
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_join_committee_command() -> Result<(), anyhow::Error> {
+    // The stake must be at least the genesis minimum validator stake.
+    let min_stake = 100_000_000_000_000;
+    let stake_coin = ObjectID::random();
+    let gas = ObjectID::random();
+    let mut config = GenesisConfig::for_local_testing();
+    config.accounts.clear();
+    config.accounts.push(AccountConfig {
+        address: None,
+        gas_objects: vec![
+            ObjectConfig {
+                object_id: stake_coin,
+                gas_value: 2 * min_stake,
+            },
+            ObjectConfig {
+                object_id: gas,
+                gas_value: 100_000_000,
+            },
+        ],
+        gas_object_ranges: None,
+    });
+    let network = start_test_network(Some(config)).await?;
+    let mut context = WalletContext::new(&network.dir().join(SUI_CLIENT_CONFIG)).await?;
+    let address = context.active_address()?;
+    SuiClientCommands::SyncClientState {
+        address: Some(address),
+    }
+    .execute(&mut context)
+    .await?;
+
+    let key_dir = tempfile::tempdir()?;
+    SuiValidatorCommand::MakeValidatorKeys {
+        dir: key_dir.path().to_path_buf(),
+    }
+    .execute(&mut context)
+    .await?;
+
+    let join = |stake| SuiValidatorCommand::JoinCommittee {
+        name: "new-validator".to_string(),
+        key_dir: key_dir.path().to_path_buf(),
+        network_address: "/dns/localhost/tcp/8080/http".parse().unwrap(),
+        gas_price: 1,
+        coin: Some(stake_coin),
+        stake: Some(stake),
+        gas: Some(gas),
+        gas_budget: 10000,
+    };
+
+    // A stake below the minimum is rejected by the system.
+    assert!(join(min_stake - 1).execute(&mut context).await.is_err());
+
+    // The requested amount is split off the coin and staked.
+    let resp = join(min_stake).execute(&mut context).await?;
+    if let SuiValidatorCommandResponse::JoinCommittee(_, effects) = resp {
+        assert!(effects.status.is_ok());
+    } else {
+        panic!("Command failed")
+    }
+    // A validator cannot request to join twice.
+    assert!(join(min_stake).execute(&mut context).await.is_err());
+
+    let resp = SuiValidatorCommand::Status { address: None }
+        .execute(&mut context)
+        .await?;
+    if let SuiValidatorCommandResponse::Status(status) = resp {
+        assert_eq!(status.state, ValidatorState::Pending);
+        assert_eq!(status.current.unwrap().metadata.name, "new-validator");
+        assert_eq!(status.next_epoch.unwrap().next_epoch_stake, min_stake);
+    } else {
+        panic!("Command failed")
+    }
+
+    Ok(())
+}
@@ -0,0 +1,20 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+module published_dependency::counter {
+    use sui::object::{Self, UID};
+    use sui::tx_context::TxContext;
+
+    struct Counter has key, store {
+        id: UID,
+        value: u64,
+    }
+
+    public fun new(ctx: &mut TxContext): Counter {
+        Counter { id: object::new(ctx), value: 0 }
+    }
+
+    public fun increment(counter: &mut Counter) {
+        counter.value = counter.value + 1;
+    }
+}
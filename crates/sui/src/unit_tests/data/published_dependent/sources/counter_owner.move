@@ -0,0 +1,14 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+module published_dependent::counter_owner {
+    use published_dependency::counter;
+    use sui::transfer;
+    use sui::tx_context::{Self, TxContext};
+
+    public entry fun create(ctx: &mut TxContext) {
+        let counter = counter::new(ctx);
+        counter::increment(&mut counter);
+        transfer::transfer(counter, tx_context::sender(ctx))
+    }
+}
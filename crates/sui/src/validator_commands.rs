@@ -0,0 +1,410 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::fmt::{Debug, Display, Formatter, Write};
+use std::fs;
+use std::path::PathBuf;
+
+use anyhow::anyhow;
+use clap::*;
+use move_core_types::account_address::AccountAddress;
+use multiaddr::Multiaddr;
+use serde::Serialize;
+use serde_json::json;
+use tracing::info;
+
+use sui_json::SuiJsonValue;
+use sui_json_rpc_types::{
+    SuiCertifiedTransaction, SuiTransactionEffects, SuiValidator, SuiValidatorMetadata,
+};
+use sui_types::base_types::{ObjectID, SuiAddress};
+use sui_types::crypto::{
+    generate_proof_of_possession, get_key_pair, AuthorityKeyPair, KeypairTraits, SuiKeyPair,
+};
+use sui_types::sui_system_state::{
+    SuiSystemState, REQUEST_ADD_VALIDATOR_FUNCTION_NAME, REQUEST_REMOVE_VALIDATOR_FUNCTION_NAME,
+    REQUEST_SET_GAS_PRICE_FUNCTION_NAME,
+};
+use sui_types::SUI_SYSTEM_STATE_OBJECT_ID;
+
+use crate::client_commands::{call_sui_system, read_move_object, select_sui_coin, WalletContext};
+use crate::keytool::{
+    read_authority_keypair_from_file, read_keypair_from_file, write_keypair_to_file,
+};
+
+pub const PROTOCOL_KEY_FILE: &str = "protocol.key";
+pub const WORKER_KEY_FILE: &str = "worker.key";
+pub const NETWORK_KEY_FILE: &str = "network.key";
+
+/// Commands for validators joining or leaving a running network. The validator account is the
+/// active address of the client, which must hold the stake and pay for gas.
+#[derive(Parser)]
+#[clap(rename_all = "kebab-case")]
+pub enum SuiValidatorCommand {
+    /// Generate the protocol, worker and network keys of a new validator.
+    #[clap(name = "make-validator-keys")]
+    MakeValidatorKeys {
+        /// Directory to write the key files to, it must not contain keys already.
+        #[clap(long, parse(from_os_str), default_value = ".")]
+        dir: PathBuf,
+    },
+
+    /// Request to join the committee at the next epoch, staking SUI from the active address.
+    #[clap(name = "join-committee")]
+    JoinCommittee {
+        /// A unique human-readable name of the validator
+        #[clap(long)]
+        name: String,
+
+        /// Directory containing the key files generated by `make-validator-keys`
+        #[clap(long, parse(from_os_str), default_value = ".")]
+        key_dir: PathBuf,
+
+        /// The network address other validators and clients reach the validator at
+        #[clap(long)]
+        network_address: Multiaddr,
+
+        /// The gas price quote of the validator
+        #[clap(long)]
+        gas_price: u64,
+
+        /// SUI coin to stake, in 20 bytes Hex string.
+        /// If not provided, a coin with at least `stake` value will be selected
+        #[clap(long)]
+        coin: Option<ObjectID>,
+
+        /// The amount to stake. If not specified, the entire coin is staked.
+        #[clap(long)]
+        stake: Option<u64>,
+
+        /// ID of the gas object for gas payment, in 20 bytes Hex string
+        /// If not provided, a gas object with at least gas_budget value will be selected
+        #[clap(long)]
+        gas: Option<ObjectID>,
+
+        /// Gas budget for this call
+        #[clap(long)]
+        gas_budget: u64,
+    },
+
+    /// Request to leave the committee at the end of the epoch.
+    #[clap(name = "leave-committee")]
+    LeaveCommittee {
+        /// ID of the gas object for gas payment, in 20 bytes Hex string
+        /// If not provided, a gas object with at least gas_budget value will be selected
+        #[clap(long)]
+        gas: Option<ObjectID>,
+
+        /// Gas budget for this call
+        #[clap(long)]
+        gas_budget: u64,
+    },
+
+    /// Update the gas price quote of the validator, effective at the next epoch.
+    #[clap(name = "set-gas-price")]
+    SetGasPrice {
+        #[clap(long)]
+        gas_price: u64,
+
+        /// ID of the gas object for gas payment, in 20 bytes Hex string
+        /// If not provided, a gas object with at least gas_budget value will be selected
+        #[clap(long)]
+        gas: Option<ObjectID>,
+
+        /// Gas budget for this call
+        #[clap(long)]
+        gas_budget: u64,
+    },
+
+    /// Show the current and next epoch status of a validator.
+    #[clap(name = "status")]
+    Status {
+        /// Address of the validator, the active address if not specified
+        #[clap(long)]
+        address: Option<SuiAddress>,
+    },
+}
+
+impl SuiValidatorCommand {
+    pub async fn execute(
+        self,
+        context: &mut WalletContext,
+    ) -> Result<SuiValidatorCommandResponse, anyhow::Error> {
+        Ok(match self {
+            SuiValidatorCommand::MakeValidatorKeys { dir } => {
+                fs::create_dir_all(&dir)?;
+                let protocol_key_file = dir.join(PROTOCOL_KEY_FILE);
+                let worker_key_file = dir.join(WORKER_KEY_FILE);
+                let network_key_file = dir.join(NETWORK_KEY_FILE);
+                for file in [&protocol_key_file, &worker_key_file, &network_key_file] {
+                    if file.exists() {
+                        return Err(anyhow!("Key file {:?} already exists", file));
+                    }
+                }
+
+                let (_, protocol_key): (_, AuthorityKeyPair) = get_key_pair();
+                let (_, worker_key): (_, AuthorityKeyPair) = get_key_pair();
+                let (_, network_key): (_, AuthorityKeyPair) = get_key_pair();
+                write_keypair_to_file(
+                    &SuiKeyPair::Ed25519SuiKeyPair(protocol_key),
+                    &protocol_key_file,
+                )?;
+                write_keypair_to_file(
+                    &SuiKeyPair::Ed25519SuiKeyPair(worker_key),
+                    &worker_key_file,
+                )?;
+                write_keypair_to_file(
+                    &SuiKeyPair::Ed25519SuiKeyPair(network_key),
+                    &network_key_file,
+                )?;
+                info!("Validator keys written to {:?}", dir);
+
+                SuiValidatorCommandResponse::MakeValidatorKeys(vec![
+                    protocol_key_file,
+                    worker_key_file,
+                    network_key_file,
+                ])
+            }
+
+            SuiValidatorCommand::JoinCommittee {
+                name,
+                key_dir,
+                network_address,
+                gas_price,
+                coin,
+                stake,
+                gas,
+                gas_budget,
+            } => {
+                let sender = context.active_address()?;
+                let protocol_key =
+                    read_authority_keypair_from_file(key_dir.join(PROTOCOL_KEY_FILE))?;
+                let network_key = read_keypair_from_file(key_dir.join(NETWORK_KEY_FILE))?;
+                // Binds the protocol key to the account that submits the request, see
+                // `validator::verify_proof_of_possession`.
+                let proof_of_possession = generate_proof_of_possession(&protocol_key, sender);
+
+                let coin = select_sui_coin(context, sender, coin, stake, gas, gas_budget).await?;
+                let (cert, effects) = call_sui_system(
+                    context,
+                    sender,
+                    REQUEST_ADD_VALIDATOR_FUNCTION_NAME,
+                    vec![
+                        bytes_arg(protocol_key.public().as_ref())?,
+                        bytes_arg(network_key.public().as_ref())?,
+                        bytes_arg(proof_of_possession.as_ref())?,
+                        bytes_arg(name.as_bytes())?,
+                        bytes_arg(&network_address.to_vec())?,
+                        SuiJsonValue::from_object_id(coin),
+                        SuiJsonValue::new(json!(gas_price.to_string()))?,
+                    ],
+                    gas,
+                    gas_budget,
+                )
+                .await?;
+                SuiValidatorCommandResponse::JoinCommittee(cert, effects)
+            }
+
+            SuiValidatorCommand::LeaveCommittee { gas, gas_budget } => {
+                let sender = context.active_address()?;
+                let (cert, effects) = call_sui_system(
+                    context,
+                    sender,
+                    REQUEST_REMOVE_VALIDATOR_FUNCTION_NAME,
+                    vec![],
+                    gas,
+                    gas_budget,
+                )
+                .await?;
+                SuiValidatorCommandResponse::LeaveCommittee(cert, effects)
+            }
+
+            SuiValidatorCommand::SetGasPrice {
+                gas_price,
+                gas,
+                gas_budget,
+            } => {
+                let sender = context.active_address()?;
+                let (cert, effects) = call_sui_system(
+                    context,
+                    sender,
+                    REQUEST_SET_GAS_PRICE_FUNCTION_NAME,
+                    vec![SuiJsonValue::new(json!(gas_price.to_string()))?],
+                    gas,
+                    gas_budget,
+                )
+                .await?;
+                SuiValidatorCommandResponse::SetGasPrice(cert, effects)
+            }
+
+            SuiValidatorCommand::Status { address } => {
+                let address = address.unwrap_or(context.active_address()?);
+                let system_state: SuiSystemState = read_move_object(
+                    context,
+                    SUI_SYSTEM_STATE_OBJECT_ID,
+                    &SuiSystemState::type_(),
+                )
+                .await?
+                .ok_or_else(|| anyhow!("Cannot read the Sui system state object"))?;
+                SuiValidatorCommandResponse::Status(ValidatorStatus::new(address, system_state))
+            }
+        })
+    }
+}
+
+/// A `vector<u8>` argument. Arrays of numbers are decoded as vectors by `SuiJsonValue`.
+fn bytes_arg(bytes: &[u8]) -> Result<SuiJsonValue, anyhow::Error> {
+    SuiJsonValue::new(json!(bytes))
+}
+
+#[derive(Serialize, Clone, Debug, Eq, PartialEq)]
+pub enum ValidatorState {
+    /// Not an active or pending validator.
+    Inactive,
+    /// Joins the committee at the end of the epoch.
+    Pending,
+    Active,
+    /// Leaves the committee at the end of the epoch.
+    Leaving,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct ValidatorStatus {
+    pub address: SuiAddress,
+    pub epoch: u64,
+    pub state: ValidatorState,
+    /// Set if the validator is active or pending.
+    pub current: Option<SuiValidator>,
+    /// Set if the validator is in the committee of the next epoch.
+    pub next_epoch: Option<SuiValidatorMetadata>,
+}
+
+impl ValidatorStatus {
+    fn new(address: SuiAddress, system_state: SuiSystemState) -> Self {
+        let validators = system_state.validators;
+        let is_address = |a: &AccountAddress| SuiAddress::from(ObjectID::from(*a)) == address;
+
+        let active = validators
+            .active_validators
+            .iter()
+            .position(|v| is_address(&v.metadata.sui_address));
+        let pending = validators
+            .pending_validators
+            .iter()
+            .find(|v| is_address(&v.metadata.sui_address));
+        let (state, current) = match (active, pending) {
+            (Some(index), _) => {
+                let leaving = validators.pending_removals.contains(&(index as u64));
+                (
+                    if leaving {
+                        ValidatorState::Leaving
+                    } else {
+                        ValidatorState::Active
+                    },
+                    Some(validators.active_validators[index].clone().into()),
+                )
+            }
+            (None, Some(validator)) => (ValidatorState::Pending, Some(validator.clone().into())),
+            (None, None) => (ValidatorState::Inactive, None),
+        };
+        let next_epoch = validators
+            .next_epoch_validators
+            .into_iter()
+            .find(|metadata| is_address(&metadata.sui_address))
+            .map(SuiValidatorMetadata::from);
+
+        Self {
+            address,
+            epoch: system_state.epoch,
+            state,
+            current,
+            next_epoch,
+        }
+    }
+}
+
+impl Display for ValidatorStatus {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut writer = String::new();
+        writeln!(writer, "Validator: {}", self.address)?;
+        writeln!(writer, "Epoch: {}", self.epoch)?;
+        writeln!(writer, "State: {:?}", self.state)?;
+        if let Some(current) = &self.current {
+            writeln!(writer, "Name: {}", current.metadata.name)?;
+            writeln!(writer, "Stake: {}", current.stake_amount)?;
+            writeln!(writer, "Delegation: {}", current.delegation)?;
+            writeln!(writer, "Delegators: {}", current.delegator_count)?;
+            writeln!(writer, "Gas price: {}", current.gas_price)?;
+        }
+        if let Some(next_epoch) = &self.next_epoch {
+            writeln!(writer, "Next epoch stake: {}", next_epoch.next_epoch_stake)?;
+            writeln!(
+                writer,
+                "Next epoch delegation: {}",
+                next_epoch.next_epoch_delegation
+            )?;
+            writeln!(
+                writer,
+                "Next epoch gas price: {}",
+                next_epoch.next_epoch_gas_price
+            )?;
+        } else {
+            writeln!(writer, "Not in the committee of the next epoch")?;
+        }
+        write!(f, "{}", writer)
+    }
+}
+
+#[derive(Serialize)]
+#[serde(untagged)]
+pub enum SuiValidatorCommandResponse {
+    MakeValidatorKeys(Vec<PathBuf>),
+    JoinCommittee(SuiCertifiedTransaction, SuiTransactionEffects),
+    LeaveCommittee(SuiCertifiedTransaction, SuiTransactionEffects),
+    SetGasPrice(SuiCertifiedTransaction, SuiTransactionEffects),
+    Status(ValidatorStatus),
+}
+
+impl Display for SuiValidatorCommandResponse {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut writer = String::new();
+        match self {
+            SuiValidatorCommandResponse::MakeValidatorKeys(files) => {
+                for file in files {
+                    writeln!(writer, "Generated {}", file.display())?;
+                }
+            }
+            SuiValidatorCommandResponse::JoinCommittee(cert, effects)
+            | SuiValidatorCommandResponse::LeaveCommittee(cert, effects)
+            | SuiValidatorCommandResponse::SetGasPrice(cert, effects) => {
+                writeln!(writer, "{}", cert)?;
+                write!(writer, "{}", effects)?;
+            }
+            SuiValidatorCommandResponse::Status(status) => {
+                write!(writer, "{}", status)?;
+            }
+        }
+        write!(f, "{}", writer.trim_end_matches('\n'))
+    }
+}
+
+impl Debug for SuiValidatorCommandResponse {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let string = serde_json::to_string_pretty(self);
+        let s = string.unwrap_or_else(|err| format!("{err}"));
+        write!(f, "{}", s)
+    }
+}
+
+impl SuiValidatorCommandResponse {
+    pub fn print(&self, pretty: bool) {
+        let line = if pretty {
+            format!("{self}")
+        } else {
+            format!("{:?}", self)
+        };
+        for line in line.lines() {
+            println!("{line}");
+        }
+    }
+}
@@ -1,9 +1,10 @@
 // Copyright (c) 2022, Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
+use prometheus::Registry;
 use std::collections::HashSet;
 use sui_core::{
     authority::AuthorityState, authority_aggregator::AuthorityAggregator,
-    authority_client::NetworkAuthorityClient,
+    authority_client::NetworkAuthorityClient, state_sync::StateSyncPeer,
 };
 use sui_node::SuiNode;
 use sui_sdk::crypto::KeystoreType;
@@ -11,7 +12,9 @@ use sui_types::{
     base_types::{ExecutionDigests, TransactionDigest},
     messages::{CallArg, ExecutionStatus, ObjectArg, Transaction},
 };
-use test_utils::transaction::{publish_counter_package, submit_shared_object_transaction};
+use test_utils::transaction::{
+    publish_counter_package, submit_shared_object_transaction, wait_for_all_txes,
+};
 use test_utils::{
     authority::{
         spawn_checkpoint_processes, spawn_test_authorities, test_authority_aggregator,
@@ -305,3 +308,128 @@ async fn checkpoint_with_shared_objects() {
 
     wait_for_advance_to_next_checkpoint(&handles, &transaction_digests).await;
 }
+
+#[tokio::test]
+async fn full_node_checkpoint_sync() {
+    telemetry_subscribers::init_for_testing();
+    // Make a few test transactions.
+    let total_transactions = 3;
+    let keys = KeystoreType::InMem(total_transactions).init().unwrap();
+    let (transactions, input_objects) = make_transactions_with_pre_genesis_objects(keys);
+    let transaction_digests: HashSet<_> = transactions.iter().map(|x| *x.digest()).collect();
+
+    // Spawn a quorum of authorities and let them certify a checkpoint.
+    let configs = test_authority_configs();
+    let handles = spawn_test_authorities(input_objects, &configs).await;
+    let aggregator = test_authority_aggregator(&configs, handles[0].state().epoch_store().clone());
+    spawn_checkpoint_processes(&aggregator, &handles).await;
+    execute_transactions(&aggregator, &transactions).await;
+    wait_for_advance_to_next_checkpoint(&handles, &transaction_digests).await;
+
+    // A full node in checkpoint sync mode executes the transactions of the certified checkpoint.
+    let mut config = configs.generate_fullnode_config();
+    config.enable_checkpoint_sync = true;
+    let node = SuiNode::start(&config, Registry::new()).await.unwrap();
+
+    wait_for_all_txes(
+        transaction_digests.iter().cloned().collect(),
+        node.state().clone(),
+    )
+    .await;
+
+    let sync_store = node.active().node_sync_state.store();
+    let (synced, _) = sync_store.latest_synced_checkpoint().unwrap().unwrap();
+    assert!(sync_store.get_pending_checkpoint(synced).unwrap().is_none());
+    assert!(transactions_in_checkpoint(&node.state()).is_superset(&transaction_digests));
+}
+
+#[tokio::test]
+async fn full_node_state_sync_from_peer() {
+    telemetry_subscribers::init_for_testing();
+    let total_transactions = 3;
+    let keys = KeystoreType::InMem(total_transactions).init().unwrap();
+    let (transactions, input_objects) = make_transactions_with_pre_genesis_objects(keys);
+    let transaction_digests: HashSet<_> = transactions.iter().map(|x| *x.digest()).collect();
+
+    let configs = test_authority_configs();
+    let handles = spawn_test_authorities(input_objects, &configs).await;
+    let aggregator = test_authority_aggregator(&configs, handles[0].state().epoch_store().clone());
+    spawn_checkpoint_processes(&aggregator, &handles).await;
+    execute_transactions(&aggregator, &transactions).await;
+    wait_for_advance_to_next_checkpoint(&handles, &transaction_digests).await;
+
+    // A first full node syncs from the validators.
+    let mut peer_config = configs.generate_fullnode_config();
+    peer_config.enable_checkpoint_sync = true;
+    let peer = SuiNode::start(&peer_config, Registry::new()).await.unwrap();
+    wait_for_all_txes(
+        transaction_digests.iter().cloned().collect(),
+        peer.state().clone(),
+    )
+    .await;
+
+    // A second full node syncs using the first one as its state sync peer.
+    let mut config = configs.generate_fullnode_config();
+    config.enable_checkpoint_sync = true;
+    config.state_sync_peers = vec![peer_config.network_address.clone()];
+    let registry = Registry::new();
+    let node = SuiNode::start(&config, registry.clone()).await.unwrap();
+    wait_for_all_txes(
+        transaction_digests.iter().cloned().collect(),
+        node.state().clone(),
+    )
+    .await;
+
+    assert!(transactions_in_checkpoint(&node.state()).is_superset(&transaction_digests));
+    // The checkpoints and transactions came from the peer rather than the validators.
+    assert!(counter_value(&registry, "node_sync_peer_checkpoints") > 0.0);
+    assert!(
+        counter_value(&registry, "node_sync_peer_transactions") >= transaction_digests.len() as f64
+    );
+
+    // The peer also serves the objects written by the checkpointed transactions, each checked
+    // against the reference in the verified effects.
+    let state_sync = StateSyncPeer::connect_lazy(&peer_config.network_address).unwrap();
+    let (synced, _) = peer
+        .active()
+        .node_sync_state
+        .store()
+        .latest_synced_checkpoint()
+        .unwrap()
+        .unwrap();
+    let mut num_objects = 0;
+    for seq in 0..=synced {
+        let (_, contents) = state_sync
+            .get_checkpoint(seq, &aggregator.committee)
+            .await
+            .unwrap();
+        for digests in contents.iter() {
+            let (_, effects) = state_sync
+                .get_transaction(digests, &aggregator.committee)
+                .await
+                .unwrap();
+            let objects = state_sync.get_written_objects(&effects).await.unwrap();
+            assert_eq!(objects.len(), effects.effects.all_mutated().count());
+            for object in objects {
+                let local = node
+                    .state()
+                    .database
+                    .get_object_by_key(&object.id(), object.version())
+                    .unwrap()
+                    .unwrap();
+                assert_eq!(local.digest(), object.digest());
+                num_objects += 1;
+            }
+        }
+    }
+    assert!(num_objects > 0);
+}
+
+fn counter_value(registry: &Registry, name: &str) -> f64 {
+    registry
+        .gather()
+        .iter()
+        .find(|family| family.get_name() == name)
+        .map(|family| family.get_metric()[0].get_counter().get_value())
+        .unwrap_or_default()
+}
@@ -11,11 +11,15 @@ use move_core_types::identifier::Identifier;
 use move_core_types::language_storage::ModuleId;
 use move_core_types::value::MoveStructLayout;
 use prometheus::Registry;
+use serde_json::json;
 use std::net::SocketAddr;
 use std::str::FromStr;
 use std::{collections::BTreeMap, sync::Arc};
+use sui_sdk::light_client::LightClient;
 use sui_sdk::{ClientType, SuiClient};
-use sui_types::base_types::{ObjectRef, SequenceNumber};
+use sui_types::base_types::{AuthorityName, ObjectRef, SequenceNumber};
+use sui_types::committee::Committee;
+use sui_types::crypto::{get_key_pair, AuthorityKeyPair, KeypairTraits};
 use sui_types::event::TransferType;
 use sui_types::object::{Object, ObjectRead, Owner, PastObjectRead};
 use sui_types::sui_framework_address_concat_string;
@@ -33,6 +37,7 @@ use tokio::time::{sleep, Duration};
 
 use sui::client_commands::{SuiClientCommandResult, SuiClientCommands};
 use sui_config::utils::get_available_port;
+use sui_json::SuiJsonValue;
 use sui_json_rpc_types::{
     SuiEvent, SuiEventEnvelope, SuiEventFilter, SuiExecuteTransactionResponse, SuiExecutionStatus,
     SuiMoveStruct, SuiMoveValue, SuiTransactionFilter, SuiTransactionResponse,
@@ -40,7 +45,8 @@ use sui_json_rpc_types::{
 use sui_node::SuiNode;
 use sui_swarm::memory::Swarm;
 use sui_types::messages::{
-    ExecuteTransactionRequest, ExecuteTransactionRequestType, ExecuteTransactionResponse,
+    AuthenticatedEpoch, ExecuteTransactionRequest, ExecuteTransactionRequestType,
+    ExecuteTransactionResponse, GenesisEpoch,
 };
 use sui_types::{
     base_types::{ObjectID, SuiAddress, TransactionDigest},
@@ -445,6 +451,56 @@ async fn set_up_jsonrpc(
     Ok((node, http_client, sui_client))
 }
 
+#[tokio::test]
+async fn test_full_node_light_client() -> Result<(), anyhow::Error> {
+    let (swarm, mut context, _) = setup_network_and_wallet().await?;
+
+    let jsonrpc_addr: SocketAddr = format!("127.0.0.1:{}", get_available_port()).parse()?;
+    let mut config = swarm.config().generate_fullnode_config();
+    config.json_rpc_address = jsonrpc_addr;
+    // The light client verifies effects against the certified checkpoints the node syncs.
+    config.enable_checkpoint_sync = true;
+    let _node = SuiNode::start(&config, Registry::new()).await?;
+    let url = format!("http://{}", jsonrpc_addr);
+
+    let (transferred_object, _, receiver, digest) = transfer_coin(&mut context).await?;
+
+    let light_client = LightClient::new_from_genesis(&url, &swarm.config().genesis)?;
+    // Reading the transaction fails until it is included in a certified checkpoint.
+    let (certificate, effects) = timeout(Duration::from_secs(60), async {
+        loop {
+            match light_client.get_transaction(digest).await {
+                Ok(transaction) => break transaction,
+                Err(_) => sleep(Duration::from_millis(500)).await,
+            }
+        }
+    })
+    .await?;
+    assert_eq!(*certificate.digest(), digest);
+    assert!(effects.status.is_ok());
+
+    let object = light_client.get_object(transferred_object).await?;
+    assert_eq!(object.id(), transferred_object);
+    assert_eq!(object.owner, Owner::AddressOwner(receiver));
+    assert_eq!(object.previous_transaction, digest);
+
+    // A client trusting another committee rejects what the node serves.
+    let (_, key): (_, AuthorityKeyPair) = get_key_pair();
+    let name: AuthorityName = key.public().into();
+    let committee = Committee::new(0, BTreeMap::from([(name, 1)]))?;
+    let untrusting_client = LightClient::new(
+        &url,
+        AuthenticatedEpoch::Genesis(GenesisEpoch::new(committee)),
+    )?;
+    assert!(untrusting_client.get_transaction(digest).await.is_err());
+    assert!(untrusting_client
+        .get_object(transferred_object)
+        .await
+        .is_err());
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_full_node_transaction_streaming_basic() -> Result<(), anyhow::Error> {
     let (swarm, mut context, _) = setup_network_and_wallet().await?;
@@ -836,12 +892,12 @@ async fn test_validator_node_has_no_quorum_driver() {
 #[tokio::test]
 async fn test_full_node_quorum_driver_rpc_ok() -> Result<(), anyhow::Error> {
     let (swarm, mut context, _address) = setup_network_and_wallet().await?;
-    let (_node, jsonrpc_client, _) = set_up_jsonrpc(&swarm, None).await?;
+    let (node, jsonrpc_client, _) = set_up_jsonrpc(&swarm, None).await?;
 
-    let mut txns = make_transactions_with_wallet_context(&mut context, 3).await;
+    let mut txns = make_transactions_with_wallet_context(&mut context, 4).await;
     assert!(
-        txns.len() >= 3,
-        "Expect at least 3 txns but only got {}. Do we generate enough gas objects during genesis?",
+        txns.len() >= 4,
+        "Expect at least 4 txns but only got {}. Do we generate enough gas objects during genesis?",
         txns.len(),
     );
     let txn = txns.swap_remove(0);
@@ -864,9 +920,11 @@ async fn test_full_node_quorum_driver_rpc_ok() -> Result<(), anyhow::Error> {
     if let SuiExecuteTransactionResponse::EffectsCert {
         certificate,
         effects: _,
+        confirmed_local_execution,
     } = response
     {
         assert_eq!(&certificate.transaction_digest, tx_digest);
+        assert!(!confirmed_local_execution);
     } else {
         panic!("Expect EffectsCert but got {:?}", response);
     }
@@ -918,6 +976,92 @@ async fn test_full_node_quorum_driver_rpc_ok() -> Result<(), anyhow::Error> {
         panic!("Expect ImmediateReturn but got {:?}", response);
     }
 
+    // Test request with ExecuteTransactionRequestType::WaitForLocalExecution
+    let txn = txns.swap_remove(0);
+    let tx_digest = *txn.digest();
+    let (tx_bytes, flag, signature, pub_key) = txn.to_network_data_for_execution();
+    let params = rpc_params![
+        tx_bytes,
+        flag,
+        signature,
+        pub_key,
+        ExecuteTransactionRequestType::WaitForLocalExecution
+    ];
+    let response: SuiExecuteTransactionResponse = jsonrpc_client
+        .request("sui_executeTransaction", params)
+        .await
+        .unwrap();
+
+    if let SuiExecuteTransactionResponse::EffectsCert {
+        certificate,
+        effects: _,
+        confirmed_local_execution,
+    } = response
+    {
+        assert_eq!(certificate.transaction_digest, tx_digest);
+        assert!(confirmed_local_execution);
+        // The effects are readable from the full node as soon as the request returns.
+        assert!(node.state().get_transaction(tx_digest).await.is_ok());
+    } else {
+        panic!("Expect EffectsCert but got {:?}", response);
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_full_node_dry_run_gas_profile() -> Result<(), anyhow::Error> {
+    let (swarm, mut context, _address) = setup_network_and_wallet().await?;
+    let (node, _jsonrpc_client, sui_client) = set_up_jsonrpc(&swarm, None).await?;
+
+    let sender = context.keystore.addresses().get(0).cloned().unwrap();
+    let (package_ref, counter_id) = publish_basics_package_and_make_counter(&context, sender).await;
+    let effects = increment_counter(&context, sender, None, package_ref, counter_id).await;
+    wait_for_tx(effects.certificate.transaction_digest, node.state().clone()).await;
+    let counter = node.state().get_object(&counter_id).await?.unwrap();
+
+    // The profile is requested from the full node.
+    context.client = sui_client;
+    let profile_output = tempfile::tempdir()?.into_path().join("gas.folded");
+    let resp = SuiClientCommands::Call {
+        package: package_ref.0,
+        module: "counter".to_string(),
+        function: "increment".to_string(),
+        type_args: vec![],
+        args: vec![SuiJsonValue::new(json!(counter_id.to_hex_literal()))?],
+        gas: None,
+        gas_budget: 1000,
+        profile_output: Some(profile_output.clone()),
+    }
+    .execute(&mut context)
+    .await?;
+    let profile = if let SuiClientCommandResult::ProfileCall(effects, profile) = resp {
+        assert_eq!(effects.status, SuiExecutionStatus::Success);
+        profile
+    } else {
+        panic!("Command failed with unexpected result")
+    };
+
+    let function = format!("{}::counter::increment", package_ref.0.to_hex_literal());
+    assert!(profile.function_costs[&function] > 0);
+    assert!(profile.instruction_costs["[storage]"] > 0);
+    let folded_stacks = std::fs::read_to_string(&profile_output)?;
+    assert_eq!(folded_stacks, profile.folded_stacks);
+    for line in folded_stacks.lines() {
+        let (stack, gas) = line.rsplit_once(' ').unwrap();
+        assert!(stack.starts_with(&function) || stack.starts_with('['));
+        assert!(gas.parse::<u64>()? > 0);
+    }
+
+    // Nothing was committed.
+    assert_eq!(
+        node.state()
+            .get_object(&counter_id)
+            .await?
+            .unwrap()
+            .version(),
+        counter.version()
+    );
     Ok(())
 }
 
@@ -1043,3 +1187,53 @@ async fn test_get_objects_read() -> Result<(), anyhow::Error> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_full_node_governance_read_api() -> Result<(), anyhow::Error> {
+    let (swarm, mut context, address) = setup_network_and_wallet().await?;
+    let (node, _jsonrpc_client, sui_client) = set_up_jsonrpc(&swarm, None).await?;
+    let validators = swarm.config().validator_set();
+
+    let system_state = sui_client.governance_api().get_sui_system_state().await?;
+    assert_eq!(system_state.epoch, 0);
+    assert_eq!(
+        system_state.validators.active_validators.len(),
+        validators.len()
+    );
+
+    let active_validators = sui_client.governance_api().get_validators().await?;
+    assert_eq!(active_validators, system_state.validators.active_validators);
+    for validator in validators {
+        assert!(active_validators
+            .iter()
+            .any(|v| v.metadata.sui_address == validator.sui_address()));
+    }
+
+    let validator = validators[0].sui_address();
+    let resp = SuiClientCommands::Delegate {
+        validator,
+        coin: None,
+        amount: Some(1000),
+        gas: None,
+        gas_budget: 1000,
+    }
+    .execute(&mut context)
+    .await?;
+    let digest = if let SuiClientCommandResult::Delegate(cert, _) = resp {
+        cert.transaction_digest
+    } else {
+        panic!("Command failed with unexpected result")
+    };
+    wait_for_tx(digest, node.state().clone()).await;
+
+    let delegations = sui_client
+        .governance_api()
+        .get_delegated_stakes(address)
+        .await?;
+    assert_eq!(delegations.len(), 1);
+    assert_eq!(delegations[0].validator_address, validator);
+    assert_eq!(delegations[0].delegate_amount, 1000);
+    assert!(delegations[0].active);
+
+    Ok(())
+}
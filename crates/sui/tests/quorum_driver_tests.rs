@@ -1,12 +1,14 @@
 // Copyright (c) 2022, Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use std::collections::BTreeMap;
 use std::time::Duration;
 use sui_core::authority_aggregator::AuthorityAggregator;
 use sui_core::authority_client::NetworkAuthorityClient;
 use sui_node::SuiNode;
 use sui_quorum_driver::{QuorumDriverHandler, QuorumDriverMetrics};
 use sui_types::base_types::SuiAddress;
+use sui_types::error::SuiError;
 use sui_types::messages::{
     ExecuteTransactionRequest, ExecuteTransactionRequestType, ExecuteTransactionResponse,
     Transaction,
@@ -38,6 +40,30 @@ async fn setup() -> (
     (handles, clients, tx)
 }
 
+/// Lock the gas object of `tx` on a different transaction on the first `num_authorities`
+/// authorities, and return that transaction.
+async fn lock_on_conflicting_transaction(
+    clients: &AuthorityAggregator<NetworkAuthorityClient>,
+    tx: &Transaction,
+    num_authorities: usize,
+) -> Transaction {
+    let (_, keypair) = test_account_keys().pop().unwrap();
+    let conflicting_tx = make_transfer_sui_transaction(
+        *tx.gas_payment_object_ref(),
+        SuiAddress::default(),
+        Some(1),
+        tx.sender_address(),
+        &keypair,
+    );
+    for client in clients.authority_clients.values().take(num_authorities) {
+        client
+            .handle_transaction(conflicting_tx.clone())
+            .await
+            .unwrap();
+    }
+    conflicting_tx
+}
+
 #[tokio::test]
 async fn test_execute_transaction_immediate() {
     let (_handles, clients, tx) = setup().await;
@@ -154,3 +180,60 @@ async fn test_update_validators() {
 
     handle.await.unwrap();
 }
+
+#[tokio::test]
+async fn test_execute_transaction_with_minority_conflicting_lock() {
+    let (_handles, clients, tx) = setup().await;
+    let digest = *tx.digest();
+    // A single authority locked the gas object on another transaction, the remaining authorities
+    // still form a quorum.
+    lock_on_conflicting_transaction(&clients, &tx, 1).await;
+
+    let quorum_driver_handler =
+        QuorumDriverHandler::new(clients, QuorumDriverMetrics::new_for_tests());
+    let quorum_driver = quorum_driver_handler.clone_quorum_driver();
+    if let ExecuteTransactionResponse::EffectsCert(result) = quorum_driver
+        .execute_transaction(ExecuteTransactionRequest {
+            transaction: tx,
+            request_type: ExecuteTransactionRequestType::WaitForEffectsCert,
+        })
+        .await
+        .unwrap()
+    {
+        let (cert, effects) = *result;
+        assert_eq!(*cert.digest(), digest);
+        assert_eq!(effects.effects.transaction_digest, digest);
+    } else {
+        unreachable!();
+    }
+}
+
+#[tokio::test]
+async fn test_execute_transaction_with_equivocated_locks() {
+    let (_handles, clients, tx) = setup().await;
+    let gas_object = *tx.gas_payment_object_ref();
+    // Half of the stake locked the gas object on another transaction, neither transaction can
+    // get a certificate in this epoch.
+    let conflicting_tx = lock_on_conflicting_transaction(&clients, &tx, 2).await;
+
+    let quorum_driver_handler =
+        QuorumDriverHandler::new(clients, QuorumDriverMetrics::new_for_tests());
+    let quorum_driver = quorum_driver_handler.clone_quorum_driver();
+    let result = quorum_driver
+        .execute_transaction(ExecuteTransactionRequest {
+            transaction: tx,
+            request_type: ExecuteTransactionRequestType::WaitForEffectsCert,
+        })
+        .await;
+
+    // The conflict is reported without retrying the transaction.
+    assert_eq!(
+        result.unwrap_err(),
+        SuiError::ObjectsLockedByConflictingTransactions {
+            conflicting_transactions: BTreeMap::from([(
+                *conflicting_tx.digest(),
+                vec![gas_object]
+            )]),
+        }
+    );
+}
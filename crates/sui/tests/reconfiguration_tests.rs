@@ -17,7 +17,7 @@ use sui_types::crypto::{
     AuthoritySignature, KeypairTraits,
 };
 use sui_types::error::SuiResult;
-use sui_types::messages::ObjectInfoResponse;
+use sui_types::messages::{AuthenticatedEpoch, ObjectInfoResponse};
 use sui_types::messages::{CallArg, ObjectArg, ObjectInfoRequest, TransactionEffects};
 use sui_types::object::Object;
 use sui_types::SUI_SYSTEM_STATE_OBJECT_ID;
@@ -142,6 +142,24 @@ async fn reconfig_end_to_end_tests() {
     assert_eq!(sui_system_state.epoch, 1);
     // We should now have one more active validator.
     assert_eq!(sui_system_state.validators.active_validators.len(), 5);
+
+    // A full node follows the epoch change certified by the validators.
+    let fullnode = SuiNode::start(&configs.generate_fullnode_config(), Registry::new())
+        .await
+        .unwrap();
+    let fullnode_state = fullnode.state();
+    let mut epoch_changes = fullnode_state.subscribe_epoch_change();
+    assert!(fullnode.active().sync_epochs().await.unwrap());
+    assert_eq!(fullnode_state.epoch(), 1);
+    assert_eq!(epoch_changes.recv().await.unwrap(), 1);
+    let epoch_store = fullnode_state.epoch_store();
+    assert!(matches!(
+        epoch_store.get_authenticated_epoch(&1).unwrap(),
+        Some(AuthenticatedEpoch::Certified(_))
+    ));
+    // The full node had not executed past the end of epoch 0.
+    assert!(epoch_store.get_epoch_economics(&0).unwrap().is_some());
+    assert!(!fullnode.active().sync_epochs().await.unwrap());
 }
 
 pub async fn create_and_register_new_validator(
@@ -2,8 +2,9 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use std::sync::Arc;
+use sui_config::ConsensusProtocol;
 use sui_core::authority::GatewayStore;
-use sui_core::authority_client::AuthorityAPI;
+use sui_core::authority_client::{AuthorityAPI, NetworkAuthorityClientMetrics};
 use sui_core::gateway_state::{GatewayAPI, GatewayMetrics, GatewayState};
 use sui_types::messages::{
     CallArg, ExecutionStatus, ObjectArg, ObjectInfoRequest, ObjectInfoRequestKind,
@@ -14,7 +15,9 @@ use test_utils::transaction::{
     publish_counter_package, submit_shared_object_transaction, submit_single_owner_transaction,
 };
 use test_utils::{
-    authority::{spawn_test_authorities, test_authority_configs},
+    authority::{
+        spawn_test_authorities, test_and_configure_authority_configs, test_authority_configs,
+    },
     messages::{move_transaction, test_shared_object_transactions},
     objects::{test_gas_objects, test_shared_object},
 };
@@ -41,6 +44,26 @@ async fn shared_object_transaction() {
         .unwrap();
 }
 
+/// Same as `shared_object_transaction` but with a single validator sequencing transactions
+/// in-process instead of running Narwhal.
+#[tokio::test]
+async fn shared_object_transaction_single_node_consensus() {
+    let mut objects = test_gas_objects();
+    objects.push(test_shared_object());
+
+    let mut configs = test_and_configure_authority_configs(1);
+    for config in configs.validator_configs.iter_mut() {
+        config.consensus_config.as_mut().unwrap().protocol = ConsensusProtocol::SingleNode;
+    }
+    let handles = spawn_test_authorities(objects, &configs).await;
+    let aggregator = test_authority_aggregator(&configs, handles[0].state().epoch_store().clone());
+
+    // The shared object transaction is sequenced without any Narwhal node running.
+    let transaction = test_shared_object_transactions().pop().unwrap();
+    let (_certificate, effects) = aggregator.execute_transaction(&transaction).await.unwrap();
+    assert_eq!(effects.effects.transaction_digest, *transaction.digest());
+}
+
 /// Same as `shared_object_transaction` but every authorities submit the transaction.
 #[tokio::test]
 async fn many_shared_object_transactions() {
@@ -372,8 +395,13 @@ async fn shared_object_on_gateway() {
     let path = tempfile::tempdir().unwrap().into_path();
     let gateway_store = Arc::new(GatewayStore::open(&path.join("store"), None));
     let gateway = Arc::new(
-        GatewayState::new_with_authorities(gateway_store, clients, GatewayMetrics::new_for_tests())
-            .unwrap(),
+        GatewayState::new_with_authorities(
+            gateway_store,
+            clients,
+            Arc::new(NetworkAuthorityClientMetrics::new_for_tests()),
+            GatewayMetrics::new_for_tests(),
+        )
+        .unwrap(),
     );
 
     // Publish the move package to all authorities and get the new package ref.
@@ -435,5 +435,6 @@ fn dummy_transaction_effects(tx: &Transaction) -> TransactionEffects {
         ),
         events: Vec::new(),
         dependencies: Vec::new(),
+        clock_timestamp_ms: None,
     }
 }
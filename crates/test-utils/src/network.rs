@@ -41,9 +41,21 @@ pub async fn start_test_network_with_fullnodes(
     fullnode_port: Option<u16>,
     websocket_port: Option<u16>,
 ) -> Result<Swarm, anyhow::Error> {
-    let mut builder: SwarmBuilder = Swarm::builder()
+    let builder = Swarm::builder()
         .committee_size(NonZeroUsize::new(NUM_VALIDAOTR).unwrap())
         .with_fullnode_count(fullnode_count);
+    start_test_network_with_swarm_builder(builder, genesis_config, fullnode_port, websocket_port)
+        .await
+}
+
+/// Like `start_test_network_with_fullnodes`, but launches the swarm described by `builder`, so
+/// callers can pick the committee size and consensus protocol.
+pub async fn start_test_network_with_swarm_builder(
+    mut builder: SwarmBuilder,
+    genesis_config: Option<GenesisConfig>,
+    fullnode_port: Option<u16>,
+    websocket_port: Option<u16>,
+) -> Result<Swarm, anyhow::Error> {
     if let Some(fullnode_port) = fullnode_port {
         builder =
             builder.with_fullnode_rpc_addr(format!("127.0.0.1:{}", fullnode_port).parse().unwrap());
@@ -149,9 +161,29 @@ pub async fn start_rpc_test_network_with_fullnode(
     fullnode_port: Option<u16>,
     websocket_port: Option<u16>,
 ) -> Result<TestNetwork, anyhow::Error> {
-    let network = start_test_network_with_fullnodes(
+    let builder = Swarm::builder()
+        .committee_size(NonZeroUsize::new(NUM_VALIDAOTR).unwrap())
+        .with_fullnode_count(fullnode_count);
+    start_rpc_test_network_with_swarm_builder(
+        builder,
+        genesis_config,
+        gateway_port,
+        fullnode_port,
+        websocket_port,
+    )
+    .await
+}
+
+pub async fn start_rpc_test_network_with_swarm_builder(
+    builder: SwarmBuilder,
+    genesis_config: Option<GenesisConfig>,
+    gateway_port: Option<u16>,
+    fullnode_port: Option<u16>,
+    websocket_port: Option<u16>,
+) -> Result<TestNetwork, anyhow::Error> {
+    let network = start_test_network_with_swarm_builder(
+        builder,
         genesis_config,
-        fullnode_count,
         fullnode_port,
         websocket_port,
     )
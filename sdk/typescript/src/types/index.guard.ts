@@ -338,7 +338,14 @@ export function isSuiObject(obj: any, _argumentName?: string): obj is SuiObject
         isObjectOwner(obj.owner) as boolean &&
         isTransactionDigest(obj.previousTransaction) as boolean &&
         isSuiMoveTypeParameterIndex(obj.storageRebate) as boolean &&
-        isSuiObjectRef(obj.reference) as boolean
+        isSuiObjectRef(obj.reference) as boolean &&
+        (typeof obj.display === "undefined" ||
+            (obj.display !== null &&
+                typeof obj.display === "object" ||
+                typeof obj.display === "function") &&
+            Object.entries<any>(obj.display)
+                .every(([key, value]) => (isTransactionDigest(value) as boolean &&
+                    isTransactionDigest(key) as boolean)))
     )
 }
 
@@ -647,7 +654,8 @@ export function isExecuteTransactionRequestType(obj: any, _argumentName?: string
     return (
         (obj === "ImmediateReturn" ||
             obj === "WaitForTxCert" ||
-            obj === "WaitForEffectsCert")
+            obj === "WaitForEffectsCert" ||
+            obj === "WaitForLocalExecution")
     )
 }
 
@@ -877,7 +885,9 @@ export function isSuiExecuteTransactionResponse(obj: any, _argumentName?: string
                 typeof obj.EffectsCert === "object" ||
                 typeof obj.EffectsCert === "function") &&
             isCertifiedTransaction(obj.EffectsCert.certificate) as boolean &&
-            isSuiCertifiedTransactionEffects(obj.EffectsCert.effects) as boolean)
+            isSuiCertifiedTransactionEffects(obj.EffectsCert.effects) as boolean &&
+            (obj.EffectsCert.confirmedLocalExecution === false ||
+                obj.EffectsCert.confirmedLocalExecution === true))
     )
 }
 
@@ -130,6 +130,8 @@ export type SuiObject = {
    */
   storageRebate: number;
   reference: SuiObjectRef;
+  /** The object rendered with the `Display` of its type, if there is one */
+  display?: Record<string, string>;
 };
 
 export type ObjectStatus = 'Exists' | 'NotExists' | 'Deleted';
@@ -25,7 +25,8 @@ export type SuiChangeEpoch = {
 export type ExecuteTransactionRequestType =
   | 'ImmediateReturn'
   | 'WaitForTxCert'
-  | 'WaitForEffectsCert';
+  | 'WaitForEffectsCert'
+  | 'WaitForLocalExecution';
 
 export type TransactionKindName =
   | 'TransferObject'
@@ -136,6 +137,7 @@ export type SuiExecuteTransactionResponse =
       EffectsCert: {
         certificate: CertifiedTransaction;
         effects: SuiCertifiedTransactionEffects;
+        confirmedLocalExecution: boolean;
       };
     };
 
@@ -0,0 +1,48 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+/// A transfer policy rule only allowing objects to be transferred to addresses on a list.
+module nfts::allowlist_rule {
+    use std::vector;
+    use sui::transfer_policy::{Self, TransferPolicy, TransferPolicyCap, TransferRequest};
+    use sui::vec_set::{Self, VecSet};
+
+    // For when the recipient is not on the list.
+    const ERecipientNotAllowed: u64 = 0;
+
+    /// The rule witness.
+    struct Rule has drop {}
+
+    /// The addresses objects can be transferred to.
+    struct Config has store, drop {
+        allowed: VecSet<address>,
+    }
+
+    /// Only allow transfers under `policy` to the addresses in `allowed`.
+    public fun add<T: key>(
+        policy: &mut TransferPolicy<T>,
+        cap: &TransferPolicyCap<T>,
+        allowed: vector<address>,
+    ) {
+        let set = vec_set::empty();
+        while (!vector::is_empty(&allowed)) {
+            let addr = vector::pop_back(&mut allowed);
+            if (!vec_set::contains(&set, &addr)) {
+                vec_set::insert(&mut set, addr)
+            }
+        };
+        transfer_policy::add_rule(Rule {}, policy, cap, Config { allowed: set })
+    }
+
+    /// Confirm that the recipient of `request` is allowed by `policy`.
+    public fun confirm<T: key>(policy: &TransferPolicy<T>, request: &mut TransferRequest<T>) {
+        assert!(is_allowed(policy, transfer_policy::recipient(request)), ERecipientNotAllowed);
+        transfer_policy::add_receipt(Rule {}, request)
+    }
+
+    /// Whether `policy` allows transfers to `addr`.
+    public fun is_allowed<T: key>(policy: &TransferPolicy<T>, addr: address): bool {
+        let config: &Config = transfer_policy::get_rule(Rule {}, policy);
+        vec_set::contains(&config.allowed, &addr)
+    }
+}
@@ -0,0 +1,63 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+/// A transfer policy rule requiring a royalty, a share of the amount paid for the object but no
+/// less than a minimum amount, to be paid into the policy on each transfer.
+///
+/// The royalty is taken out of the payment held by the transfer request, so it is based on what
+/// was actually paid. The minimum amount still applies when the object is transferred without a
+/// payment, or with the payment made outside of the request.
+module nfts::royalty_rule {
+    use sui::transfer_policy::{Self, TransferPolicy, TransferPolicyCap, TransferRequest};
+
+    // For when the royalty is more than the whole amount paid.
+    const EIncorrectArgument: u64 = 0;
+
+    // For when the payment does not cover the royalty.
+    const EInsufficientAmount: u64 = 1;
+
+    /// Basis points in the whole amount paid.
+    const MAX_BPS: u64 = 10000;
+
+    /// The rule witness.
+    struct Rule has drop {}
+
+    /// The royalty, in basis points of the amount paid, and the minimum royalty.
+    struct Config has store, drop {
+        amount_bp: u64,
+        min_amount: u64,
+    }
+
+    /// Require a royalty of `amount_bp` basis points of the amount paid, and at least
+    /// `min_amount`, on transfers under `policy`.
+    public fun add<T: key>(
+        policy: &mut TransferPolicy<T>,
+        cap: &TransferPolicyCap<T>,
+        amount_bp: u64,
+        min_amount: u64,
+    ) {
+        assert!(amount_bp <= MAX_BPS, EIncorrectArgument);
+        transfer_policy::add_rule(Rule {}, policy, cap, Config { amount_bp, min_amount })
+    }
+
+    /// Pay the royalty for `request` out of the payment it holds.
+    public fun pay<T: key>(policy: &mut TransferPolicy<T>, request: &mut TransferRequest<T>) {
+        let paid = transfer_policy::paid(request);
+        let amount = fee_amount(policy, paid);
+        assert!(paid >= amount, EInsufficientAmount);
+
+        transfer_policy::take_fee(Rule {}, policy, request, amount);
+        transfer_policy::add_receipt(Rule {}, request)
+    }
+
+    /// The royalty due under `policy` when `paid` is paid for an object.
+    public fun fee_amount<T: key>(policy: &TransferPolicy<T>, paid: u64): u64 {
+        let config: &Config = transfer_policy::get_rule(Rule {}, policy);
+        let amount = (((paid as u128) * (config.amount_bp as u128) / (MAX_BPS as u128)) as u64);
+        if (amount < config.min_amount) {
+            config.min_amount
+        } else {
+            amount
+        }
+    }
+}
@@ -0,0 +1,170 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+#[test_only]
+module nfts::transfer_policy_tests {
+    use nfts::allowlist_rule;
+    use nfts::royalty_rule;
+    use std::option;
+    use sui::coin;
+    use sui::object::{Self, UID};
+    use sui::package::{Self, Publisher};
+    use sui::sui::SUI;
+    use sui::transfer;
+    use sui::transfer_policy::{Self, TransferPolicy, TransferPolicyCap, TransferRequest};
+    use sui::tx_context::{Self, TxContext};
+
+    const BUYER: address = @0xB001;
+
+    /// One-time witness of this module.
+    struct TRANSFER_POLICY_TESTS has drop {}
+
+    /// Without `store`, so it can only be transferred through its policy.
+    struct Capy has key {
+        id: UID,
+    }
+
+    fun policy(ctx: &mut TxContext): (Publisher, TransferPolicy<Capy>, TransferPolicyCap<Capy>) {
+        let publisher = package::claim(TRANSFER_POLICY_TESTS {}, ctx);
+        let (policy, cap) = transfer_policy::new<Capy>(&publisher, ctx);
+        (publisher, policy, cap)
+    }
+
+    fun wrap_up(
+        publisher: Publisher,
+        policy: TransferPolicy<Capy>,
+        cap: TransferPolicyCap<Capy>,
+        ctx: &TxContext,
+    ) {
+        package::burn_publisher(publisher);
+        transfer::share_object(policy);
+        transfer::transfer(cap, tx_context::sender(ctx));
+    }
+
+    fun new_request(paid: u64, ctx: &mut TxContext): TransferRequest<Capy> {
+        let payment = coin::mint_for_testing<SUI>(paid, ctx);
+        transfer_policy::new_request(Capy { id: object::new(ctx) }, BUYER, payment)
+    }
+
+    #[test]
+    fun test_transfer_without_rules() {
+        let ctx = tx_context::dummy();
+        let (publisher, policy, cap) = policy(&mut ctx);
+
+        let request = new_request(1000, &mut ctx);
+        let proceeds = transfer_policy::confirm_request(&policy, request, &mut ctx);
+        assert!(coin::value(&proceeds) == 1000, 0);
+
+        coin::destroy_for_testing(proceeds);
+        wrap_up(publisher, policy, cap, &ctx);
+    }
+
+    #[test]
+    fun test_royalty_and_allowlist() {
+        let ctx = tx_context::dummy();
+        let (publisher, policy, cap) = policy(&mut ctx);
+        royalty_rule::add(&mut policy, &cap, 250, 10);
+        allowlist_rule::add(&mut policy, &cap, vector[BUYER]);
+        assert!(royalty_rule::fee_amount(&policy, 1000) == 25, 0);
+        assert!(royalty_rule::fee_amount(&policy, 100) == 10, 0);
+
+        let request = new_request(1000, &mut ctx);
+        royalty_rule::pay(&mut policy, &mut request);
+        allowlist_rule::confirm(&policy, &mut request);
+        let proceeds = transfer_policy::confirm_request(&policy, request, &mut ctx);
+        assert!(coin::value(&proceeds) == 975, 0);
+        assert!(transfer_policy::balance(&policy) == 25, 0);
+
+        let fees = transfer_policy::withdraw(&mut policy, &cap, option::none(), &mut ctx);
+        assert!(coin::value(&fees) == 25, 0);
+        assert!(transfer_policy::balance(&policy) == 0, 0);
+
+        coin::destroy_for_testing(fees);
+        coin::destroy_for_testing(proceeds);
+        wrap_up(publisher, policy, cap, &ctx);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 1)]
+    fun test_missing_receipt() {
+        let ctx = tx_context::dummy();
+        let (publisher, policy, cap) = policy(&mut ctx);
+        royalty_rule::add(&mut policy, &cap, 250, 0);
+        allowlist_rule::add(&mut policy, &cap, vector[BUYER]);
+
+        let request = new_request(1000, &mut ctx);
+        allowlist_rule::confirm(&policy, &mut request);
+        let proceeds = transfer_policy::confirm_request(&policy, request, &mut ctx);
+
+        coin::destroy_for_testing(proceeds);
+        wrap_up(publisher, policy, cap, &ctx);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 0)]
+    fun test_recipient_not_allowed() {
+        let ctx = tx_context::dummy();
+        let (publisher, policy, cap) = policy(&mut ctx);
+        allowlist_rule::add(&mut policy, &cap, vector[BUYER]);
+
+        let payment = coin::zero<SUI>(&mut ctx);
+        let request =
+            transfer_policy::new_request(Capy { id: object::new(&mut ctx) }, @0xC001, payment);
+        allowlist_rule::confirm(&policy, &mut request);
+        let proceeds = transfer_policy::confirm_request(&policy, request, &mut ctx);
+
+        coin::destroy_for_testing(proceeds);
+        wrap_up(publisher, policy, cap, &ctx);
+    }
+
+    #[test]
+    fun test_royalty_from_payment() {
+        let ctx = tx_context::dummy();
+        let (publisher, policy, cap) = policy(&mut ctx);
+        royalty_rule::add(&mut policy, &cap, 1000, 10);
+
+        // 10% of the 99 paid is less than the minimum royalty, so the minimum is taken instead.
+        let request = new_request(99, &mut ctx);
+        royalty_rule::pay(&mut policy, &mut request);
+        let proceeds = transfer_policy::confirm_request(&policy, request, &mut ctx);
+        assert!(coin::value(&proceeds) == 89, 0);
+        assert!(transfer_policy::balance(&policy) == 10, 0);
+
+        coin::destroy_for_testing(proceeds);
+        wrap_up(publisher, policy, cap, &ctx);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 1)]
+    fun test_zero_payment_rejected() {
+        let ctx = tx_context::dummy();
+        let (publisher, policy, cap) = policy(&mut ctx);
+        royalty_rule::add(&mut policy, &cap, 1000, 10);
+
+        // Claiming nothing was paid does not avoid the minimum royalty.
+        let request = new_request(0, &mut ctx);
+        royalty_rule::pay(&mut policy, &mut request);
+        let proceeds = transfer_policy::confirm_request(&policy, request, &mut ctx);
+
+        coin::destroy_for_testing(proceeds);
+        wrap_up(publisher, policy, cap, &ctx);
+    }
+
+    #[test]
+    fun test_remove_rule() {
+        let ctx = tx_context::dummy();
+        let (publisher, policy, cap) = policy(&mut ctx);
+        royalty_rule::add(&mut policy, &cap, 250, 0);
+        transfer_policy::remove_rule<Capy, royalty_rule::Rule, royalty_rule::Config>(
+            &mut policy,
+            &cap,
+        );
+
+        let request = new_request(1000, &mut ctx);
+        let proceeds = transfer_policy::confirm_request(&policy, request, &mut ctx);
+        assert!(coin::value(&proceeds) == 1000, 0);
+
+        coin::destroy_for_testing(proceeds);
+        wrap_up(publisher, policy, cap, &ctx);
+    }
+}